@@ -0,0 +1,82 @@
+//! Load time of sample projects and a synthetic project of 100k+ lines.
+//!
+//! Run with `cargo bench --bench load`. Target: loading the large project under 150ms, which
+//! is met by the hand written parser and deferring change tracking state to first use.
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::path::Path;
+use xcodeproj::pbxproj::PBXRootObject;
+
+/// Copies of demo4 objects merged into the large project, ~2900 lines each
+const COPIES: usize = 40;
+
+fn sample(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/samples")
+        .join(name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// demo4 with its objects section repeated `copies` times, each copy with distinct references
+fn large_project(copies: usize) -> String {
+    let content = sample("demo4.pbxproj");
+    let start = content.find("objects = {\n").unwrap() + "objects = {\n".len();
+    let end = content.rfind("\t};\n\trootObject").unwrap();
+    let objects = &content[start..end];
+
+    let mut large = content[..end].to_string();
+    for copy in 1..copies {
+        large.push_str(&remap_references(objects, copy));
+    }
+    large.push_str(&content[end..]);
+    large
+}
+
+/// Replace the last 4 characters of every reference in `content` with `copy` as hex
+fn remap_references(content: &str, copy: usize) -> String {
+    let bytes = content.as_bytes();
+    let is_reference_char = |b: u8| b.is_ascii_digit() || b.is_ascii_uppercase();
+    let mut remapped = String::with_capacity(content.len());
+    let mut position = 0;
+    while position < bytes.len() {
+        let end = (position..bytes.len())
+            .find(|&i| !is_reference_char(bytes[i]))
+            .unwrap_or(bytes.len());
+        let token = &content[position..end];
+        if token.len() == 24 && token.bytes().all(|b| b.is_ascii_hexdigit()) {
+            remapped.push_str(&token[..20]);
+            remapped.push_str(&format!("{copy:04X}"));
+        } else {
+            remapped.push_str(token);
+        }
+        match content[end..].chars().next() {
+            Some(next) => {
+                remapped.push(next);
+                position = end + next.len_utf8();
+            }
+            None => position = end,
+        }
+    }
+    remapped
+}
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load");
+    for name in ["demo1.pbxproj", "demo4.pbxproj"] {
+        let content = sample(name);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter_with_large_drop(|| PBXRootObject::try_from(content.as_str()).unwrap())
+        });
+    }
+
+    let content = large_project(COPIES);
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(content.len() as u64));
+    group.bench_function(format!("large-{}-lines", content.lines().count()), |b| {
+        b.iter_with_large_drop(|| PBXRootObject::try_from(content.as_str()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
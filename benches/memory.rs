@@ -0,0 +1,81 @@
+//! Peak heap memory used while parsing sample projects.
+//!
+//! Run with `cargo bench --bench memory`.
+//!
+//! Storing [`PBXHashMap`] keys as `SmolStr` instead of `String`, with parse capacities
+//! reserved, measured with the pest parser. Values stay `String`s: they are mostly
+//! references, paths and settings longer than `SmolStr` inline storage, so allocations would
+//! not drop.
+//!
+//! | sample | peak before | peak after | allocations before | allocations after |
+//! |--------|------------:|-----------:|-------------------:|------------------:|
+//! | demo1  |     173 267 |    170 854 |              2 082 |             1 589 |
+//! | demo2  |     590 072 |    590 072 |              4 612 |             3 498 |
+//! | demo4  |   2 598 213 |  2 549 606 |             27 551 |            21 429 |
+//! | demo7  |     162 519 |    160 007 |              1 738 |             1 330 |
+//!
+//! The hand written scanner since brings demo4 down to 1 109 601 bytes peak and 9 685
+//! allocations.
+//!
+//! [`PBXHashMap`]: xcodeproj::pbxproj::PBXHashMap
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use xcodeproj::pbxproj::PBXRootObject;
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+    let mut paths = std::fs::read_dir(samples)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    println!(
+        "{:<20} {:>12} {:>12} {:>12}",
+        "sample", "size", "peak", "allocations"
+    );
+    for path in paths {
+        let content = std::fs::read_to_string(&path).unwrap();
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+
+        let root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+        let peak = PEAK.load(Ordering::Relaxed) - baseline;
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        drop(root_object);
+
+        println!(
+            "{:<20} {:>12} {:>12} {:>12}",
+            path.file_name().unwrap().to_string_lossy(),
+            content.len(),
+            peak,
+            allocations
+        );
+    }
+}
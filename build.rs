@@ -5,11 +5,11 @@ use std::path::Path;
 
 use phf_codegen::Map;
 
-fn main() {
+/// Write `name` phf map of `key: value` lines of `txt` to `file` in `OUT_DIR`
+fn write_map(file: &str, name: &str, txt: &str) {
     let out_dir = env::var("OUT_DIR").unwrap();
-    let ref mut f = File::create(Path::new(&out_dir).join("file_types.rs")).unwrap();
+    let f = &mut File::create(Path::new(&out_dir).join(file)).unwrap();
     let mut map: Map<&str> = phf_codegen::Map::new();
-    let txt = include_str!("./res/file_type.txt");
 
     txt.lines().for_each(|line| {
         let (key, value) = line.split_once(": ").unwrap();
@@ -17,7 +17,20 @@ fn main() {
     });
 
     let map = map.build();
-    let base = "static XCODE_FILE_TYPES: phf::Map<&'static str, &'static str>";
+    let base = format!("static {name}: phf::Map<&'static str, &'static str>");
 
     write!(f, "{base} = \n{map};\n",).unwrap();
 }
+
+fn main() {
+    write_map(
+        "file_types.rs",
+        "XCODE_FILE_TYPES",
+        include_str!("./res/file_type.txt"),
+    );
+    write_map(
+        "build_settings.rs",
+        "XCODE_BUILD_SETTINGS",
+        include_str!("./res/build_settings.txt"),
+    );
+}
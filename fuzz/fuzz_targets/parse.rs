@@ -0,0 +1,18 @@
+//! Parse and decode arbitrary content, which must never panic.
+//!
+//! Run with `cargo +nightly fuzz run parse -- -dict=fuzz/pbxproj.dict`, seeding the corpus
+//! from `tests/samples`.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use xcodeproj::pbxproj::PBXRootObject;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(root_object) = PBXRootObject::try_from(content) {
+            root_object.fingerprint();
+            root_object.changes();
+        }
+        let (root_object, _) = PBXRootObject::parse_lossy(content);
+        root_object.fingerprint();
+    }
+});
@@ -0,0 +1,228 @@
+//! Python bindings of the core project API, built as the `xcodeproj` module.
+//!
+//! ```python
+//! import xcodeproj
+//!
+//! project = xcodeproj.Project.open("App.xcodeproj")
+//! app = project.target("App")
+//! project.add_file("App/Sources/Feature.swift", group="App/Sources", target=app.name)
+//! project.add_package(app.name, "https://github.com/apple/swift-log", "Logging", version="1.5.0")
+//! project.set_build_setting(app.name, "SWIFT_VERSION", "5.0")
+//! project.save()
+//! ```
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use std::path::PathBuf;
+use xcodeproj::pbxproj::{PBXBuildPhaseKind, PBXTarget, PBXValue, XCVersionRequirement};
+use xcodeproj::XCodeProject;
+
+/// Target of a project
+#[pyclass(name = "Target", frozen)]
+#[derive(Clone)]
+struct PyTarget {
+    /// Target reference
+    #[pyo3(get)]
+    id: String,
+    /// Target name
+    #[pyo3(get)]
+    name: Option<String>,
+    /// Product type, e.g. `com.apple.product-type.application`
+    #[pyo3(get)]
+    product_type: String,
+}
+
+impl From<PBXTarget<'_>> for PyTarget {
+    fn from(target: PBXTarget<'_>) -> Self {
+        Self {
+            id: target.id,
+            name: target.name.cloned(),
+            product_type: target.product_type.to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyTarget {
+    fn __repr__(&self) -> String {
+        format!(
+            "Target({:?}, {:?})",
+            self.name.as_deref().unwrap_or_default(),
+            self.id
+        )
+    }
+}
+
+/// Project opened from its `.xcodeproj` bundle
+#[pyclass(name = "Project", unsendable)]
+struct PyProject {
+    project: XCodeProject,
+}
+
+impl PyProject {
+    /// Get reference of the target named `name`
+    fn target_reference(&self, name: &str) -> PyResult<String> {
+        self.project
+            .pbxproj()
+            .get_target_by_name(name)
+            .map(|target| target.id)
+            .ok_or_else(|| PyKeyError::new_err(format!("No target named {name:?}")))
+    }
+}
+
+#[pymethods]
+impl PyProject {
+    /// Open project at `path`, e.g. `App.xcodeproj`
+    #[staticmethod]
+    fn open(path: PathBuf) -> PyResult<Self> {
+        Ok(Self {
+            project: XCodeProject::open(path)?,
+        })
+    }
+
+    /// Project name
+    #[getter]
+    fn name(&self) -> String {
+        self.project.name().to_string()
+    }
+
+    /// Directory holding the `.xcodeproj` bundle
+    #[getter]
+    fn root(&self) -> PathBuf {
+        self.project.root().clone()
+    }
+
+    /// Targets of the project
+    fn targets(&self) -> Vec<PyTarget> {
+        self.project
+            .pbxproj()
+            .targets()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Target named `name`, `None` if missing
+    fn target(&self, name: &str) -> Option<PyTarget> {
+        self.project
+            .pbxproj()
+            .get_target_by_name(name)
+            .map(Into::into)
+    }
+
+    /// Absolute paths of files referenced by the project, leaving out files relative to
+    /// build products or SDKs
+    fn files(&self) -> Vec<PathBuf> {
+        let pbxproj = self.project.pbxproj();
+        let mut files = pbxproj
+            .files()
+            .into_iter()
+            .filter_map(|file| pbxproj.get_full_path(&file.id, self.project.root()))
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    /// Add file at `path`, relative to the project root, to the group at `group` path, the
+    /// main group by default, and build it with the target named `target` if given: sources
+    /// are compiled, other files copied as resources. Returns the file reference.
+    #[pyo3(signature = (path, group = None, target = None))]
+    fn add_file(
+        &mut self,
+        path: PathBuf,
+        group: Option<PathBuf>,
+        target: Option<&str>,
+    ) -> PyResult<String> {
+        let target = target.map(|name| self.target_reference(name)).transpose()?;
+        let root = self.project.root().clone();
+        let pbxproj = self.project.pbxproj_mut();
+        let group = match group {
+            Some(group) => pbxproj
+                .find_group_by_path(&group)
+                .map(|group| group.id)
+                .ok_or_else(|| PyKeyError::new_err(format!("No group at {group:?}")))?,
+            None => pbxproj.root_project().main_group.id,
+        };
+
+        let file = pbxproj.objects_mut().add_file(
+            &group,
+            root.join(path).as_path(),
+            root.as_path(),
+            None,
+        )?;
+        if let Some(target) = target {
+            let is_source = pbxproj
+                .get_file(&file)
+                .map(|file| matches!(file.file_type(), Some(t) if t.starts_with("sourcecode")))
+                .unwrap_or_default();
+            if is_source {
+                let phase = pbxproj
+                    .get_target_build_phase(&target, &PBXBuildPhaseKind::Sources)
+                    .ok_or_else(|| PyValueError::new_err("Target has no sources phase"))?;
+                pbxproj.objects_mut().add_build_file(&phase, &file)?;
+            } else {
+                pbxproj.objects_mut().add_resource(&target, &file)?;
+            }
+        }
+        Ok(file)
+    }
+
+    /// Link product `product` of the swift package at `url` to the target named `target`,
+    /// pinned to `version` up to the next major version, or to `branch`. Returns the reference
+    /// of the package product dependency.
+    #[pyo3(signature = (target, url, product, version = None, branch = None))]
+    fn add_package(
+        &mut self,
+        target: &str,
+        url: &str,
+        product: &str,
+        version: Option<String>,
+        branch: Option<String>,
+    ) -> PyResult<String> {
+        let requirement = match (version, branch) {
+            (Some(version), None) => XCVersionRequirement::UpToNextMajorVersion(version),
+            (None, Some(branch)) => XCVersionRequirement::Branch(branch),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Expected exactly one of version or branch",
+                ))
+            }
+        };
+        let target = self.target_reference(target)?;
+        Ok(self
+            .project
+            .pbxproj_mut()
+            .add_swift_package(&target, url, product, requirement)?)
+    }
+
+    /// Set build setting `key` to `value`, a string or a bool, in every configuration of the
+    /// target named `target`
+    fn set_build_setting(&mut self, target: &str, key: &str, value: &PyAny) -> PyResult<()> {
+        let value: PBXValue = match value.extract::<bool>() {
+            Ok(value) => value.into(),
+            Err(_) => value.extract::<&str>()?.into(),
+        };
+        let target = self.target_reference(target)?;
+        self.project
+            .pbxproj_mut()
+            .objects_mut()
+            .set_setting_all(&target, key, value)?;
+        Ok(())
+    }
+
+    /// Write the project back to its `.xcodeproj` bundle
+    fn save(&mut self) -> PyResult<()> {
+        Ok(self.project.save()?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Project({:?})", self.project.name())
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "xcodeproj")]
+fn xcodeproj_python(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyProject>()?;
+    module.add_class::<PyTarget>()?;
+    Ok(())
+}
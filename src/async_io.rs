@@ -0,0 +1,149 @@
+#[cfg(feature = "scheme")]
+use crate::XCScheme;
+#[cfg(feature = "workspace")]
+use crate::XCWorkspace;
+use crate::{XCSaveOptions, XCodeProject};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Run blocking `f`, e.g. parsing, on tokio's blocking pool
+async fn blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .context("Blocking task failed")?
+}
+
+impl XCodeProject {
+    /// Async variant of [`XCodeProject::open`], reading and parsing the project on tokio's
+    /// blocking pool.
+    pub async fn open_async<P: AsRef<Path>>(xcodeproj_folder: P) -> Result<Self> {
+        let xcodeproj_folder = xcodeproj_folder.as_ref().to_path_buf();
+        blocking(move || Self::open(xcodeproj_folder)).await
+    }
+
+    /// Async variant of [`XCodeProject::save`]. Content is serialized on the caller and written
+    /// on tokio's blocking pool, the project is marked as saved once every file is written.
+    pub async fn save_async(&mut self) -> Result<()> {
+        let options = XCSaveOptions::new();
+        let writes = self.pending_writes(&options);
+        let writes = blocking(move || {
+            crate::bundle::write_files(&writes, &options)?;
+            Ok(writes)
+        })
+        .await?;
+        self.mark_written(&writes[0].content);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "scheme")]
+impl XCodeProject {
+    /// Write every scheme loaded from a file back to its path, see [`XCScheme::save_async`]
+    pub async fn save_schemes_async(&self) -> Result<()> {
+        for scheme in self.schemes().iter() {
+            if let Some(path) = scheme.path.as_ref() {
+                scheme.save_async(path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "workspace")]
+impl XCWorkspace {
+    /// Async variant of [`XCWorkspace::new`], reading the workspace and its projects on tokio's
+    /// blocking pool.
+    pub async fn open_async<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        blocking(move || Self::new(path)).await
+    }
+}
+
+#[cfg(feature = "scheme")]
+impl XCScheme {
+    /// Async variant of [`XCScheme::new`], parsing on tokio's blocking pool
+    pub async fn open_async<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        blocking(move || Self::new(path)).await
+    }
+
+    /// Async variant of [`XCScheme::save`], writing on tokio's blocking pool
+    pub async fn save_async<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let content = self.to_xml();
+        blocking(move || XCSaveOptions::new().write(path, content).map(|_| ())).await
+    }
+}
+
+#[cfg(all(test, feature = "scheme"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_save_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+            let scheme = XCScheme::open_async(root.join("schemes/demo1.xcscheme"))
+                .await
+                .unwrap();
+            let path = std::env::temp_dir().join(format!("{}.xcscheme", std::process::id()));
+            scheme.save_async(&path).await.unwrap();
+            assert_eq!(scheme.to_xml(), XCScheme::new(&path).unwrap().to_xml());
+            std::fs::remove_file(&path).unwrap();
+
+            assert!(XCodeProject::open_async(root.join("Missing.xcodeproj"))
+                .await
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn save_project_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let root = std::env::temp_dir().join(format!("xcodeproj-async-{}", std::process::id()));
+            let mut project = XCodeProject::from_template(
+                crate::Template::IosApp {
+                    name: "Demo".into(),
+                    bundle_id: "com.example.Demo".into(),
+                    swiftui: true,
+                },
+                &root,
+            )
+            .unwrap();
+
+            // Dropping the save before it completes leaves the project as is
+            {
+                let mut save = std::pin::pin!(project.save_async());
+                std::future::poll_fn(|cx| {
+                    let _ = std::future::Future::poll(save.as_mut(), cx);
+                    std::task::Poll::Ready(())
+                })
+                .await;
+            }
+            assert_eq!(1, project.targets().len());
+            assert_eq!(1, project.schemes().len());
+
+            project.save_async().await.unwrap();
+            assert!(!project.pbxproj().is_modified());
+            assert!(project.schemes()[0].path.is_some());
+
+            assert_eq!(&root.join("Demo.xcodeproj"), project.path());
+            let saved = XCodeProject::open_async(root.join("Demo.xcodeproj"))
+                .await
+                .unwrap();
+            assert_eq!(project.targets().len(), saved.targets().len());
+            assert_eq!(Some("Demo"), saved.pbxproj().project_name());
+            std::fs::remove_dir_all(&root).unwrap();
+        });
+    }
+}
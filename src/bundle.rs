@@ -0,0 +1,362 @@
+use crate::macros::log;
+#[cfg(feature = "scheme")]
+use crate::XCScheme;
+#[cfg(feature = "workspace")]
+use crate::XCWorkspace;
+use crate::{XCSaveOptions, XCodeProject};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Shared settings of the workspace embedded in a `.xcodeproj` bundle,
+/// `project.xcworkspace/xcshareddata/WorkspaceSettings.xcsettings`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct XCWorkspaceSettings {
+    /// Every setting, as read from the file
+    pub values: plist::Dictionary,
+}
+
+impl XCWorkspaceSettings {
+    fn string(&self, key: &str) -> Option<&str> {
+        self.values.get(key).and_then(|value| value.as_string())
+    }
+
+    /// Build system, e.g. `Original` for the legacy build system, `None` for Xcode's default
+    pub fn build_system(&self) -> Option<&str> {
+        self.string("BuildSystemType")
+    }
+
+    /// Whether Xcode creates schemes for new targets,
+    /// `IDEWorkspaceSharedSettings_AutocreateContextsIfNeeded`
+    pub fn autocreate_schemes(&self) -> Option<bool> {
+        self.values
+            .get("IDEWorkspaceSharedSettings_AutocreateContextsIfNeeded")
+            .and_then(|value| value.as_boolean())
+    }
+
+    /// Derived data location style, e.g. `WorkspaceRelativePath`, `None` for Xcode's default
+    pub fn derived_data_location_style(&self) -> Option<&str> {
+        self.string("DerivedDataLocationStyle")
+    }
+
+    /// Custom derived data location, used with a `DerivedDataLocationStyle` other than the
+    /// default
+    pub fn derived_data_custom_location(&self) -> Option<&str> {
+        self.string("DerivedDataCustomLocation")
+    }
+}
+
+/// Parts of a `.xcodeproj` bundle other than project.pbxproj, read on first access
+#[derive(Clone, Debug, Default)]
+pub(crate) struct XCProjectBundle {
+    path: PathBuf,
+    #[cfg(feature = "scheme")]
+    schemes: OnceLock<Vec<XCScheme>>,
+    #[cfg(feature = "workspace")]
+    workspace: OnceLock<Option<XCWorkspace>>,
+    workspace_settings: OnceLock<Option<XCWorkspaceSettings>>,
+    /// Files outside the bundle written on next save when missing, e.g. template sources
+    files: Vec<(PathBuf, String)>,
+}
+
+impl XCProjectBundle {
+    /// Create bundle at `path`, reading nothing yet
+    pub(crate) fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    /// Create bundle at `path` with already known schemes, e.g. for projects not saved yet
+    #[cfg(feature = "scheme")]
+    pub(crate) fn with_schemes<P: AsRef<Path>>(path: P, schemes: Vec<XCScheme>) -> Self {
+        Self {
+            schemes: OnceLock::from(schemes),
+            ..Self::new(path)
+        }
+    }
+
+    /// Set files to write on next save when missing, see [`XCodeProject::save`]
+    pub(crate) fn with_files(self, files: Vec<(PathBuf, String)>) -> Self {
+        Self { files, ..self }
+    }
+
+    fn embedded_workspace(&self) -> PathBuf {
+        self.path.join("project.xcworkspace")
+    }
+}
+
+/// File written when saving a project
+pub(crate) struct XCPendingWrite {
+    pub(crate) path: PathBuf,
+    pub(crate) content: String,
+    /// Leave the file as is if it exists, e.g. template sources
+    pub(crate) keep_existing: bool,
+}
+
+/// Write `writes`, creating missing parent directories
+pub(crate) fn write_files(writes: &[XCPendingWrite], options: &XCSaveOptions) -> Result<()> {
+    for write in writes {
+        if write.keep_existing && write.path.exists() {
+            continue;
+        }
+        if let Some(parent) = write.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        options.write(&write.path, &write.content)?;
+    }
+    Ok(())
+}
+
+impl XCodeProject {
+    /// Get path of the `.xcodeproj` bundle the project was opened from
+    #[must_use]
+    pub fn path(&self) -> &PathBuf {
+        &self.bundle.path
+    }
+
+    /// Write the project to its `.xcodeproj` bundle, creating the bundle if missing:
+    /// project.pbxproj, see [`PBXRootObject::to_pbxproj_string`], along with schemes not
+    /// saved yet and missing files of the template the project was created from.
+    ///
+    /// [`PBXRootObject::to_pbxproj_string`]: crate::pbxproj::PBXRootObject::to_pbxproj_string
+    pub fn save(&mut self) -> Result<()> {
+        self.save_with(&XCSaveOptions::new())
+    }
+
+    /// Write the project to its `.xcodeproj` bundle with the given options, see
+    /// [`XCodeProject::save`]
+    pub fn save_with(&mut self, options: &XCSaveOptions) -> Result<()> {
+        let writes = self.pending_writes(options);
+        write_files(&writes, options)?;
+        self.mark_written(&writes[0].content);
+        Ok(())
+    }
+
+    /// Get files [`XCodeProject::save_with`] writes, project.pbxproj first
+    pub(crate) fn pending_writes(&self, options: &XCSaveOptions) -> Vec<XCPendingWrite> {
+        let bundle = &self.bundle.path;
+        let mut writes = vec![XCPendingWrite {
+            path: bundle.join("project.pbxproj"),
+            content: self.pbxproj.to_pbxproj_string_with(options),
+            keep_existing: false,
+        }];
+
+        #[cfg(feature = "scheme")]
+        if let Some(schemes) = self.bundle.schemes.get() {
+            let schemes = schemes.iter().filter(|scheme| scheme.path.is_none());
+            writes.extend(schemes.map(|scheme| XCPendingWrite {
+                path: scheme.path_in(bundle),
+                content: scheme.to_xml(),
+                keep_existing: false,
+            }));
+        }
+
+        writes.extend(
+            self.bundle
+                .files
+                .iter()
+                .map(|(path, content)| XCPendingWrite {
+                    path: path.clone(),
+                    content: content.clone(),
+                    keep_existing: true,
+                }),
+        );
+        writes
+    }
+
+    /// Record files of [`XCodeProject::pending_writes`] as written, `pbxproj` being the
+    /// project.pbxproj content
+    pub(crate) fn mark_written(&mut self, pbxproj: &str) {
+        self.pbxproj.mark_written(pbxproj);
+
+        #[cfg(feature = "scheme")]
+        if let Some(schemes) = self.bundle.schemes.get_mut() {
+            let bundle = &self.bundle.path;
+            for scheme in schemes.iter_mut().filter(|scheme| scheme.path.is_none()) {
+                scheme.path = Some(scheme.path_in(bundle));
+            }
+        }
+        self.bundle.files.clear();
+    }
+
+    /// Get scheme and workspace files present on disk, read on first access by
+    /// [`XCodeProject::schemes`], [`XCodeProject::workspace`] and
+    /// [`XCodeProject::workspace_settings`]
+    pub(crate) fn bundle_files(&self) -> Vec<PathBuf> {
+        let sibling = self.root.join(format!("{}.xcworkspace", self.name));
+        let workspace = self.bundle.embedded_workspace();
+        let mut files = vec![
+            workspace.join("contents.xcworkspacedata"),
+            workspace
+                .join("xcshareddata")
+                .join("WorkspaceSettings.xcsettings"),
+        ];
+        for bundle in [self.bundle.path.as_path(), sibling.as_path()] {
+            let mut folders = vec![bundle.join("xcshareddata").join("xcschemes")];
+            let users = std::fs::read_dir(bundle.join("xcuserdata"))
+                .into_iter()
+                .flatten();
+            folders.extend(users.flatten().map(|user| user.path().join("xcschemes")));
+            for folder in folders {
+                let entries = std::fs::read_dir(folder).into_iter().flatten().flatten();
+                files.extend(
+                    entries
+                        .map(|entry| entry.path())
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "xcscheme")),
+                );
+            }
+        }
+        files.retain(|path| path.is_file());
+        files.sort();
+        files
+    }
+
+    /// Get shared and user schemes of the project and its sibling workspace, shared first. See
+    /// [`XCScheme::ownership`]. Read on first access.
+    #[cfg(feature = "scheme")]
+    pub fn schemes(&self) -> &[XCScheme] {
+        self.bundle.schemes.get_or_init(|| {
+            let sibling = self.root.join(format!("{}.xcworkspace", self.name));
+            let mut schemes = vec![];
+            for bundle in [self.bundle.path.as_path(), sibling.as_path()] {
+                match XCScheme::from_bundle(bundle) {
+                    Ok(found) => schemes.extend(found),
+                    Err(error) => log!(warn, "Skipping schemes of {bundle:?}: {error}"),
+                }
+            }
+            schemes.sort_by(|a, b| (&a.ownership, &a.name).cmp(&(&b.ownership, &b.name)));
+            schemes
+        })
+    }
+
+    /// Get mutable schemes, reading them first if not read yet
+    #[cfg(feature = "scheme")]
+    pub(crate) fn schemes_mut(&mut self) -> &mut Vec<XCScheme> {
+        self.schemes();
+        self.bundle.schemes.get_mut().unwrap()
+    }
+
+    /// Get workspace embedded in the `.xcodeproj` bundle, `project.xcworkspace`, which usually
+    /// references the project itself. Read on first access, `None` if missing or unreadable.
+    #[cfg(feature = "workspace")]
+    pub fn workspace(&self) -> Option<&XCWorkspace> {
+        self.bundle
+            .workspace
+            .get_or_init(|| {
+                let path = self.bundle.embedded_workspace();
+                if !path.join("contents.xcworkspacedata").exists() {
+                    return None;
+                }
+                XCWorkspace::new(&path)
+                    .map_err(|error| log!(warn, "Skipping workspace {path:?}: {error}"))
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    /// Get shared settings of the embedded workspace. Read on first access, `None` if missing
+    /// or unreadable.
+    pub fn workspace_settings(&self) -> Option<&XCWorkspaceSettings> {
+        self.bundle
+            .workspace_settings
+            .get_or_init(|| {
+                let path = self
+                    .bundle
+                    .embedded_workspace()
+                    .join("xcshareddata")
+                    .join("WorkspaceSettings.xcsettings");
+                if !path.exists() {
+                    return None;
+                }
+                plist::Value::from_file(&path)
+                    .map_err(|error| log!(warn, "Skipping settings {path:?}: {error}"))
+                    .ok()?
+                    .into_dictionary()
+                    .map(|values| XCWorkspaceSettings { values })
+            })
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn open_bundle() {
+        let root = std::env::temp_dir().join(format!("xcodeproj-bundle-{}", std::process::id()));
+        let bundle = root.join("Demo.xcodeproj");
+        let workspace = bundle.join("project.xcworkspace");
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+        fs::create_dir_all(workspace.join("xcshareddata")).unwrap();
+        fs::create_dir_all(bundle.join("xcshareddata/xcschemes")).unwrap();
+        fs::copy(
+            samples.join("samples/demo1.pbxproj"),
+            bundle.join("project.pbxproj"),
+        )
+        .unwrap();
+        fs::copy(
+            samples.join("schemes/demo1.xcscheme"),
+            bundle.join("xcshareddata/xcschemes/Demo.xcscheme"),
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("contents.xcworkspacedata"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Workspace\n   version = \"1.0\">\n   \
+             <FileRef\n      location = \"self:\">\n   </FileRef>\n</Workspace>\n",
+        )
+        .unwrap();
+        let mut settings = plist::Dictionary::new();
+        settings.insert("BuildSystemType".into(), "Original".into());
+        plist::Value::Dictionary(settings)
+            .to_file_xml(workspace.join("xcshareddata/WorkspaceSettings.xcsettings"))
+            .unwrap();
+
+        let project = XCodeProject::open(&bundle).unwrap();
+        assert_eq!(&bundle, project.path());
+        #[cfg(feature = "scheme")]
+        {
+            assert!(project.bundle.schemes.get().is_none());
+            assert_eq!(1, project.schemes().len());
+            assert_eq!("Demo", project.schemes()[0].name);
+        }
+        #[cfg(feature = "workspace")]
+        assert_eq!(1, project.workspace().unwrap().projects().len());
+        assert_eq!(
+            Some("Original"),
+            project.workspace_settings().unwrap().build_system()
+        );
+        assert_eq!(
+            None,
+            project.workspace_settings().unwrap().autocreate_schemes()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn save_bundle() {
+        let root = std::env::temp_dir().join(format!("xcodeproj-save-{}", std::process::id()));
+        let mut project = XCodeProject::from_template(
+            crate::Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            &root,
+        )
+        .unwrap();
+        project.save().unwrap();
+
+        let saved = XCodeProject::open(root.join("Demo.xcodeproj")).unwrap();
+        assert_eq!(project.targets().len(), saved.targets().len());
+        assert_eq!(Some("Demo"), saved.pbxproj().project_name());
+        #[cfg(feature = "scheme")]
+        assert_eq!("Demo", saved.schemes()[0].name);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
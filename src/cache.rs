@@ -0,0 +1,209 @@
+use crate::pbxproj::PBXRootObject;
+use crate::XCodeProject;
+use anyhow::Result;
+use md5::{Digest, Md5};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+static CACHE_ENABLED: AtomicBool = AtomicBool::new(false);
+static CACHE: OnceLock<Mutex<XCodeProjectCache>> = OnceLock::new();
+
+/// Cache of parsed projects keyed by xcodeproj folder path.
+///
+/// Entries are reused as long as `project.pbxproj` modification time and size are unchanged,
+/// or its content hash is unchanged when they differ, e.g. after a `touch` or checkout. Scheme
+/// and workspace files of the bundle must be unchanged too, by modification time and size, as
+/// cached projects keep them once read.
+#[derive(Debug, Default)]
+pub struct XCodeProjectCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    modified: SystemTime,
+    len: u64,
+    hash: Vec<u8>,
+    /// Modification time and size of scheme and workspace files, see [`stamps`]
+    stamps: Vec<(PathBuf, SystemTime, u64)>,
+    project: XCodeProject,
+}
+
+impl XCodeProjectCache {
+    /// Create a new empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open project at `xcodeproj_folder`, returning the cached parse if the project is
+    /// unchanged since it was last opened.
+    pub fn open<P: AsRef<Path>>(&mut self, xcodeproj_folder: P) -> Result<XCodeProject> {
+        let xcodeproj_folder = xcodeproj_folder.as_ref();
+        let pbxproj_path = xcodeproj_folder.join("project.pbxproj");
+        let metadata = std::fs::metadata(&pbxproj_path)?;
+        let (modified, len) = (metadata.modified()?, metadata.len());
+
+        let mut content = None;
+        if let Some(entry) = self.entries.get_mut(xcodeproj_folder) {
+            if entry.stamps == stamps(&entry.project) {
+                if entry.modified == modified && entry.len == len {
+                    return Ok(entry.project.clone());
+                }
+
+                let bytes = std::fs::read(&pbxproj_path)?;
+                if entry.hash == Md5::digest(&bytes).as_slice() {
+                    entry.modified = modified;
+                    entry.len = len;
+                    return Ok(entry.project.clone());
+                }
+                content = Some(bytes);
+            }
+        }
+
+        let bytes = match content {
+            Some(bytes) => bytes,
+            None => std::fs::read(&pbxproj_path)?,
+        };
+        let hash = Md5::digest(&bytes).to_vec();
+        let pbxproj = PBXRootObject::from_file_bytes(&pbxproj_path, &bytes)?;
+        let project = XCodeProject::with_pbxproj(xcodeproj_folder, pbxproj);
+        self.entries.insert(
+            xcodeproj_folder.to_path_buf(),
+            CacheEntry {
+                modified,
+                len,
+                hash,
+                stamps: stamps(&project),
+                project: project.clone(),
+            },
+        );
+
+        Ok(project)
+    }
+
+    /// Remove cached project at `xcodeproj_folder`
+    pub fn invalidate<P: AsRef<Path>>(&mut self, xcodeproj_folder: P) {
+        self.entries.remove(xcodeproj_folder.as_ref());
+    }
+
+    /// Remove all cached projects
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached projects
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no projects are cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Get modification time and size of scheme and workspace files of `project`
+fn stamps(project: &XCodeProject) -> Vec<(PathBuf, SystemTime, u64)> {
+    project
+        .bundle_files()
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(&path).ok()?;
+            Some((path, metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+impl XCodeProject {
+    /// Open the `.xcodeproj` bundle at `xcodeproj_folder` as one handle: project.pbxproj is
+    /// parsed right away, while schemes, the embedded workspace and its settings are read on
+    /// first access, see [`XCodeProject::schemes`] and [`XCodeProject::workspace_settings`].
+    ///
+    /// When caching is enabled with [`XCodeProject::set_cache_enabled`], the parsed project is
+    /// returned from a process wide [`XCodeProjectCache`] if the project is unchanged.
+    pub fn open<P: AsRef<Path>>(xcodeproj_folder: P) -> Result<Self> {
+        if !CACHE_ENABLED.load(Ordering::Relaxed) {
+            return Self::new(xcodeproj_folder);
+        }
+
+        CACHE
+            .get_or_init(Default::default)
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Project cache is poisoned"))?
+            .open(xcodeproj_folder)
+    }
+
+    /// Enable or disable the process wide cache used by [`XCodeProject::open`]. Disabling the
+    /// cache drops all cached projects.
+    pub fn set_cache_enabled(enabled: bool) {
+        CACHE_ENABLED.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            if let Some(Ok(mut cache)) = CACHE.get().map(|cache| cache.lock()) {
+                cache.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_cached() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-cache-{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let xcodeproj_folder = root.join("Demo.xcodeproj");
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        std::fs::create_dir_all(&xcodeproj_folder).unwrap();
+        std::fs::copy(
+            samples.join("demo1.pbxproj"),
+            xcodeproj_folder.join("project.pbxproj"),
+        )
+        .unwrap();
+
+        let mut cache = XCodeProjectCache::new();
+        let project = cache.open(&xcodeproj_folder).unwrap();
+        assert_eq!(
+            project.fingerprint(),
+            cache.open(&xcodeproj_folder).unwrap().fingerprint()
+        );
+        assert_eq!(1, cache.len());
+
+        std::fs::copy(
+            samples.join("demo2.pbxproj"),
+            xcodeproj_folder.join("project.pbxproj"),
+        )
+        .unwrap();
+        assert_ne!(
+            project.fingerprint(),
+            cache.open(&xcodeproj_folder).unwrap().fingerprint()
+        );
+
+        #[cfg(feature = "scheme")]
+        {
+            assert!(cache.open(&xcodeproj_folder).unwrap().schemes().is_empty());
+            let schemes = xcodeproj_folder.join("xcshareddata/xcschemes");
+            std::fs::create_dir_all(&schemes).unwrap();
+            std::fs::copy(
+                samples.join("../schemes/demo1.xcscheme"),
+                schemes.join("Demo.xcscheme"),
+            )
+            .unwrap();
+            assert_eq!(1, cache.open(&xcodeproj_folder).unwrap().schemes().len());
+        }
+
+        cache.invalidate(&xcodeproj_folder);
+        assert!(cache.is_empty());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+use crate::pbxproj::*;
+use crate::XCodeProject;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Entry of a `compile_commands.json` compilation database
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct XCCompileCommand {
+    /// Working directory of the compilation, the project source root
+    pub directory: PathBuf,
+    /// Absolute path of the compiled source file
+    pub file: PathBuf,
+    /// Compiler invocation, starting with `swiftc` or `clang`
+    pub arguments: Vec<String>,
+}
+
+impl XCodeProject {
+    /// Get approximate compiler invocations of every source file of the target with the given
+    /// reference for `configuration`, for clangd or sourcekit-lsp on projects never built.
+    ///
+    /// Arguments are derived from [`PBXRootObject::resolved_build_settings`], so SDK paths,
+    /// xcconfig settings and generated sources are missing. Swift entries list all swift sources
+    /// of the target, as the whole module is compiled at once.
+    pub fn compile_commands(
+        &self,
+        target: &str,
+        configuration: &str,
+    ) -> Result<Vec<XCCompileCommand>> {
+        let mut settings = self.resolved_build_settings(target, configuration)?;
+        let target = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?;
+        let name = target.name.map(|name| name.as_str()).unwrap_or_default();
+        let root = self.root.to_string_lossy().to_string();
+        for (key, value) in [
+            ("SRCROOT", root.as_str()),
+            ("PROJECT_DIR", root.as_str()),
+            ("SOURCE_ROOT", root.as_str()),
+            ("PROJECT_NAME", self.name.as_str()),
+            ("TARGET_NAME", name),
+            ("CONFIGURATION", configuration),
+        ] {
+            settings.insert_value(key, value);
+        }
+        if settings.get_value("PRODUCT_NAME").is_none() {
+            settings.insert_value("PRODUCT_NAME", name);
+        }
+
+        let sources = target
+            .build_phases
+            .iter()
+            .filter(|phase| phase.is_sources())
+            .flat_map(|phase| phase.files.iter())
+            .filter_map(|build_file| {
+                let file = build_file.file.as_ref()?;
+                self.objects().get_full_path(&file.id, &self.root)
+            })
+            .collect::<Vec<_>>();
+        let swift_sources = sources
+            .iter()
+            .filter(|path| path.extension().map(|ext| ext == "swift") == Some(true))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        let swift = swift_arguments(&settings, &swift_sources);
+        Ok(sources
+            .into_iter()
+            .filter_map(|file| {
+                let extension = file.extension()?.to_str()?;
+                let arguments = match extension {
+                    "swift" => swift.clone(),
+                    extension => clang_arguments(&settings, clang_language(extension)?, &file),
+                };
+                Some(XCCompileCommand {
+                    directory: self.root.clone(),
+                    file,
+                    arguments,
+                })
+            })
+            .collect())
+    }
+
+    /// Write [`XCodeProject::compile_commands`] of a target as `compile_commands.json` content
+    /// to `path`.
+    pub fn write_compile_commands<P: AsRef<Path>>(
+        &self,
+        target: &str,
+        configuration: &str,
+        path: P,
+    ) -> Result<()> {
+        let commands = self.compile_commands(target, configuration)?;
+        std::fs::write(path, serde_json::to_string_pretty(&commands)?)?;
+        Ok(())
+    }
+}
+
+/// Expanded values of list setting `key`, with recursive `/**` search path suffixes removed
+fn setting_values(settings: &PBXHashMap, key: &str) -> Vec<String> {
+    let values = match settings.get_value(key) {
+        Some(PBXValue::Vec(values)) => values
+            .as_vec_strings()
+            .into_iter()
+            .map(|value| expand_build_setting(value, settings))
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(PBXValue::String(value)) => expand_build_setting(value, settings),
+        _ => return vec![],
+    };
+    split_setting(&values)
+        .into_iter()
+        .map(|value| value.trim_end_matches("/**").to_string())
+        .collect()
+}
+
+fn search_path_arguments(settings: &PBXHashMap, args: &mut Vec<String>, prefix: &str) {
+    for (flag, key) in [
+        ("-I", "HEADER_SEARCH_PATHS"),
+        ("-I", "USER_HEADER_SEARCH_PATHS"),
+        ("-F", "FRAMEWORK_SEARCH_PATHS"),
+    ] {
+        for path in setting_values(settings, key) {
+            if !prefix.is_empty() {
+                args.push(prefix.to_string());
+            }
+            args.push(format!("{flag}{path}"));
+        }
+    }
+}
+
+fn swift_arguments(settings: &PBXHashMap, sources: &[String]) -> Vec<String> {
+    let module = settings
+        .get_string("PRODUCT_MODULE_NAME")
+        .map(|name| expand_build_setting(name, settings))
+        .unwrap_or_else(|| expand_build_setting("$(PRODUCT_NAME:c99extidentifier)", settings));
+    let mut args = vec!["swiftc".to_string(), "-module-name".into(), module];
+    for condition in setting_values(settings, "SWIFT_ACTIVE_COMPILATION_CONDITIONS") {
+        args.push(format!("-D{condition}"));
+    }
+    for path in setting_values(settings, "SWIFT_INCLUDE_PATHS") {
+        args.push(format!("-I{path}"));
+    }
+    search_path_arguments(settings, &mut args, "-Xcc");
+    if let Some(header) = settings.get_string("SWIFT_OBJC_BRIDGING_HEADER") {
+        args.push("-import-objc-header".into());
+        args.push(expand_build_setting(header, settings));
+    }
+    args.extend(setting_values(settings, "OTHER_SWIFT_FLAGS"));
+    args.extend(sources.iter().cloned());
+    args
+}
+
+fn clang_language(extension: &str) -> Option<&'static str> {
+    match extension {
+        "c" => Some("c"),
+        "m" => Some("objective-c"),
+        "mm" => Some("objective-c++"),
+        "cc" | "cpp" | "cxx" => Some("c++"),
+        _ => None,
+    }
+}
+
+fn clang_arguments(settings: &PBXHashMap, language: &str, file: &Path) -> Vec<String> {
+    let mut args = vec!["clang".to_string(), "-x".into(), language.into()];
+    if settings
+        .get_string("CLANG_ENABLE_OBJC_ARC")
+        .map(|v| v.as_str())
+        == Some("YES")
+        || settings.get_bool("CLANG_ENABLE_OBJC_ARC") == Some(&true)
+    {
+        args.push("-fobjc-arc".into());
+    }
+    for definition in setting_values(settings, "GCC_PREPROCESSOR_DEFINITIONS") {
+        args.push(format!("-D{definition}"));
+    }
+    search_path_arguments(settings, &mut args, "");
+    args.extend(setting_values(settings, "OTHER_CFLAGS"));
+    if language.ends_with("++") {
+        args.extend(setting_values(settings, "OTHER_CPLUSPLUSFLAGS"));
+    }
+    args.extend(["-c".into(), file.to_string_lossy().to_string()]);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Template, XCodeProject};
+    use std::path::Path;
+
+    #[test]
+    fn compile_commands() {
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            "/tmp/Demo",
+        )
+        .unwrap();
+        let target = project.targets()[0].id.clone();
+        project
+            .set_setting_all(
+                &target,
+                "SWIFT_ACTIVE_COMPILATION_CONDITIONS",
+                "DEBUG $(inherited)",
+            )
+            .unwrap();
+
+        let commands = project.compile_commands(&target, "Debug").unwrap();
+        assert!(!commands.is_empty());
+        for command in commands.iter() {
+            assert_eq!(Path::new("/tmp/Demo"), command.directory);
+            assert!(command.file.starts_with("/tmp/Demo/Demo"));
+            assert_eq!(
+                ["swiftc", "-module-name", "Demo", "-DDEBUG"],
+                command.arguments[..4]
+            );
+            assert!(command
+                .arguments
+                .contains(&command.file.to_string_lossy().to_string()));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::pbxproj::expand_build_setting;
+use crate::XCodeProject;
+use anyhow::Result;
+use std::path::PathBuf;
+
+const CODE_SIGN_ENTITLEMENTS: &str = "CODE_SIGN_ENTITLEMENTS";
+const APP_SANDBOX_ENTITLEMENT: &str = "com.apple.security.app-sandbox";
+
+impl XCodeProject {
+    /// Enable hardened runtime of the target with the given reference, required for macOS
+    /// notarization.
+    ///
+    /// `ENABLE_HARDENED_RUNTIME` is set across all target configurations and the target
+    /// entitlements files are created if missing. Returns the entitlements file paths, see
+    /// [`XCodeProject::entitlements_paths`].
+    pub fn enable_hardened_runtime(&mut self, target: &str) -> Result<Vec<PathBuf>> {
+        self.pbxproj
+            .objects_mut()
+            .update_target_build_settings(target, |settings| {
+                settings.insert_value("ENABLE_HARDENED_RUNTIME", true);
+            })?;
+        self.update_entitlements(target, |_| {})
+    }
+
+    /// Enable app sandbox of the target with the given reference.
+    ///
+    /// `ENABLE_APP_SANDBOX` is set across all target configurations and
+    /// `com.apple.security.app-sandbox` is enabled in the target entitlements files, which are
+    /// created if missing. Returns the entitlements file paths, see
+    /// [`XCodeProject::entitlements_paths`].
+    pub fn enable_app_sandbox(&mut self, target: &str) -> Result<Vec<PathBuf>> {
+        self.pbxproj
+            .objects_mut()
+            .update_target_build_settings(target, |settings| {
+                settings.insert_value("ENABLE_APP_SANDBOX", true);
+            })?;
+        self.update_entitlements(target, |entitlements| {
+            entitlements.insert(APP_SANDBOX_ENTITLEMENT.into(), true.into());
+        })
+    }
+
+    /// Get entitlements file paths of the target with the given reference, one per distinct
+    /// `CODE_SIGN_ENTITLEMENTS` across its configurations.
+    ///
+    /// Settings are resolved for each configuration, expanding references such as `$(SRCROOT)`
+    /// and `$(PROJECT_DIR)` to the project root. Relative paths are joined to the project root.
+    pub fn entitlements_paths(&self, target: &str) -> Result<Vec<PathBuf>> {
+        let configurations = self
+            .pbxproj
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?
+            .build_configuration_list
+            .as_ref()
+            .map(|list| {
+                list.build_configurations
+                    .iter()
+                    .map(|configuration| configuration.name.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let root = self.root.to_string_lossy().to_string();
+        let mut paths = vec![];
+        for configuration in configurations {
+            let mut settings = self
+                .pbxproj
+                .resolved_build_settings(target, &configuration)?;
+            for key in ["SRCROOT", "PROJECT_DIR", "SOURCE_ROOT"] {
+                settings.insert_value(key, root.as_str());
+            }
+            let Some(value) = settings.get_string(CODE_SIGN_ENTITLEMENTS) else {
+                continue;
+            };
+            let path = self.root.join(expand_build_setting(value, &settings));
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Apply `update` to the entitlements of the target with the given reference and write them
+    /// back to disk.
+    ///
+    /// Entitlements files are resolved from `CODE_SIGN_ENTITLEMENTS` of each configuration, see
+    /// [`XCodeProject::entitlements_paths`], defaulting to `<Target>/<Target>.entitlements` for
+    /// configurations without it. Files under the project root are registered in the project if
+    /// missing.
+    fn update_entitlements<F>(&mut self, target: &str, update: F) -> Result<Vec<PathBuf>>
+    where
+        F: Fn(&mut plist::Dictionary),
+    {
+        let default = {
+            let target = self
+                .pbxproj
+                .get_target(target)
+                .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?;
+            let name = target
+                .name
+                .ok_or_else(|| anyhow::anyhow!("Target {:?} has no name", target.id))?;
+            format!("{name}/{name}.entitlements")
+        };
+        self.pbxproj
+            .objects_mut()
+            .update_target_build_settings(target, |settings| {
+                if settings.get_value(CODE_SIGN_ENTITLEMENTS).is_none() {
+                    settings.insert_value(CODE_SIGN_ENTITLEMENTS, default.as_str());
+                }
+            })?;
+
+        let paths = self.entitlements_paths(target)?;
+        for path in paths.iter() {
+            if let Ok(relative_path) = path.strip_prefix(&self.root) {
+                self.pbxproj.get_or_add_file_reference(relative_path)?;
+            }
+
+            let mut entitlements = if path.exists() {
+                plist::Value::from_file(path)?
+                    .into_dictionary()
+                    .ok_or_else(|| anyhow::anyhow!("{path:?} is not a dictionary"))?
+            } else {
+                plist::Dictionary::new()
+            };
+
+            update(&mut entitlements);
+
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            plist::Value::Dictionary(entitlements).to_file_xml(path)?;
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::PBXObjectCollection;
+    use crate::{Template, XCodeProject};
+
+    #[test]
+    fn enable_app_sandbox() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-entitlements-{}",
+            PBXObjectCollection::default().generate_reference()
+        ));
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            &root,
+        )
+        .unwrap();
+        let target = project.targets()[0].id.clone();
+
+        let paths = project.enable_app_sandbox(&target).unwrap();
+        assert_eq!(paths, project.enable_hardened_runtime(&target).unwrap());
+        assert_eq!(vec![project.root.join("Demo/Demo.entitlements")], paths);
+
+        let entitlements = plist::Value::from_file(&paths[0])
+            .unwrap()
+            .into_dictionary()
+            .unwrap();
+        let targets = project.targets();
+        let settings = &targets[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+
+        assert_eq!(
+            Some(&plist::Value::Boolean(true)),
+            entitlements.get("com.apple.security.app-sandbox")
+        );
+        assert_eq!(
+            Some(&"Demo/Demo.entitlements".to_string()),
+            settings.get_string("CODE_SIGN_ENTITLEMENTS")
+        );
+        assert_eq!(Some(&true), settings.get_bool("ENABLE_HARDENED_RUNTIME"));
+
+        std::fs::remove_dir_all(&project.root).unwrap();
+    }
+
+    #[test]
+    fn entitlements_per_configuration() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-entitlements-{}",
+            PBXObjectCollection::default().generate_reference()
+        ));
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            &root,
+        )
+        .unwrap();
+        let target = project.targets()[0].id.clone();
+        let objects = project.pbxproj.objects_mut();
+        for mut configuration in objects.get_build_configurations_mut(&target) {
+            let path = match configuration.get_string("name").unwrap().as_str() {
+                "Debug" => "$(SRCROOT)/Demo/Debug.entitlements",
+                _ => "$(PROJECT_DIR)/Demo/Release.entitlements",
+            };
+            configuration
+                .build_settings_mut()
+                .insert_value("CODE_SIGN_ENTITLEMENTS", path);
+        }
+
+        let paths = project.enable_app_sandbox(&target).unwrap();
+        assert_eq!(
+            vec![
+                project.root.join("Demo/Debug.entitlements"),
+                project.root.join("Demo/Release.entitlements"),
+            ],
+            paths
+        );
+        for path in paths.iter() {
+            let entitlements = plist::Value::from_file(path)
+                .unwrap()
+                .into_dictionary()
+                .unwrap();
+            assert_eq!(
+                Some(&plist::Value::Boolean(true)),
+                entitlements.get("com.apple.security.app-sandbox")
+            );
+        }
+        assert!(project.pbxproj.files().iter().any(|file| file
+            .path
+            .is_some_and(|path| path.ends_with("Release.entitlements"))));
+
+        std::fs::remove_dir_all(&project.root).unwrap();
+    }
+}
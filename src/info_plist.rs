@@ -0,0 +1,184 @@
+use crate::pbxproj::{PBXValue, XCInfoPlistKey};
+use crate::XCodeProject;
+use anyhow::Result;
+
+const INFOPLIST_FILE: &str = "INFOPLIST_FILE";
+
+/// Get build setting value Xcode generates `value` of `key` from, `None` for values that
+/// can't be expressed as a build setting.
+fn setting_value(key: XCInfoPlistKey, value: &plist::Value) -> Option<PBXValue> {
+    match value {
+        plist::Value::String(value) => Some(value.as_str().into()),
+        plist::Value::Boolean(value) => Some((*value).into()),
+        plist::Value::Integer(value) => Some(value.to_string().as_str().into()),
+        plist::Value::Array(values) => values
+            .iter()
+            .map(|value| value.as_string())
+            .collect::<Option<Vec<_>>>()
+            .map(|values| values.join(" ").as_str().into()),
+        // An empty launch screen is what `UILaunchScreen_Generation` generates
+        plist::Value::Dictionary(values)
+            if key == XCInfoPlistKey::LaunchScreenGeneration && values.is_empty() =>
+        {
+            Some(true.into())
+        }
+        _ => None,
+    }
+}
+
+impl XCodeProject {
+    /// Move keys of the Info.plist file of the target with the given reference into
+    /// `INFOPLIST_KEY_*` build settings, and enable `GENERATE_INFOPLIST_FILE`.
+    ///
+    /// Only keys Xcode can generate are moved, see [`XCInfoPlistKey`], along with an empty
+    /// `UILaunchScreen`. Other keys stay in the file, which remains set as `INFOPLIST_FILE`
+    /// and is merged into the generated Info.plist. Returns the Info.plist names of moved
+    /// keys, in file order.
+    pub fn migrate_info_plist(&mut self, target: &str) -> Result<Vec<String>> {
+        let relative_path = self
+            .pbxproj
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?
+            .build_configuration_list
+            .as_ref()
+            .and_then(|list| {
+                list.build_configurations
+                    .iter()
+                    .find_map(|c| c.build_settings.get_string(INFOPLIST_FILE))
+            })
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} has no {INFOPLIST_FILE}"))?;
+
+        let path = self
+            .root
+            .join(relative_path.trim_start_matches("$(SRCROOT)/"));
+        let mut info = plist::Value::from_file(&path)?
+            .into_dictionary()
+            .ok_or_else(|| anyhow::anyhow!("{path:?} is not a dictionary"))?;
+
+        let mut migrated = vec![];
+        for (name, value) in info.iter() {
+            let key = match name.as_str() {
+                "UILaunchScreen" => Some(XCInfoPlistKey::LaunchScreenGeneration),
+                name => XCInfoPlistKey::from_plist_key(name),
+            };
+            if let Some(key) = key {
+                if let Some(value) = setting_value(key, value) {
+                    migrated.push((name.clone(), key.build_setting(), value));
+                }
+            }
+        }
+
+        self.pbxproj
+            .objects_mut()
+            .update_target_build_settings(target, |settings| {
+                for (_, setting, value) in migrated.iter() {
+                    settings.insert_value(setting.as_str(), value.clone());
+                }
+                settings.insert_value("GENERATE_INFOPLIST_FILE", true);
+            })?;
+
+        for (name, _, _) in migrated.iter() {
+            info.remove(name);
+        }
+        plist::Value::Dictionary(info).to_file_xml(&path)?;
+
+        Ok(migrated.into_iter().map(|(name, _, _)| name).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::PBXObjectCollection;
+    use crate::{Template, XCodeProject};
+
+    #[test]
+    fn migrate_info_plist() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-info-plist-{}",
+            PBXObjectCollection::default().generate_reference()
+        ));
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            &root,
+        )
+        .unwrap();
+        let target = project.targets()[0].id.clone();
+        assert!(project.migrate_info_plist(&target).is_err());
+
+        let mut info = plist::Dictionary::new();
+        info.insert(
+            "CFBundleIdentifier".into(),
+            "$(PRODUCT_BUNDLE_IDENTIFIER)".into(),
+        );
+        info.insert("NSCameraUsageDescription".into(), "Scan words".into());
+        info.insert(
+            "UISupportedInterfaceOrientations~ipad".into(),
+            vec![
+                plist::Value::from("UIInterfaceOrientationPortrait"),
+                plist::Value::from("UIInterfaceOrientationLandscapeLeft"),
+            ]
+            .into(),
+        );
+        info.insert("UIRequiresFullScreen".into(), true.into());
+        info.insert("UILaunchScreen".into(), plist::Dictionary::new().into());
+        std::fs::create_dir_all(project.root.join("Demo")).unwrap();
+        let path = project.root.join("Demo/Info.plist");
+        plist::Value::Dictionary(info).to_file_xml(&path).unwrap();
+        project
+            .pbxproj
+            .objects_mut()
+            .update_target_build_settings(&target, |settings| {
+                settings.insert_value("INFOPLIST_FILE", "Demo/Info.plist");
+                settings.insert_value("GENERATE_INFOPLIST_FILE", false);
+            })
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                "NSCameraUsageDescription",
+                "UISupportedInterfaceOrientations~ipad",
+                "UIRequiresFullScreen",
+                "UILaunchScreen"
+            ],
+            project.migrate_info_plist(&target).unwrap()
+        );
+
+        let info = plist::Value::from_file(&path)
+            .unwrap()
+            .into_dictionary()
+            .unwrap();
+        assert_eq!(vec!["CFBundleIdentifier"], info.keys().collect::<Vec<_>>());
+
+        let targets = project.targets();
+        let settings = &targets[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+        assert_eq!(Some(&true), settings.get_bool("GENERATE_INFOPLIST_FILE"));
+        assert_eq!(
+            Some(&"Scan words".to_string()),
+            settings.get_string("INFOPLIST_KEY_NSCameraUsageDescription")
+        );
+        assert_eq!(
+            Some(&"UIInterfaceOrientationPortrait UIInterfaceOrientationLandscapeLeft".to_string()),
+            settings.get_string("INFOPLIST_KEY_UISupportedInterfaceOrientations_iPad")
+        );
+        assert_eq!(
+            Some(&true),
+            settings.get_bool("INFOPLIST_KEY_UIRequiresFullScreen")
+        );
+        assert_eq!(
+            Some(&true),
+            settings.get_bool("INFOPLIST_KEY_UILaunchScreen_Generation")
+        );
+
+        std::fs::remove_dir_all(&project.root).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::too_many_arguments)]
 #![deny(future_incompatible)]
 #![deny(nonstandard_style)]
 #![deny(missing_docs)]
@@ -9,54 +10,82 @@ use anyhow::Result;
 use pbxproj::PBXRootObject;
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bundle;
+mod cache;
+mod compile_commands;
+mod entitlements;
+mod info_plist;
+mod localization;
 mod macros;
+mod package_resolved;
 pub mod pbxproj;
+mod save;
+#[cfg(feature = "scheme")]
 mod scheme;
+mod spec;
+mod template;
+#[cfg(feature = "scheme")]
+mod test_plan;
+#[cfg(feature = "workspace")]
+mod workspace;
 pub mod xcode;
-pub use scheme::XCScheme;
+pub use bundle::XCWorkspaceSettings;
+pub use cache::XCodeProjectCache;
+pub use compile_commands::XCCompileCommand;
+pub use localization::{XCLocalizationReport, XCLocalizedResource, XCLocalizedResourceKind};
+pub use package_resolved::{XCPackagePin, XCPackageUrlRewrite};
+pub use save::XCSaveOptions;
+#[cfg(feature = "scheme")]
+pub use scheme::{
+    XCAnalyzeAction, XCArchiveAction, XCBuildableReference, XCLaunchAction, XCLaunchRunnable,
+    XCProfileAction, XCScheme, XCSchemeElement, XCSchemeOwnership, XCTestExecutionOrdering,
+    XCTestableReference,
+};
+pub use spec::{PackageSpec, ProjectSpec, SchemeSpec, TargetSpec};
+pub use template::Template;
+#[cfg(feature = "scheme")]
+pub use test_plan::XCTestPlanOptions;
+#[cfg(feature = "workspace")]
+pub use workspace::{
+    XCWorkspace, XCWorkspaceData, XCWorkspaceDependency, XCWorkspaceElement, XCWorkspaceLocation,
+    XCWorkspacePackage,
+};
 
 /// Main presentation of XCodeProject
-#[derive(Debug, Default, derive_deref_rs::Deref)]
+#[derive(Clone, Debug, Default, derive_deref_rs::Deref)]
 pub struct XCodeProject {
     name: String,
     root: PathBuf,
     #[deref]
     pbxproj: PBXRootObject,
-    schemes: Vec<XCScheme>,
+    bundle: bundle::XCProjectBundle,
 }
 
 impl XCodeProject {
-    // /xcshareddata/xcschemes
-    /// Create new XCodeProject object from xcodeproj_folder
+    /// Create new XCodeProject object from xcodeproj_folder, parsing project.pbxproj. Schemes,
+    /// the embedded workspace and its settings are read on first access.
     pub fn new<P: AsRef<Path>>(xcodeproj_folder: P) -> Result<Self> {
         let xcodeproj_folder = xcodeproj_folder.as_ref();
+        let pbxproj = PBXRootObject::try_from(xcodeproj_folder.join("project.pbxproj"))?;
+        Ok(Self::with_pbxproj(xcodeproj_folder, pbxproj))
+    }
+
+    /// Create XCodeProject object from xcodeproj_folder with its already parsed project.pbxproj
+    pub(crate) fn with_pbxproj(xcodeproj_folder: &Path, pbxproj: PBXRootObject) -> Self {
         let name = xcodeproj_folder
             .file_name()
             .and_then(|name| Some(name.to_str()?.split_once(".")?.0.to_string()))
             .unwrap();
         let root = xcodeproj_folder.parent().unwrap().to_path_buf();
-        let mut schemes = vec![];
-        let xcworkspace_folder = root.join(format!("{name}.xcworkspace"));
-        let schemes_folder = xcworkspace_folder.join("xcshareddata").join("xcschemes");
-
-        // NOTE: Should xcodeproj folder be accounted for?
-        if schemes_folder.exists() {
-            let mut files = std::fs::read_dir(schemes_folder)?;
-            while let Some(Ok(entry)) = files.next() {
-                if let Ok(xcscheme) = XCScheme::new(entry.path()) {
-                    schemes.push(xcscheme);
-                }
-            }
-        }
 
-        let pbxproj = PBXRootObject::try_from(xcodeproj_folder.join("project.pbxproj"))?;
-
-        Ok(Self {
+        Self {
             name,
             root,
             pbxproj,
-            schemes,
-        })
+            bundle: bundle::XCProjectBundle::new(xcodeproj_folder),
+        }
     }
 
     /// Get a reference to the xcode project's name.
@@ -77,6 +106,39 @@ impl XCodeProject {
         &self.pbxproj
     }
 
+    /// Get a mutable reference to the xcode project's pbxproj.
+    #[must_use]
+    pub fn pbxproj_mut(&mut self) -> &mut PBXRootObject {
+        &mut self.pbxproj
+    }
+
+    /// Apply multi-step pbxproj edits atomically, see [`PBXRootObject::transaction`].
+    pub fn transaction<T, F>(&mut self, edit: F) -> Result<T>
+    where
+        F: FnOnce(&mut PBXRootObject) -> Result<T>,
+    {
+        self.pbxproj.transaction(edit)
+    }
+
+    /// Normalize the pbxproj, see [`PBXRootObject::normalize`], checking missing files against
+    /// the project root.
+    pub fn normalize(
+        &mut self,
+        options: &pbxproj::PBXNormalizeOptions,
+    ) -> pbxproj::PBXNormalizeReport {
+        self.pbxproj.normalize(options, Some(&self.root))
+    }
+
+    /// Copy the target with the given name from `source`, see [`PBXRootObject::import_target`].
+    pub fn import_target(
+        &mut self,
+        source: &XCodeProject,
+        name: &str,
+        options: &pbxproj::PBXImportOptions,
+    ) -> Result<String> {
+        self.pbxproj.import_target(&source.pbxproj, name, options)
+    }
+
     /// Get build file names with all targets
     pub fn build_file_names(&self) -> Vec<String> {
         self.build_files()
@@ -88,8 +150,13 @@ impl XCodeProject {
             .collect::<Vec<_>>()
     }
 
-    /// Get XCSchemes
-    pub fn schemes(&self) -> &[XCScheme] {
-        self.schemes.as_ref()
+    /// Get `xcodebuild` command operating on the project, or on its sibling workspace if any.
+    pub fn build_command(&self) -> xcode::XCBuildCommand {
+        let workspace = self.root.join(format!("{}.xcworkspace", self.name));
+        if workspace.exists() {
+            xcode::XCBuildCommand::workspace(workspace)
+        } else {
+            xcode::XCBuildCommand::project(self.root.join(format!("{}.xcodeproj", self.name)))
+        }
     }
 }
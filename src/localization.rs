@@ -0,0 +1,281 @@
+use crate::pbxproj::*;
+use crate::XCodeProject;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Region of base internationalization, not a language of its own
+const BASE_REGION: &str = "Base";
+
+/// Localization state of a project, see [`XCodeProject::localization_report`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct XCLocalizationReport {
+    /// Project development region, `developmentRegion`
+    pub development_region: Option<String>,
+    /// Project regions, `knownRegions`
+    pub known_regions: Vec<String>,
+    /// Localizable resources, sorted by path
+    pub resources: Vec<XCLocalizedResource>,
+}
+
+impl XCLocalizationReport {
+    /// Whether every resource is localized in every known region
+    pub fn is_complete(&self) -> bool {
+        self.resources
+            .iter()
+            .all(|resource| resource.missing_languages.is_empty())
+    }
+}
+
+/// Kind of [`XCLocalizedResource`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum XCLocalizedResourceKind {
+    /// `PBXVariantGroup` with a file per language, e.g. `Localizable.strings` or storyboards
+    VariantGroup,
+    /// `.xcstrings` string catalog holding every language
+    StringCatalog,
+}
+
+/// Localizable resource of a [`XCLocalizationReport`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct XCLocalizedResource {
+    /// Reference of the variant group or catalog file reference
+    pub reference: String,
+    /// Path relative to the project source root
+    pub path: PathBuf,
+    /// Resource kind
+    pub kind: XCLocalizedResourceKind,
+    /// Languages the resource is localized in, sorted
+    pub languages: Vec<String>,
+    /// Known regions the resource isn't fully localized in, sorted. A base localization covers
+    /// the development region.
+    pub missing_languages: Vec<String>,
+}
+
+impl XCodeProject {
+    /// Get known regions, every variant group and string catalog of the project, and languages
+    /// each of them is missing, e.g. to fail CI on incomplete translations.
+    ///
+    /// String catalogs are read from disk. A catalog language is missing when a string to
+    /// translate has no localization in it.
+    pub fn localization_report(&self) -> Result<XCLocalizationReport> {
+        let project = self.pbxproj.root_project();
+        let development_region = project.development_region.cloned();
+        let known_regions = project
+            .known_regions
+            .iter()
+            .map(|region| region.to_string())
+            .collect::<Vec<_>>();
+        let objects = self.pbxproj.objects();
+        let path = |reference: &str| {
+            objects
+                .get_fs_reference_path(reference)
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        };
+
+        let mut resources = vec![];
+        for group in self.groups() {
+            if !group.is_varient_group() {
+                continue;
+            }
+            let mut languages = group
+                .children
+                .iter()
+                .filter_map(child_language)
+                .collect::<Vec<_>>();
+            languages.sort();
+            languages.dedup();
+            let covered = |region: &str| {
+                languages.iter().any(|l| l == region)
+                    || (languages.iter().any(|l| l == BASE_REGION)
+                        && development_region.as_deref() == Some(region))
+            };
+            let missing_languages = missing(&known_regions, covered);
+            resources.push(XCLocalizedResource {
+                path: path(&group.id),
+                reference: group.id,
+                kind: XCLocalizedResourceKind::VariantGroup,
+                languages,
+                missing_languages,
+            });
+        }
+
+        for file in self.files() {
+            let is_catalog = file
+                .path
+                .map(|path| path.ends_with(".xcstrings"))
+                .unwrap_or_default();
+            let full_path = match objects.get_full_path(&file.id, &self.root) {
+                Some(full_path) if is_catalog => full_path,
+                _ => continue,
+            };
+            let content = std::fs::read_to_string(&full_path)
+                .with_context(|| format!("Reading string catalog {full_path:?}"))?;
+            let catalog: serde_json::Value = serde_json::from_str(&content)
+                .with_context(|| format!("Parsing string catalog {full_path:?}"))?;
+            let (languages, complete) = catalog_languages(&catalog);
+            let missing_languages = missing(&known_regions, |region| {
+                complete.iter().any(|l| l == region)
+            });
+            resources.push(XCLocalizedResource {
+                path: path(&file.id),
+                reference: file.id,
+                kind: XCLocalizedResourceKind::StringCatalog,
+                languages,
+                missing_languages,
+            });
+        }
+        resources.sort_by(|a, b| (&a.path, &a.reference).cmp(&(&b.path, &b.reference)));
+
+        Ok(XCLocalizationReport {
+            development_region,
+            known_regions,
+            resources,
+        })
+    }
+}
+
+/// Language of a variant group child, from its `<language>.lproj` folder or its name
+fn child_language(child: &PBXFSReference) -> Option<String> {
+    child
+        .path
+        .and_then(|path| {
+            Path::new(path)
+                .iter()
+                .find_map(|component| component.to_str()?.strip_suffix(".lproj"))
+        })
+        .or_else(|| child.name.map(|name| name.as_str()))
+        .map(String::from)
+}
+
+/// Known regions but base not `covered`, sorted
+fn missing<F: Fn(&str) -> bool>(known_regions: &[String], covered: F) -> Vec<String> {
+    let mut missing = known_regions
+        .iter()
+        .filter(|region| region.as_str() != BASE_REGION && !covered(region))
+        .cloned()
+        .collect::<Vec<_>>();
+    missing.sort();
+    missing
+}
+
+/// Languages of a string catalog, sorted, and those every string to translate is localized in
+fn catalog_languages(catalog: &serde_json::Value) -> (Vec<String>, Vec<String>) {
+    let source_language = catalog["sourceLanguage"].as_str();
+    let strings = catalog["strings"]
+        .as_object()
+        .map(|strings| strings.values().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let localizations = |string: &serde_json::Value| {
+        string["localizations"]
+            .as_object()
+            .map(|localizations| localizations.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    let mut languages = strings
+        .iter()
+        .flat_map(|string| localizations(string))
+        .chain(source_language.map(String::from))
+        .collect::<Vec<_>>();
+    languages.sort();
+    languages.dedup();
+
+    let localized = strings
+        .iter()
+        .filter(|string| string["shouldTranslate"].as_bool() != Some(false))
+        .map(|string| localizations(string))
+        .collect::<Vec<_>>();
+    let complete = languages
+        .iter()
+        .filter(|language| {
+            Some(language.as_str()) == source_language
+                || localized.iter().all(|string| string.contains(language))
+        })
+        .cloned()
+        .collect();
+    (languages, complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+    use serde_json::json;
+
+    #[test]
+    fn localization_report() {
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            std::env::temp_dir().join(format!("xcodeproj-localization-{}", std::process::id())),
+        )
+        .unwrap();
+        std::fs::create_dir_all(&project.root).unwrap();
+
+        let pbxproj = project.pbxproj_mut();
+        let root = pbxproj.root_object_reference().to_string();
+        let main_group = pbxproj.root_group().id;
+        let objects = pbxproj.objects_mut();
+        let mut project_object = objects.get_object_mut(&root).unwrap();
+        project_object.insert_value("developmentRegion", "en");
+        project_object.insert_value("knownRegions", vec!["en", "Base", "fr", "de"]);
+        drop(project_object);
+
+        let mut group = PBXHashMap::default();
+        group.insert_value("isa", PBXObjectKind::from("PBXVariantGroup"));
+        group.insert_value("children", Vec::<String>::new());
+        group.insert_value("name", "Main.storyboard");
+        group.insert_value("sourceTree", "<group>");
+        let group = objects.add_child(&main_group, group).unwrap();
+        for path in ["Base.lproj/Main.storyboard", "fr.lproj/Main.strings"] {
+            objects.add_file_reference(&group, path).unwrap();
+        }
+        let catalog = pbxproj
+            .get_or_add_file_reference("Localizable.xcstrings")
+            .unwrap();
+        let content = json!({
+            "sourceLanguage": "en",
+            "strings": {
+                "Hello": { "localizations": { "fr": {}, "de": {} } },
+                "Bye": { "localizations": { "fr": {} } },
+                "ID": { "shouldTranslate": false },
+            },
+            "version": "1.0",
+        });
+        std::fs::write(
+            project.root.join("Localizable.xcstrings"),
+            content.to_string(),
+        )
+        .unwrap();
+
+        let report = project.localization_report().unwrap();
+        std::fs::remove_dir_all(&project.root).unwrap();
+
+        assert_eq!(Some("en"), report.development_region.as_deref());
+        assert_eq!(4, report.known_regions.len());
+        assert!(!report.is_complete());
+        let resource = |reference: &str| {
+            report
+                .resources
+                .iter()
+                .find(|resource| resource.reference == reference)
+                .unwrap()
+        };
+
+        let storyboard = resource(&group);
+        assert_eq!(XCLocalizedResourceKind::VariantGroup, storyboard.kind);
+        assert_eq!(vec!["Base", "fr"], storyboard.languages);
+        assert_eq!(vec!["de"], storyboard.missing_languages);
+
+        let strings = resource(&catalog);
+        assert_eq!(XCLocalizedResourceKind::StringCatalog, strings.kind);
+        assert_eq!(Path::new("Localizable.xcstrings"), strings.path);
+        assert_eq!(vec!["de", "en", "fr"], strings.languages);
+        assert_eq!(vec!["de"], strings.missing_languages);
+    }
+}
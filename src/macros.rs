@@ -1,17 +1,27 @@
 macro_rules! gen_hash_map_helpers {
-    ($([$key:ident, $output:ident]),*) => {
+    ($([$key:ident, $output:ident, $as:ident]),*) => {
         paste::paste! {
             impl PBXHashMap {
                 $(
                     #[doc = "Get " $key " if value is of type " $output]
                     pub fn [<get_ $key>](&self, key: &str) -> Option<&$output> {
-                        self.0.get(key)?.[<as_ $key>]()
+                        self.0.get(key)?.$as()
+                    }
+
+                    #[doc = "Get " $key " if value is of type " $output ", matching `key` ignoring case, see [`PBXHashMap::find_key`]"]
+                    pub fn [<get_ $key _ignore_case>](&self, key: &str) -> Option<&$output> {
+                        self.get_value_ignore_case(key)?.$as()
+                    }
+
+                    #[doc = "Get mutable " $key " if value is of type " $output]
+                    pub fn [<get_ $key _mut>](&mut self, key: &str) -> Option<&mut $output> {
+                        self.0.get_mut(key)?.[<as_ $key _mut>]()
                     }
 
                     #[doc = "Try get " $key " of type " $output]
                     pub fn [<try_get_ $key>](&self, key: &str) -> Result<&$output> {
                         let value = self.try_get_value(key)?;
-                        value.[<as_ $key>]().ok_or_else(|| anyhow::anyhow!("expected value to be {}, got {:?}", stringify!($key), value))
+                        value.$as().ok_or_else(|| anyhow::anyhow!("expected value to be {}, got {:?}", stringify!($key), value))
                     }
 
                     #[doc = "remove " $key " of type " $output]
@@ -31,3 +41,14 @@ macro_rules! gen_hash_map_helpers {
     };
 }
 pub(crate) use gen_hash_map_helpers;
+
+/// Log through `tracing` at the given level when the `with_tracing` feature is enabled
+macro_rules! log {
+    ($level:ident, $($arg:tt)*) => {{
+        #[cfg(feature = "with_tracing")]
+        tracing::$level!($($arg)*);
+        #[cfg(not(feature = "with_tracing"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+pub(crate) use log;
@@ -0,0 +1,196 @@
+use crate::XCodeProject;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Pinned package of a `Package.resolved` file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCPackagePin {
+    /// Package identity, e.g. `swift-log`
+    pub identity: String,
+    /// Repository url
+    pub location: String,
+    /// Resolved version, if pinned to a version
+    pub version: Option<String>,
+    /// Resolved branch, if pinned to a branch
+    pub branch: Option<String>,
+    /// Resolved commit
+    pub revision: Option<String>,
+}
+
+/// Package reference url changed by [`XCodeProject::rewrite_package_urls`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCPackageUrlRewrite {
+    /// XCRemoteSwiftPackageReference reference
+    pub reference: String,
+    /// Url before the rewrite
+    pub old_url: String,
+    /// Url after the rewrite
+    pub new_url: String,
+    /// `Package.resolved` pin of `old_url`, which Xcode discards and resolves again
+    pub invalidated_pin: Option<XCPackagePin>,
+}
+
+impl XCPackagePin {
+    /// Read pins of a `Package.resolved` file, in any of the format versions 1 to 3
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_context(|| format!("Read {path:?}"))?;
+        let resolved =
+            serde_json::from_str::<Value>(&content).with_context(|| format!("Parse {path:?}"))?;
+
+        let pins = match resolved.get("version").and_then(Value::as_u64) {
+            Some(1) => resolved.pointer("/object/pins"),
+            _ => resolved.get("pins"),
+        }
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("{path:?} has no pins"))?;
+
+        let string = |value: &Value, key: &str| value.get(key)?.as_str().map(String::from);
+        Ok(pins
+            .iter()
+            .filter_map(|pin| {
+                // Version 1 names fields `package` and `repositoryURL`
+                let location = string(pin, "location").or_else(|| string(pin, "repositoryURL"))?;
+                let identity =
+                    string(pin, "identity").unwrap_or_else(|| package_identity(&location));
+                let state = pin.get("state");
+                Some(Self {
+                    identity,
+                    location,
+                    version: state.and_then(|s| string(s, "version")),
+                    branch: state.and_then(|s| string(s, "branch")),
+                    revision: state.and_then(|s| string(s, "revision")),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Identity Swift Package Manager derives from a repository url, e.g. `swift-log` for
+/// `https://github.com/apple/swift-log.git`.
+pub(crate) fn package_identity(url: &str) -> String {
+    package_url_key(url)
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Url with case, trailing slash and `.git` suffix differences removed, for comparison
+pub(crate) fn package_url_key(url: &str) -> String {
+    url.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+impl XCodeProject {
+    /// Get path of the `Package.resolved` file of the project, preferring the one of its
+    /// sibling workspace. `None` if packages were never resolved.
+    pub fn package_resolved_path(&self) -> Option<PathBuf> {
+        let shared = Path::new("xcshareddata")
+            .join("swiftpm")
+            .join("Package.resolved");
+        [
+            self.root.join(format!("{}.xcworkspace", self.name)),
+            self.root
+                .join(format!("{}.xcodeproj", self.name))
+                .join("project.xcworkspace"),
+        ]
+        .into_iter()
+        .map(|bundle| bundle.join(&shared))
+        .find(|path| path.is_file())
+    }
+
+    /// Redirect package references to the urls returned by `rewrite`, e.g. to mirrors or
+    /// forks, reporting which `Package.resolved` pins the changes invalidate.
+    ///
+    /// `Package.resolved` itself is left untouched, Xcode resolves the new urls on next open.
+    pub fn rewrite_package_urls<F>(&mut self, rewrite: F) -> Result<Vec<XCPackageUrlRewrite>>
+    where
+        F: FnMut(&str) -> String,
+    {
+        let pins = match self.package_resolved_path() {
+            Some(path) => XCPackagePin::from_file(path)?,
+            None => vec![],
+        };
+        let changed = self.pbxproj.objects_mut().rewrite_package_urls(rewrite);
+        Ok(changed
+            .into_iter()
+            .map(|(reference, old_url)| {
+                let new_url = self
+                    .get_swift_package_reference(&reference)
+                    .and_then(|package| package.repository_url.cloned())
+                    .unwrap_or_default();
+                let key = package_url_key(&old_url);
+                let invalidated_pin = pins
+                    .iter()
+                    .find(|pin| package_url_key(&pin.location) == key)
+                    .cloned();
+                XCPackageUrlRewrite {
+                    reference,
+                    old_url,
+                    new_url,
+                    invalidated_pin,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_package_urls() {
+        let root = std::env::temp_dir().join(format!("xcodeproj-resolved-{}", std::process::id()));
+        let bundle = root.join("Demo.xcodeproj");
+        let swiftpm = bundle.join("project.xcworkspace/xcshareddata/swiftpm");
+        std::fs::create_dir_all(&swiftpm).unwrap();
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples/demo1.pbxproj"),
+            bundle.join("project.pbxproj"),
+        )
+        .unwrap();
+        std::fs::write(
+            swiftpm.join("Package.resolved"),
+            r#"{
+  "pins" : [
+    {
+      "identity" : "swift-log",
+      "kind" : "remoteSourceControl",
+      "location" : "https://github.com/apple/swift-log",
+      "state" : {
+        "revision" : "173f567a2dfec11d74588eea82cecea555bdc0bc",
+        "version" : "1.4.2"
+      }
+    }
+  ],
+  "version" : 2
+}"#,
+        )
+        .unwrap();
+
+        let mut project = XCodeProject::new(&bundle).unwrap();
+        let rewrites = project
+            .rewrite_package_urls(|url| url.replace("github.com", "mirror.example.com"))
+            .unwrap();
+        assert_eq!(1, rewrites.len());
+        let rewrite = &rewrites[0];
+        assert_eq!("https://github.com/apple/swift-log.git", rewrite.old_url);
+        assert_eq!(
+            "https://mirror.example.com/apple/swift-log.git",
+            rewrite.new_url
+        );
+        let pin = rewrite.invalidated_pin.as_ref().unwrap();
+        assert_eq!("swift-log", pin.identity);
+        assert_eq!(Some("1.4.2"), pin.version.as_deref());
+
+        assert_eq!(
+            "swift-log",
+            package_identity("git@github.com:apple/swift-log.git")
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+use crate::pbxproj::*;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+/// Change of a single object field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXFieldChange {
+    /// Field key
+    pub key: String,
+    /// Value before the change, `None` if the field was added
+    pub old: Option<PBXValue>,
+    /// Value after the change, `None` if the field was removed
+    pub new: Option<PBXValue>,
+}
+
+/// Change of an object since the project was loaded or last saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PBXObjectChange {
+    /// Object was inserted
+    Added {
+        /// Object reference
+        reference: String,
+    },
+    /// Object was removed
+    Removed {
+        /// Object reference
+        reference: String,
+    },
+    /// Object fields were modified
+    Modified {
+        /// Object reference
+        reference: String,
+        /// Changed fields, sorted by key
+        fields: Vec<PBXFieldChange>,
+    },
+}
+
+impl PBXObjectChange {
+    /// Reference of the changed object
+    pub fn reference(&self) -> &str {
+        match self {
+            Self::Added { reference }
+            | Self::Removed { reference }
+            | Self::Modified { reference, .. } => reference,
+        }
+    }
+}
+
+impl PBXRootObject {
+    /// Whether objects were modified since the project was loaded or last marked as saved.
+    pub fn is_modified(&self) -> bool {
+        &self.objects != self.baseline()
+    }
+
+    /// Object changes since the project was loaded or last marked as saved, sorted by reference.
+    pub fn changes(&self) -> Vec<PBXObjectChange> {
+        object_changes(self.baseline(), &self.objects)
+    }
+
+    /// Mark current state as saved, resetting [`PBXRootObject::changes`].
+    pub fn mark_saved(&mut self) {
+        self.baseline = OnceLock::from(self.objects.clone());
+    }
+
+    /// Objects as last loaded or saved, parsed from loaded content on first use.
+    pub(crate) fn baseline(&self) -> &PBXObjectCollection {
+        self.baseline.get_or_init(|| {
+            self.source
+                .as_ref()
+                .map(|source| source.objects())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Changes between `old` and `new` objects, sorted by reference.
+pub(crate) fn object_changes(
+    old: &PBXObjectCollection,
+    new: &PBXObjectCollection,
+) -> Vec<PBXObjectChange> {
+    old.keys()
+        .chain(new.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|reference| {
+            let change = match (old.map.get(reference), new.map.get(reference)) {
+                (None, Some(_)) => PBXObjectChange::Added {
+                    reference: reference.clone(),
+                },
+                (Some(_), None) => PBXObjectChange::Removed {
+                    reference: reference.clone(),
+                },
+                (Some(old), Some(new)) if old != new => PBXObjectChange::Modified {
+                    reference: reference.clone(),
+                    fields: field_changes(old, new),
+                },
+                _ => return None,
+            };
+            Some(change)
+        })
+        .collect()
+}
+
+/// Changes between `old` and `new` fields of an object, sorted by key.
+pub(crate) fn field_changes(old: &PBXHashMap, new: &PBXHashMap) -> Vec<PBXFieldChange> {
+    old.keys()
+        .chain(new.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|key| {
+            let (old, new) = (old.get(key), new.get(key));
+            (old != new).then(|| PBXFieldChange {
+                key: key.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn changes() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        assert!(!root_object.is_modified());
+
+        root_object.set_defines_module(&target, true).unwrap();
+        let group = root_object.get_or_add_group("Kit").unwrap();
+        assert!(root_object.is_modified());
+
+        let changes = root_object.changes();
+        let main_group = root_object.root_group().id;
+        assert!(changes.contains(&PBXObjectChange::Added {
+            reference: group.clone()
+        }));
+        assert!(changes.iter().any(|change| matches!(
+            change,
+            PBXObjectChange::Modified { reference, fields }
+                if reference == &main_group && fields[0].key == "children"
+        )));
+        // added group, modified main group and both target configurations
+        assert_eq!(4, changes.len());
+
+        root_object.mark_saved();
+        assert!(!root_object.is_modified());
+        assert!(root_object.changes().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::pbxproj::*;
+
+/// Delimiters of a [`PBXComment`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PBXCommentKind {
+    /// `/* … */` comment
+    Block,
+    /// `// …` comment, up to the end of the line
+    Line,
+}
+
+/// Hand written comment of a pbxproj file, see [`PBXRootObject::comments`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PBXComment {
+    /// Text without delimiters, trimmed
+    pub text: String,
+    /// Delimiters of the comment
+    pub kind: PBXCommentKind,
+    /// Keys and array indices leading to the value the comment is attached to, e.g.
+    /// `["objects", "<reference>", "buildSettings", "SWIFT_VERSION"]`
+    pub path: Vec<String>,
+    /// Whether the comment precedes the closing `}` or `)` of the object or array at `path`,
+    /// rather than the field or element itself
+    pub trailing: bool,
+}
+
+impl PBXRootObject {
+    /// Get hand written comments of the loaded file, in file order. Each comment is attached to
+    /// the field or array element following it, or to the end of its object or array when
+    /// nothing follows.
+    ///
+    /// Comments generated by Xcode are left out: section markers, e.g.
+    /// `/* Begin PBXBuildFile section */`, and annotations following a reference on the same
+    /// line, e.g. `/* main.swift */`. Empty for projects not loaded from content.
+    pub fn comments(&self) -> &[PBXComment] {
+        self.source
+            .as_ref()
+            .map(|source| source.comments())
+            .unwrap_or_default()
+    }
+
+    /// Get hand written comments attached to the value at `path`, see
+    /// [`PBXRootObject::comments`]
+    pub fn comments_at(&self, path: &[&str]) -> Vec<&PBXComment> {
+        self.comments()
+            .iter()
+            .filter(|comment| {
+                comment
+                    .path
+                    .iter()
+                    .map(String::as_str)
+                    .eq(path.iter().copied())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn comments() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        assert!(test_demo_file!(demo1).comments().is_empty());
+
+        let content = content
+            .replacen(
+                "\t\t\t\tSWIFT_VERSION = 5.0;",
+                "\t\t\t\t/* Pinned for Xcode 13 */\n\t\t\t\tSWIFT_VERSION = 5.0;",
+                1,
+            )
+            .replacen(
+                "\t};\n\trootObject = ",
+                "\t\t/* Last object */\n\t};\n\t// Managed by tooling\n\trootObject = ",
+                1,
+            );
+        let root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(
+            PBXRootObject::try_from(include_str!("../../tests/samples/demo1.pbxproj"))
+                .unwrap()
+                .fingerprint(),
+            root_object.fingerprint()
+        );
+
+        let comments = root_object.comments();
+        assert_eq!(3, comments.len());
+        assert_eq!("Pinned for Xcode 13", comments[0].text);
+        assert_eq!(PBXCommentKind::Block, comments[0].kind);
+        assert_eq!("SWIFT_VERSION", comments[0].path.last().unwrap());
+        assert_eq!(4, comments[0].path.len());
+        assert!(!comments[0].trailing);
+
+        assert_eq!("Last object", comments[1].text);
+        assert_eq!(vec!["objects"], comments[1].path);
+        assert!(comments[1].trailing);
+
+        assert_eq!(PBXCommentKind::Line, comments[2].kind);
+        assert_eq!(
+            vec!["Managed by tooling"],
+            root_object
+                .comments_at(&["rootObject"])
+                .iter()
+                .map(|comment| comment.text.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let written = root_object.to_pbxproj_string();
+        assert!(written.contains("\t\t\t\t/* Pinned for Xcode 13 */\n\t\t\t\tSWIFT_VERSION = 5.0;"));
+        assert!(written.contains("\t\t/* Last object */\n\t};\n\t// Managed by tooling\n"));
+        let written = PBXRootObject::try_from(written.as_str()).unwrap();
+        assert_eq!(comments, written.comments());
+    }
+}
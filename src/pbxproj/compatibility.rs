@@ -0,0 +1,261 @@
+use crate::pbxproj::*;
+
+/// Known `compatibilityVersion` values with the `objectVersion` Xcode writes for them
+const COMPATIBILITY_VERSIONS: [(&str, u8); 15] = [
+    ("Xcode 3.0", 44),
+    ("Xcode 3.1", 45),
+    ("Xcode 3.2", 46),
+    ("Xcode 6.3", 47),
+    ("Xcode 8.0", 48),
+    ("Xcode 9.3", 50),
+    ("Xcode 10.0", 51),
+    ("Xcode 11.0", 52),
+    ("Xcode 11.4", 53),
+    ("Xcode 12.0", 54),
+    ("Xcode 13.0", 55),
+    ("Xcode 14.0", 56),
+    ("Xcode 15.0", 60),
+    ("Xcode 15.3", 63),
+    ("Xcode 16.0", 77),
+];
+
+/// First `objectVersion` without `compatibilityVersion`, written by Xcode 16, which records
+/// `preferredProjectObjectVersion` instead
+pub const MIN_OBJECT_VERSION_WITHOUT_COMPATIBILITY: u8 = 70;
+
+/// Object kinds and the minimum `objectVersion` Xcode opens them with. Swift package objects
+/// aren't listed, as Xcode 11 added them to existing projects without raising objectVersion.
+const FEATURE_VERSIONS: [(&str, u8); 3] = [
+    ("PBXFileSystemSynchronizedRootGroup", 77),
+    ("PBXFileSystemSynchronizedBuildFileExceptionSet", 77),
+    (
+        "PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet",
+        77,
+    ),
+];
+
+/// Mismatch between `compatibilityVersion`, `objectVersion` and the objects of a project,
+/// likely to make Xcode refuse to open it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PBXCompatibilityIssue {
+    /// `compatibilityVersion` isn't a known Xcode version
+    UnknownCompatibilityVersion(String),
+    /// `compatibilityVersion` is missing, which Xcode requires below objectVersion 70
+    MissingCompatibilityVersion {
+        /// Declared object version
+        object_version: u8,
+    },
+    /// `objectVersion` is older than the one `compatibilityVersion` requires
+    ObjectVersionTooOld {
+        /// Declared compatibility version
+        compatibility_version: String,
+        /// Declared object version
+        object_version: u8,
+        /// Minimum object version
+        required: u8,
+    },
+    /// Object kind requires a newer `objectVersion`
+    UnsupportedObject {
+        /// Object reference
+        reference: String,
+        /// Object isa
+        isa: String,
+        /// Declared object version
+        object_version: u8,
+        /// Minimum object version
+        required: u8,
+    },
+}
+
+impl std::fmt::Display for PBXCompatibilityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCompatibilityVersion(version) => {
+                write!(f, "Unknown compatibilityVersion {version:?}")
+            }
+            Self::MissingCompatibilityVersion { object_version } => write!(
+                f,
+                "Missing compatibilityVersion, required by objectVersion {object_version}"
+            ),
+            Self::ObjectVersionTooOld {
+                compatibility_version,
+                object_version,
+                required,
+            } => write!(
+                f,
+                "{compatibility_version:?} requires objectVersion {required}, found {object_version}"
+            ),
+            Self::UnsupportedObject {
+                reference,
+                isa,
+                object_version,
+                required,
+            } => write!(
+                f,
+                "{isa} {reference:?} requires objectVersion {required}, found {object_version}"
+            ),
+        }
+    }
+}
+
+/// Minimum `objectVersion` of the given `compatibilityVersion`, if known
+pub fn compatibility_object_version(compatibility_version: &str) -> Option<u8> {
+    COMPATIBILITY_VERSIONS
+        .iter()
+        .find(|(version, _)| *version == compatibility_version)
+        .map(|(_, object_version)| *object_version)
+}
+
+impl PBXRootObject {
+    /// Get `compatibilityVersion` of the root project, e.g. `Xcode 14.0`. Dropped by Xcode 16,
+    /// see [`MIN_OBJECT_VERSION_WITHOUT_COMPATIBILITY`].
+    pub fn compatibility_version(&self) -> Option<&String> {
+        self.objects
+            .map
+            .get(&self.root_object_reference)?
+            .get_string("compatibilityVersion")
+    }
+
+    /// Set `compatibilityVersion` of the root project, raising `objectVersion` to the one
+    /// Xcode writes for it if older. Fails for unknown versions.
+    pub fn set_compatibility_version(&mut self, compatibility_version: &str) -> Result<()> {
+        let required = compatibility_object_version(compatibility_version).ok_or_else(|| {
+            anyhow::anyhow!("Unknown compatibilityVersion {compatibility_version:?}")
+        })?;
+        let reference = self.root_object_reference.clone();
+        self.objects
+            .get_project_mut(&reference)
+            .ok_or_else(|| anyhow::anyhow!("Root project {reference:?} not found"))?
+            .set_string("compatibilityVersion", Some(compatibility_version));
+        self.object_version = self.object_version.max(required);
+        Ok(())
+    }
+
+    /// Set the pbxproject's object version.
+    pub fn set_object_version(&mut self, object_version: u8) {
+        self.object_version = object_version;
+    }
+
+    /// Check `compatibilityVersion` and the kinds of project objects against `objectVersion`.
+    pub fn validate_compatibility(&self) -> Vec<PBXCompatibilityIssue> {
+        let mut issues = vec![];
+        match self.compatibility_version() {
+            None if self.object_version < MIN_OBJECT_VERSION_WITHOUT_COMPATIBILITY => {
+                issues.push(PBXCompatibilityIssue::MissingCompatibilityVersion {
+                    object_version: self.object_version,
+                })
+            }
+            None => {}
+            Some(compatibility_version) => {
+                match compatibility_object_version(compatibility_version) {
+                    None => issues.push(PBXCompatibilityIssue::UnknownCompatibilityVersion(
+                        compatibility_version.clone(),
+                    )),
+                    Some(required) if required > self.object_version => {
+                        issues.push(PBXCompatibilityIssue::ObjectVersionTooOld {
+                            compatibility_version: compatibility_version.clone(),
+                            object_version: self.object_version,
+                            required,
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let mut objects = self
+            .objects
+            .iter()
+            .filter_map(|(reference, object)| {
+                let isa = object.get_kind("isa")?.to_string();
+                let (_, required) = FEATURE_VERSIONS.iter().find(|(kind, _)| *kind == isa)?;
+                (*required > self.object_version).then(|| {
+                    PBXCompatibilityIssue::UnsupportedObject {
+                        reference: reference.clone(),
+                        isa,
+                        object_version: self.object_version,
+                        required: *required,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        objects.sort_by_key(|issue| issue.to_string());
+        issues.extend(objects);
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compatibility() {
+        let mut root_object = test_demo_file!(demo1);
+        assert_eq!(
+            Vec::<PBXCompatibilityIssue>::new(),
+            root_object.validate_compatibility()
+        );
+
+        root_object.set_object_version(46);
+        let mut group = PBXHashMap::default();
+        group.insert_value("isa", PBXObjectKind::PBXFileSystemSynchronizedRootGroup);
+        let group = root_object.objects_mut().insert_new(group).unwrap();
+        let issues = root_object.validate_compatibility();
+        assert!(issues.contains(&PBXCompatibilityIssue::UnsupportedObject {
+            reference: group.to_string(),
+            isa: "PBXFileSystemSynchronizedRootGroup".into(),
+            object_version: 46,
+            required: 77,
+        }));
+
+        assert!(root_object.set_compatibility_version("Xcode 1.0").is_err());
+        root_object.set_compatibility_version("Xcode 16.0").unwrap();
+        assert_eq!(
+            Some(&"Xcode 16.0".to_string()),
+            root_object.compatibility_version()
+        );
+        assert_eq!(77, root_object.object_version());
+        assert!(root_object.validate_compatibility().is_empty());
+    }
+
+    #[test]
+    fn xcode16_format() {
+        let root_object = test_demo_file!(demo13);
+        assert_eq!(77, root_object.object_version());
+        assert!(root_object.validate_compatibility().is_empty());
+
+        let project = root_object.root_project();
+        assert_eq!(None, project.compatibility_version);
+        assert_eq!(Some(&77), project.preferred_project_object_version);
+        assert_eq!(Some(true), project.minimized_project_reference_proxies);
+        let lowered = project.to_hashmap();
+        let raw = &root_object.objects()[&project.id];
+        for key in [
+            "preferredProjectObjectVersion",
+            "minimizedProjectReferenceProxies",
+        ] {
+            assert_eq!(raw.get_value(key), lowered.get_value(key));
+        }
+        assert_eq!(None, lowered.get_value("compatibilityVersion"));
+
+        // Synchronized root groups are kept among children of the main group
+        let main_group = &project.main_group.id;
+        assert_eq!(
+            root_object.objects()[main_group].get_vec("children"),
+            project.main_group.to_hashmap().get_vec("children")
+        );
+
+        let mut root_object = test_demo_file!(demo1);
+        let reference = root_object.root_object_reference().to_string();
+        root_object
+            .objects_mut()
+            .get_object_mut(&reference)
+            .unwrap()
+            .remove_value("compatibilityVersion");
+        assert_eq!(
+            vec![PBXCompatibilityIssue::MissingCompatibilityVersion { object_version: 51 }],
+            root_object.validate_compatibility()
+        );
+    }
+}
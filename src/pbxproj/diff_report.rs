@@ -0,0 +1,519 @@
+use crate::pbxproj::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Names listed in a change line before summarizing the rest as a count
+const MAX_LISTED_NAMES: usize = 5;
+
+/// Readable summary of the changes between two states of a project, e.g. for PR comments, see
+/// [`PBXRootObject::diff_report`].
+///
+/// Displays as plain text, one `<section>: <change>` line per change, e.g.
+/// `Target App: SWIFT_VERSION 5.9 → 5.10`. See [`PBXDiffReport::to_markdown`] for markdown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PBXDiffReport {
+    /// Changed parts of the project: the project itself first, then targets sorted by name
+    pub sections: Vec<PBXDiffSection>,
+}
+
+/// Changes of a part of a project in a [`PBXDiffReport`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PBXDiffSection {
+    /// Section title, `Project` or `Target <name>`
+    pub title: String,
+    /// Change descriptions, e.g. `added 3 files to Sources (A.swift, B.swift, C.swift)`
+    pub changes: Vec<String>,
+}
+
+impl PBXDiffReport {
+    /// Whether no change was found
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Render the report as markdown, a heading and bullet list per section
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "No project changes\n".into();
+        }
+        let mut markdown = String::new();
+        for section in self.sections.iter() {
+            markdown.push_str(&format!("#### {}\n\n", section.title));
+            for change in section.changes.iter() {
+                markdown.push_str(&format!("- {change}\n"));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
+
+impl std::fmt::Display for PBXDiffReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for section in self.sections.iter() {
+            for change in section.changes.iter() {
+                writeln!(f, "{}: {change}", section.title)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PBXRootObject {
+    /// Summarize the changes from `self` to `other`, e.g. from the base to the head of a PR:
+    /// targets, files, packages, build phases, dependencies and build settings.
+    pub fn diff_report(&self, other: &PBXRootObject) -> PBXDiffReport {
+        Differ {
+            old: &self.objects,
+            new: &other.objects,
+            project: &other.root_object_reference,
+        }
+        .report()
+    }
+
+    /// Summarize the changes since the project was loaded or last marked as saved, see
+    /// [`PBXRootObject::changes`] and [`PBXRootObject::diff_report`].
+    pub fn changes_report(&self) -> PBXDiffReport {
+        Differ {
+            old: self.baseline(),
+            new: &self.objects,
+            project: &self.root_object_reference,
+        }
+        .report()
+    }
+}
+
+struct Differ<'a> {
+    old: &'a PBXObjectCollection,
+    new: &'a PBXObjectCollection,
+    /// Root project reference
+    project: &'a str,
+}
+
+impl<'a> Differ<'a> {
+    fn report(&self) -> PBXDiffReport {
+        let mut sections = vec![];
+        let project = self.project_changes();
+        if !project.is_empty() {
+            sections.push(PBXDiffSection {
+                title: "Project".into(),
+                changes: project,
+            });
+        }
+
+        let mut targets = vec![];
+        for new in self.new.targets() {
+            let old = match self.old.get_target(&new.id) {
+                Some(old) => old,
+                None => continue,
+            };
+            let changes = self.target_changes(&old, &new);
+            if !changes.is_empty() {
+                targets.push(PBXDiffSection {
+                    title: format!("Target {}", display_name(new.name, &new.id)),
+                    changes,
+                });
+            }
+        }
+        targets.sort_by(|a, b| a.title.cmp(&b.title));
+        sections.extend(targets);
+        PBXDiffReport { sections }
+    }
+
+    fn project_changes(&self) -> Vec<String> {
+        let mut changes = vec![];
+        let target_names = |objects: &PBXObjectCollection| {
+            objects
+                .targets()
+                .into_iter()
+                .map(|t| (t.id.clone(), display_name(t.name, &t.id)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (old, new) = (target_names(self.old), target_names(self.new));
+        changes.extend(added_removed(
+            "target",
+            &values(&old, &new),
+            &values(&new, &old),
+        ));
+
+        let files = |objects: &PBXObjectCollection| {
+            objects
+                .files()
+                .into_iter()
+                .filter_map(|file| {
+                    let path = objects.get_fs_reference_path(&file.id)?;
+                    // Products come and go with their targets
+                    let path = path.to_string_lossy();
+                    (!path.starts_with("$(")).then(|| (file.id.clone(), path.to_string()))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (old, new) = (files(self.old), files(self.new));
+        changes.extend(added_removed(
+            "file",
+            &values(&old, &new),
+            &values(&new, &old),
+        ));
+
+        let packages = |objects: &PBXObjectCollection| {
+            objects
+                .map
+                .get(self.project)
+                .and_then(|project| project.get_vec("packageReferences"))
+                .map(|packages| packages.as_vec_strings())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|package| {
+                    let object = objects.map.get(package.as_str())?;
+                    let location = object
+                        .get_string("repositoryURL")
+                        .or_else(|| object.get_string("relativePath"))?;
+                    Some((location.clone(), object.get_value("requirement").cloned()))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (old, new) = (packages(self.old), packages(self.new));
+        let removed = old
+            .keys()
+            .filter(|package| !new.contains_key(*package))
+            .cloned()
+            .collect::<Vec<_>>();
+        let added = new
+            .keys()
+            .filter(|package| !old.contains_key(*package))
+            .cloned()
+            .collect::<Vec<_>>();
+        changes.extend(added_removed("package", &removed, &added));
+        for (package, requirement) in new.iter() {
+            match old.get(package) {
+                Some(old) if old != requirement => changes.push(format!(
+                    "package {package} requirement {} → {}",
+                    display_requirement(old.as_ref()),
+                    display_requirement(requirement.as_ref())
+                )),
+                _ => {}
+            }
+        }
+
+        changes.extend(self.settings_changes(self.project));
+        changes
+    }
+
+    fn target_changes(&self, old: &PBXTarget, new: &PBXTarget) -> Vec<String> {
+        let mut changes = vec![];
+        if old.name != new.name {
+            changes.push(format!("renamed from {}", display_name(old.name, &old.id)));
+        }
+
+        for phase in old.build_phases.iter() {
+            if !new.build_phases.iter().any(|p| p.id == phase.id) {
+                changes.push(format!(
+                    "removed {} phase",
+                    self.phase_name(self.old, phase)
+                ));
+            }
+        }
+        for phase in new.build_phases.iter() {
+            let name = self.phase_name(self.new, phase);
+            let old_files = match old.build_phases.iter().find(|p| p.id == phase.id) {
+                Some(old) => build_file_names(old),
+                None => {
+                    changes.push(format!("added {name} phase"));
+                    vec![]
+                }
+            };
+            let new_files = build_file_names(phase);
+            let added = difference(&new_files, &old_files);
+            let removed = difference(&old_files, &new_files);
+            if !added.is_empty() {
+                changes.push(file_changes("added", &added, "to", &name));
+            }
+            if !removed.is_empty() {
+                changes.push(file_changes("removed", &removed, "from", &name));
+            }
+        }
+
+        let dependencies = |target: &PBXTarget| {
+            target
+                .target_dependencies
+                .iter()
+                .filter_map(|d| {
+                    d.target
+                        .as_ref()
+                        .and_then(|t| t.name.cloned())
+                        .or_else(|| d.name.cloned())
+                })
+                .collect::<Vec<_>>()
+        };
+        changes.extend(added_removed(
+            "dependency",
+            &difference(&dependencies(old), &dependencies(new)),
+            &difference(&dependencies(new), &dependencies(old)),
+        ));
+        let products = |target: &PBXTarget| {
+            target
+                .package_product_dependencies
+                .iter()
+                .map(|p| p.product_name.clone())
+                .collect::<Vec<_>>()
+        };
+        changes.extend(added_removed(
+            "package product",
+            &difference(&products(old), &products(new)),
+            &difference(&products(new), &products(old)),
+        ));
+
+        changes.extend(self.settings_changes(&new.id));
+        changes
+    }
+
+    /// Build setting changes of the configurations of the target or project with the given
+    /// reference, e.g. `SWIFT_VERSION 5.9 → 5.10 (Debug)`. Configurations are matched by name;
+    /// changes made to every configuration aren't suffixed with configuration names.
+    fn settings_changes(&self, owner: &str) -> Vec<String> {
+        let configurations = |objects: &'a PBXObjectCollection| {
+            objects
+                .map
+                .get(owner)
+                .and_then(|owner| owner.get_string("buildConfigurationList"))
+                .and_then(|list| objects.map.get(list))
+                .and_then(|list| list.get_vec("buildConfigurations"))
+                .map(|configurations| configurations.as_vec_strings())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|configuration| {
+                    let configuration = objects.map.get(configuration.as_str())?;
+                    Some((
+                        configuration.get_string("name")?.clone(),
+                        configuration.get_object("buildSettings")?,
+                    ))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (old, new) = (configurations(self.old), configurations(self.new));
+
+        let mut changes = BTreeMap::<String, Vec<&String>>::new();
+        for (name, new_settings) in new.iter() {
+            let old_settings = match old.get(name) {
+                Some(old_settings) => old_settings,
+                None => continue,
+            };
+            let keys = old_settings
+                .0
+                .keys()
+                .chain(new_settings.0.keys())
+                .collect::<BTreeSet<_>>();
+            for key in keys {
+                let change = match (old_settings.get_value(key), new_settings.get_value(key)) {
+                    (Some(old), Some(new)) if old != new => {
+                        format!("{key} {} → {}", display_value(old), display_value(new))
+                    }
+                    (None, Some(new)) => format!("set {key} = {}", display_value(new)),
+                    (Some(old), None) => format!("unset {key} (was {})", display_value(old)),
+                    _ => continue,
+                };
+                changes.entry(change).or_default().push(name);
+            }
+        }
+
+        changes
+            .into_iter()
+            .map(|(change, names)| {
+                if names.len() == new.len() {
+                    change
+                } else {
+                    let names = names.iter().map(|n| n.as_str()).collect::<Vec<_>>();
+                    format!("{change} ({})", names.join(", "))
+                }
+            })
+            .collect()
+    }
+
+    /// Name of `phase` as shown in Xcode, e.g. `Sources` or the script phase name
+    fn phase_name(&self, objects: &PBXObjectCollection, phase: &PBXBuildPhase) -> String {
+        objects
+            .map
+            .get(&phase.id)
+            .and_then(|phase| phase.get_string("name"))
+            .cloned()
+            .unwrap_or_else(|| phase.kind.to_string())
+    }
+}
+
+/// Names of files built by `phase`, sorted
+fn build_file_names(phase: &PBXBuildPhase) -> Vec<String> {
+    let mut names = phase
+        .files
+        .iter()
+        .filter_map(|file| match (&file.file, &file.product) {
+            (Some(file), _) => file.name.or(file.path).cloned(),
+            (None, Some(product)) => Some(product.product_name.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Items of `a` not in `b`, counting duplicates
+fn difference(a: &[String], b: &[String]) -> Vec<String> {
+    let mut b = b.to_vec();
+    a.iter()
+        .filter(|item| match b.iter().position(|other| other == *item) {
+            Some(index) => {
+                b.remove(index);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Values of `a` whose key isn't in `b`, sorted
+fn values(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> Vec<String> {
+    let mut values = a
+        .iter()
+        .filter(|(key, _)| !b.contains_key(*key))
+        .map(|(_, value)| value.clone())
+        .collect::<Vec<_>>();
+    values.sort();
+    values
+}
+
+/// `removed <what> <name>` and `added <what> <name>` lines
+fn added_removed(what: &str, removed: &[String], added: &[String]) -> Vec<String> {
+    let removed = removed.iter().map(|name| format!("removed {what} {name}"));
+    let added = added.iter().map(|name| format!("added {what} {name}"));
+    added.chain(removed).collect()
+}
+
+/// `added 3 files to Sources (A.swift, B.swift, C.swift)`, listing at most
+/// [`MAX_LISTED_NAMES`] names
+fn file_changes(change: &str, names: &[String], preposition: &str, phase: &str) -> String {
+    let files = if names.len() == 1 { "file" } else { "files" };
+    let mut listed = names
+        .iter()
+        .take(MAX_LISTED_NAMES)
+        .cloned()
+        .collect::<Vec<_>>();
+    if names.len() > MAX_LISTED_NAMES {
+        listed.push(format!("and {} more", names.len() - MAX_LISTED_NAMES));
+    }
+    format!(
+        "{change} {} {files} {preposition} {phase} ({})",
+        names.len(),
+        listed.join(", ")
+    )
+}
+
+fn display_name(name: Option<&String>, reference: &str) -> String {
+    name.cloned().unwrap_or_else(|| reference.to_string())
+}
+
+/// Build setting value as written in Xcode build settings editor
+fn display_value(value: &PBXValue) -> String {
+    match value {
+        PBXValue::String(value) if value.is_empty() => "\"\"".into(),
+        PBXValue::String(value) => value.clone(),
+        PBXValue::Number(value) => value.to_string(),
+        PBXValue::Float(value) => value.to_string(),
+        PBXValue::Bool(value) => if *value { "YES" } else { "NO" }.into(),
+        PBXValue::Vec(values) => values
+            .0
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(" "),
+        PBXValue::Kind(kind) => format!("{kind:?}"),
+        PBXValue::Object(_) => "{…}".into(),
+        PBXValue::Null(_) => "\"\"".into(),
+        PBXValue::Data(bytes) => writer::data_literal(bytes),
+    }
+}
+
+fn display_requirement(requirement: Option<&PBXValue>) -> String {
+    use XCVersionRequirement::*;
+    match requirement.map(XCVersionRequirement::try_from) {
+        Some(Ok(UpToNextMajorVersion(version))) => format!("up to next major from {version}"),
+        Some(Ok(UpToNextMinorVersion(version))) => format!("up to next minor from {version}"),
+        Some(Ok(Range(min, max))) => format!("{min}..<{max}"),
+        Some(Ok(Exact(version))) => format!("exactly {version}"),
+        Some(Ok(Branch(branch))) => format!("branch {branch}"),
+        Some(Ok(Revision(revision))) => format!("revision {revision}"),
+        _ => "none".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn diff_report() {
+        let old = test_demo_file!(demo1);
+        let mut new = old.clone();
+        assert!(old.diff_report(&new).is_empty());
+        assert_eq!("No project changes\n", new.changes_report().to_markdown());
+
+        let target = new.get_target_by_name("Wordle").unwrap().id;
+        let phase = new
+            .objects()
+            .get_target_build_phase(&target, &PBXBuildPhaseKind::Sources)
+            .unwrap();
+        for path in ["Source/A.swift", "Source/B.swift"] {
+            let file = new.get_or_add_file_reference(path).unwrap();
+            new.objects_mut().add_build_file(&phase, &file).unwrap();
+        }
+        let configuration = new
+            .get_target(&target)
+            .unwrap()
+            .build_configuration_list
+            .unwrap()
+            .build_configurations[0]
+            .id
+            .clone();
+        let name = new
+            .objects()
+            .map
+            .get(&configuration)
+            .and_then(|c| c.get_string("name"))
+            .cloned()
+            .unwrap();
+        new.objects
+            .update_target_build_settings(&target, |settings| {
+                settings.insert_value("IPHONEOS_DEPLOYMENT_TARGET", "16.0");
+            })
+            .unwrap();
+        new.objects
+            .get_object_mut(&configuration)
+            .unwrap()
+            .object_entry("buildSettings")
+            .insert_value("OTHER_LDFLAGS", vec!["-ObjC"]);
+        PBXTarget::builder("Widget").build(&mut new).unwrap();
+
+        let report = old.diff_report(&new);
+        assert_eq!(
+            vec!["Project", "Target Wordle"],
+            report
+                .sections
+                .iter()
+                .map(|s| s.title.as_str())
+                .collect::<Vec<_>>()
+        );
+        let project = &report.sections[0].changes;
+        assert!(project.contains(&"added target Widget".to_string()));
+        assert!(project.contains(&"added file Source/A.swift".to_string()));
+
+        let text = report.to_string();
+        assert!(text.contains("Target Wordle: added 2 files to Sources (A.swift, B.swift)\n"));
+        assert!(text.contains("Target Wordle: IPHONEOS_DEPLOYMENT_TARGET 15.0 → 16.0\n"));
+        assert!(text.contains(&format!(
+            "Target Wordle: set OTHER_LDFLAGS = -ObjC ({name})\n"
+        )));
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("#### Project\n\n- added target Widget\n"));
+        assert!(markdown.contains("#### Target Wordle\n\n"));
+        assert_eq!(report, new.changes_report());
+    }
+}
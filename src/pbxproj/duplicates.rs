@@ -0,0 +1,236 @@
+use crate::macros::log;
+use crate::pbxproj::*;
+
+/// Object given a new reference by [`PBXRootObject::repair_duplicate_references`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PBXDuplicateRepair {
+    /// Reference shared by both objects, kept by the first one
+    pub reference: String,
+    /// New reference of the other object
+    pub new_reference: String,
+    /// References of objects now pointing at the new reference
+    pub referents: Vec<String>,
+}
+
+impl PBXRootObject {
+    /// Record `duplicates` found on load, warning about each of them
+    pub(crate) fn set_duplicates(&mut self, duplicates: Vec<(String, PBXHashMap)>) {
+        for (reference, _) in duplicates.iter() {
+            log!(warn, "Object {reference:?} is defined more than once");
+        }
+        self.duplicates = duplicates;
+    }
+
+    /// Get references defined by more than one object on load, e.g. after a bad merge, sorted.
+    /// Only the first object of each reference is loaded, see
+    /// [`PBXRootObject::repair_duplicate_references`].
+    pub fn duplicate_references(&self) -> Vec<&str> {
+        let mut references = self
+            .duplicates
+            .iter()
+            .map(|(reference, _)| reference.as_str())
+            .collect::<Vec<_>>();
+        references.sort_unstable();
+        references.dedup();
+        references
+    }
+
+    /// Insert objects dropped on load for sharing their reference with another object under
+    /// new references, and point referents at them.
+    ///
+    /// A field is pointed at the re-keyed object when it refers to objects of its kind but not
+    /// of the kind of the first object, e.g. a `fileRef` when a file reference and a build
+    /// phase collide. When both objects are of the same kind, repeated occurrences of the
+    /// reference in the same list, e.g. group children, are pointed at the re-keyed object and
+    /// other referents keep the first object.
+    pub fn repair_duplicate_references(&mut self) -> Vec<PBXDuplicateRepair> {
+        let mut repairs = vec![];
+        for (reference, object) in std::mem::take(&mut self.duplicates) {
+            let kind = object.get_kind("isa").cloned();
+            let first_kind = self
+                .objects
+                .map
+                .get(&reference)
+                .and_then(|first| first.get_kind("isa"))
+                .cloned();
+            let new_reference = String::from(self.objects.insert_object(object, None));
+
+            // Whether a `field` pointing at `reference` is meant for the re-keyed object
+            let moves = |field: &str| {
+                let accepts = |kind: &Option<PBXObjectKind>| {
+                    kind.as_ref()
+                        .map(|kind| field_accepts(field, kind))
+                        .unwrap_or_default()
+                };
+                accepts(&kind) && !accepts(&first_kind)
+            };
+            let same_kind = kind == first_kind;
+
+            let mut referents = vec![];
+            self.objects.edit_objects(|holder, fields| {
+                if holder == new_reference {
+                    return;
+                }
+                let mut repointed = false;
+                for (field, value) in fields.0.iter_mut() {
+                    match value {
+                        PBXValue::String(value) if value == &reference && moves(field) => {
+                            *value = new_reference.clone();
+                            repointed = true;
+                        }
+                        PBXValue::Vec(values) => {
+                            let mut occurrences = values.0.iter_mut().filter(|value| {
+                                value.as_string().map(|v| v == &reference) == Some(true)
+                            });
+                            let repeated = if moves(field) {
+                                occurrences.collect::<Vec<_>>()
+                            } else if same_kind {
+                                occurrences.next();
+                                occurrences.collect()
+                            } else {
+                                vec![]
+                            };
+                            for value in repeated {
+                                *value = PBXValue::String(new_reference.clone());
+                                repointed = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if repointed {
+                    referents.push(holder.to_string());
+                }
+            });
+            if self.root_object_reference == reference
+                && kind == Some(PBXObjectKind::PBXProject)
+                && first_kind != kind
+            {
+                self.root_object_reference = new_reference.clone();
+            }
+
+            referents.sort();
+            repairs.push(PBXDuplicateRepair {
+                reference,
+                new_reference,
+                referents,
+            });
+        }
+        self.objects.invalidate_path_index();
+        repairs
+    }
+}
+
+/// Whether `field` holds references to objects of `kind`
+fn field_accepts(field: &str, kind: &PBXObjectKind) -> bool {
+    use PBXObjectKind::*;
+    match field {
+        "children"
+        | "mainGroup"
+        | "productRefGroup"
+        | "fileRef"
+        | "productReference"
+        | "baseConfigurationReference" => kind.is_fs_reference(),
+        "containerPortal" => kind.is_fs_reference() || kind == &PBXProject,
+        "buildPhases" => kind.is_build_phase(),
+        "files" => kind == &PBXBuildFile,
+        "targets" | "target" => kind.is_target(),
+        "dependencies" => kind == &PBXTargetDependency,
+        "buildConfigurations" => kind == &XCBuildConfiguration,
+        "buildConfigurationList" => kind == &XCConfigurationList,
+        "targetProxy" | "remoteRef" => kind == &PBXContainerItemProxy,
+        "buildRules" => kind == &PBXBuildRule,
+        "packageReferences" | "package" => {
+            matches!(
+                kind,
+                XCRemoteSwiftPackageReference | XCLocalSwiftPackageReference
+            )
+        }
+        "packageProductDependencies" | "productRef" => kind == &XCSwiftPackageProductDependency,
+        "fileSystemSynchronizedGroups" => kind == &PBXFileSystemSynchronizedRootGroup,
+        "exceptions" => matches!(
+            kind,
+            PBXFileSystemSynchronizedBuildFileExceptionSet
+                | PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn repair_duplicate_references() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let file = "F2E640B5C2B85914F6801498";
+        let build_file = content
+            .lines()
+            .find(|line| line.contains(&format!("fileRef = {file}")))
+            .and_then(|line| line.split_whitespace().next())
+            .unwrap();
+        // A merge left a second file reference under the same reference, listed twice in its
+        // group, and a build phase colliding with the build file of the first one
+        let merged = content
+            .replacen(
+                "objects = {\n",
+                &format!(
+                    "objects = {{\n\
+                     \t\t{file} = {{isa = PBXFileReference; path = merged.swift; \
+                     sourceTree = \"<group>\"; }};\n\
+                     \t\t{build_file} = {{isa = PBXResourcesBuildPhase; files = (); }};\n"
+                ),
+                1,
+            )
+            .replacen(
+                &format!("\t\t\t\t{file},\n"),
+                &format!("\t\t\t\t{file},\n\t\t\t\t{file},\n"),
+                1,
+            );
+        let target = "0B66A7280B2FFE35D5F8E9A6";
+        let merged = merged.replacen(
+            "buildPhases = (\n",
+            &format!("buildPhases = (\n\t\t\t\t{build_file},\n"),
+            1,
+        );
+
+        let mut root_object = PBXRootObject::try_from(merged.as_str()).unwrap();
+        let mut duplicates = vec![file, build_file];
+        duplicates.sort();
+        assert_eq!(duplicates, root_object.duplicate_references());
+        assert_eq!(
+            Some("merged.swift"),
+            root_object.get_file(file).unwrap().path.map(|p| p.as_str())
+        );
+
+        let repairs = root_object.repair_duplicate_references();
+        assert!(root_object.duplicate_references().is_empty());
+        let new_reference = |reference: &str| {
+            repairs
+                .iter()
+                .find(|repair| repair.reference == reference)
+                .map(|repair| repair.new_reference.clone())
+                .unwrap()
+        };
+
+        let shake = root_object.get_file(&new_reference(file)).unwrap();
+        assert_eq!(Some("shake.swift"), shake.path.map(|p| p.as_str()));
+        let group = root_object
+            .groups()
+            .into_iter()
+            .find(|group| group.children.iter().any(|child| child.id == file))
+            .unwrap();
+        assert!(group
+            .children
+            .iter()
+            .any(|child| child.id == new_reference(file)));
+
+        let phases = root_object.get_target(target).unwrap().build_phases;
+        assert!(phases.iter().any(|phase| phase.id == build_file));
+        assert!(root_object.build_phases().iter().any(|phase| phase
+            .files
+            .iter()
+            .any(|f| f.id == new_reference(build_file))));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::pbxproj::*;
+use std::io::Read;
+
+impl PBXRootObject {
+    /// Parse project.pbxproj content read from `reader`, e.g. an archive entry, a git blob or a
+    /// network stream, see [`PBXRootObject::from_bytes`].
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut bytes = vec![];
+        reader
+            .read_to_end(&mut bytes)
+            .context("Read project content")?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse project.pbxproj content from `bytes`.
+    ///
+    /// The encoding is detected from the byte order mark, or from the placement of nul bytes
+    /// when missing: UTF-8 and UTF-16 in either byte order are supported. Both OpenStep and XML
+    /// plist content are read, the latter being written back as OpenStep content.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode(bytes)?.pipe(Self::try_from)
+    }
+}
+
+/// Decode `bytes` as UTF-8 or UTF-16, dropping any byte order mark
+fn decode(bytes: &[u8]) -> Result<String> {
+    let (bytes, little_endian) = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => return decode_utf8(rest),
+        [0xFF, 0xFE, rest @ ..] => (rest, true),
+        [0xFE, 0xFF, rest @ ..] => (rest, false),
+        [0, b, ..] if *b != 0 => (bytes, false),
+        [b, 0, ..] if *b != 0 => (bytes, true),
+        _ => return decode_utf8(bytes),
+    };
+
+    if bytes.len() % 2 != 0 {
+        anyhow::bail!("Invalid UTF-16 content: odd number of bytes");
+    }
+    let units = bytes.chunks_exact(2).map(|unit| match little_endian {
+        true => u16::from_le_bytes([unit[0], unit[1]]),
+        false => u16::from_be_bytes([unit[0], unit[1]]),
+    });
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .context("Invalid UTF-16 content")
+}
+
+fn decode_utf8(bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec()).context("Invalid UTF-8 content")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn from_bytes() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let targets = |root_object: PBXRootObject| root_object.targets().len();
+        let expected = targets(PBXRootObject::try_from(content).unwrap());
+
+        let utf8_bom = [&[0xEF, 0xBB, 0xBF], content.as_bytes()].concat();
+        let utf16_le = [0xFF, 0xFE]
+            .into_iter()
+            .chain(content.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect::<Vec<_>>();
+        let utf16_be = content
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect::<Vec<_>>();
+        for bytes in [content.as_bytes(), &utf8_bom, &utf16_le, &utf16_be] {
+            assert_eq!(expected, targets(PBXRootObject::from_bytes(bytes).unwrap()));
+        }
+        assert_eq!(
+            expected,
+            targets(PBXRootObject::from_reader(utf16_le.as_slice()).unwrap())
+        );
+
+        assert!(PBXRootObject::from_bytes(&[0xFF, 0xFF, 0xFF]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::pbxproj::*;
+use md5::{Digest, Md5};
+
+impl PBXRootObject {
+    /// Stable hash of the project semantic content, as 32 hex characters.
+    ///
+    /// Formatting, comments, object order and dictionary key order are ignored, so the
+    /// fingerprint only changes when something meaningful changes. Numbers, decimals and
+    /// booleans hash as the strings they are written as, e.g. `X = 5.0;` and `X = "5.0";` are
+    /// the same. List order is significant, e.g. reordering build phases changes the
+    /// fingerprint. The fingerprint is the same on every platform.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Md5::new();
+        hasher.update([self.archive_version, self.object_version]);
+        hash_map(&mut hasher, &self.classes);
+        hash_str(&mut hasher, &self.root_object_reference);
+
+        let mut objects = self.objects.iter().collect::<Vec<_>>();
+        objects.sort_by_key(|(reference, _)| *reference);
+        for (reference, object) in objects {
+            hash_str(&mut hasher, reference);
+            hash_map(&mut hasher, object);
+        }
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+fn hash_str(hasher: &mut Md5, value: &str) {
+    hasher.update((value.len() as u64).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
+fn hash_map(hasher: &mut Md5, map: &PBXHashMap) {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(key, _)| *key);
+    hasher.update((entries.len() as u64).to_le_bytes());
+    for (key, value) in entries {
+        hash_str(hasher, key);
+        hash_value(hasher, value);
+    }
+}
+
+fn hash_value(hasher: &mut Md5, value: &PBXValue) {
+    match value {
+        // Scalars hash as the text they are written as, quoting doesn't change their meaning
+        PBXValue::String(value) => {
+            hasher.update([0]);
+            hash_str(hasher, value);
+        }
+        PBXValue::Float(value) => {
+            hasher.update([0]);
+            hash_str(hasher, value.as_str());
+        }
+        PBXValue::Number(value) => {
+            hasher.update([0]);
+            hash_str(hasher, &value.to_string());
+        }
+        PBXValue::Bool(value) => {
+            hasher.update([0]);
+            hash_str(hasher, if *value { "YES" } else { "NO" });
+        }
+        PBXValue::Kind(value) => {
+            hasher.update([0]);
+            hash_str(hasher, &value.to_string());
+        }
+        PBXValue::Object(value) => {
+            hasher.update([1]);
+            hash_map(hasher, value);
+        }
+        PBXValue::Vec(values) => {
+            hasher.update([2]);
+            hasher.update((values.len() as u64).to_le_bytes());
+            values.iter().for_each(|value| hash_value(hasher, value));
+        }
+        PBXValue::Null(_) => hasher.update([6]),
+        PBXValue::Data(bytes) => {
+            hasher.update([7]);
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn fingerprint() {
+        let mut root_object = test_demo_file!(demo1);
+        let fingerprint = root_object.fingerprint();
+        assert_eq!(32, fingerprint.len());
+        assert_eq!(fingerprint, test_demo_file!(demo1).fingerprint());
+        assert_ne!(fingerprint, test_demo_file!(demo2).fingerprint());
+
+        let snapshot = root_object.snapshot();
+        root_object.get_or_add_group("Kit").unwrap();
+        assert_ne!(fingerprint, root_object.fingerprint());
+
+        root_object.restore(snapshot);
+        assert_eq!(fingerprint, root_object.fingerprint());
+    }
+
+    #[test]
+    fn fingerprint_ignores_quoting() {
+        let project = |value: &str| {
+            let content = format!(
+                "// !$*UTF8*$!\n{{ archiveVersion = 1; classes = {{}}; objectVersion = 56; \
+                 objects = {{ A = {{ isa = XCBuildConfiguration; buildSettings = {{ X = {value}; }}; \
+                 name = Debug; }}; }}; rootObject = A; }}"
+            );
+            PBXRootObject::try_from(content.as_str()).unwrap()
+        };
+        assert_eq!(
+            project("5.0").fingerprint(),
+            project("\"5.0\"").fingerprint()
+        );
+        assert_eq!(project("5").fingerprint(), project("\"5\"").fingerprint());
+        assert_eq!(
+            project("YES").fingerprint(),
+            project("\"YES\"").fingerprint()
+        );
+        assert_ne!(project("5").fingerprint(), project("5.0").fingerprint());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::path::Path;
+
+const CARTHAGE_BUILD_DIR: &str = "Carthage/Build";
+const CARTHAGE_COPY_FRAMEWORKS_NAME: &str = "Carthage Copy Frameworks";
+const CARTHAGE_COPY_FRAMEWORKS_SCRIPT: &str = "/usr/local/bin/carthage copy-frameworks\n";
+
+impl PBXRootObject {
+    /// Link carthage built framework to the target with the given reference.
+    ///
+    /// `path` is relative to project root, e.g. `Carthage/Build/iOS/Alamofire.framework` or
+    /// `Carthage/Build/Alamofire.xcframework`. The framework is added to the `Frameworks` group
+    /// relative to the project root, linked in the frameworks build phase, its directory is
+    /// appended to `FRAMEWORK_SEARCH_PATHS` and the `carthage copy-frameworks` script is kept
+    /// in sync. Returns the reference of the framework file reference.
+    pub fn add_carthage_framework<P: AsRef<Path>>(
+        &mut self,
+        target: &str,
+        path: P,
+    ) -> Result<String> {
+        let path = path.as_ref();
+        if !path.starts_with(CARTHAGE_BUILD_DIR) {
+            anyhow::bail!("{path:?} is not located in {CARTHAGE_BUILD_DIR:?}");
+        }
+        let file = self.link_framework(target, path)?;
+
+        if let Some(directory) = path.parent() {
+            let search_path = format!("$(PROJECT_DIR)/{}", directory.to_string_lossy());
+            self.objects
+                .update_target_build_settings(target, |settings| {
+                    settings.append_setting_value("FRAMEWORK_SEARCH_PATHS", &search_path);
+                })?;
+        }
+
+        self.sync_carthage_copy_frameworks(target)?;
+
+        Ok(file)
+    }
+
+    /// Unlink carthage built framework at `path` from the target with the given reference and
+    /// keep the `carthage copy-frameworks` script in sync.
+    pub fn remove_carthage_framework<P: AsRef<Path>>(
+        &mut self,
+        target: &str,
+        path: P,
+    ) -> Result<()> {
+        let path = path.as_ref().to_string_lossy().to_string();
+        let build_files = self
+            .objects
+            .get_target_build_phase(target, &PBXBuildPhaseKind::Frameworks)
+            .and_then(|phase| self.get_build_phase(&phase))
+            .map(|phase| {
+                phase
+                    .files
+                    .into_iter()
+                    .filter(|f| f.file.as_ref().and_then(|f| f.path) == Some(&path))
+                    .map(|f| f.id)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        for build_file in build_files {
+            self.objects.remove_build_file(&build_file);
+        }
+
+        self.sync_carthage_copy_frameworks(target)?;
+        Ok(())
+    }
+
+    /// Sync `carthage copy-frameworks` run script input and output paths with carthage built
+    /// frameworks linked by the target with the given reference. The run script phase is created
+    /// when a carthage framework is linked. Returns the reference of the run script phase if any.
+    pub fn sync_carthage_copy_frameworks(&mut self, target: &str) -> Result<Option<String>> {
+        let target_object = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?;
+
+        let frameworks = target_object
+            .build_phases
+            .iter()
+            .filter(|phase| phase.is_frameworks())
+            .flat_map(|phase| phase.files.iter())
+            .flat_map(|build_file| build_file.file.as_ref()?.path)
+            .filter(|path| path.starts_with(CARTHAGE_BUILD_DIR) && path.ends_with(".framework"))
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>();
+
+        let phase = target_object
+            .build_phases
+            .iter()
+            .find(|phase| {
+                phase
+                    .inner
+                    .as_ref()
+                    .and_then(|script| script.shell_script)
+                    .map(|script| script.contains("carthage copy-frameworks"))
+                    .unwrap_or_default()
+            })
+            .map(|phase| phase.id.clone());
+
+        let phase = match phase {
+            Some(phase) => phase,
+            None if frameworks.is_empty() => return Ok(None),
+            None => self.objects.add_shell_script_build_phase(
+                target,
+                CARTHAGE_COPY_FRAMEWORKS_NAME,
+                CARTHAGE_COPY_FRAMEWORKS_SCRIPT,
+            )?,
+        };
+
+        let input_paths = frameworks
+            .iter()
+            .map(|path| format!("$(SRCROOT)/{path}"))
+            .collect::<Vec<_>>();
+        let output_paths = frameworks
+            .iter()
+            .flat_map(|path| Path::new(path).file_name())
+            .map(|name| {
+                format!(
+                    "$(BUILT_PRODUCTS_DIR)/$(FRAMEWORKS_FOLDER_PATH)/{}",
+                    name.to_string_lossy()
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(mut phase) = self.objects.get_object_mut(&phase) {
+            phase.insert_value("inputPaths", input_paths);
+            phase.insert_value("outputPaths", output_paths);
+        }
+
+        Ok(Some(phase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn add_and_remove_carthage_framework() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let path = "Carthage/Build/iOS/Alamofire.framework";
+
+        let file = root_object.add_carthage_framework(&target, path).unwrap();
+        assert_eq!(
+            file,
+            root_object.add_carthage_framework(&target, path).unwrap()
+        );
+        assert_eq!(
+            Some(PathBuf::from(
+                "/tmp/Demo/Carthage/Build/iOS/Alamofire.framework"
+            )),
+            root_object.objects().get_full_path(&file, "/tmp/Demo")
+        );
+
+        let script = |root_object: &PBXRootObject| {
+            root_object.targets()[0]
+                .build_phases
+                .iter()
+                .find(|p| p.is_run_script())
+                .map(|p| p.inner.as_ref().unwrap().input_paths.len())
+        };
+
+        let target_object = root_object.get_target(&target).unwrap();
+        let frameworks = target_object
+            .build_phases
+            .iter()
+            .find(|p| p.is_frameworks())
+            .unwrap();
+        assert_eq!(2, frameworks.files.len());
+        assert_eq!(Some(1), script(&root_object));
+        assert_eq!(
+            Some(&PBXValue::from(vec![
+                "$(inherited)",
+                "$(PROJECT_DIR)/Carthage/Build/iOS"
+            ])),
+            target_object
+                .build_configuration_list
+                .as_ref()
+                .unwrap()
+                .build_configurations[0]
+                .build_settings
+                .get_value("FRAMEWORK_SEARCH_PATHS")
+        );
+
+        root_object
+            .remove_carthage_framework(&target, path)
+            .unwrap();
+        assert_eq!(Some(0), script(&root_object));
+    }
+}
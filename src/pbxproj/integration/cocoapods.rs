@@ -0,0 +1,239 @@
+use crate::pbxproj::*;
+use std::path::Path;
+
+const PODS_GROUP_NAME: &str = "Pods";
+const PODS_SCRIPT_PHASE_PREFIX: &str = "[CP]";
+
+/// CocoaPods generated artifacts found in a project.
+///
+/// All fields hold object references.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CocoaPodsArtifacts {
+    /// `Pods` groups holding Pods xcconfig files
+    pub groups: Vec<String>,
+    /// `[CP]` run script build phases, e.g. `[CP] Check Pods Manifest.lock`
+    pub script_phases: Vec<String>,
+    /// Build configurations based on Pods xcconfig files
+    pub base_configurations: Vec<String>,
+    /// Pods xcconfig file references
+    pub xcconfig_files: Vec<String>,
+    /// Pods product file references, e.g. `Pods_App.framework` or `libPods-App.a`
+    pub product_files: Vec<String>,
+    /// Build files linking or embedding Pods products
+    pub build_files: Vec<String>,
+}
+
+impl CocoaPodsArtifacts {
+    /// Whether no CocoaPods artifacts were found
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+            && self.script_phases.is_empty()
+            && self.base_configurations.is_empty()
+            && self.xcconfig_files.is_empty()
+            && self.product_files.is_empty()
+            && self.build_files.is_empty()
+    }
+}
+
+impl PBXRootObject {
+    /// Collect CocoaPods generated artifacts, see [`CocoaPodsArtifacts`].
+    pub fn cocoapods_artifacts(&self) -> CocoaPodsArtifacts {
+        let mut artifacts = CocoaPodsArtifacts::default();
+
+        for (reference, object) in self.objects.map.iter() {
+            let kind = match object.get_kind("isa") {
+                Some(kind) => kind,
+                None => continue,
+            };
+            if let Some(fs_kind) = kind.as_pbxfs_reference() {
+                if fs_kind.is_file_group() {
+                    let is_pods_group = object
+                        .get_string("name")
+                        .or_else(|| object.get_string("path"))
+                        .map(|name| name == PODS_GROUP_NAME)
+                        .unwrap_or_default();
+                    if is_pods_group {
+                        artifacts.groups.push(reference.clone());
+                    }
+                } else if let Some(path) = object.get_string("path").filter(|_| fs_kind.is_file()) {
+                    if is_pods_xcconfig(path) {
+                        artifacts.xcconfig_files.push(reference.clone());
+                    } else if is_pods_product(path) {
+                        artifacts.product_files.push(reference.clone());
+                    }
+                }
+            } else if kind.as_pbx_build_phase().map(|k| k.is_run_script()) == Some(true) {
+                let is_pods_phase = object
+                    .get_string("name")
+                    .map(|name| name.starts_with(PODS_SCRIPT_PHASE_PREFIX))
+                    .unwrap_or_default();
+                if is_pods_phase {
+                    artifacts.script_phases.push(reference.clone());
+                }
+            }
+        }
+
+        for (reference, object) in self.objects.map.iter() {
+            if let Some(base) = object.get_string("baseConfigurationReference") {
+                if artifacts.xcconfig_files.contains(base) {
+                    artifacts.base_configurations.push(reference.clone());
+                }
+            }
+            if let Some(file) = object.get_string("fileRef") {
+                if artifacts.product_files.contains(file) {
+                    artifacts.build_files.push(reference.clone());
+                }
+            }
+        }
+
+        for references in [
+            &mut artifacts.groups,
+            &mut artifacts.script_phases,
+            &mut artifacts.base_configurations,
+            &mut artifacts.xcconfig_files,
+            &mut artifacts.product_files,
+            &mut artifacts.build_files,
+        ] {
+            references.sort();
+        }
+
+        artifacts
+    }
+
+    /// Remove CocoaPods generated artifacts from the project, like `pod deintegrate` does.
+    ///
+    /// `[CP]` script phases, Pods products and their build files, Pods xcconfig files and `Pods`
+    /// groups are removed and build configurations no longer inherit from Pods xcconfig files.
+    /// Files on disk aren't touched. Returns the removed artifacts.
+    pub fn deintegrate_cocoapods(&mut self) -> CocoaPodsArtifacts {
+        let artifacts = self.cocoapods_artifacts();
+
+        for configuration in artifacts.base_configurations.iter() {
+            if let Some(mut configuration) = self.objects.get_object_mut(configuration) {
+                configuration.remove_value("baseConfigurationReference");
+            }
+        }
+
+        for build_file in artifacts.build_files.iter() {
+            self.objects.remove_build_file(build_file);
+        }
+
+        for reference in artifacts
+            .script_phases
+            .iter()
+            .chain(artifacts.xcconfig_files.iter())
+            .chain(artifacts.product_files.iter())
+            .chain(artifacts.groups.iter())
+        {
+            self.objects.invalidate_path_index();
+            self.objects.remove_object(reference);
+            self.objects.edit_objects(|_, object| {
+                for key in ["children", "buildPhases"] {
+                    if let Some(references) = object.get_vec_mut(key) {
+                        references
+                            .0
+                            .retain(|r| r.as_string().map(|r| r.as_str()) != Some(reference));
+                    }
+                }
+            });
+        }
+
+        artifacts
+    }
+}
+
+fn is_pods_xcconfig(path: &str) -> bool {
+    let file_name = file_name(path);
+    file_name.starts_with("Pods-") && file_name.ends_with(".xcconfig")
+}
+
+fn is_pods_product(path: &str) -> bool {
+    let file_name = file_name(path);
+    (file_name.starts_with("Pods_") && file_name.ends_with(".framework"))
+        || (file_name.starts_with("libPods-") && file_name.ends_with(".a"))
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn deintegrate_cocoapods() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        assert!(root_object.cocoapods_artifacts().is_empty());
+
+        let pods = root_object.get_or_add_group("Pods").unwrap();
+        let xcconfig = root_object
+            .objects_mut()
+            .add_file_reference(
+                &pods,
+                "Target Support Files/Pods-Wordle/Pods-Wordle.debug.xcconfig",
+            )
+            .unwrap();
+        let configurations = root_object.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations
+            .iter()
+            .map(|c| c.id.clone())
+            .collect::<Vec<_>>();
+        for configuration in configurations.iter() {
+            root_object
+                .objects_mut()
+                .get_object_mut(configuration)
+                .unwrap()
+                .insert_value("baseConfigurationReference", xcconfig.as_str());
+        }
+
+        let frameworks = root_object.get_or_add_group("Frameworks").unwrap();
+        let product = root_object
+            .objects_mut()
+            .add_file_reference(&frameworks, "Pods_Wordle.framework")
+            .unwrap();
+        let phase = root_object
+            .objects()
+            .get_target_build_phase(&target, &PBXBuildPhaseKind::Frameworks)
+            .unwrap();
+        root_object
+            .objects_mut()
+            .add_build_file(&phase, &product)
+            .unwrap();
+        root_object
+            .objects_mut()
+            .add_shell_script_build_phase(&target, "[CP] Check Pods Manifest.lock", "diff\n")
+            .unwrap();
+
+        let artifacts = root_object.cocoapods_artifacts();
+        assert_eq!(1, artifacts.groups.len());
+        assert_eq!(1, artifacts.script_phases.len());
+        assert_eq!(2, artifacts.base_configurations.len());
+        assert_eq!(1, artifacts.build_files.len());
+
+        assert_eq!(artifacts, root_object.deintegrate_cocoapods());
+        assert!(root_object.cocoapods_artifacts().is_empty());
+
+        let target = root_object.get_target(&target).unwrap();
+        assert_eq!(3, target.build_phases.len());
+        assert_eq!(1, target.build_phases[2].files.len());
+        assert!(target
+            .build_configuration_list
+            .unwrap()
+            .build_configurations
+            .iter()
+            .all(|c| c.base_configuration.is_none()));
+        assert!(root_object
+            .root_group()
+            .children
+            .iter()
+            .all(|c| c.name != Some(&"Pods".to_string())));
+    }
+}
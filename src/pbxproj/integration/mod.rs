@@ -0,0 +1,63 @@
+//! Helpers for integrating third party dependency managers
+mod carthage;
+mod cocoapods;
+mod xcframework;
+
+pub use cocoapods::*;
+
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::path::Path;
+
+impl PBXRootObject {
+    /// Add the framework at `path`, relative to project root, to the `Frameworks` group and link
+    /// it in the frameworks build phase of the target with the given reference. The file
+    /// reference is relative to the project root whatever the path of the group, and is reused
+    /// when the group already has one for `path`. Returns the reference of the file reference.
+    fn link_framework(&mut self, target: &str, path: &Path) -> Result<String> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("{path:?} has no file name"))?
+            .to_string_lossy()
+            .to_string();
+        let path_str = path.to_string_lossy().to_string();
+
+        let group = self.get_or_add_group("Frameworks")?;
+        let file = match self.objects.get_child_by_name_or_path(&group, &path_str) {
+            Some(file) => file,
+            None => {
+                let file = self.objects.add_file_reference(&group, &path_str)?;
+                if let Some(mut file) = self.objects.get_object_mut(&file) {
+                    file.insert_value("name", file_name);
+                    file.insert_value("sourceTree", PBXSourceTree::SourceRoot.to_string());
+                }
+                file
+            }
+        };
+
+        let phase = match self
+            .objects
+            .get_target_build_phase(target, &PBXBuildPhaseKind::Frameworks)
+        {
+            Some(phase) => phase,
+            None => self
+                .objects
+                .add_build_phase(target, PBXBuildPhaseKind::Frameworks)?,
+        };
+        if !self.phase_has_file(&phase, &file) {
+            self.objects.add_build_file(&phase, &file)?;
+        }
+        Ok(file)
+    }
+
+    /// Whether the build phase with the given reference builds `file`
+    fn phase_has_file(&self, phase: &str, file: &str) -> bool {
+        self.get_build_phase(phase)
+            .map(|p| {
+                p.files
+                    .iter()
+                    .any(|f| f.file.as_ref().map(|f| f.id.as_str()) == Some(file))
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,186 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::path::Path;
+
+const EMBED_FRAMEWORKS_NAME: &str = "Embed Frameworks";
+/// `dstSubfolderSpec` of copy files phases copying to the product frameworks folder
+const FRAMEWORKS_SUBFOLDER_SPEC: isize = 10;
+const EMBED_ATTRIBUTES: [&str; 2] = ["CodeSignOnCopy", "RemoveHeadersOnCopy"];
+
+impl PBXRootObject {
+    /// Link the xcframework at `path` to the target with the given reference.
+    ///
+    /// `path` is relative to project root, e.g. `Vendor/Sentry.xcframework`. The xcframework is
+    /// added to the `Frameworks` group relative to the project root, linked in the frameworks
+    /// build phase and its directory is appended to `FRAMEWORK_SEARCH_PATHS`. With `embed`, it
+    /// is also copied to the product by the `Embed Frameworks` phase with code sign on copy, and
+    /// `LD_RUNPATH_SEARCH_PATHS` gets the product frameworks folder. Static xcframeworks
+    /// shouldn't be embedded. Returns the reference of the xcframework file reference.
+    pub fn add_xcframework<P: AsRef<Path>>(
+        &mut self,
+        target: &str,
+        path: P,
+        embed: bool,
+    ) -> Result<String> {
+        let path = path.as_ref();
+        if path
+            .extension()
+            .map(|ext| ext != "xcframework")
+            .unwrap_or(true)
+        {
+            anyhow::bail!("{path:?} is not an xcframework");
+        }
+        let platform = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?
+            .info(&self.objects)
+            .platform;
+
+        let file = self.link_framework(target, path)?;
+
+        if embed {
+            let phase = match self.embed_frameworks_phase(target) {
+                Some(phase) => phase,
+                None => {
+                    let phase = self
+                        .objects
+                        .add_build_phase(target, PBXBuildPhaseKind::CopyFiles)?;
+                    if let Some(mut phase) = self.objects.get_object_mut(&phase) {
+                        phase.insert_value("dstPath", "");
+                        phase.insert_value("dstSubfolderSpec", FRAMEWORKS_SUBFOLDER_SPEC);
+                        phase.insert_value("name", EMBED_FRAMEWORKS_NAME);
+                    }
+                    phase
+                }
+            };
+            if !self.phase_has_file(&phase, &file) {
+                let build_file = self.objects.add_build_file(&phase, &file)?;
+                let mut settings = PBXHashMap::default();
+                settings.insert_value("ATTRIBUTES", EMBED_ATTRIBUTES.to_vec());
+                if let Some(mut build_file) = self.objects.get_object_mut(&build_file) {
+                    build_file.insert_value("settings", settings);
+                }
+            }
+        }
+
+        let search_path = match path.parent().map(|p| p.to_string_lossy()) {
+            Some(directory) if !directory.is_empty() => format!("$(PROJECT_DIR)/{directory}"),
+            _ => "$(PROJECT_DIR)".to_string(),
+        };
+        let runpath = match platform {
+            PBXTargetPlatform::MacOS => "@executable_path/../Frameworks",
+            _ => "@executable_path/Frameworks",
+        };
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.append_setting_value("FRAMEWORK_SEARCH_PATHS", &search_path);
+                if embed {
+                    settings.append_setting_value("LD_RUNPATH_SEARCH_PATHS", runpath);
+                }
+            })?;
+
+        Ok(file)
+    }
+
+    /// Get reference of the copy files phase of the target with the given reference copying to
+    /// the product frameworks folder, if any
+    fn embed_frameworks_phase(&self, target: &str) -> Option<String> {
+        self.get_target(target)?
+            .build_phases
+            .into_iter()
+            .filter(|phase| phase.is_copy_files())
+            .find(|phase| {
+                self.objects
+                    .map
+                    .get(&phase.id)
+                    .and_then(|phase| phase.get_number("dstSubfolderSpec"))
+                    == Some(&FRAMEWORKS_SUBFOLDER_SPEC)
+            })
+            .map(|phase| phase.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn add_xcframework() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let path = "Vendor/Sentry.xcframework";
+        assert!(root_object
+            .add_xcframework(&target, "Vendor/Sentry.framework", false)
+            .is_err());
+
+        let file = root_object.add_xcframework(&target, path, true).unwrap();
+        assert_eq!(
+            file,
+            root_object.add_xcframework(&target, path, true).unwrap()
+        );
+        assert_eq!(
+            Some(PathBuf::from("/tmp/Demo/Vendor/Sentry.xcframework")),
+            root_object.objects().get_full_path(&file, "/tmp/Demo")
+        );
+        assert_eq!(
+            Some("wrapper.xcframework"),
+            root_object
+                .get_file(&file)
+                .unwrap()
+                .last_known_file_type
+                .map(|t| t.as_str())
+        );
+
+        let target_object = root_object.get_target(&target).unwrap();
+        let builds = |phase: &PBXBuildPhase| {
+            phase
+                .files
+                .iter()
+                .filter(|f| f.file.as_ref().map(|f| &f.id) == Some(&file))
+                .count()
+        };
+        let frameworks = target_object
+            .build_phases
+            .iter()
+            .find(|p| p.is_frameworks())
+            .unwrap();
+        assert_eq!(1, builds(frameworks));
+        let embed = target_object
+            .build_phases
+            .iter()
+            .find(|p| p.is_copy_files())
+            .unwrap();
+        assert_eq!(1, builds(embed));
+        assert_eq!(
+            Some(&PBXValue::from(vec![
+                "CodeSignOnCopy",
+                "RemoveHeadersOnCopy"
+            ])),
+            embed.files[0]
+                .settings
+                .and_then(|s| s.as_object())
+                .and_then(|s| s.get_value("ATTRIBUTES"))
+        );
+
+        let settings = &target_object
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+        assert_eq!(
+            Some(&PBXValue::from(vec![
+                "$(inherited)",
+                "$(PROJECT_DIR)/Vendor"
+            ])),
+            settings.get_value("FRAMEWORK_SEARCH_PATHS")
+        );
+        assert!(settings
+            .get_vec("LD_RUNPATH_SEARCH_PATHS")
+            .unwrap()
+            .as_vec_strings()
+            .iter()
+            .any(|path| path.as_str() == "@executable_path/Frameworks"));
+    }
+}
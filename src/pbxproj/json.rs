@@ -0,0 +1,113 @@
+use crate::pbxproj::pest::unquoted_value;
+use crate::pbxproj::writer::quote;
+use crate::pbxproj::*;
+use serde_json::{Map, Value};
+
+impl PBXRootObject {
+    /// Serialize as JSON, like `plutil -convert json` does: every scalar is a string, e.g.
+    /// `"YES"` or `"56"`, and keys are sorted.
+    pub fn to_json(&self) -> String {
+        let mut root = PBXHashMap::default();
+        root.insert_value("archiveVersion", self.archive_version as isize);
+        root.insert_value("classes", self.classes.clone());
+        root.insert_value("objectVersion", self.object_version as isize);
+        let objects = self
+            .objects
+            .iter()
+            .map(|(reference, object)| (reference.as_str().into(), object.clone().into()))
+            .collect();
+        root.insert_value("objects", PBXHashMap::new(objects));
+        root.insert_value("rootObject", self.root_object_reference.as_str());
+        to_json(&PBXValue::Object(root)).to_string()
+    }
+
+    /// Create project from JSON written by [`PBXRootObject::to_json`] or `plutil -convert
+    /// json`. Strings are read like unquoted pbxproj values, e.g. `"YES"` as a bool and `"56"`
+    /// as a number, while JSON numbers and bools are taken as is.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value = serde_json::from_str::<Value>(json).context("Parse json")?;
+        match from_json(value) {
+            PBXValue::Object(map) => Self::try_from(map),
+            _ => anyhow::bail!("Expected a json object"),
+        }
+    }
+}
+
+fn to_json(value: &PBXValue) -> Value {
+    match value {
+        PBXValue::String(value) => value.as_str().into(),
+        PBXValue::Object(map) => map
+            .iter()
+            .map(|(key, value)| (key.to_string(), to_json(value)))
+            .collect::<Map<_, _>>()
+            .into(),
+        PBXValue::Vec(values) => values.iter().map(to_json).collect(),
+        PBXValue::Number(value) => value.to_string().into(),
+        PBXValue::Float(value) => value.as_str().into(),
+        PBXValue::Bool(value) => if *value { "YES" } else { "NO" }.into(),
+        PBXValue::Kind(kind) => kind.to_string().into(),
+        // Not representable in JSON, kept as the data literal string
+        PBXValue::Data(bytes) => writer::data_literal(bytes).into(),
+        PBXValue::Null(()) => Value::Null,
+    }
+}
+
+/// Value of string `value` read from a format without quoting, e.g. `"YES"` as a bool
+pub(crate) fn string_value(value: String) -> PBXValue {
+    match quote(&value) == value {
+        true => unquoted_value(&value).unwrap_or(PBXValue::String(value)),
+        false => PBXValue::String(value),
+    }
+}
+
+fn from_json(value: Value) -> PBXValue {
+    match value {
+        Value::String(value) => string_value(value),
+        Value::Object(map) => PBXHashMap::new(
+            map.into_iter()
+                .map(|(key, value)| (key.into(), from_json(value)))
+                .collect(),
+        )
+        .into(),
+        Value::Array(values) => PBXVec::new(values.into_iter().map(from_json).collect()).into(),
+        Value::Number(number) => match number.as_i64() {
+            Some(number) => PBXValue::Number(number as isize),
+            None => string_value(number.to_string()),
+        },
+        Value::Bool(value) => PBXValue::Bool(value),
+        Value::Null => PBXValue::Null(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn json_round_trip() {
+        let root_object = test_demo_file!(demo1);
+        let json = root_object.to_json();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(Some("1"), value["archiveVersion"].as_str());
+        assert_eq!(
+            Some("PBXProject"),
+            value["objects"][root_object.root_object_reference()]["isa"].as_str()
+        );
+
+        let read = PBXRootObject::from_json(&json).unwrap();
+        assert_eq!(root_object.objects(), read.objects());
+        assert_eq!(root_object.object_version(), read.object_version());
+        assert_eq!(
+            root_object.root_object_reference(),
+            read.root_object_reference()
+        );
+        assert!(PBXRootObject::from_json("[]").is_err());
+
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        for entry in std::fs::read_dir(samples).unwrap().flatten() {
+            let root_object = PBXRootObject::try_from(entry.path()).unwrap();
+            let read = PBXRootObject::from_json(&root_object.to_json()).unwrap();
+            assert_eq!(root_object.objects(), read.objects(), "{:?}", entry.path());
+        }
+    }
+}
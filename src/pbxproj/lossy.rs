@@ -0,0 +1,211 @@
+use crate::pbxproj::pest::Scanner;
+use crate::pbxproj::*;
+
+/// Options of [`PBXRootObject::parse_with`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PBXParseOptions {
+    /// Collect problems as warnings instead of failing: malformed fields and sections are
+    /// skipped as in [`PBXRootObject::parse_lossy`], unexpected top level keys and objects
+    /// without isa are reported, and objects are decoded in [`PBXDecodeMode::Permissive`]
+    /// mode, reporting defaulted fields.
+    pub lenient: bool,
+}
+
+impl PBXRootObject {
+    /// Parse `content` with the given options, returning the project along with warnings,
+    /// always empty unless [`PBXParseOptions::lenient`].
+    pub fn parse_with(
+        content: &str,
+        options: &PBXParseOptions,
+    ) -> Result<(Self, Vec<PBXParseError>)> {
+        if !options.lenient {
+            return Ok((Self::try_from(content)?, vec![]));
+        }
+
+        let (mut root_object, mut warnings, unexpected) = Self::salvage(content);
+        for key in unexpected {
+            warnings.push(PBXParseError::new(format!("Unexpected key {key:?}")));
+        }
+        let mut missing_isa = root_object
+            .objects
+            .iter()
+            .filter(|(_, object)| object.get_kind("isa").is_none())
+            .map(|(reference, _)| reference.clone())
+            .collect::<Vec<_>>();
+        missing_isa.sort();
+        for reference in missing_isa {
+            warnings.push(PBXParseError::new(format!(
+                "Object {reference:?} has no isa"
+            )));
+        }
+        root_object
+            .objects
+            .set_decode_mode(PBXDecodeMode::Permissive);
+        for warning in root_object.objects.decode_warnings() {
+            warnings.push(PBXParseError::new(warning));
+        }
+        Ok((root_object, warnings))
+    }
+
+    /// Parse `content` salvaging as much as possible instead of failing.
+    ///
+    /// Malformed fields are skipped, missing top level fields fall back to defaults and the
+    /// root object falls back to the first `PBXProject` found. Returns the salvaged project
+    /// along with every error found, which is empty for well formed content.
+    pub fn parse_lossy(content: &str) -> (Self, Vec<PBXParseError>) {
+        let (root_object, errors, _) = Self::salvage(content);
+        (root_object, errors)
+    }
+
+    /// See [`PBXRootObject::parse_lossy`], also returning unexpected top level keys
+    fn salvage(content: &str) -> (Self, Vec<PBXParseError>, Vec<SmolStr>) {
+        let mut scanner = Scanner::lossy(content);
+        let map = scanner.parse_file();
+        let duplicates = scanner.take_duplicates();
+        let mut errors = scanner.errors();
+        let mut map = map.unwrap_or_else(|error| {
+            errors.push(PBXParseError::from_error(error));
+            Default::default()
+        });
+
+        let archive_version = salvage(&mut errors, map.try_remove_number("archiveVersion"), 1);
+        let object_version = salvage(&mut errors, map.try_remove_number("objectVersion"), 46);
+        let classes = map.try_remove_object("classes").unwrap_or_default();
+        let root_object_reference = map.try_remove_string("rootObject");
+        let objects = salvage(
+            &mut errors,
+            map.try_remove_object("objects"),
+            Default::default(),
+        );
+
+        let mut collection = HashMap::with_capacity(objects.len());
+        for (reference, object) in objects.0 {
+            match object {
+                PBXValue::Object(object) => {
+                    collection.insert(reference.to_string(), object);
+                }
+                _ => errors.push(PBXParseError::new(format!(
+                    "Object {reference:?} is not an object"
+                ))),
+            }
+        }
+        let mut objects = PBXObjectCollection::new(collection);
+        objects.set_decode_mode(PBXDecodeMode::for_object_version(object_version as u8));
+
+        let root_object_reference = match root_object_reference {
+            Ok(reference) if objects.contains_key(&reference) => reference,
+            reference => {
+                errors.push(PBXParseError::new(match reference {
+                    Ok(reference) => format!("Root object {reference:?} not found"),
+                    Err(error) => format!("{error:#}"),
+                }));
+                objects
+                    .iter()
+                    .filter(|(_, object)| {
+                        object
+                            .get_kind("isa")
+                            .map(|kind| kind.is_pbx_project())
+                            .unwrap_or_default()
+                    })
+                    .map(|(reference, _)| reference)
+                    .min()
+                    .cloned()
+                    .unwrap_or_default()
+            }
+        };
+
+        let mut root_object = Self {
+            archive_version: archive_version as u8,
+            object_version: object_version as u8,
+            classes,
+            baseline: OnceLock::from(objects.clone()),
+            objects,
+            root_object_reference,
+            source: None,
+            duplicates: vec![],
+            project_name: None,
+        };
+        root_object.set_duplicates(duplicates);
+        let mut unexpected = map.0.into_keys().collect::<Vec<_>>();
+        unexpected.sort();
+        (root_object, errors, unexpected)
+    }
+}
+
+/// Value of `result`, or `default` recording the error
+fn salvage<T>(errors: &mut Vec<PBXParseError>, result: Result<T>, default: T) -> T {
+    result.unwrap_or_else(|error| {
+        errors.push(PBXParseError::from_error(error));
+        default
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn parse_lossy() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let (root_object, errors) = PBXRootObject::parse_lossy(content);
+        assert!(errors.is_empty());
+        assert_eq!(
+            test_demo_file!(demo1).fingerprint(),
+            root_object.fingerprint()
+        );
+
+        let damaged = content
+            .replace("path = Log.swift;", "path = = Log.swift;")
+            .replace("rootObject = ", "rootObjekt = ");
+        assert!(PBXRootObject::try_from(damaged.as_str()).is_err());
+
+        let (root_object, errors) = PBXRootObject::parse_lossy(&damaged);
+        assert_eq!(2, errors.len());
+        assert!(errors[0].location.is_some());
+        assert_eq!(
+            None,
+            root_object
+                .get_file("D890FDAA55C865F97B25842A")
+                .unwrap()
+                .path
+        );
+        assert_eq!(1, root_object.targets().len());
+
+        let (root_object, errors) = PBXRootObject::parse_lossy("{ objects = (");
+        assert!(root_object.objects().is_empty());
+        assert!(!errors.is_empty());
+
+        let nested = format!("// !$*UTF8*$!\n{{ objects = {}", "(".repeat(100_000));
+        assert!(PBXRootObject::try_from(nested.as_str()).is_err());
+        assert!(!PBXRootObject::parse_lossy(&nested).1.is_empty());
+    }
+
+    #[test]
+    fn parse_with() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let strict = PBXParseOptions::default();
+        let lenient = PBXParseOptions { lenient: true };
+        assert!(PBXRootObject::parse_with(content, &strict)
+            .unwrap()
+            .1
+            .is_empty());
+        assert!(PBXRootObject::parse_with(content, &lenient)
+            .unwrap()
+            .1
+            .is_empty());
+
+        let damaged = content
+            .replace("path = Log.swift;", "path = = Log.swift;")
+            .replacen("isa = XCConfigurationList;", "", 1)
+            .replace("rootObject = ", "extra = 1;\n\trootObject = ");
+        assert!(PBXRootObject::parse_with(&damaged, &strict).is_err());
+
+        let (root_object, warnings) = PBXRootObject::parse_with(&damaged, &lenient).unwrap();
+        let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert_eq!(3, warnings.len(), "{warnings:#?}");
+        assert!(warnings[0].starts_with("Expected value"));
+        assert_eq!("Unexpected key \"extra\"", warnings[1]);
+        assert!(warnings[2].ends_with("has no isa"));
+        assert_eq!(1, root_object.targets().len());
+    }
+}
@@ -1,20 +1,58 @@
 //! pbxproj file serialize and deserializer
+mod changes;
+mod comments;
+mod compatibility;
+mod diff_report;
+mod duplicates;
+mod encoding;
+mod fingerprint;
+mod integration;
+mod json;
+mod lossy;
+mod normalize;
 mod object;
+mod observer;
+mod preserve;
+mod recovery;
+mod reload;
+#[cfg(feature = "serde")]
+mod serialize;
+mod settings_lint;
+mod snapshot;
+mod transaction;
 mod value;
+mod writer;
+mod xml;
 
 pub(crate) mod pest;
+pub use self::pest::{PBXMergeConflictError, PBXParseError};
+pub use changes::*;
+pub use comments::*;
+pub use compatibility::*;
+pub use diff_report::*;
+pub use duplicates::*;
+pub use integration::*;
+pub use lossy::PBXParseOptions;
+pub use normalize::*;
 pub use object::*;
+pub use observer::PBXObserver;
+pub(crate) use observer::PBXObservers;
+pub use recovery::PBXSkippedEntry;
+use reload::PBXSource;
+pub use settings_lint::*;
+pub use snapshot::PBXSnapshot;
 pub use value::*;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    sync::OnceLock,
 };
 use tap::Pipe;
 
 /// `Main` Representation of project.pbxproj file
-#[derive(Default, derive_new::new, derive_deref_rs::Deref)]
+#[derive(Clone, Default, derive_new::new, derive_deref_rs::Deref)]
 pub struct PBXRootObject {
     /// archiveVersion
     archive_version: u8,
@@ -27,6 +65,19 @@ pub struct PBXRootObject {
     objects: PBXObjectCollection,
     /// rootObjectReference
     root_object_reference: String,
+    /// Objects as last loaded or saved, used for change tracking
+    #[new(default)]
+    baseline: OnceLock<PBXObjectCollection>,
+    /// Loaded file content, used for change tracking and incremental reloads
+    #[new(default)]
+    source: Option<PBXSource>,
+    /// Objects dropped on load as their reference was already taken, see
+    /// [`PBXRootObject::repair_duplicate_references`]
+    #[new(default)]
+    duplicates: Vec<(String, PBXHashMap)>,
+    /// Name of the `.xcodeproj` bundle, see [`PBXRootObject::project_name`]
+    #[new(default)]
+    project_name: Option<String>,
 }
 
 impl PBXRootObject {
@@ -54,8 +105,22 @@ impl PBXRootObject {
         self.root_object_reference.as_ref()
     }
 
+    /// Get name of the `.xcodeproj` bundle holding the project, e.g. `App` for
+    /// `App.xcodeproj/project.pbxproj`. Known when read from a bundle or set with
+    /// [`PBXRootObject::set_project_name`].
+    #[must_use]
+    pub fn project_name(&self) -> Option<&str> {
+        self.project_name.as_deref()
+    }
+
+    /// Set name of the `.xcodeproj` bundle holding the project, see
+    /// [`PBXRootObject::project_name`]
+    pub fn set_project_name<S: Into<String>>(&mut self, name: S) {
+        self.project_name = Some(name.into());
+    }
+
     /// Get Root PBXProject
-    pub fn root_project(&self) -> PBXProject {
+    pub fn root_project(&self) -> PBXProject<'_> {
         self.objects
             .projects()
             .into_iter()
@@ -64,7 +129,7 @@ impl PBXRootObject {
     }
 
     /// Get root group
-    pub fn root_group(&self) -> PBXFSReference {
+    pub fn root_group(&self) -> PBXFSReference<'_> {
         self.root_project().main_group
     }
 
@@ -104,20 +169,15 @@ impl std::fmt::Debug for PBXRootObject {
     }
 }
 
-impl TryFrom<PBXHashMap> for PBXRootObject {
-    type Error = anyhow::Error;
-    fn try_from(mut map: PBXHashMap) -> Result<Self> {
+impl PBXRootObject {
+    /// Create root object from parsed `map`, leaving change tracking baseline unset.
+    fn from_map(mut map: PBXHashMap) -> Result<Self> {
         let archive_version = map.try_remove_number("archiveVersion")? as u8;
         let object_version = map.try_remove_number("objectVersion")? as u8;
         let classes = map.try_remove_object("classes").unwrap_or_default();
         let root_object_reference = map.try_remove_string("rootObject")?;
-        let objects = PBXObjectCollection(
-            map.try_remove_object("objects")?
-                .0
-                .into_iter()
-                .map(|(k, v)| (k, v.try_into_object().unwrap()))
-                .collect(),
-        );
+        let mut objects = PBXObjectCollection::from_map(map.try_remove_object("objects")?)?;
+        objects.set_decode_mode(PBXDecodeMode::for_object_version(object_version));
 
         Ok(Self {
             archive_version,
@@ -125,16 +185,50 @@ impl TryFrom<PBXHashMap> for PBXRootObject {
             classes,
             objects,
             root_object_reference,
+            baseline: Default::default(),
+            source: None,
+            duplicates: vec![],
+            project_name: None,
         })
     }
 }
 
+impl PBXObjectCollection {
+    /// Create collection from the parsed objects section
+    pub(crate) fn from_map(objects: PBXHashMap) -> Result<Self> {
+        let mut collection = HashMap::with_capacity(objects.len());
+        for (reference, object) in objects.0 {
+            collection.insert(reference.into(), object.try_into_object()?);
+        }
+        Ok(Self::new(collection))
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXRootObject {
+    type Error = anyhow::Error;
+    fn try_from(map: PBXHashMap) -> Result<Self> {
+        let root_object = Self::from_map(map)?;
+        let _ = root_object.baseline.set(root_object.objects.clone());
+        Ok(root_object)
+    }
+}
+
 impl TryFrom<&str> for PBXRootObject {
     type Error = anyhow::Error;
     fn try_from(content: &str) -> Result<Self> {
-        use crate::pbxproj::pest::PBXProjectParser;
+        use crate::pbxproj::pest::Scanner;
 
-        PBXProjectParser::try_from_str(content)?.pipe(Self::try_from)
+        // Written back as OpenStep content, as Xcode does
+        if xml::is_xml_plist(content) {
+            return xml::parse_xml_plist(content)?.pipe(Self::try_from);
+        }
+
+        // Change tracking baseline and reload index are derived from content on first use
+        let mut scanner = Scanner::new(content);
+        let mut root_object = scanner.parse_file()?.pipe(Self::from_map)?;
+        root_object.source = Some(PBXSource::new(content));
+        root_object.set_duplicates(scanner.take_duplicates());
+        Ok(root_object)
     }
 }
 
@@ -149,9 +243,22 @@ impl TryFrom<&Path> for PBXRootObject {
     type Error = anyhow::Error;
 
     fn try_from(value: &Path) -> Result<Self> {
-        std::fs::read_to_string(&value)
+        std::fs::read(value)
             .map_err(|e| anyhow::anyhow!("PBXProjectData from path {value:?}: {e}"))?
-            .pipe(TryFrom::try_from)
+            .pipe(|bytes| Self::from_file_bytes(value, &bytes))
+    }
+}
+
+impl PBXRootObject {
+    /// Create project from `bytes` already read from project.pbxproj file at `path`
+    pub(crate) fn from_file_bytes(path: &Path, bytes: &[u8]) -> Result<Self> {
+        let mut root_object = Self::from_bytes(bytes)?;
+        root_object.project_name = path
+            .parent()
+            .filter(|bundle| bundle.extension().is_some_and(|e| e == "xcodeproj"))
+            .and_then(|bundle| bundle.file_stem()?.to_str())
+            .map(String::from);
+        Ok(root_object)
     }
 }
 
@@ -262,8 +369,8 @@ macro_rules! test_demo_file {
         let (root, name) = (env!("CARGO_MANIFEST_DIR"), stringify!($name));
         let path = format!("{root}/tests/samples/{name}.pbxproj");
         let file = crate::pbxproj::PBXRootObject::try_from(std::path::PathBuf::from(path));
-        if file.is_err() {
-            println!("Error: {:#?}", file.as_ref().unwrap_err())
+        if let Err(err) = &file {
+            println!("Error: {err:#?}")
         }
         assert!(file.is_ok());
         file.unwrap()
@@ -284,5 +391,37 @@ mod tests {
         };
     }
 
-    test_samples![demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11];
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, legacy1
+    ];
+
+    #[test]
+    fn legacy_quirks() {
+        use crate::pbxproj::*;
+
+        let root_object = test_demo_file!(legacy1);
+        let file = root_object.find_file_by_path("Foo-Bar+Baz.m").unwrap().id;
+        assert_eq!(2, root_object.referrers(&file).len());
+        let target = root_object.get_target_by_name("Legacy").unwrap().id;
+        let settings = root_object
+            .resolved_build_settings(&target, "Debug")
+            .unwrap();
+        assert_eq!(
+            Some(&"-ObjC".to_string()),
+            settings.get_string("OTHER_LDFLAGS")
+        );
+
+        // Byte order mark, vertical tab and form feed whitespace, and empty values
+        let content = include_str!("../../tests/samples/legacy1.pbxproj")
+            .replacen("// !$*UTF8*$!", "\u{FEFF}// !$*UTF8*$!", 1)
+            .replace("\t\t\t\tPREBINDING = NO;", "\x0B\x0C\t\t\t\tPREBINDING = ;");
+        let root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+        let settings = root_object
+            .objects()
+            .map
+            .get("C01FCF4F08A954540054247B")
+            .and_then(|configuration| configuration.get_object("buildSettings"))
+            .unwrap();
+        assert_eq!(Some(&String::new()), settings.get_string("PREBINDING"));
+    }
 }
@@ -0,0 +1,296 @@
+use crate::pbxproj::*;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Passes run by [`PBXRootObject::normalize`], all enabled by default
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PBXNormalizeOptions {
+    /// Remove file references to files missing on disk, see
+    /// [`PBXRootObject::prune_missing_file_references`]
+    pub prune_missing_file_references: bool,
+    /// Remove build files repeating a file of the same build phase, see
+    /// [`PBXRootObject::dedupe_build_files`]
+    pub dedupe_build_files: bool,
+    /// Remove objects unreachable from the root object, see [`PBXRootObject::prune_orphans`]
+    pub prune_orphans: bool,
+    /// Sort group children, see [`PBXRootObject::sort_group_children`]
+    pub sort_group_children: bool,
+}
+
+impl Default for PBXNormalizeOptions {
+    fn default() -> Self {
+        Self {
+            prune_missing_file_references: true,
+            dedupe_build_files: true,
+            prune_orphans: true,
+            sort_group_children: true,
+        }
+    }
+}
+
+/// Objects changed by [`PBXRootObject::normalize`], each sorted
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PBXNormalizeReport {
+    /// Removed file references to missing files
+    pub missing_file_references: Vec<String>,
+    /// Removed duplicate build files
+    pub duplicate_build_files: Vec<String>,
+    /// Removed unreachable objects
+    pub orphans: Vec<String>,
+    /// Groups whose children were reordered
+    pub sorted_groups: Vec<String>,
+}
+
+impl PBXObjectCollection {
+    /// Remove file reference with the given reference, along with build files and group
+    /// children entries pointing at it. Returns the removed object if any.
+    pub fn remove_file_reference(&mut self, file: &str) -> Option<PBXHashMap> {
+        let build_files = self
+            .build_files()
+            .into_iter()
+            .filter(|build_file| build_file.file.as_ref().map(|f| f.id.as_str()) == Some(file))
+            .map(|build_file| build_file.id)
+            .collect::<Vec<_>>();
+        for build_file in build_files {
+            self.remove_build_file(&build_file);
+        }
+        self.edit_objects(|_, object| {
+            if let Some(children) = object.get_vec_mut("children") {
+                children
+                    .0
+                    .retain(|child| child.as_string().map(|c| c.as_str()) != Some(file));
+            }
+        });
+        self.invalidate_path_index();
+        self.remove_object(file)
+    }
+}
+
+impl PBXRootObject {
+    /// Run the passes enabled in `options`, e.g. from a pre-commit hook, so a project always
+    /// lands in the same shape whoever edited it. `source_root` is the directory holding the
+    /// xcodeproj bundle, used to find missing files; the pass is skipped without it.
+    pub fn normalize(
+        &mut self,
+        options: &PBXNormalizeOptions,
+        source_root: Option<&Path>,
+    ) -> PBXNormalizeReport {
+        let mut report = PBXNormalizeReport::default();
+        if let (true, Some(source_root)) = (options.prune_missing_file_references, source_root) {
+            report.missing_file_references = self.prune_missing_file_references(source_root);
+        }
+        if options.dedupe_build_files {
+            report.duplicate_build_files = self.dedupe_build_files();
+        }
+        if options.prune_orphans {
+            report.orphans = self.prune_orphans();
+        }
+        if options.sort_group_children {
+            report.sorted_groups = self.sort_group_children();
+        }
+        report
+    }
+
+    /// Remove file references whose file doesn't exist under `source_root`, the directory
+    /// holding the xcodeproj bundle, along with their build files. References relative to build
+    /// products or SDKs are kept. Returns removed references, sorted.
+    pub fn prune_missing_file_references(&mut self, source_root: &Path) -> Vec<String> {
+        let mut missing = self
+            .files()
+            .into_iter()
+            .filter(|file| file.is_file())
+            .filter(|file| {
+                self.get_full_path(&file.id, source_root)
+                    .map(|path| !path.exists())
+                    .unwrap_or_default()
+            })
+            .map(|file| file.id)
+            .collect::<Vec<_>>();
+        missing.sort();
+        for file in missing.iter() {
+            self.objects.remove_file_reference(file);
+        }
+        missing
+    }
+
+    /// Remove build files referring to a file or package product already built by an earlier
+    /// build file of the same phase, and repeated entries of a phase. Returns removed build
+    /// files, sorted.
+    pub fn dedupe_build_files(&mut self) -> Vec<String> {
+        let mut duplicates = vec![];
+        self.objects.edit_objects(|_, phase| {
+            if phase.get_kind("isa").map(|k| k.is_build_phase()) != Some(true) {
+                return;
+            }
+            if let Some(files) = phase.get_vec_mut("files") {
+                let mut seen = HashSet::new();
+                files.0.retain(|file| {
+                    let file = file.as_string().cloned().unwrap_or_default();
+                    seen.insert(file)
+                });
+            }
+        });
+
+        for phase in self.build_phases() {
+            let mut seen = HashSet::new();
+            for build_file in phase.files.iter() {
+                let file = build_file.file.as_ref().map(|f| f.id.as_str());
+                let product = build_file.product.as_ref().map(|p| p.id.as_str());
+                if (file.is_some() || product.is_some()) && !seen.insert((file, product)) {
+                    duplicates.push(build_file.id.clone());
+                }
+            }
+        }
+        duplicates.sort();
+        for build_file in duplicates.iter() {
+            self.objects.remove_build_file(build_file);
+        }
+        duplicates
+    }
+
+    /// Remove objects not reachable from the root object, e.g. leftovers of bad merges or of
+    /// tools removing objects without their dependents. Returns removed references, sorted.
+    pub fn prune_orphans(&mut self) -> Vec<String> {
+        let mut reachable = HashSet::new();
+        let mut pending = vec![self.root_object_reference.clone()];
+        while let Some(reference) = pending.pop() {
+            if self.objects.map.contains_key(&reference) && reachable.insert(reference.clone()) {
+                pending.extend(
+                    self.objects
+                        .get_references(&reference)
+                        .into_iter()
+                        .map(String::from),
+                );
+            }
+        }
+
+        let mut orphans = self
+            .objects
+            .map
+            .keys()
+            .filter(|reference| !reachable.contains(reference.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        orphans.sort();
+        for orphan in orphans.iter() {
+            self.objects.remove_object(orphan);
+        }
+        if !orphans.is_empty() {
+            self.objects.invalidate_path_index();
+        }
+        orphans
+    }
+
+    /// Sort children of every group but the main group, whose order usually reflects the
+    /// project layout: groups first, then files, each by name ignoring case. Returns groups
+    /// whose children were reordered, sorted.
+    pub fn sort_group_children(&mut self) -> Vec<String> {
+        let main_group = self.root_group().id;
+        let mut orders = vec![];
+        for group in self.groups() {
+            if group.id == main_group {
+                continue;
+            }
+            let mut children = group
+                .children
+                .iter()
+                .map(|child| {
+                    let name = child
+                        .name
+                        .or(child.path)
+                        .map(|name| name.to_lowercase())
+                        .unwrap_or_default();
+                    (!child.kind.is_file_group(), name, child.id.clone())
+                })
+                .collect::<Vec<_>>();
+            children.sort();
+            let children = children
+                .into_iter()
+                .map(|(_, _, child)| child)
+                .collect::<Vec<_>>();
+            let current = group
+                .children
+                .iter()
+                .map(|child| child.id.clone())
+                .collect::<Vec<_>>();
+            // Children that aren't fs references are kept at the end
+            if current != children && current.len() == children.len() {
+                orders.push((group.id, children));
+            }
+        }
+
+        let mut sorted = vec![];
+        for (group, children) in orders {
+            if let Some(mut object) = self.objects.get_object_mut(&group) {
+                object.insert_value("children", children);
+                sorted.push(group);
+            }
+        }
+        sorted.sort();
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn normalize() {
+        let mut root_object = test_demo_file!(demo1);
+        let source_root =
+            std::env::temp_dir().join(format!("xcodeproj-normalize-{}", std::process::id()));
+        for file in root_object.files() {
+            if let Some(path) = root_object.get_full_path(&file.id, &source_root) {
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, "").unwrap();
+            }
+        }
+
+        let group = root_object.get_or_add_group("Kit").unwrap();
+        let objects = root_object.objects_mut();
+        let ghost = objects.add_file_reference(&group, "Ghost.swift").unwrap();
+        let view = objects.add_file_reference(&group, "View.swift").unwrap();
+        objects.add_group(&group, "Sources").unwrap();
+        let view_path = root_object.get_full_path(&view, &source_root).unwrap();
+        std::fs::create_dir_all(view_path.parent().unwrap()).unwrap();
+        std::fs::write(view_path, "").unwrap();
+        let (phase, file) = root_object
+            .build_phases()
+            .into_iter()
+            .find_map(|phase| {
+                let file = phase.files.iter().find_map(|f| f.file.as_ref())?.id.clone();
+                Some((phase.id, file))
+            })
+            .unwrap();
+        let objects = root_object.objects_mut();
+        objects.add_build_file(&phase, &ghost).unwrap();
+        let duplicate = objects.add_build_file(&phase, &file).unwrap();
+        let mut rule = PBXHashMap::default();
+        rule.insert_value("isa", PBXObjectKind::PBXBuildRule);
+        let orphan = String::from(objects.insert_new(rule).unwrap());
+
+        let report = root_object.normalize(&Default::default(), Some(&source_root));
+        std::fs::remove_dir_all(&source_root).unwrap();
+
+        assert_eq!(vec![ghost.clone()], report.missing_file_references);
+        assert_eq!(vec![duplicate], report.duplicate_build_files);
+        assert_eq!(vec![orphan], report.orphans);
+        assert!(report.sorted_groups.contains(&group));
+        assert!(root_object.get_file(&ghost).is_none());
+        let kit = root_object.get_group(&group).unwrap();
+        assert_eq!(
+            vec![Some("Sources"), Some("View.swift")],
+            kit.children
+                .iter()
+                .map(|child| child.path.map(|p| p.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(view, kit.children[1].id);
+
+        assert_eq!(
+            PBXNormalizeReport::default(),
+            root_object.normalize(&Default::default(), None)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use super::resolve::split_setting;
+use crate::pbxproj::*;
+use std::str::FromStr;
+
+/// Dotted version of a build setting, e.g. `IPHONEOS_DEPLOYMENT_TARGET = 15.0` or
+/// `MARKETING_VERSION = 1.2.3`. Missing components are zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, derive_new::new)]
+pub struct PBXSettingVersion {
+    /// Major version
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+    /// Patch version
+    pub patch: u32,
+}
+
+impl FromStr for PBXSettingVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut components = [0; 3];
+        let parts = value.trim().split('.').collect::<Vec<_>>();
+        if parts.len() > components.len() {
+            anyhow::bail!("{value:?} has more than {} components", components.len());
+        }
+        for (component, part) in components.iter_mut().zip(parts) {
+            *component = part
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{value:?} is not a version"))?;
+        }
+        let [major, minor, patch] = components;
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+impl std::fmt::Display for PBXSettingVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if self.patch != 0 {
+            write!(f, ".{}", self.patch)?;
+        }
+        Ok(())
+    }
+}
+
+impl PBXValue {
+    /// Coerce build setting value to a boolean: `YES`/`NO` in any case, `1`/`0`
+    pub fn as_setting_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            Self::Number(1) => Some(true),
+            Self::Number(0) => Some(false),
+            Self::String(value) => match value.to_ascii_uppercase().as_str() {
+                "YES" | "1" => Some(true),
+                "NO" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Coerce build setting value to a list, splitting strings on whitespace except in double
+    /// quotes, e.g. `"$(inherited) @executable_path/Frameworks"`
+    pub fn as_setting_list(&self) -> Option<Vec<String>> {
+        match self {
+            Self::Vec(values) => Some(
+                values
+                    .iter()
+                    .filter_map(|value| value.as_setting_string())
+                    .collect(),
+            ),
+            Self::String(value) => Some(split_setting(value)),
+            value => value.as_setting_string().map(|value| vec![value]),
+        }
+    }
+
+    /// Coerce build setting value to a version, e.g. `15.0`
+    pub fn as_setting_version(&self) -> Option<PBXSettingVersion> {
+        match self {
+            Self::Number(major) => Some(PBXSettingVersion::new((*major).try_into().ok()?, 0, 0)),
+            Self::String(value) => value.parse().ok(),
+            Self::Float(value) => value.as_str().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Scalar build setting value as written in the project file
+    fn as_setting_string(&self) -> Option<String> {
+        match self {
+            Self::String(value) => Some(value.clone()),
+            Self::Number(value) => Some(value.to_string()),
+            Self::Float(value) => Some(value.to_string()),
+            Self::Bool(value) => Some(if *value { "YES" } else { "NO" }.into()),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> XCBuildConfiguration<'a> {
+    /// Get setting `key` as a boolean, see [`PBXValue::as_setting_bool`]
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.build_settings.get_value(key)?.as_setting_bool()
+    }
+
+    /// Get setting `key` as a list, see [`PBXValue::as_setting_list`]
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        self.build_settings.get_value(key)?.as_setting_list()
+    }
+
+    /// Get setting `key` as a version, see [`PBXValue::as_setting_version`]
+    pub fn get_version(&self, key: &str) -> Option<PBXSettingVersion> {
+        self.build_settings.get_value(key)?.as_setting_version()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coerce_settings() {
+        let mut settings = PBXHashMap::default();
+        settings.insert_value("ENABLE_BITCODE", false);
+        settings.insert_value("SKIP_INSTALL", "yes");
+        settings.insert_value("ONLY_ACTIVE_ARCH", 1);
+        settings.insert_value("PRODUCT_NAME", "$(TARGET_NAME)");
+        settings.insert_value(
+            "LD_RUNPATH_SEARCH_PATHS",
+            "$(inherited) \"@executable_path/My Frameworks\"",
+        );
+        settings.insert_value("OTHER_LDFLAGS", vec!["-ObjC", "-lz"]);
+        settings.insert_value("IPHONEOS_DEPLOYMENT_TARGET", "15.0");
+        settings.insert_value("MARKETING_VERSION", "1.2.3");
+        settings.insert_value("SWIFT_VERSION", 5);
+        let name = "Debug".to_string();
+        let configuration = XCBuildConfiguration::new("A".into(), &name, &settings, None);
+        assert_eq!(None, configuration.get_bool("MISSING_SETTING"));
+
+        assert_eq!(Some(false), configuration.get_bool("ENABLE_BITCODE"));
+        assert_eq!(Some(true), configuration.get_bool("SKIP_INSTALL"));
+        assert_eq!(Some(true), configuration.get_bool("ONLY_ACTIVE_ARCH"));
+        assert_eq!(None, configuration.get_bool("PRODUCT_NAME"));
+
+        assert_eq!(
+            Some(vec![
+                "$(inherited)".to_string(),
+                "@executable_path/My Frameworks".to_string()
+            ]),
+            configuration.get_list("LD_RUNPATH_SEARCH_PATHS")
+        );
+        assert_eq!(
+            Some(vec!["-ObjC".to_string(), "-lz".to_string()]),
+            configuration.get_list("OTHER_LDFLAGS")
+        );
+        assert_eq!(
+            Some(vec!["NO".to_string()]),
+            configuration.get_list("ENABLE_BITCODE")
+        );
+
+        let version = configuration
+            .get_version("IPHONEOS_DEPLOYMENT_TARGET")
+            .unwrap();
+        assert_eq!(PBXSettingVersion::new(15, 0, 0), version);
+        assert_eq!("15.0", version.to_string());
+        assert_eq!(
+            Some(PBXSettingVersion::new(1, 2, 3)),
+            configuration.get_version("MARKETING_VERSION")
+        );
+        assert!(version > PBXSettingVersion::new(14, 5, 0));
+        assert_eq!(
+            Some(PBXSettingVersion::new(5, 0, 0)),
+            configuration.get_version("SWIFT_VERSION")
+        );
+        assert_eq!(None, configuration.get_version("PRODUCT_NAME"));
+    }
+}
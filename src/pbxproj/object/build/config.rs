@@ -1,4 +1,6 @@
 use crate::pbxproj::*;
+use anyhow::Result;
+use smol_str::SmolStr;
 
 /// [`PBXObject`] specifying build configurations
 ///
@@ -15,6 +17,135 @@ pub struct XCBuildConfiguration<'a> {
     pub base_configuration: Option<PBXFSReference<'a>>,
 }
 
+impl<'a> XCBuildConfiguration<'a> {
+    /// Start building a new build configuration with the given name.
+    ///
+    /// ```
+    /// use xcodeproj::pbxproj::{PBXObjectCollection, XCBuildConfiguration};
+    ///
+    /// let mut objects = PBXObjectCollection::default();
+    /// let list = objects.insert_configuration_list(vec![], "Debug");
+    /// let debug = XCBuildConfiguration::builder("Debug")
+    ///     .setting("SWIFT_VERSION", "5.10")
+    ///     .setting("ENABLE_TESTABILITY", true)
+    ///     .add_to(&mut objects, list.as_str())
+    ///     .unwrap();
+    /// ```
+    pub fn builder<S: Into<String>>(name: S) -> XCBuildConfigurationBuilder {
+        XCBuildConfigurationBuilder::new(name)
+    }
+}
+
+/// Builder of [`XCBuildConfiguration`] objects, see [`XCBuildConfiguration::builder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCBuildConfigurationBuilder {
+    name: String,
+    build_settings: PBXHashMap,
+    base_configuration: Option<String>,
+}
+
+impl XCBuildConfigurationBuilder {
+    /// Build configuration with the given name and no settings
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            build_settings: PBXHashMap::default(),
+            base_configuration: None,
+        }
+    }
+
+    /// Set build setting `key` to `value`
+    pub fn setting<K: Into<SmolStr>, V: Into<PBXValue>>(mut self, key: K, value: V) -> Self {
+        self.build_settings.insert_value(key, value);
+        self
+    }
+
+    /// Set every setting of `build_settings`, overriding settings already set
+    pub fn settings(mut self, build_settings: PBXHashMap) -> Self {
+        self.build_settings.0.extend(build_settings.0);
+        self
+    }
+
+    /// Base the configuration on the xcconfig file reference with the given reference,
+    /// `baseConfigurationReference`
+    pub fn base_configuration<S: Into<String>>(mut self, file: S) -> Self {
+        self.base_configuration = Some(file.into());
+        self
+    }
+
+    /// Build the `XCBuildConfiguration` object, to be inserted in a collection
+    pub fn build(self) -> PBXHashMap {
+        let mut configuration = PBXHashMap::default();
+        configuration.insert_value("isa", PBXObjectKind::XCBuildConfiguration);
+        if let Some(file) = self.base_configuration {
+            configuration.insert_value("baseConfigurationReference", file);
+        }
+        configuration.insert_value("buildSettings", self.build_settings);
+        configuration.insert_value("name", self.name);
+        configuration
+    }
+
+    /// Insert the configuration in `objects`, without adding it to any configuration list.
+    /// Returns the reference of the created configuration.
+    pub fn insert(self, objects: &mut PBXObjectCollection) -> PBXObjectReference {
+        objects.insert_object(self.build(), None)
+    }
+
+    /// Insert the configuration in `objects` and append it to the configuration list with the
+    /// given reference, or of the target or project with the given reference. Fails if the list
+    /// already has a configuration with the same name. Returns the reference of the created
+    /// configuration.
+    pub fn add_to(self, objects: &mut PBXObjectCollection, list: &str) -> Result<String> {
+        let list = match objects
+            .map
+            .get(list)
+            .and_then(|object| object.get_string("buildConfigurationList"))
+        {
+            Some(list) => list.clone(),
+            None => list.to_string(),
+        };
+        let configurations = objects
+            .map
+            .get(&list)
+            .filter(|object| object.get_kind("isa") == Some(&PBXObjectKind::XCConfigurationList))
+            .ok_or_else(|| anyhow::anyhow!("{list:?} is not a configuration list"))?
+            .get_vec("buildConfigurations")
+            .map(|configurations| configurations.as_vec_strings())
+            .unwrap_or_default();
+        let exists = configurations.into_iter().any(|configuration| {
+            objects
+                .map
+                .get(configuration.as_str())
+                .and_then(|configuration| configuration.get_string("name"))
+                == Some(&self.name)
+        });
+        if exists {
+            anyhow::bail!("Configuration {:?} already exists in {list:?}", self.name);
+        }
+
+        let reference = String::from(objects.insert_object(self.build(), Some(&list)));
+        objects
+            .get_object_mut(&list)
+            .unwrap()
+            .vec_entry("buildConfigurations")
+            .push(reference.clone());
+        Ok(reference)
+    }
+}
+
+impl PBXObjectCollection {
+    /// Insert a new [`XCBuildConfiguration`] with the given name and build settings.
+    pub fn insert_build_configuration(
+        &mut self,
+        name: &str,
+        build_settings: PBXHashMap,
+    ) -> PBXObjectReference {
+        XCBuildConfiguration::builder(name)
+            .settings(build_settings)
+            .insert(self)
+    }
+}
+
 impl<'a> AsPBXObject<'a> for XCBuildConfiguration<'a> {
     fn as_pbx_object(
         id: String,
@@ -26,8 +157,8 @@ impl<'a> AsPBXObject<'a> for XCBuildConfiguration<'a> {
     {
         Ok(Self {
             id,
-            name: value.try_get_string("name")?,
-            build_settings: value.try_get_object("buildSettings")?,
+            name: objects.required_string(value, "name")?,
+            build_settings: objects.required_object(value, "buildSettings")?,
             base_configuration: value
                 .get_value("baseConfigurationReference")
                 .and_then(|v| v.as_string())
@@ -35,3 +166,66 @@ impl<'a> AsPBXObject<'a> for XCBuildConfiguration<'a> {
         })
     }
 }
+
+impl ToPBXHashMap for XCBuildConfiguration<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::XCBuildConfiguration);
+        value.insert_value("name", self.name.as_str());
+        value.insert_value("buildSettings", self.build_settings.clone());
+        value.insert_some(
+            "baseConfigurationReference",
+            self.base_configuration.as_ref().map(|f| f.id.as_str()),
+        );
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_configuration_builder() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let xcconfig = root_object.files()[0].id.clone();
+
+        let staging = XCBuildConfiguration::builder("Staging")
+            .settings(PBXHashMap::new(
+                [("SWIFT_VERSION".into(), "5.0".into())].into(),
+            ))
+            .setting("SWIFT_VERSION", "5.10")
+            .setting("ENABLE_TESTABILITY", true)
+            .base_configuration(&xcconfig)
+            .add_to(root_object.objects_mut(), &target)
+            .unwrap();
+        assert!(XCBuildConfiguration::builder("Staging")
+            .add_to(root_object.objects_mut(), &target)
+            .is_err());
+        assert!(XCBuildConfiguration::builder("Staging")
+            .add_to(root_object.objects_mut(), &staging)
+            .is_err());
+
+        let list = root_object
+            .get_target(&target)
+            .unwrap()
+            .build_configuration_list
+            .unwrap();
+        let staging = list
+            .build_configurations
+            .iter()
+            .find(|configuration| configuration.id == staging)
+            .unwrap();
+        assert_eq!("Staging", staging.name);
+        assert_eq!(
+            Some(&"5.10".to_string()),
+            staging.build_settings.get_string("SWIFT_VERSION")
+        );
+        assert_eq!(Some(true), staging.get_bool("ENABLE_TESTABILITY"));
+        assert_eq!(
+            Some(xcconfig.as_str()),
+            staging.base_configuration.as_ref().map(|f| f.id.as_str())
+        );
+    }
+}
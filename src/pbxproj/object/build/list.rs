@@ -1,4 +1,6 @@
+use crate::macros::log;
 use crate::pbxproj::*;
+use anyhow::Result;
 
 /// [`PBXObject`] aggregating a list of [`XCBuildConfiguration`] references
 ///
@@ -29,13 +31,36 @@ impl<'a> AsPBXObject<'a> for XCConfigurationList<'a> {
                 .get_vec("buildConfigurations")
                 .map(|v| objects.get_vec(v.as_vec_strings()))
                 .unwrap_or_default(),
-            default_configuration_is_visible: value
-                .try_get_number("defaultConfigurationIsVisible")?
+            default_configuration_is_visible: objects
+                .required_number(value, "defaultConfigurationIsVisible")?
                 == &1,
             default_configuration_name: value.get_string("defaultConfigurationName"),
         })
     }
 }
+
+impl ToPBXHashMap for XCConfigurationList<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::XCConfigurationList);
+        value.insert_value(
+            "buildConfigurations",
+            self.build_configurations
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value.insert_value(
+            "defaultConfigurationIsVisible",
+            isize::from(self.default_configuration_is_visible),
+        );
+        value.insert_some(
+            "defaultConfigurationName",
+            self.default_configuration_name.map(String::as_str),
+        );
+        value
+    }
+}
 impl<'a> XCConfigurationList<'a> {
     /// Extract SDKROOT from build configurations
     pub fn extract_sdkroot_from_children(&self, objects: &PBXObjectCollection) -> Option<String> {
@@ -48,7 +73,8 @@ impl<'a> XCConfigurationList<'a> {
         sdkroots.dedup();
 
         if sdkroots.is_empty() {
-            tracing::trace!(
+            log!(
+                trace,
                 "no sdkroot found in build_configuration_list: {:?}",
                 self.id
             );
@@ -65,20 +91,94 @@ impl<'a> XCConfigurationList<'a> {
 
             // Means base configuration no defined
             if sdkroots.is_empty() {
-                tracing::trace!(
+                log!(
+                    trace,
                     "Find SDKROOT: No base configuration in all config_list's configuration",
                 );
                 return None;
             }
         } else if sdkroots.len() > 1 {
-            tracing::trace!("Find SDKROOT: Get more then one sdkroot  {:?}", self.id);
-            tracing::trace!("Find SDKROOT Using {:?} as sdkroot", &sdkroots[0]);
+            log!(
+                trace,
+                "Find SDKROOT: Get more then one sdkroot  {:?}",
+                self.id
+            );
+            log!(trace, "Find SDKROOT Using {:?} as sdkroot", &sdkroots[0]);
         }
 
         Some(sdkroots[0].into())
     }
 }
 
+impl PBXObjectCollection {
+    /// Adds the default configurations, debug and release, using the build settings of the
+    /// given preset. Returns the reference of the created [`XCConfigurationList`].
+    pub fn add_default_configurations(
+        &mut self,
+        preset: XCBuildSettingsPreset,
+        platform: &PBXTargetPlatform,
+    ) -> String {
+        let configurations = ["Debug", "Release"]
+            .into_iter()
+            .map(|name| {
+                XCBuildConfiguration::builder(name)
+                    .settings(preset.build_settings(name, platform))
+                    .insert(self)
+            })
+            .collect::<Vec<_>>();
+        self.insert_configuration_list(configurations, "Release")
+            .into()
+    }
+
+    /// Insert a new [`XCConfigurationList`] of the given build configurations.
+    pub fn insert_configuration_list(
+        &mut self,
+        configurations: Vec<PBXObjectReference>,
+        default_configuration_name: &str,
+    ) -> PBXObjectReference {
+        let mut list = PBXHashMap::default();
+        list.insert_value("isa", PBXObjectKind::XCConfigurationList);
+        list.insert_value("buildConfigurations", configurations);
+        list.insert_value("defaultConfigurationIsVisible", 0);
+        list.insert_value("defaultConfigurationName", default_configuration_name);
+        self.insert_object(list, None)
+    }
+
+    /// Set build setting `key` to `value` in every configuration of the configuration list with
+    /// the given reference, e.g. `SWIFT_VERSION` across Debug and Release. Target and project
+    /// references resolve to their configuration list.
+    pub fn set_setting_all<V>(&mut self, list: &str, key: &str, value: V) -> Result<()>
+    where
+        V: Into<PBXValue> + Clone,
+    {
+        let configurations = self.get_build_configurations_mut(list);
+        if configurations.is_empty() {
+            anyhow::bail!("No build configurations found for {list:?}");
+        }
+        for mut configuration in configurations {
+            configuration
+                .build_settings_mut()
+                .insert_value(key, value.clone());
+        }
+        Ok(())
+    }
+
+    /// Remove build setting `key` from every configuration of the configuration list with the
+    /// given reference, see [`PBXObjectCollection::set_setting_all`].
+    pub fn remove_setting_all(&mut self, list: &str, key: &str) -> Result<()> {
+        let configurations = self.get_build_configurations_mut(list);
+        if configurations.is_empty() {
+            anyhow::bail!("No build configurations found for {list:?}");
+        }
+        for mut configuration in configurations {
+            if let Some(settings) = configuration.get_object_mut("buildSettings") {
+                settings.remove_value(key);
+            }
+        }
+        Ok(())
+    }
+}
+
 // impl XCConfigurationList {
 //     /// Build configurations
 //     pub fn set_build_configuration_references(&mut self, references: Vec<String>) -> Vec<String> {
@@ -137,3 +237,42 @@ impl<'a> XCConfigurationList<'a> {
 //         todo!()
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn set_setting_all() {
+        let mut root_object = test_demo_file!(demo1);
+        let project = root_object.projects()[0].id.clone();
+        let list = root_object.projects()[0]
+            .build_configuration_list
+            .id
+            .clone();
+        let objects = root_object.objects_mut();
+
+        objects
+            .set_setting_all(&list, "SWIFT_VERSION", "5.10")
+            .unwrap();
+        let versions = |objects: &PBXObjectCollection| {
+            objects
+                .get_project(&project)
+                .unwrap()
+                .build_configuration_list
+                .build_configurations
+                .iter()
+                .map(|c| c.build_settings.get_string("SWIFT_VERSION").cloned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![Some("5.10".to_string()); 2], versions(objects));
+
+        objects
+            .remove_setting_all(&project, "SWIFT_VERSION")
+            .unwrap();
+        assert_eq!(vec![None; 2], versions(objects));
+        assert!(objects
+            .set_setting_all("missing", "SWIFT_VERSION", "5.10")
+            .is_err());
+    }
+}
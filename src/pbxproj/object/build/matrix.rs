@@ -0,0 +1,167 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+/// Values of selected build settings across targets and configurations, see
+/// [`PBXRootObject::settings_matrix`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PBXSettingsMatrix {
+    /// Setting keys, in column order
+    pub keys: Vec<String>,
+    /// One row per target and configuration
+    pub rows: Vec<PBXSettingsMatrixRow>,
+}
+
+/// Row of [`PBXSettingsMatrix`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PBXSettingsMatrixRow {
+    /// Target name
+    pub target: String,
+    /// Configuration name
+    pub configuration: String,
+    /// Setting values in [`PBXSettingsMatrix::keys`] order, `None` if unset
+    pub values: Vec<Option<String>>,
+}
+
+impl PBXSettingsMatrix {
+    /// Render as CSV with a `target,configuration,<keys>` header, unset values left empty
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header = ["target", "configuration"]
+            .into_iter()
+            .chain(self.keys.iter().map(|k| k.as_str()));
+        push_csv_line(&mut csv, header);
+        for row in self.rows.iter() {
+            let values = [row.target.as_str(), row.configuration.as_str()]
+                .into_iter()
+                .chain(row.values.iter().map(|v| v.as_deref().unwrap_or_default()));
+            push_csv_line(&mut csv, values);
+        }
+        csv
+    }
+
+    /// Render as a JSON array of `{"target", "configuration", "settings"}` objects, with unset
+    /// values as `null`
+    pub fn to_json(&self) -> Result<String> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let settings = self
+                    .keys
+                    .iter()
+                    .cloned()
+                    .zip(row.values.iter().map(|v| serde_json::json!(v)))
+                    .collect::<serde_json::Map<_, _>>();
+                serde_json::json!({
+                    "target": row.target,
+                    "configuration": row.configuration,
+                    "settings": settings,
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(serde_json::to_string_pretty(&rows)?)
+    }
+}
+
+fn push_csv_line<'a, I: Iterator<Item = &'a str>>(csv: &mut String, fields: I) {
+    let fields = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    csv.push_str(&fields.join(","));
+    csv.push('\n');
+}
+
+/// Render setting value as written in Xcode's build settings editor
+fn setting_string(value: &PBXValue) -> Option<String> {
+    match value {
+        PBXValue::String(value) => Some(value.clone()),
+        PBXValue::Number(value) => Some(value.to_string()),
+        PBXValue::Float(value) => Some(value.to_string()),
+        PBXValue::Bool(value) => Some(if *value { "YES" } else { "NO" }.to_string()),
+        PBXValue::Vec(values) => Some(
+            values
+                .as_vec_strings()
+                .into_iter()
+                .map(|value| value.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+impl PBXRootObject {
+    /// Get values of `keys` for every target and configuration, in project target and
+    /// configuration list order, e.g. to audit deployment targets, bundle identifiers or
+    /// signing at a glance.
+    ///
+    /// Values come from [`PBXRootObject::resolved_build_settings`], so target settings
+    /// override project ones and are left unexpanded.
+    pub fn settings_matrix<S: AsRef<str>>(&self, keys: &[S]) -> Result<PBXSettingsMatrix> {
+        let keys = keys
+            .iter()
+            .map(|k| k.as_ref().to_string())
+            .collect::<Vec<_>>();
+        let mut rows = vec![];
+        for target in self.root_project().targets {
+            let configurations = target
+                .build_configuration_list
+                .map(|list| list.build_configurations)
+                .unwrap_or_default();
+            for configuration in configurations {
+                let settings = self.resolved_build_settings(&target.id, configuration.name)?;
+                rows.push(PBXSettingsMatrixRow {
+                    target: target.name.cloned().unwrap_or_else(|| target.id.clone()),
+                    configuration: configuration.name.to_string(),
+                    values: keys
+                        .iter()
+                        .map(|key| settings.get_value(key).and_then(setting_string))
+                        .collect(),
+                });
+            }
+        }
+        Ok(PBXSettingsMatrix { keys, rows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn settings_matrix() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        root_object
+            .set_setting_all(&target, "PRODUCT_BUNDLE_IDENTIFIER", "com.example.a,b")
+            .unwrap();
+        let matrix = root_object
+            .settings_matrix(&["PRODUCT_BUNDLE_IDENTIFIER", "MISSING_SETTING"])
+            .unwrap();
+        assert_eq!(2, matrix.rows.len());
+        for row in matrix.rows.iter() {
+            assert_eq!(vec![Some("com.example.a,b".to_string()), None], row.values);
+        }
+
+        let csv = matrix.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            Some("target,configuration,PRODUCT_BUNDLE_IDENTIFIER,MISSING_SETTING"),
+            lines.next()
+        );
+        assert!(lines.all(|line| line.ends_with(",\"com.example.a,b\",")));
+
+        let json = serde_json::from_str::<serde_json::Value>(&matrix.to_json().unwrap()).unwrap();
+        assert_eq!(
+            serde_json::json!("com.example.a,b"),
+            json[0]["settings"]["PRODUCT_BUNDLE_IDENTIFIER"]
+        );
+        assert!(json[1]["settings"]["MISSING_SETTING"].is_null());
+    }
+}
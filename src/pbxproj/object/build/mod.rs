@@ -1,7 +1,16 @@
+mod coerce;
 mod config;
 mod list;
+mod matrix;
 mod phase;
+mod preset;
+mod resolve;
 
+pub use coerce::*;
 pub use config::*;
 pub use list::*;
+pub use matrix::*;
 pub use phase::*;
+pub use preset::*;
+pub use resolve::expand_build_setting;
+pub(crate) use resolve::split_setting;
@@ -0,0 +1,81 @@
+use crate::pbxproj::*;
+
+/// Part of [`PBXBuildPhase`] when [`PBXBuildPhaseKind`] is [`AppleScript`], found in legacy
+/// AppleScript Studio projects.
+///
+/// [`AppleScript`]: crate::pbxproj::PBXBuildPhaseKind::AppleScript
+#[derive(Debug, derive_new::new)]
+pub struct PBXAppleScriptBuildPhase<'a> {
+    /// Name of the AppleScript context scripts are compiled in.
+    pub context_name: Option<&'a String>,
+    /// Whether scripts share a single context.
+    pub is_shared_context: bool,
+}
+
+impl<'a> AsPBXObject<'a> for PBXAppleScriptBuildPhase<'a> {
+    fn as_pbx_object(
+        _id: String,
+        value: &'a PBXHashMap,
+        _objects: &'a PBXObjectCollection,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized + 'a,
+    {
+        Ok(Self {
+            context_name: value.get_string("contextName"),
+            is_shared_context: value
+                .get_number("isSharedContext")
+                .map(|v| v == &1)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Lowers AppleScript fields only, see [`PBXBuildPhase`] for the whole build phase
+impl ToPBXHashMap for PBXAppleScriptBuildPhase<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_some("contextName", self.context_name.map(String::as_str));
+        value.insert_value("isSharedContext", isize::from(self.is_shared_context));
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::pest::PBXProjectParser;
+    use crate::pbxproj::*;
+
+    #[test]
+    fn legacy_build_phases() {
+        let mut root_object = test_demo_file!(demo1);
+        for (isa, kind) in [
+            ("PBXAppleScriptBuildPhase", PBXBuildPhaseKind::AppleScript),
+            ("PBXJavaArchiveBuildPhase", PBXBuildPhaseKind::JavaArchive),
+            ("PBXRezBuildPhase", PBXBuildPhaseKind::CarbonResources),
+        ] {
+            let content = format!(
+                "// !$*UTF8*$!\n{{ isa = {isa}; buildActionMask = 2147483647; \
+                 contextName = \"Main\"; files = ( ); isSharedContext = 1; \
+                 runOnlyForDeploymentPostprocessing = 0; }}"
+            );
+            let object = PBXProjectParser::try_from_str(content).unwrap();
+            let reference = root_object.objects_mut().insert_new(object).unwrap();
+            let phase = root_object.get_build_phase(&reference).unwrap();
+            assert_eq!(kind, phase.kind);
+            assert_eq!(isa, phase.kind.as_isa());
+            assert_eq!(kind.is_apple_script(), phase.apple_script.is_some());
+        }
+
+        let content = "// !$*UTF8*$!\n{ isa = PBXAppleScriptBuildPhase; contextName = \"Main\"; \
+                       files = ( ); isSharedContext = 1; }";
+        let reference = root_object
+            .objects_mut()
+            .insert_new(PBXProjectParser::try_from_str(content).unwrap())
+            .unwrap();
+        let phase = root_object.get_build_phase(&reference).unwrap();
+        let apple_script = phase.apple_script.unwrap();
+        assert_eq!(Some(&"Main".to_string()), apple_script.context_name);
+        assert!(apple_script.is_shared_context);
+    }
+}
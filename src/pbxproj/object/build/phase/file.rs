@@ -18,6 +18,165 @@ pub struct PBXBuildFile<'a> {
     pub build_phase: Option<PBXBuildPhase<'a>>,
 }
 
+impl PBXObjectCollection {
+    /// Add a new build file referencing `file` to the end of the build phase with the given
+    /// reference. Returns the reference of the created build file.
+    pub fn add_build_file(&mut self, phase: &str, file: &str) -> anyhow::Result<String> {
+        let index = self
+            .map
+            .get(phase)
+            .and_then(|p| p.get_vec("files"))
+            .map(|files| files.len())
+            .unwrap_or_default();
+        self.insert_build_file(phase, file, index)
+    }
+
+    /// Add a new build file referencing `file` at `index` of the files of the build phase with
+    /// the given reference. Files are built, and resources copied, in phase order, so a later
+    /// file overwrites an earlier one with the same output. Returns the reference of the
+    /// created build file.
+    pub fn insert_build_file(
+        &mut self,
+        phase: &str,
+        file: &str,
+        index: usize,
+    ) -> anyhow::Result<String> {
+        let is_build_phase = self
+            .map
+            .get(phase)
+            .and_then(|p| p.get_kind("isa"))
+            .map(|k| k.is_build_phase())
+            .unwrap_or_default();
+        if !is_build_phase {
+            anyhow::bail!("{phase:?} is not a build phase");
+        }
+        let is_playground = self
+            .map
+            .get(file)
+            .and_then(|f| f.get_string("lastKnownFileType"))
+            .map(|file_type| file_type == PLAYGROUND_FILE_TYPE)
+            .unwrap_or_default();
+        if is_playground {
+            anyhow::bail!("{file:?} is a playground, which can't be built");
+        }
+        let len = self
+            .map
+            .get(phase)
+            .and_then(|p| p.get_vec("files"))
+            .map(|files| files.len())
+            .unwrap_or_default();
+        if index > len {
+            anyhow::bail!("Index {index} is out of bounds of {phase:?} files ({len})");
+        }
+
+        let mut build_file = PBXHashMap::default();
+        build_file.insert_value("isa", PBXObjectKind::PBXBuildFile);
+        build_file.insert_value("fileRef", file);
+
+        let reference = String::from(self.insert_object(build_file, Some(phase)));
+
+        self.get_build_phase_mut(phase)
+            .unwrap()
+            .files_mut()
+            .insert(index, reference.clone());
+
+        Ok(reference)
+    }
+
+    /// Move build file to `index` of the files of the build phase with the given reference
+    pub fn move_build_file(
+        &mut self,
+        phase: &str,
+        build_file: &str,
+        index: usize,
+    ) -> anyhow::Result<()> {
+        let mut phase_object = self
+            .get_build_phase_mut(phase)
+            .ok_or_else(|| anyhow::anyhow!("{phase:?} is not a build phase"))?;
+        let files = phase_object.files_mut();
+        let current = files
+            .position(build_file)
+            .ok_or_else(|| anyhow::anyhow!("{build_file:?} is not a file of {phase:?}"))?;
+        if index >= files.len() {
+            anyhow::bail!(
+                "Index {index} is out of bounds of {phase:?} files ({})",
+                files.len()
+            );
+        }
+        let value = files.0.remove(current);
+        files.0.insert(index, value);
+        Ok(())
+    }
+
+    /// Move build file right before `anchor` in the files of the build phase with the given
+    /// reference
+    pub fn move_build_file_before(
+        &mut self,
+        phase: &str,
+        build_file: &str,
+        anchor: &str,
+    ) -> anyhow::Result<()> {
+        self.move_build_file_next_to(phase, build_file, anchor, 0)
+    }
+
+    /// Move build file right after `anchor` in the files of the build phase with the given
+    /// reference
+    pub fn move_build_file_after(
+        &mut self,
+        phase: &str,
+        build_file: &str,
+        anchor: &str,
+    ) -> anyhow::Result<()> {
+        self.move_build_file_next_to(phase, build_file, anchor, 1)
+    }
+
+    fn move_build_file_next_to(
+        &mut self,
+        phase: &str,
+        build_file: &str,
+        anchor: &str,
+        offset: usize,
+    ) -> anyhow::Result<()> {
+        let mut phase_object = self
+            .get_build_phase_mut(phase)
+            .ok_or_else(|| anyhow::anyhow!("{phase:?} is not a build phase"))?;
+        let files = phase_object.files_mut();
+        let current = files
+            .position(build_file)
+            .ok_or_else(|| anyhow::anyhow!("{build_file:?} is not a file of {phase:?}"))?;
+        if files.position(anchor).is_none() {
+            anyhow::bail!("{anchor:?} is not a file of {phase:?}");
+        }
+        if build_file == anchor {
+            return Ok(());
+        }
+        let value = files.0.remove(current);
+        let index = files.position(anchor).unwrap() + offset;
+        files.0.insert(index, value);
+        Ok(())
+    }
+
+    /// Remove build file with the given reference from the collection and from any build phase
+    /// referencing it. Returns the removed object if any.
+    pub fn remove_build_file(&mut self, build_file: &str) -> Option<PBXHashMap> {
+        self.edit_objects(|_, object| {
+            let is_build_phase = object
+                .get_kind("isa")
+                .map(|k| k.is_build_phase())
+                .unwrap_or_default();
+            if is_build_phase {
+                if let Some(files) = object.get_vec_mut("files") {
+                    files
+                        .0
+                        .retain(|f| f.as_string().map(|f| f.as_str()) != Some(build_file));
+                }
+            }
+        });
+
+        self.remove_object(build_file)
+    }
+}
+
 impl<'a> AsPBXObject<'a> for PBXBuildFile<'a> {
     fn as_pbx_object(
         id: String,
@@ -39,3 +198,86 @@ impl<'a> AsPBXObject<'a> for PBXBuildFile<'a> {
         })
     }
 }
+
+impl ToPBXHashMap for PBXBuildFile<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXBuildFile);
+        value.insert_some("settings", self.settings.cloned());
+        value.insert_some("platformFilter", self.platform_filter.map(String::as_str));
+        value.insert_some("fileRef", self.file.as_ref().map(|f| f.id.as_str()));
+        value.insert_some("productRef", self.product.as_ref().map(|p| p.id.as_str()));
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn build_file_order() {
+        let mut root_object = test_demo_file!(demo1);
+        let files = |root_object: &PBXRootObject, phase: &str| {
+            root_object
+                .build_phases()
+                .into_iter()
+                .find(|p| p.id == phase)
+                .unwrap()
+                .files
+                .into_iter()
+                .map(|file| file.id)
+                .collect::<Vec<_>>()
+        };
+        let phase = root_object
+            .build_phases()
+            .into_iter()
+            .max_by_key(|phase| phase.files.len())
+            .unwrap()
+            .id;
+        // Parsed in file order
+        let original = files(&root_object, &phase);
+        let content = include_str!("../../../../../tests/samples/demo1.pbxproj");
+        let offsets = original
+            .iter()
+            .map(|id| content.find(&format!("\t\t\t\t{id},")).unwrap())
+            .collect::<Vec<_>>();
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        let first = root_object
+            .objects_mut()
+            .insert_build_file(&phase, &file, 0)
+            .unwrap();
+        let mut expected = original.clone();
+        expected.insert(0, first.clone());
+        assert_eq!(expected, files(&root_object, &phase));
+
+        let last = expected.last().unwrap().clone();
+        let objects = root_object.objects_mut();
+        objects
+            .move_build_file_after(&phase, &first, &last)
+            .unwrap();
+        objects
+            .move_build_file_before(&phase, &last, &original[0])
+            .unwrap();
+        objects.move_build_file(&phase, &original[0], 0).unwrap();
+        let mut expected = original.clone();
+        let last = expected.pop().unwrap();
+        expected.insert(1, last);
+        expected.push(first.clone());
+        assert_eq!(expected, files(&root_object, &phase));
+
+        let objects = root_object.objects_mut();
+        let len = expected.len();
+        assert!(objects.insert_build_file(&phase, &file, len + 1).is_err());
+        assert!(objects.move_build_file(&phase, &first, len).is_err());
+        assert!(objects
+            .move_build_file_before(&phase, &first, "missing")
+            .is_err());
+        assert!(objects.move_build_file(&file, &first, 0).is_err());
+    }
+}
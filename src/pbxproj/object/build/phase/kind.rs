@@ -18,6 +18,10 @@ pub enum PBXBuildPhaseKind {
     Headers,
     /// Build Legacy Carbon Resources
     CarbonResources,
+    /// Compile AppleScript, found in legacy AppleScript Studio projects
+    AppleScript,
+    /// Build Java archive, found in legacy Java projects
+    JavaArchive,
 }
 
 impl PBXBuildPhaseKind {
@@ -31,13 +35,15 @@ impl PBXBuildPhaseKind {
             Self::Sources => "PBXSourcesBuildPhase",
             Self::CopyFiles => "PBXCopyFilesBuildPhase",
             Self::CarbonResources => "PBXRezBuildPhase",
+            Self::AppleScript => "PBXAppleScriptBuildPhase",
+            Self::JavaArchive => "PBXJavaArchiveBuildPhase",
         }
     }
 }
 
-impl ToString for PBXBuildPhaseKind {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for PBXBuildPhaseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
             Self::Sources => "Sources",
             Self::Frameworks => "Frameworks",
             Self::Resources => "Resources",
@@ -45,8 +51,10 @@ impl ToString for PBXBuildPhaseKind {
             Self::RunScript => "Run Script",
             Self::Headers => "Headers",
             Self::CarbonResources => "Rez",
-        }
-        .into()
+            Self::AppleScript => "AppleScript",
+            Self::JavaArchive => "JavaArchive",
+        };
+        write!(f, "{value}")
     }
 }
 
@@ -62,6 +70,8 @@ impl TryFrom<&str> for PBXBuildPhaseKind {
             "Run Script" => Self::RunScript,
             "Headers" => Self::Headers,
             "Rez" => Self::CarbonResources,
+            "AppleScript" => Self::AppleScript,
+            "JavaArchive" => Self::JavaArchive,
             str => anyhow::bail!("Unable to generate BuildPhase from '{str}'"),
         }
         .pipe(Ok)
@@ -1,12 +1,16 @@
+mod apple_script;
 mod file;
 mod kind;
 mod rule;
 mod script;
+mod template;
 
+pub use apple_script::*;
 pub use file::*;
 pub use kind::*;
 pub use rule::*;
 pub use script::*;
+pub use template::*;
 
 use crate::pbxproj::*;
 
@@ -29,6 +33,8 @@ pub struct PBXBuildPhase<'a> {
     pub kind: PBXBuildPhaseKind,
     /// inner (Some if PBXBuildPhase is PBXShellScriptBuildPhase)
     pub inner: Option<PBXShellScriptBuildPhase<'a>>,
+    /// Some if PBXBuildPhase is PBXAppleScriptBuildPhase
+    pub apple_script: Option<PBXAppleScriptBuildPhase<'a>>,
 }
 
 impl<'a> PBXBuildPhase<'a> {
@@ -67,6 +73,72 @@ impl<'a> PBXBuildPhase<'a> {
     pub fn is_carbon_resources(&self) -> bool {
         self.kind.is_carbon_resources()
     }
+
+    /// Whether build phase is PBXAppleScriptBuildPhase
+    pub fn is_apple_script(&self) -> bool {
+        self.kind.is_apple_script()
+    }
+
+    /// Whether build phase is PBXJavaArchiveBuildPhase
+    pub fn is_java_archive(&self) -> bool {
+        self.kind.is_java_archive()
+    }
+}
+
+impl PBXObjectCollection {
+    /// Add a new empty build phase of the given kind to the target with the given reference.
+    /// Returns the reference of the created build phase.
+    pub fn add_build_phase(
+        &mut self,
+        target: &str,
+        kind: PBXBuildPhaseKind,
+    ) -> anyhow::Result<String> {
+        let mut phase = PBXHashMap::default();
+        phase.insert_value("isa", PBXObjectKind::PBXBuildPhase(kind));
+        phase.insert_value("buildActionMask", PBXBuildPhase::DEFAULT_BUILD_ACTION_MASK);
+        phase.insert_value("files", Vec::<String>::new());
+        phase.insert_value("runOnlyForDeploymentPostprocessing", 0);
+        self.add_target_build_phase(target, phase)
+    }
+
+    /// Insert `phase` object and register it in `target` build phases.
+    pub(crate) fn add_target_build_phase(
+        &mut self,
+        target: &str,
+        phase: PBXHashMap,
+    ) -> anyhow::Result<String> {
+        let is_target = self
+            .map
+            .get(target)
+            .and_then(|t| t.get_kind("isa"))
+            .map(|k| k.is_target())
+            .unwrap_or_default();
+        if !is_target {
+            anyhow::bail!("{target:?} is not a target");
+        }
+
+        let reference = String::from(self.insert_object(phase, Some(target)));
+
+        let mut target = self.get_object_mut(target).unwrap();
+        match target.get_vec_mut("buildPhases") {
+            Some(phases) => phases.push(reference.clone()),
+            None => {
+                target.insert_value("buildPhases", vec![reference.clone()]);
+            }
+        }
+
+        Ok(reference)
+    }
+
+    /// Get reference of the first build phase of the given kind in the target with the given
+    /// reference.
+    pub fn get_target_build_phase(&self, target: &str, kind: &PBXBuildPhaseKind) -> Option<String> {
+        self.get_target(target)?
+            .build_phases
+            .into_iter()
+            .find(|phase| &phase.kind == kind)
+            .map(|phase| phase.id)
+    }
 }
 
 impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
@@ -88,17 +160,15 @@ impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
             id,
             build_action_mask: value
                 .try_get_number("buildActionMask")
-                .map(|v| v.clone())
-                .unwrap_or_else(|_| Self::DEFAULT_BUILD_ACTION_MASK),
+                .copied()
+                .unwrap_or(Self::DEFAULT_BUILD_ACTION_MASK),
             files: value
                 .get_vec("files")
-                .and_then(|vec| {
-                    Some(
-                        vec.as_vec_strings()
-                            .iter()
-                            .flat_map(|&k| objects.get(k))
-                            .collect::<Vec<_>>(),
-                    )
+                .map(|vec| {
+                    vec.as_vec_strings()
+                        .iter()
+                        .flat_map(|&k| objects.get(k))
+                        .collect::<Vec<_>>()
                 })
                 .unwrap_or_default(),
             input_file_list_paths: value
@@ -120,7 +190,51 @@ impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
             } else {
                 None
             },
+            apple_script: if kind.is_apple_script() {
+                Some(AsPBXObject::as_pbx_object(
+                    Default::default(),
+                    value,
+                    objects,
+                )?)
+            } else {
+                None
+            },
             kind,
         })
     }
 }
+
+impl ToPBXHashMap for PBXBuildPhase<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXBuildPhase(self.kind.clone()));
+        value.insert_value("buildActionMask", self.build_action_mask);
+        value.insert_value(
+            "files",
+            self.files.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(),
+        );
+        value.insert_some(
+            "inputFileListPaths",
+            self.input_file_list_paths
+                .as_ref()
+                .map(|v| v.iter().map(|p| p.as_str()).collect::<Vec<_>>()),
+        );
+        value.insert_some(
+            "outputFileListPaths",
+            self.output_file_list_paths
+                .as_ref()
+                .map(|v| v.iter().map(|p| p.as_str()).collect::<Vec<_>>()),
+        );
+        value.insert_value(
+            "runOnlyForDeploymentPostprocessing",
+            isize::from(self.run_only_for_deployment_postprocessing),
+        );
+        if let Some(inner) = self.inner.as_ref() {
+            value.0.extend(inner.to_hashmap().0);
+        }
+        if let Some(apple_script) = self.apple_script.as_ref() {
+            value.0.extend(apple_script.to_hashmap().0);
+        }
+        value
+    }
+}
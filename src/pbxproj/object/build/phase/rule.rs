@@ -57,3 +57,28 @@ impl<'a> AsPBXObject<'a> for PBXBuildRule<'a> {
         })
     }
 }
+
+impl ToPBXHashMap for PBXBuildRule<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let strings = |v: &Vec<&String>| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXBuildRule);
+        value.insert_some("compilerSpec", self.compiler_spec.map(String::as_str));
+        value.insert_some("filePatterns", self.file_patterns.map(String::as_str));
+        value.insert_some("fileType", self.file_type.map(String::as_str));
+        value.insert_some("isEditable", self.is_editable.map(isize::from));
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some("outputFiles", self.output_files.as_ref().map(strings));
+        value.insert_some("inputFiles", self.input_files.as_ref().map(strings));
+        value.insert_some(
+            "outputFilesCompilerFlags",
+            self.output_files_compiler_flags.as_ref().map(strings),
+        );
+        value.insert_some("script", self.script.map(String::as_str));
+        value.insert_some(
+            "runOncePerArchitecture",
+            self.run_once_per_architecture.map(isize::from),
+        );
+        value
+    }
+}
@@ -1,4 +1,5 @@
 use crate::pbxproj::*;
+use anyhow::Result;
 
 /// Part of [`PBXBuildPhase`] when [`PBXBuildPhaseKind`] is [`RunScript`]
 ///
@@ -23,23 +24,134 @@ pub struct PBXShellScriptBuildPhase<'a> {
     pub dependency_file: Option<&'a String>,
 }
 
+impl<'a> PBXShellScriptBuildPhase<'a> {
+    /// Whether the script only runs when its inputs or outputs changed, "Based on dependency
+    /// analysis" in Xcode.
+    pub fn based_on_dependency_analysis(&self) -> bool {
+        !self.always_out_of_date
+    }
+}
+
+impl PBXObjectCollection {
+    /// Get mutable run script build phase with the given reference
+    fn get_shell_script_build_phase_mut(&mut self, phase: &str) -> Result<PBXObjectMut<'_>> {
+        self.get_build_phase_mut(phase)
+            .filter(|p| p.kind().as_pbx_build_phase() == Some(&PBXBuildPhaseKind::RunScript))
+            .ok_or_else(|| anyhow::anyhow!("{phase:?} is not a run script build phase"))
+    }
+
+    /// Set whether the run script build phase with the given reference only runs when
+    /// installing, "For install builds only" in Xcode.
+    pub fn set_run_only_for_deployment_postprocessing(
+        &mut self,
+        phase: &str,
+        value: bool,
+    ) -> Result<()> {
+        self.get_shell_script_build_phase_mut(phase)?
+            .insert_value("runOnlyForDeploymentPostprocessing", isize::from(value));
+        Ok(())
+    }
+
+    /// Set whether the run script build phase with the given reference runs in every build,
+    /// the inverse of "Based on dependency analysis" in Xcode.
+    pub fn set_always_out_of_date(&mut self, phase: &str, value: bool) -> Result<()> {
+        let mut phase = self.get_shell_script_build_phase_mut(phase)?;
+        // Xcode omits the key for the default
+        if value {
+            phase.insert_value("alwaysOutOfDate", 1);
+        } else {
+            phase.remove_value("alwaysOutOfDate");
+        }
+        Ok(())
+    }
+
+    /// Set whether the run script build phase with the given reference logs environment
+    /// variables.
+    pub fn set_show_env_vars_in_log(&mut self, phase: &str, value: bool) -> Result<()> {
+        let mut phase = self.get_shell_script_build_phase_mut(phase)?;
+        // Xcode omits the key for the default
+        if value {
+            phase.remove_value("showEnvVarsInLog");
+        } else {
+            phase.insert_value("showEnvVarsInLog", 0);
+        }
+        Ok(())
+    }
+
+    /// Set input file list (`.xcfilelist`) paths of the run script build phase with the given
+    /// reference.
+    pub fn set_input_file_list_paths<S: AsRef<str>>(
+        &mut self,
+        phase: &str,
+        paths: &[S],
+    ) -> Result<()> {
+        let paths = paths.iter().map(|p| p.as_ref()).collect::<Vec<_>>();
+        self.get_shell_script_build_phase_mut(phase)?
+            .insert_value("inputFileListPaths", paths);
+        Ok(())
+    }
+
+    /// Set output file list (`.xcfilelist`) paths of the run script build phase with the given
+    /// reference.
+    pub fn set_output_file_list_paths<S: AsRef<str>>(
+        &mut self,
+        phase: &str,
+        paths: &[S],
+    ) -> Result<()> {
+        let paths = paths.iter().map(|p| p.as_ref()).collect::<Vec<_>>();
+        self.get_shell_script_build_phase_mut(phase)?
+            .insert_value("outputFileListPaths", paths);
+        Ok(())
+    }
+
+    /// Add a new run script build phase with the given name and script to the target with the
+    /// given reference. Returns the reference of the created build phase.
+    pub fn add_shell_script_build_phase<N, S>(
+        &mut self,
+        target: &str,
+        name: N,
+        script: S,
+    ) -> anyhow::Result<String>
+    where
+        N: AsRef<str>,
+        S: AsRef<str>,
+    {
+        let mut phase = PBXHashMap::default();
+        phase.insert_value(
+            "isa",
+            PBXObjectKind::PBXBuildPhase(PBXBuildPhaseKind::RunScript),
+        );
+        phase.insert_value("buildActionMask", PBXBuildPhase::DEFAULT_BUILD_ACTION_MASK);
+        phase.insert_value("files", Vec::<String>::new());
+        phase.insert_value("inputFileListPaths", Vec::<String>::new());
+        phase.insert_value("inputPaths", Vec::<String>::new());
+        phase.insert_value("name", name.as_ref());
+        phase.insert_value("outputFileListPaths", Vec::<String>::new());
+        phase.insert_value("outputPaths", Vec::<String>::new());
+        phase.insert_value("runOnlyForDeploymentPostprocessing", 0);
+        phase.insert_value("shellPath", "/bin/sh");
+        phase.insert_value("shellScript", script.as_ref());
+        self.add_target_build_phase(target, phase)
+    }
+}
+
 impl<'a> AsPBXObject<'a> for PBXShellScriptBuildPhase<'a> {
     fn as_pbx_object(
         _id: String,
         value: &'a PBXHashMap,
-        _objects: &'a PBXObjectCollection,
+        objects: &'a PBXObjectCollection,
     ) -> anyhow::Result<Self>
     where
         Self: Sized + 'a,
     {
         Ok(Self {
             name: value.get_string("name"),
-            input_paths: value.try_get_vec("inputPaths")?.as_vec_strings(),
-            output_paths: value.try_get_vec("outputPaths")?.as_vec_strings(),
+            input_paths: objects.required_vec(value, "inputPaths")?.as_vec_strings(),
+            output_paths: objects.required_vec(value, "outputPaths")?.as_vec_strings(),
             shell_path: value.get_string("shellPath"),
             shell_script: value.get_string("shellScript"),
             show_env_vars_in_log: value
-                .get_number("runOnlyForDeploymentPostprocessing")
+                .get_number("showEnvVarsInLog")
                 .map(|v| v == &1)
                 .unwrap_or_else(|| true),
             always_out_of_date: value
@@ -50,3 +162,75 @@ impl<'a> AsPBXObject<'a> for PBXShellScriptBuildPhase<'a> {
         })
     }
 }
+
+/// Lowers script fields only, see [`PBXBuildPhase`] for the whole build phase
+impl ToPBXHashMap for PBXShellScriptBuildPhase<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_value(
+            "inputPaths",
+            self.input_paths
+                .iter()
+                .map(|p| p.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value.insert_value(
+            "outputPaths",
+            self.output_paths
+                .iter()
+                .map(|p| p.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value.insert_some("shellPath", self.shell_path.map(String::as_str));
+        value.insert_some("shellScript", self.shell_script.map(String::as_str));
+        // Xcode writes both only when they differ from their defaults
+        if !self.show_env_vars_in_log {
+            value.insert_value("showEnvVarsInLog", 0);
+        }
+        if self.always_out_of_date {
+            value.insert_value("alwaysOutOfDate", 1);
+        }
+        value.insert_some("dependencyFile", self.dependency_file.map(String::as_str));
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn script_options() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let objects = root_object.objects_mut();
+        let phase = objects
+            .add_shell_script_build_phase(&target, "Lint", "swiftlint")
+            .unwrap();
+        objects
+            .set_run_only_for_deployment_postprocessing(&phase, true)
+            .unwrap();
+        objects.set_always_out_of_date(&phase, true).unwrap();
+        objects.set_show_env_vars_in_log(&phase, false).unwrap();
+        objects
+            .set_input_file_list_paths(&phase, &["$(SRCROOT)/Lint.xcfilelist"])
+            .unwrap();
+        objects
+            .set_output_file_list_paths(&phase, &["$(DERIVED_FILE_DIR)/Lint.xcfilelist"])
+            .unwrap();
+        assert!(objects.set_always_out_of_date(&target, true).is_err());
+
+        let phase = root_object.get_build_phase(&phase).unwrap();
+        assert!(phase.run_only_for_deployment_postprocessing);
+        assert_eq!(
+            Some(vec![&"$(SRCROOT)/Lint.xcfilelist".to_string()]),
+            phase.input_file_list_paths
+        );
+        assert_eq!(1, phase.output_file_list_paths.unwrap().len());
+        let script = phase.inner.unwrap();
+        assert!(script.always_out_of_date);
+        assert!(!script.based_on_dependency_analysis());
+        assert!(!script.show_env_vars_in_log);
+    }
+}
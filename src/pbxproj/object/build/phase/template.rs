@@ -0,0 +1,171 @@
+use crate::pbxproj::*;
+
+const HOMEBREW_PATH: &str = "export PATH=\"$PATH:/opt/homebrew/bin\"\n";
+
+/// Parameterized templates of common run script build phases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PBXShellScriptTemplate {
+    /// Lint sources with `swiftlint`, optionally with a custom configuration file path relative
+    /// to project root.
+    SwiftLint {
+        /// Configuration file path, e.g. `.swiftlint.yml`
+        config: Option<String>,
+    },
+    /// Format sources with `swiftformat`, or only report violations when `lint` is set.
+    SwiftFormat {
+        /// Whether to run in lint mode without modifying sources
+        lint: bool,
+    },
+    /// Upload dSYM files with the given command, e.g. Crashlytics `upload-symbols` script.
+    DsymUpload {
+        /// Upload command
+        command: String,
+    },
+    /// Increment `CFBundleVersion` of the target Info.plist on every build.
+    BuildNumberIncrement,
+}
+
+impl PBXShellScriptTemplate {
+    /// Build phase name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::SwiftLint { .. } => "SwiftLint",
+            Self::SwiftFormat { .. } => "SwiftFormat",
+            Self::DsymUpload { .. } => "Upload dSYM",
+            Self::BuildNumberIncrement => "Increment Build Number",
+        }
+    }
+
+    /// Shell script
+    pub fn script(&self) -> String {
+        match self {
+            Self::SwiftLint { config } => {
+                let command = match config {
+                    Some(config) => format!("swiftlint --config \"${{SRCROOT}}/{config}\""),
+                    None => "swiftlint".into(),
+                };
+                format!(
+                    "{HOMEBREW_PATH}if which swiftlint > /dev/null; then\n  {command}\nelse\n  echo \"warning: SwiftLint not installed, download from https://github.com/realm/SwiftLint\"\nfi\n"
+                )
+            }
+            Self::SwiftFormat { lint } => {
+                let command = if *lint {
+                    "swiftformat --lint \"${SRCROOT}\""
+                } else {
+                    "swiftformat \"${SRCROOT}\""
+                };
+                format!(
+                    "{HOMEBREW_PATH}if which swiftformat > /dev/null; then\n  {command}\nelse\n  echo \"warning: SwiftFormat not installed, download from https://github.com/nicklockwood/SwiftFormat\"\nfi\n"
+                )
+            }
+            Self::DsymUpload { command } => format!("{command}\n"),
+            Self::BuildNumberIncrement => concat!(
+                "plist=\"${SRCROOT}/${INFOPLIST_FILE}\"\n",
+                "build=$(/usr/libexec/PlistBuddy -c \"Print :CFBundleVersion\" \"$plist\")\n",
+                "/usr/libexec/PlistBuddy -c \"Set :CFBundleVersion $((build + 1))\" \"$plist\"\n"
+            )
+            .into(),
+        }
+    }
+
+    /// Script input paths. Scripts only read declared inputs when user script sandboxing is
+    /// enabled, the default since Xcode 15.
+    pub fn input_paths(&self) -> Vec<String> {
+        match self {
+            Self::SwiftLint { config: Some(config) } => {
+                vec!["$(SRCROOT)".into(), format!("$(SRCROOT)/{config}")]
+            }
+            Self::SwiftLint { config: None } | Self::SwiftFormat { .. } => {
+                vec!["$(SRCROOT)".into()]
+            }
+            Self::DsymUpload { .. } => vec![
+                "${DWARF_DSYM_FOLDER_PATH}/${DWARF_DSYM_FILE_NAME}".into(),
+                "${DWARF_DSYM_FOLDER_PATH}/${DWARF_DSYM_FILE_NAME}/Contents/Resources/DWARF/${TARGET_NAME}".into(),
+                "$(BUILT_PRODUCTS_DIR)/$(INFOPLIST_PATH)".into(),
+            ],
+            Self::BuildNumberIncrement => vec!["$(SRCROOT)/$(INFOPLIST_FILE)".into()],
+        }
+    }
+
+    /// Script output paths, files the script writes
+    pub fn output_paths(&self) -> Vec<String> {
+        match self {
+            Self::BuildNumberIncrement => vec!["$(SRCROOT)/$(INFOPLIST_FILE)".into()],
+            _ => vec![],
+        }
+    }
+
+    /// Whether the script should only run when its inputs change. Scripts without outputs have
+    /// to opt out, otherwise Xcode warns about them running on every build, and so does the
+    /// build number increment, which is meant to run on every build.
+    pub fn based_on_dependency_analysis(&self) -> bool {
+        match self {
+            Self::SwiftLint { .. }
+            | Self::SwiftFormat { .. }
+            | Self::DsymUpload { .. }
+            | Self::BuildNumberIncrement => false,
+        }
+    }
+}
+
+impl PBXObjectCollection {
+    /// Add a run script build phase created from `template` to the target with the given
+    /// reference. Returns the reference of the created build phase.
+    pub fn add_shell_script_template(
+        &mut self,
+        target: &str,
+        template: &PBXShellScriptTemplate,
+    ) -> anyhow::Result<String> {
+        let reference =
+            self.add_shell_script_build_phase(target, template.name(), template.script())?;
+        let mut phase = self.get_object_mut(&reference).unwrap();
+        phase.insert_value("inputPaths", template.input_paths());
+        phase.insert_value("outputPaths", template.output_paths());
+        if !template.based_on_dependency_analysis() {
+            phase.insert_value("alwaysOutOfDate", 1);
+        }
+        Ok(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn add_shell_script_template() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let templates = [
+            PBXShellScriptTemplate::SwiftLint {
+                config: Some(".swiftlint.yml".into()),
+            },
+            PBXShellScriptTemplate::DsymUpload {
+                command: "\"${BUILD_DIR%/Build/*}/SourcePackages/checkouts/firebase-ios-sdk/Crashlytics/run\"".into(),
+            },
+            PBXShellScriptTemplate::BuildNumberIncrement,
+        ];
+        for template in templates.iter() {
+            root_object
+                .objects_mut()
+                .add_shell_script_template(&target, template)
+                .unwrap();
+        }
+
+        let target = root_object.get_target(&target).unwrap();
+        let swiftlint = target.build_phases[3].inner.as_ref().unwrap();
+        let upload = target.build_phases[4].inner.as_ref().unwrap();
+        let increment = target.build_phases[5].inner.as_ref().unwrap();
+
+        assert_eq!(Some(&"SwiftLint".to_string()), swiftlint.name);
+        assert_eq!(
+            vec!["$(SRCROOT)", "$(SRCROOT)/.swiftlint.yml"],
+            swiftlint.input_paths
+        );
+        assert!(swiftlint.always_out_of_date);
+        assert_eq!(3, upload.input_paths.len());
+        assert!(upload.always_out_of_date);
+        assert_eq!(vec!["$(SRCROOT)/$(INFOPLIST_FILE)"], increment.output_paths);
+        assert!(increment.always_out_of_date);
+    }
+}
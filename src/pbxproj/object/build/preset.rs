@@ -0,0 +1,354 @@
+use crate::pbxproj::*;
+use std::collections::HashMap;
+
+/// Build settings presets matching the defaults Xcode writes for new projects and targets.
+///
+/// Settings that depend on user input (e.g. `PRODUCT_BUNDLE_IDENTIFIER` or `TEST_HOST`) are not
+/// part of the presets and are expected to be set by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCBuildSettingsPreset {
+    /// Project level settings shared by all targets
+    Project,
+    /// Application target settings
+    Application,
+    /// Framework target settings
+    Framework,
+    /// Unit test bundle target settings
+    UnitTestBundle,
+    /// UI test bundle target settings
+    UITestBundle,
+    /// Minimal settings for target types without a dedicated Xcode template
+    Generic,
+}
+
+impl XCBuildSettingsPreset {
+    /// Get preset for a given target product type
+    pub fn from_product_type(product_type: &PBXProductType) -> Self {
+        use PBXProductType::*;
+        match product_type {
+            Application | OnDemandInstallCapableApplication => Self::Application,
+            Framework | StaticFramework => Self::Framework,
+            UnitTestBundle | OcUnitTestBundle => Self::UnitTestBundle,
+            UiTestBundle => Self::UITestBundle,
+            _ => Self::Generic,
+        }
+    }
+
+    /// Get build settings for the configuration with the given name.
+    ///
+    /// `Debug` gets the debug variant, any other name gets the release variant.
+    pub fn build_settings(&self, name: &str, platform: &PBXTargetPlatform) -> PBXHashMap {
+        let debug = name.eq_ignore_ascii_case("debug");
+        let mut settings = match self {
+            Self::Project => project_settings(debug, platform),
+            Self::Application => application_settings(platform),
+            Self::Framework => framework_settings(platform),
+            Self::UnitTestBundle => test_bundle_settings(platform, false),
+            Self::UITestBundle => test_bundle_settings(platform, true),
+            Self::Generic => settings([
+                ("CODE_SIGN_STYLE", "Automatic".into()),
+                ("PRODUCT_NAME", "$(TARGET_NAME)".into()),
+            ]),
+        };
+
+        if !matches!(self, Self::Project | Self::Generic) {
+            if let Some(family) = targeted_device_family(platform) {
+                settings.insert_value("TARGETED_DEVICE_FAMILY", family);
+            }
+        }
+
+        settings
+    }
+}
+
+fn settings<const N: usize>(values: [(&str, PBXValue); N]) -> PBXHashMap {
+    values
+        .into_iter()
+        .map(|(k, v)| (SmolStr::new(k), v))
+        .collect::<HashMap<_, _>>()
+        .pipe(PBXHashMap::new)
+}
+
+fn strings(values: &[&str]) -> PBXValue {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn sdk_root(platform: &PBXTargetPlatform) -> Option<(&'static str, &'static str, &'static str)> {
+    match platform {
+        PBXTargetPlatform::IOS => Some(("iphoneos", "IPHONEOS_DEPLOYMENT_TARGET", "17.0")),
+        PBXTargetPlatform::MacOS => Some(("macosx", "MACOSX_DEPLOYMENT_TARGET", "14.0")),
+        PBXTargetPlatform::TvOS => Some(("appletvos", "TVOS_DEPLOYMENT_TARGET", "17.0")),
+        PBXTargetPlatform::WatchOS => Some(("watchos", "WATCHOS_DEPLOYMENT_TARGET", "10.0")),
+        PBXTargetPlatform::Unknown => None,
+    }
+}
+
+fn targeted_device_family(platform: &PBXTargetPlatform) -> Option<&'static str> {
+    match platform {
+        PBXTargetPlatform::IOS => Some("1,2"),
+        PBXTargetPlatform::TvOS => Some("3"),
+        PBXTargetPlatform::WatchOS => Some("4"),
+        _ => None,
+    }
+}
+
+fn project_settings(debug: bool, platform: &PBXTargetPlatform) -> PBXHashMap {
+    let mut map = settings([
+        ("ALWAYS_SEARCH_USER_PATHS", false.into()),
+        (
+            "ASSETCATALOG_COMPILER_GENERATE_SWIFT_ASSET_SYMBOL_EXTENSIONS",
+            true.into(),
+        ),
+        ("CLANG_ANALYZER_NONNULL", true.into()),
+        (
+            "CLANG_ANALYZER_NUMBER_OBJECT_CONVERSION",
+            "YES_AGGRESSIVE".into(),
+        ),
+        ("CLANG_CXX_LANGUAGE_STANDARD", "gnu++20".into()),
+        ("CLANG_ENABLE_MODULES", true.into()),
+        ("CLANG_ENABLE_OBJC_ARC", true.into()),
+        ("CLANG_ENABLE_OBJC_WEAK", true.into()),
+        ("CLANG_WARN_BLOCK_CAPTURE_AUTORELEASING", true.into()),
+        ("CLANG_WARN_BOOL_CONVERSION", true.into()),
+        ("CLANG_WARN_COMMA", true.into()),
+        ("CLANG_WARN_CONSTANT_CONVERSION", true.into()),
+        ("CLANG_WARN_DEPRECATED_OBJC_IMPLEMENTATIONS", true.into()),
+        ("CLANG_WARN_DIRECT_OBJC_ISA_USAGE", "YES_ERROR".into()),
+        ("CLANG_WARN_DOCUMENTATION_COMMENTS", true.into()),
+        ("CLANG_WARN_EMPTY_BODY", true.into()),
+        ("CLANG_WARN_ENUM_CONVERSION", true.into()),
+        ("CLANG_WARN_INFINITE_RECURSION", true.into()),
+        ("CLANG_WARN_INT_CONVERSION", true.into()),
+        ("CLANG_WARN_NON_LITERAL_NULL_CONVERSION", true.into()),
+        ("CLANG_WARN_OBJC_IMPLICIT_RETAIN_SELF", true.into()),
+        ("CLANG_WARN_OBJC_LITERAL_CONVERSION", true.into()),
+        ("CLANG_WARN_OBJC_ROOT_CLASS", "YES_ERROR".into()),
+        ("CLANG_WARN_QUOTED_INCLUDE_IN_FRAMEWORK_HEADER", true.into()),
+        ("CLANG_WARN_RANGE_LOOP_ANALYSIS", true.into()),
+        ("CLANG_WARN_STRICT_PROTOTYPES", true.into()),
+        ("CLANG_WARN_SUSPICIOUS_MOVE", true.into()),
+        ("CLANG_WARN_UNGUARDED_AVAILABILITY", "YES_AGGRESSIVE".into()),
+        ("CLANG_WARN_UNREACHABLE_CODE", true.into()),
+        ("CLANG_WARN__DUPLICATE_METHOD_MATCH", true.into()),
+        ("COPY_PHASE_STRIP", false.into()),
+        ("ENABLE_STRICT_OBJC_MSGSEND", true.into()),
+        ("ENABLE_USER_SCRIPT_SANDBOXING", true.into()),
+        ("GCC_C_LANGUAGE_STANDARD", "gnu17".into()),
+        ("GCC_NO_COMMON_BLOCKS", true.into()),
+        ("GCC_WARN_64_TO_32_BIT_CONVERSION", true.into()),
+        ("GCC_WARN_ABOUT_RETURN_TYPE", "YES_ERROR".into()),
+        ("GCC_WARN_UNDECLARED_SELECTOR", true.into()),
+        ("GCC_WARN_UNINITIALIZED_AUTOS", "YES_AGGRESSIVE".into()),
+        ("GCC_WARN_UNUSED_FUNCTION", true.into()),
+        ("GCC_WARN_UNUSED_VARIABLE", true.into()),
+        ("LOCALIZATION_PREFERS_STRING_CATALOGS", true.into()),
+        ("MTL_FAST_MATH", true.into()),
+    ]);
+
+    if debug {
+        map.0.extend(
+            settings([
+                ("DEBUG_INFORMATION_FORMAT", "dwarf".into()),
+                ("ENABLE_TESTABILITY", true.into()),
+                ("GCC_DYNAMIC_NO_PIC", false.into()),
+                ("GCC_OPTIMIZATION_LEVEL", 0.into()),
+                (
+                    "GCC_PREPROCESSOR_DEFINITIONS",
+                    strings(&["DEBUG=1", "$(inherited)"]),
+                ),
+                ("MTL_ENABLE_DEBUG_INFO", "INCLUDE_SOURCE".into()),
+                ("ONLY_ACTIVE_ARCH", true.into()),
+                (
+                    "SWIFT_ACTIVE_COMPILATION_CONDITIONS",
+                    "DEBUG $(inherited)".into(),
+                ),
+                ("SWIFT_OPTIMIZATION_LEVEL", "-Onone".into()),
+            ])
+            .0,
+        );
+    } else {
+        map.0.extend(
+            settings([
+                ("DEBUG_INFORMATION_FORMAT", "dwarf-with-dsym".into()),
+                ("ENABLE_NS_ASSERTIONS", false.into()),
+                ("MTL_ENABLE_DEBUG_INFO", false.into()),
+                ("SWIFT_COMPILATION_MODE", "wholemodule".into()),
+            ])
+            .0,
+        );
+        if !platform.is_mac_os() {
+            map.insert_value("VALIDATE_PRODUCT", true);
+        }
+    }
+
+    if let Some((sdkroot, deployment_target_key, deployment_target)) = sdk_root(platform) {
+        map.insert_value("SDKROOT", sdkroot);
+        map.insert_value(deployment_target_key, deployment_target);
+    }
+
+    map
+}
+
+fn application_settings(platform: &PBXTargetPlatform) -> PBXHashMap {
+    let mut map = settings([
+        ("ASSETCATALOG_COMPILER_APPICON_NAME", "AppIcon".into()),
+        (
+            "ASSETCATALOG_COMPILER_GLOBAL_ACCENT_COLOR_NAME",
+            "AccentColor".into(),
+        ),
+        ("CODE_SIGN_STYLE", "Automatic".into()),
+        ("CURRENT_PROJECT_VERSION", 1.into()),
+        ("ENABLE_PREVIEWS", true.into()),
+        ("GENERATE_INFOPLIST_FILE", true.into()),
+        ("MARKETING_VERSION", "1.0".into()),
+        ("PRODUCT_NAME", "$(TARGET_NAME)".into()),
+        ("SWIFT_EMIT_LOC_STRINGS", true.into()),
+        ("SWIFT_VERSION", "5.0".into()),
+    ]);
+
+    if platform.is_mac_os() {
+        map.0.extend(
+            settings([
+                ("COMBINE_HIDPI_IMAGES", true.into()),
+                ("ENABLE_HARDENED_RUNTIME", true.into()),
+                ("INFOPLIST_KEY_NSHumanReadableCopyright", "".into()),
+                (
+                    "LD_RUNPATH_SEARCH_PATHS",
+                    strings(&["$(inherited)", "@executable_path/../Frameworks"]),
+                ),
+            ])
+            .0,
+        );
+    } else {
+        map.insert_value(
+            "LD_RUNPATH_SEARCH_PATHS",
+            strings(&["$(inherited)", "@executable_path/Frameworks"]),
+        );
+    }
+
+    if platform.is_ios() {
+        map.0.extend(
+            settings([
+                ("INFOPLIST_KEY_UIApplicationSceneManifest_Generation", true.into()),
+                ("INFOPLIST_KEY_UIApplicationSupportsIndirectInputEvents", true.into()),
+                ("INFOPLIST_KEY_UILaunchScreen_Generation", true.into()),
+                (
+                    "INFOPLIST_KEY_UISupportedInterfaceOrientations_iPad",
+                    "UIInterfaceOrientationPortrait UIInterfaceOrientationPortraitUpsideDown UIInterfaceOrientationLandscapeLeft UIInterfaceOrientationLandscapeRight".into(),
+                ),
+                (
+                    "INFOPLIST_KEY_UISupportedInterfaceOrientations_iPhone",
+                    "UIInterfaceOrientationPortrait UIInterfaceOrientationLandscapeLeft UIInterfaceOrientationLandscapeRight".into(),
+                ),
+            ])
+            .0,
+        );
+    }
+
+    map
+}
+
+fn framework_settings(platform: &PBXTargetPlatform) -> PBXHashMap {
+    let runpath = if platform.is_mac_os() {
+        strings(&[
+            "$(inherited)",
+            "@executable_path/../Frameworks",
+            "@loader_path/Frameworks",
+        ])
+    } else {
+        strings(&[
+            "$(inherited)",
+            "@executable_path/Frameworks",
+            "@loader_path/Frameworks",
+        ])
+    };
+
+    settings([
+        ("CODE_SIGN_STYLE", "Automatic".into()),
+        ("CURRENT_PROJECT_VERSION", 1.into()),
+        ("DEFINES_MODULE", true.into()),
+        ("DYLIB_COMPATIBILITY_VERSION", 1.into()),
+        ("DYLIB_CURRENT_VERSION", 1.into()),
+        ("DYLIB_INSTALL_NAME_BASE", "@rpath".into()),
+        ("ENABLE_MODULE_VERIFIER", true.into()),
+        ("GENERATE_INFOPLIST_FILE", true.into()),
+        ("INFOPLIST_KEY_NSHumanReadableCopyright", "".into()),
+        ("INSTALL_PATH", "$(LOCAL_LIBRARY_DIR)/Frameworks".into()),
+        ("LD_RUNPATH_SEARCH_PATHS", runpath),
+        ("MARKETING_VERSION", "1.0".into()),
+        (
+            "MODULE_VERIFIER_SUPPORTED_LANGUAGES",
+            "objective-c objective-c++".into(),
+        ),
+        (
+            "MODULE_VERIFIER_SUPPORTED_LANGUAGE_STANDARDS",
+            "gnu17 gnu++20".into(),
+        ),
+        ("PRODUCT_NAME", "$(TARGET_NAME:c99extidentifier)".into()),
+        ("SKIP_INSTALL", true.into()),
+        ("SWIFT_EMIT_LOC_STRINGS", true.into()),
+        ("SWIFT_INSTALL_OBJC_HEADER", false.into()),
+        ("SWIFT_VERSION", "5.0".into()),
+        ("VERSIONING_SYSTEM", "apple-generic".into()),
+        ("VERSION_INFO_PREFIX", "".into()),
+    ])
+}
+
+fn test_bundle_settings(platform: &PBXTargetPlatform, ui: bool) -> PBXHashMap {
+    let mut map = settings([
+        ("CODE_SIGN_STYLE", "Automatic".into()),
+        ("CURRENT_PROJECT_VERSION", 1.into()),
+        ("GENERATE_INFOPLIST_FILE", true.into()),
+        ("MARKETING_VERSION", "1.0".into()),
+        ("PRODUCT_NAME", "$(TARGET_NAME)".into()),
+        ("SWIFT_EMIT_LOC_STRINGS", false.into()),
+        ("SWIFT_VERSION", "5.0".into()),
+    ]);
+
+    if !ui {
+        map.insert_value("BUNDLE_LOADER", "$(TEST_HOST)");
+    }
+
+    if let Some((_, deployment_target_key, deployment_target)) = sdk_root(platform) {
+        map.insert_value(deployment_target_key, deployment_target);
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_debug_and_release_differ() {
+        let platform = PBXTargetPlatform::IOS;
+        let debug = XCBuildSettingsPreset::Project.build_settings("Debug", &platform);
+        let release = XCBuildSettingsPreset::Project.build_settings("Release", &platform);
+
+        assert_eq!(debug.get_string("SDKROOT"), Some(&"iphoneos".to_string()));
+        assert_eq!(debug.get_bool("ONLY_ACTIVE_ARCH"), Some(&true));
+        assert_eq!(release.get_bool("ONLY_ACTIVE_ARCH"), None);
+        assert_eq!(release.get_bool("VALIDATE_PRODUCT"), Some(&true));
+    }
+
+    #[test]
+    fn target_preset_from_product_type() {
+        let preset = XCBuildSettingsPreset::from_product_type(&PBXProductType::UnitTestBundle);
+        let settings = preset.build_settings("Debug", &PBXTargetPlatform::IOS);
+
+        assert_eq!(preset, XCBuildSettingsPreset::UnitTestBundle);
+        assert_eq!(
+            settings.get_string("BUNDLE_LOADER"),
+            Some(&"$(TEST_HOST)".to_string())
+        );
+        assert_eq!(
+            settings.get_string("TARGETED_DEVICE_FAMILY"),
+            Some(&"1,2".to_string())
+        );
+    }
+}
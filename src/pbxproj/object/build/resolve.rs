@@ -0,0 +1,241 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+/// Maximum nesting of `$(VAR)` references expanded, guarding against cyclic settings
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+impl PBXRootObject {
+    /// Get build settings of the target with the given reference for `configuration`, with
+    /// target settings layered over the root project ones and `$(inherited)` resolved.
+    ///
+    /// Settings of `baseConfigurationReference` xcconfig files and Xcode defaults aren't
+    /// included. Values are left unexpanded, see [`expand_build_setting`].
+    pub fn resolved_build_settings(&self, target: &str, configuration: &str) -> Result<PBXHashMap> {
+        let target = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?;
+        let project = self
+            .root_project()
+            .build_configuration_list
+            .build_configurations
+            .into_iter()
+            .find(|c| c.name == configuration)
+            .map(|c| c.build_settings.clone())
+            .unwrap_or_default();
+        let target_settings = target
+            .build_configuration_list
+            .and_then(|list| {
+                list.build_configurations
+                    .into_iter()
+                    .find(|c| c.name == configuration)
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Configuration {configuration:?} not found in {:?}",
+                    target.id
+                )
+            })?
+            .build_settings;
+
+        let mut settings = PBXHashMap::default();
+        for (key, value) in project.iter() {
+            settings.insert_value(key.clone(), inherit(value, None));
+        }
+        for (key, value) in target_settings.iter() {
+            let value = inherit(value, project.get_value(key));
+            settings.insert_value(key.clone(), value);
+        }
+        Ok(settings)
+    }
+}
+
+/// Replace `$(inherited)` in `value` with `base`, or drop it without base.
+fn inherit(value: &PBXValue, base: Option<&PBXValue>) -> PBXValue {
+    let base = base.map(|base| inherit(base, None));
+    match value {
+        PBXValue::Vec(values) => {
+            let mut inherited = PBXVec::default();
+            for value in values.iter() {
+                match value.as_string().map(|s| s.as_str()) {
+                    Some("$(inherited)") | Some("${inherited}") => match &base {
+                        Some(PBXValue::Vec(base)) => {
+                            base.iter().for_each(|v| inherited.push(v.clone()))
+                        }
+                        Some(PBXValue::String(base)) => split_setting(base)
+                            .into_iter()
+                            .for_each(|v| inherited.push(v)),
+                        _ => {}
+                    },
+                    _ => inherited.push(value.clone()),
+                }
+            }
+            PBXValue::Vec(inherited)
+        }
+        PBXValue::String(value) if value.contains("inherited") => {
+            let base = match &base {
+                Some(PBXValue::String(base)) => base.clone(),
+                Some(PBXValue::Vec(base)) => base
+                    .as_vec_strings()
+                    .into_iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => String::new(),
+            };
+            let value = value
+                .replace("$(inherited)", &base)
+                .replace("${inherited}", &base);
+            PBXValue::String(value.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+        value => value.clone(),
+    }
+}
+
+/// Expand `$(VAR)` and `${VAR}` references in `value` using `settings`, following Xcode in
+/// expanding unknown settings to an empty string. Supports the `lower`, `upper`,
+/// `c99extidentifier` and `rfc1034identifier` modifiers, e.g. `$(TARGET_NAME:lower)`.
+pub fn expand_build_setting(value: &str, settings: &PBXHashMap) -> String {
+    expand(value, settings, 0)
+}
+
+fn expand(value: &str, settings: &PBXHashMap, depth: usize) -> String {
+    if depth == MAX_EXPANSION_DEPTH {
+        return value.to_string();
+    }
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let close = match rest[start + 1..].chars().next() {
+            Some('(') => ')',
+            Some('{') => '}',
+            _ => {
+                expanded.push('$');
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+        let end = match rest[start + 2..].find(close) {
+            Some(end) => start + 2 + end,
+            None => {
+                expanded.push_str(&rest[start..]);
+                return expanded;
+            }
+        };
+
+        let reference = &rest[start + 2..end];
+        let (name, modifier) = match reference.split_once(':') {
+            Some((name, modifier)) => (name, Some(modifier)),
+            None => (reference, None),
+        };
+        let value = match settings.get_value(name) {
+            Some(PBXValue::String(value)) => expand(value, settings, depth + 1),
+            Some(PBXValue::Vec(values)) => values
+                .as_vec_strings()
+                .into_iter()
+                .map(|value| expand(value, settings, depth + 1))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Some(PBXValue::Number(value)) => value.to_string(),
+            Some(PBXValue::Float(value)) => value.to_string(),
+            Some(PBXValue::Bool(value)) => if *value { "YES" } else { "NO" }.to_string(),
+            _ => String::new(),
+        };
+        expanded.push_str(&apply_modifier(value, modifier));
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn apply_modifier(value: String, modifier: Option<&str>) -> String {
+    let replace_invalid = |replacement: char| {
+        value
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c
+                } else {
+                    replacement
+                }
+            })
+            .collect::<String>()
+    };
+    match modifier {
+        Some("lower") => value.to_lowercase(),
+        Some("upper") => value.to_uppercase(),
+        Some("c99extidentifier") => replace_invalid('_'),
+        Some("rfc1034identifier") => replace_invalid('-'),
+        _ => value,
+    }
+}
+
+/// Split a string list setting on whitespace, keeping double quoted values together.
+pub(crate) fn split_setting(value: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    values.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        values.push(current);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_and_expand() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let project = root_object.root_object_reference().to_string();
+        root_object
+            .set_setting_all(&project, "OTHER_SWIFT_FLAGS", "-DPROJECT")
+            .unwrap();
+        root_object
+            .set_setting_all(
+                &target,
+                "OTHER_SWIFT_FLAGS",
+                vec!["$(inherited)", "-DTARGET"],
+            )
+            .unwrap();
+        root_object
+            .set_setting_all(
+                &target,
+                "MODULE",
+                "$(TARGET_NAME:c99extidentifier)_$(MISSING)",
+            )
+            .unwrap();
+
+        let mut settings = root_object
+            .resolved_build_settings(&target, "Debug")
+            .unwrap();
+        assert_eq!(
+            Some(&PBXVec::new(vec!["-DPROJECT".into(), "-DTARGET".into()])),
+            settings.get_vec("OTHER_SWIFT_FLAGS")
+        );
+
+        settings.insert_value("TARGET_NAME", "My App");
+        assert_eq!("My_App_", expand_build_setting("$(MODULE)", &settings));
+        assert_eq!(
+            "my app",
+            expand_build_setting("${TARGET_NAME:lower}", &settings)
+        );
+        assert!(root_object
+            .resolved_build_settings(&target, "Missing")
+            .is_err());
+        assert_eq!(vec!["a b", "c"], split_setting("\"a b\" c"));
+    }
+}
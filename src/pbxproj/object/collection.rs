@@ -1,13 +1,84 @@
-// use md5::Digest;
-// use rand::distributions::Alphanumeric;
-// use rand::{thread_rng, Rng};
 use crate::pbxproj::*;
 use anyhow::Result;
-use std::collections::HashMap;
+use md5::{Digest, Md5};
+use std::collections::{HashMap, HashSet};
 
 /// [`PBXObject`] storage with convenient helper methods
-#[derive(Default, Debug, derive_new::new, derive_deref_rs::Deref)]
-pub struct PBXObjectCollection(pub(crate) HashMap<String, PBXHashMap>);
+#[derive(Clone, Default, Debug, derive_new::new)]
+pub struct PBXObjectCollection {
+    /// Objects by reference
+    pub(crate) map: HashMap<String, PBXHashMap>,
+    /// Strategy used to generate references of inserted objects
+    #[new(default)]
+    pub(crate) reference_mode: PBXReferenceMode,
+    /// Lazily built index of file references by path
+    #[new(default)]
+    pub(crate) path_index: PBXPathIndex,
+    /// How objects missing required fields are decoded
+    #[new(default)]
+    pub(crate) decode_mode: PBXDecodeMode,
+    /// References generated references must avoid on top of existing ones
+    #[new(default)]
+    pub(crate) reserved: HashSet<String>,
+    /// Object change observers, see [`PBXRootObject::observe`]
+    #[new(default)]
+    pub(crate) observers: PBXObservers,
+}
+
+/// Strategy used to generate references of inserted objects
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum PBXReferenceMode {
+    /// Random references, like Xcode does.
+    #[default]
+    Random,
+    /// References derived from a stable hash of object isa, name and parent, so generating a
+    /// project from the same inputs yields identical references.
+    Deterministic,
+}
+
+/// Reference of an object inserted into [`PBXObjectCollection`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, derive_deref_rs::Deref)]
+pub struct PBXObjectReference(String);
+
+impl std::fmt::Display for PBXObjectReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for PBXObjectReference {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<PBXObjectReference> for String {
+    fn from(reference: PBXObjectReference) -> Self {
+        reference.0
+    }
+}
+
+impl From<PBXObjectReference> for PBXValue {
+    fn from(reference: PBXObjectReference) -> Self {
+        PBXValue::String(reference.0)
+    }
+}
+
+impl std::ops::Deref for PBXObjectCollection {
+    type Target = HashMap<String, PBXHashMap>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl PartialEq for PBXObjectCollection {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl Eq for PBXObjectCollection {}
 
 /// Get PBXObject from PBXHashMap and PBXObjectCollection
 pub trait AsPBXObject<'a> {
@@ -21,14 +92,129 @@ pub trait AsPBXObject<'a> {
         Self: Sized + 'a;
 }
 
+/// Lower PBXObject back into [`PBXHashMap`], the reverse of [`AsPBXObject`]
+pub trait ToPBXHashMap {
+    /// Create a raw object out of the pbx object, referencing other objects by reference.
+    /// Fields left out by the pbx object are left out too.
+    fn to_hashmap(&self) -> PBXHashMap;
+}
+
 impl PBXObjectCollection {
+    /// Reserve `references` so no generated reference uses them, e.g. references of objects
+    /// about to be merged from another copy of the project.
+    pub fn reserve_references<I, S>(&mut self, references: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.reserved.extend(references.into_iter().map(Into::into))
+    }
+
+    /// Release references reserved with [`PBXObjectCollection::reserve_references`]
+    pub fn clear_reserved_references(&mut self) {
+        self.reserved.clear()
+    }
+
+    /// Whether `reference` is used by an object or reserved
+    pub fn is_reference_taken(&self, reference: &str) -> bool {
+        self.map.contains_key(reference) || self.reserved.contains(reference)
+    }
+
+    /// Generate a new Xcode style reference, see [`PBXObjectId`], that is neither used nor
+    /// reserved, retrying on collision.
+    pub fn generate_reference(&self) -> String {
+        loop {
+            let reference = PBXObjectId::generate().to_string();
+            if !self.is_reference_taken(&reference) {
+                return reference;
+            }
+        }
+    }
+
+    /// Generate a new reference for `object`, inserted under `parent` if any, according to
+    /// [`PBXReferenceMode`]. Used and reserved references are never returned.
+    pub(crate) fn generate_reference_for(
+        &self,
+        object: &PBXHashMap,
+        parent: Option<&str>,
+    ) -> String {
+        if self.reference_mode == PBXReferenceMode::Random {
+            return self.generate_reference();
+        }
+
+        let isa = object
+            .get_kind("isa")
+            .map(|kind| kind.to_string())
+            .unwrap_or_default();
+        let name = ["name", "path", "fileRef", "productRef", "productName"]
+            .into_iter()
+            .find_map(|key| object.get_string(key))
+            .map(|name| name.as_str())
+            .unwrap_or_default();
+
+        // Same isa, name and parent are disambiguated by insertion order.
+        (0..)
+            .map(|index: usize| {
+                let digest = Md5::new()
+                    .chain_update(isa.as_bytes())
+                    .chain_update([0])
+                    .chain_update(name.as_bytes())
+                    .chain_update([0])
+                    .chain_update(parent.unwrap_or_default().as_bytes())
+                    .chain_update(index.to_le_bytes())
+                    .finalize();
+                let mut bytes = [0; 12];
+                bytes.copy_from_slice(&digest[..12]);
+                PBXObjectId::from(bytes).to_string()
+            })
+            .find(|reference| !self.is_reference_taken(reference))
+            .unwrap()
+    }
+
+    /// Insert `object` under a newly generated unique reference, see [`PBXReferenceMode`].
+    /// Fails if `object` has no isa.
+    pub fn insert_new(&mut self, object: PBXHashMap) -> Result<PBXObjectReference> {
+        if object.get_kind("isa").is_none() {
+            anyhow::bail!("Object to insert has no isa");
+        }
+        Ok(self.insert_object(object, None))
+    }
+
+    /// Insert `object`, added under `parent` if any, under a newly generated reference.
+    pub(crate) fn insert_object(
+        &mut self,
+        object: PBXHashMap,
+        parent: Option<&str>,
+    ) -> PBXObjectReference {
+        let reference = self.generate_reference_for(&object, parent);
+        self.insert_at(reference.clone(), object);
+        PBXObjectReference(reference)
+    }
+
+    /// Get strategy used to generate references of inserted objects
+    pub fn reference_mode(&self) -> PBXReferenceMode {
+        self.reference_mode
+    }
+
+    /// Set strategy used to generate references of inserted objects
+    pub fn set_reference_mode(&mut self, mode: PBXReferenceMode) {
+        self.reference_mode = mode;
+    }
+
+    /// Get mutable raw object from collection. Observers are notified of modifications when
+    /// the returned object is dropped.
+    pub fn get_object_mut<S: AsRef<str>>(&mut self, key: S) -> Option<PBXObjectMut<'_>> {
+        self.invalidate_path_index();
+        self.get_mut_by(key.as_ref(), |_| true)
+    }
+
     /// Get T from collection
     pub fn get<'a, T, S>(&'a self, key: S) -> Option<T>
     where
         T: AsPBXObject<'a> + 'a,
         S: AsRef<str>,
     {
-        self.0.get(key.as_ref()).and_then(|value| {
+        self.map.get(key.as_ref()).and_then(|value| {
             AsPBXObject::as_pbx_object(key.as_ref().to_string(), value, self).ok()
         })
     }
@@ -67,16 +253,12 @@ impl PBXObjectCollection {
     }
 
     /// Get all PBXTarget
-    pub fn targets<'a>(&'a self) -> Vec<PBXTarget<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_target())
-                .unwrap_or_default()
-        })
+    pub fn targets(&self) -> Vec<PBXTarget<'_>> {
+        self.get_vec_by(|(_, v)| v.get_kind("isa").map(|k| k.is_target()).unwrap_or_default())
     }
 
     /// Get all PBXProject
-    pub fn projects<'a>(&'a self) -> Vec<PBXProject<'a>> {
+    pub fn projects(&self) -> Vec<PBXProject<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_pbx_project())
@@ -85,16 +267,16 @@ impl PBXObjectCollection {
     }
 
     /// Get all build phases
-    pub fn build_phases<'a>(&'a self) -> Vec<PBXBuildPhase<'a>> {
+    pub fn build_phases(&self) -> Vec<PBXBuildPhase<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
-                .map(|k| k.is_pbx_build_phase())
+                .map(|k| k.is_build_phase())
                 .unwrap_or_default()
         })
     }
 
     /// Get all build phases
-    pub fn build_configurations<'a>(&'a self) -> Vec<XCBuildConfiguration<'a>> {
+    pub fn build_configurations(&self) -> Vec<XCBuildConfiguration<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_xc_build_configuration())
@@ -103,7 +285,7 @@ impl PBXObjectCollection {
     }
 
     /// Get all build phases
-    pub fn build_files<'a>(&'a self) -> Vec<PBXBuildFile<'a>> {
+    pub fn build_files(&self) -> Vec<PBXBuildFile<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_pbx_build_file())
@@ -112,7 +294,7 @@ impl PBXObjectCollection {
     }
 
     /// Get all build phases
-    pub fn build_rules<'a>(&'a self) -> Vec<PBXBuildRule<'a>> {
+    pub fn build_rules(&self) -> Vec<PBXBuildRule<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_pbx_build_rule())
@@ -121,37 +303,27 @@ impl PBXObjectCollection {
     }
 
     /// Get all source code files
-    pub fn files<'a>(&'a self) -> Vec<PBXFSReference<'a>> {
+    pub fn files(&self) -> Vec<PBXFSReference<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
-                .map(|k| {
-                    k.is_pbx_fsreference()
-                        && k.as_pbxfs_reference()
-                            .map(|r| r.is_file())
-                            .unwrap_or_default()
-                })
+                .and_then(|k| k.as_pbxfs_reference())
+                .map(|r| r.is_file())
                 .unwrap_or_default()
         })
     }
 
     /// Get all groups
-    pub fn groups<'a>(&'a self) -> Vec<PBXFSReference<'a>> {
+    pub fn groups(&self) -> Vec<PBXFSReference<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
-                .map(|k| {
-                    k.is_pbx_fsreference()
-                        && k.as_pbxfs_reference()
-                            .map(|r| r.is_group())
-                            .unwrap_or_default()
-                })
+                .and_then(|k| k.as_pbxfs_reference())
+                .map(|r| r.is_group())
                 .unwrap_or_default()
         })
     }
 
     /// Get All XCSwiftPackageProductDependency Objects
-    pub fn swift_package_product_dependencies<'a>(
-        &'a self,
-    ) -> Vec<XCSwiftPackageProductDependency> {
+    pub fn swift_package_product_dependencies(&self) -> Vec<XCSwiftPackageProductDependency<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_xc_swift_package_product_dependency())
@@ -160,7 +332,7 @@ impl PBXObjectCollection {
     }
 
     /// Get All XCRemoteSwiftPackageReference Objects
-    pub fn swift_package_references<'a>(&'a self) -> Vec<XCRemoteSwiftPackageReference> {
+    pub fn swift_package_references(&self) -> Vec<XCRemoteSwiftPackageReference<'_>> {
         self.get_vec_by(|(_, v)| {
             v.get_kind("isa")
                 .map(|k| k.is_xc_remote_swift_package_reference())
@@ -169,32 +341,32 @@ impl PBXObjectCollection {
     }
 
     /// Get PBXTarget
-    pub fn get_target<'a>(&'a self, key: &str) -> Option<PBXTarget> {
+    pub fn get_target(&self, key: &str) -> Option<PBXTarget<'_>> {
         self.get(key)
     }
 
     /// Get PBXBuildPhase
-    pub fn get_build_phase<'a>(&'a self, key: &str) -> Option<PBXBuildPhase> {
+    pub fn get_build_phase(&self, key: &str) -> Option<PBXBuildPhase<'_>> {
         self.get(key)
     }
 
     /// Get PBXBuildFile
-    pub fn get_build_file<'a>(&'a self, key: &str) -> Option<PBXBuildFile> {
+    pub fn get_build_file(&self, key: &str) -> Option<PBXBuildFile<'_>> {
         self.get(key)
     }
 
     /// Get PBXBuildRule
-    pub fn get_build_rule<'a>(&'a self, key: &str) -> Option<PBXBuildRule> {
+    pub fn get_build_rule(&self, key: &str) -> Option<PBXBuildRule<'_>> {
         self.get(key)
     }
 
     /// Get PBXProject
-    pub fn get_project<'a>(&'a self, key: &str) -> Option<PBXProject> {
+    pub fn get_project(&self, key: &str) -> Option<PBXProject<'_>> {
         self.get(key)
     }
 
     /// Get all files
-    pub fn get_file<'a>(&'a self, key: &str) -> Option<PBXFSReference> {
+    pub fn get_file(&self, key: &str) -> Option<PBXFSReference<'_>> {
         let fs_ref = self.get::<PBXFSReference, _>(key)?;
         if fs_ref.is_file() {
             Some(fs_ref)
@@ -204,7 +376,7 @@ impl PBXObjectCollection {
     }
 
     /// Get all files
-    pub fn get_group<'a>(&'a self, key: &str) -> Option<PBXFSReference> {
+    pub fn get_group(&self, key: &str) -> Option<PBXFSReference<'_>> {
         let fs_ref = self.get::<PBXFSReference, _>(key)?;
         if fs_ref.is_group() {
             Some(fs_ref)
@@ -214,21 +386,21 @@ impl PBXObjectCollection {
     }
 
     /// Get fs object
-    pub fn get_fs_object<'a>(&'a self, key: &str) -> Option<PBXFSReference> {
+    pub fn get_fs_object(&self, key: &str) -> Option<PBXFSReference<'_>> {
         self.get(key)
     }
 
     /// Get PBXGroup with by name or path
-    pub fn get_group_by_name_or_path<'a, S: AsRef<str>>(
-        &'a self,
+    pub fn get_group_by_name_or_path<S: AsRef<str>>(
+        &self,
         name_or_path: S,
-    ) -> Option<PBXFSReference> {
+    ) -> Option<PBXFSReference<'_>> {
         let name = name_or_path.as_ref();
         self.groups().into_iter().find(|o| {
             if let Some(n) = o.name {
-                return n == name;
+                n == name
             } else if let Some(p) = o.path {
-                return p == name;
+                p == name
             } else {
                 false
             }
@@ -239,7 +411,7 @@ impl PBXObjectCollection {
     pub fn get_build_configurations_by_base_id<S: AsRef<str>>(
         &self,
         id: S,
-    ) -> Vec<XCBuildConfiguration> {
+    ) -> Vec<XCBuildConfiguration<'_>> {
         let key = id.as_ref();
         self.get_vec_by(move |(_, v)| {
             v.get_kind("isa")
@@ -252,23 +424,23 @@ impl PBXObjectCollection {
     }
 
     /// Get XCSwiftPackageProductDependency by reference
-    pub fn get_swift_package_product_dependency<'a>(
-        &'a self,
+    pub fn get_swift_package_product_dependency(
+        &self,
         key: &str,
-    ) -> Option<XCSwiftPackageProductDependency> {
+    ) -> Option<XCSwiftPackageProductDependency<'_>> {
         self.get(key)
     }
 
     /// Get XCSwiftPackageProductDependency by reference
-    pub fn get_swift_package_reference<'a>(
-        &'a self,
+    pub fn get_swift_package_reference(
+        &self,
         key: &str,
-    ) -> Option<XCRemoteSwiftPackageReference> {
+    ) -> Option<XCRemoteSwiftPackageReference<'_>> {
         self.get(key)
     }
 
     /// Get PBXTarget by the target name
-    pub fn get_target_by_name<'a>(&'a self, name: &'a str) -> Option<PBXTarget> {
+    pub fn get_target_by_name<'a>(&'a self, name: &'a str) -> Option<PBXTarget<'a>> {
         self.targets().into_iter().find(|target| {
             if let Some(target_name) = target.name {
                 target_name == name
@@ -278,3 +450,65 @@ impl PBXObjectCollection {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn deterministic_references() {
+        let generate = || {
+            let mut root_object = test_demo_file!(demo1);
+            root_object
+                .objects_mut()
+                .set_reference_mode(PBXReferenceMode::Deterministic);
+            let target = root_object
+                .add_native_target("Kit", PBXProductType::Framework, PBXTargetPlatform::IOS)
+                .unwrap();
+            let group = root_object.get_or_add_group("Kit/Sources").unwrap();
+            (target, group, root_object.objects().clone())
+        };
+
+        let (target, group, objects) = generate();
+        assert_eq!(24, target.len());
+        assert!(target.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!((target, group, objects), generate());
+    }
+
+    #[test]
+    fn insert_new() {
+        let mut root_object = test_demo_file!(demo1);
+        let objects = root_object.objects_mut();
+        let file = objects.insert_file_reference("Kit.swift", PBXSourceTree::Group);
+        assert_eq!(
+            Some(&"Kit.swift".to_string()),
+            objects.get_file(&file).unwrap().path
+        );
+
+        let mut rule = PBXHashMap::default();
+        rule.insert_value("isa", PBXObjectKind::PBXBuildRule);
+        let rule = objects.insert_new(rule).unwrap();
+        assert_ne!(file, rule);
+        assert!(objects.get_build_rule(&rule).is_some());
+        assert!(objects.insert_new(PBXHashMap::default()).is_err());
+    }
+
+    #[test]
+    fn reserved_references() {
+        let mut root_object = test_demo_file!(demo1);
+        let objects = root_object.objects_mut();
+        objects.set_reference_mode(PBXReferenceMode::Deterministic);
+        let expected = objects
+            .clone()
+            .insert_file_reference("Kit.swift", PBXSourceTree::Group);
+
+        objects.reserve_references([expected.to_string()]);
+        assert!(objects.is_reference_taken(&expected));
+        let file = objects.insert_file_reference("Kit.swift", PBXSourceTree::Group);
+        assert_ne!(expected, file);
+        assert!(objects.get_file(&expected).is_none());
+
+        objects.clear_reserved_references();
+        assert!(!objects.is_reference_taken(&expected));
+    }
+}
@@ -22,24 +22,36 @@ impl<'a> AsPBXObject<'a> for PBXContainerItemProxy<'a> {
     fn as_pbx_object(
         id: String,
         value: &'a PBXHashMap,
-        _objects: &'a PBXObjectCollection,
+        objects: &'a PBXObjectCollection,
     ) -> anyhow::Result<Self>
     where
         Self: Sized + 'a,
     {
         Ok(Self {
             id,
-            container_portal_reference: value.try_get_string("containerPortal")?,
-            proxy_type: value
-                .get_value("proxyType")
-                .map(|v| v.try_into().ok())
-                .flatten(),
-            remote_global_id_reference: value.get_string("remoteGlobalIdString"),
+            container_portal_reference: objects.required_string(value, "containerPortal")?,
+            proxy_type: value.get_value("proxyType").and_then(|v| v.try_into().ok()),
+            remote_global_id_reference: value.get_string("remoteGlobalIDString"),
             remote_info: value.get_string("remoteInfo"),
         })
     }
 }
 
+impl ToPBXHashMap for PBXContainerItemProxy<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXContainerItemProxy);
+        value.insert_value("containerPortal", self.container_portal_reference.as_str());
+        value.insert_some("proxyType", self.proxy_type.as_ref().map(isize::from));
+        value.insert_some(
+            "remoteGlobalIDString",
+            self.remote_global_id_reference.map(String::as_str),
+        );
+        value.insert_some("remoteInfo", self.remote_info.map(String::as_str));
+        value
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 /// [`PBXContainerItemProxy`] Type
 pub enum PBXProxyType {
@@ -68,3 +80,12 @@ impl TryFrom<&PBXValue> for PBXProxyType {
     }
 }
 
+impl From<&PBXProxyType> for isize {
+    fn from(proxy_type: &PBXProxyType) -> Self {
+        match proxy_type {
+            PBXProxyType::NativeTarget => 1,
+            PBXProxyType::Reference => 2,
+            PBXProxyType::Other(value) => *value as isize,
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Keys referencing objects owned by the object holding them, which are copied along with it.
+/// Any other reference, e.g. `fileRef` of a build file, is shared between the original and
+/// the copy.
+const OWNED_KEYS: &[&str] = &[
+    "children",
+    "buildPhases",
+    "files",
+    "buildConfigurationList",
+    "buildConfigurations",
+    "buildRules",
+    "dependencies",
+    "targetProxy",
+];
+
+impl PBXObjectCollection {
+    /// Copy the object with the given reference along with all the objects it owns, e.g. a
+    /// group with all descendants or a target with its build phases, build files and
+    /// configurations. Every copied object gets a fresh reference.
+    ///
+    /// The copy isn't attached to any parent. Returns the reference of the copied object.
+    pub fn deep_copy(&mut self, reference: &str) -> Result<String> {
+        let subtree = self.subtree(reference)?;
+        Ok(self.insert_subtree(reference, subtree))
+    }
+
+    /// Like [`PBXObjectCollection::deep_copy`], but copies the object from `source`, e.g.
+    /// another project. Shared references aren't copied and must be resolved by the caller.
+    pub fn deep_copy_from(
+        &mut self,
+        source: &PBXObjectCollection,
+        reference: &str,
+    ) -> Result<String> {
+        let subtree = source.subtree(reference)?;
+        Ok(self.insert_subtree(reference, subtree))
+    }
+
+    /// Object with the given reference and all objects it owns.
+    pub(crate) fn subtree(&self, reference: &str) -> Result<Vec<(String, PBXHashMap)>> {
+        let mut subtree = vec![];
+        let mut pending = vec![reference.to_string()];
+
+        while let Some(reference) = pending.pop() {
+            if subtree.iter().any(|(r, _)| r == &reference) {
+                continue;
+            }
+            let object = self
+                .map
+                .get(&reference)
+                .ok_or_else(|| anyhow::anyhow!("Object {reference:?} not found"))?;
+
+            for key in OWNED_KEYS {
+                match object.get_value(key) {
+                    Some(PBXValue::String(owned)) => pending.push(owned.clone()),
+                    Some(PBXValue::Vec(owned)) => {
+                        pending.extend(owned.as_vec_strings().into_iter().cloned())
+                    }
+                    _ => {}
+                }
+            }
+
+            subtree.push((reference, object.clone()));
+        }
+
+        Ok(subtree)
+    }
+
+    /// Insert `subtree` objects with fresh references, rewriting references between them.
+    /// Returns the new reference of `root`.
+    pub(crate) fn insert_subtree(
+        &mut self,
+        root: &str,
+        subtree: Vec<(String, PBXHashMap)>,
+    ) -> String {
+        let mut references = HashMap::with_capacity(subtree.len());
+        for (reference, object) in subtree.iter() {
+            let mut new_reference = self.generate_reference_for(object, Some(reference));
+            while references.values().any(|r| r == &new_reference) {
+                new_reference = self.generate_reference();
+            }
+            references.insert(reference.clone(), new_reference);
+        }
+
+        for (reference, object) in subtree {
+            let mut object = PBXValue::Object(object);
+            remap_references(&mut object, &references);
+            if let PBXValue::Object(object) = object {
+                self.insert_at(references[&reference].clone(), object);
+            }
+        }
+
+        references[root].clone()
+    }
+}
+
+/// Replace every reference in `value` found in `references` keys with its value, including
+/// keys of nested objects, e.g. targets in `TargetAttributes`.
+pub(crate) fn remap_references(value: &mut PBXValue, references: &HashMap<String, String>) {
+    match value {
+        PBXValue::String(reference) => {
+            if let Some(new_reference) = references.get(reference.as_str()) {
+                *reference = new_reference.clone();
+            }
+        }
+        PBXValue::Object(object) => {
+            let keys = object
+                .0
+                .keys()
+                .filter(|key| references.contains_key(key.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
+            for key in keys {
+                let value = object.0.remove(&key).unwrap();
+                object
+                    .0
+                    .insert(references[key.as_str()].as_str().into(), value);
+            }
+            object
+                .0
+                .values_mut()
+                .for_each(|value| remap_references(value, references))
+        }
+        PBXValue::Vec(values) => values
+            .0
+            .iter_mut()
+            .for_each(|value| remap_references(value, references)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn deep_copy_target() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let objects = root_object.objects().len();
+
+        let copy = root_object.objects_mut().deep_copy(&target).unwrap();
+        // target, 3 phases, 16 build files, configuration list and 2 configurations
+        assert_eq!(objects + 23, root_object.objects().len());
+
+        let (original, copy) = (
+            root_object.get_target(&target).unwrap(),
+            root_object.get_target(&copy).unwrap(),
+        );
+        assert_eq!(original.name, copy.name);
+        assert_ne!(original.build_phases[0].id, copy.build_phases[0].id);
+        assert_ne!(
+            original.build_phases[0].files[0].id,
+            copy.build_phases[0].files[0].id
+        );
+        assert_eq!(
+            original.build_phases[0].files[0].file.as_ref().unwrap().id,
+            copy.build_phases[0].files[0].file.as_ref().unwrap().id
+        );
+        assert_ne!(
+            original.build_configuration_list.unwrap().id,
+            copy.build_configuration_list.unwrap().id
+        );
+    }
+
+    #[test]
+    fn deep_copy_group_between_projects() {
+        let source = test_demo_file!(demo1);
+        let mut root_object = test_demo_file!(demo2);
+        let group = source.get_group_by_name_or_path("Source").unwrap();
+
+        let copy = root_object
+            .objects_mut()
+            .deep_copy_from(source.objects(), &group.id)
+            .unwrap();
+        let copy = root_object.get_group(&copy).unwrap();
+
+        assert_eq!(group.children.len(), copy.children.len());
+        assert_eq!(group.path, copy.path);
+    }
+}
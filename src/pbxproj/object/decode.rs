@@ -0,0 +1,254 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::sync::OnceLock;
+
+/// How typed objects treat required fields missing from raw objects
+///
+/// In permissive mode missing fields fall back to a default instead of failing decoding:
+///
+/// | isa                               | field                          | default |
+/// |-----------------------------------|--------------------------------|---------|
+/// | `PBXProject`                      | `attributes`                   | `{}`    |
+/// | `PBXProject`                      | `hasScannedForEncodings`       | `0`     |
+/// | `PBXProject`                      | `knownRegions`                 | `()`    |
+/// | `PBXProject`                      | `projectDirPath`               | `""`    |
+/// | `PBX*Target`                      | `productType`                  | `""`    |
+/// | `XCConfigurationList`             | `defaultConfigurationIsVisible`| `0`     |
+/// | `XCBuildConfiguration`            | `name`                         | `""`    |
+/// | `XCBuildConfiguration`            | `buildSettings`                | `{}`    |
+/// | `PBXShellScriptBuildPhase`        | `inputPaths`                   | `()`    |
+/// | `PBXShellScriptBuildPhase`        | `outputPaths`                  | `()`    |
+/// | `PBXContainerItemProxy`           | `containerPortal`              | `""`    |
+/// | `XCSwiftPackageProductDependency` | `productName`                  | `""`    |
+///
+/// References to objects a typed object is built from, e.g. `mainGroup` of `PBXProject`, have
+/// no sensible default and are required in both modes. Use
+/// [`PBXObjectCollection::decode_warnings`] to list defaulted fields.
+///
+/// Projects saved before Xcode 3.2, with an object version below 46, predate fields such as
+/// `attributes` and `knownRegions` and are loaded in permissive mode.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum PBXDecodeMode {
+    /// Fail decoding objects missing required fields, e.g. for validators.
+    #[default]
+    Strict,
+    /// Use documented defaults for missing required fields.
+    Permissive,
+}
+
+impl PBXDecodeMode {
+    /// Mode projects with the given object version are loaded in
+    pub(crate) fn for_object_version(object_version: u8) -> Self {
+        match object_version < 46 {
+            true => Self::Permissive,
+            false => Self::Strict,
+        }
+    }
+}
+
+/// Required field missing from an object, decoded with a default in permissive mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXDecodeWarning {
+    /// Object reference
+    pub reference: String,
+    /// Object isa
+    pub isa: String,
+    /// Missing field key
+    pub key: &'static str,
+    /// Default used in place of the missing field
+    pub default: PBXValue,
+}
+
+impl std::fmt::Display for PBXDecodeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:?} is missing {:?}, defaulting to {:?}",
+            self.isa, self.reference, self.key, self.default
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FieldType {
+    String,
+    Number,
+    Vec,
+    Object,
+}
+
+impl FieldType {
+    fn default_value(self) -> PBXValue {
+        match self {
+            Self::String => PBXValue::String(Default::default()),
+            Self::Number => PBXValue::Number(0),
+            Self::Vec => PBXValue::Vec(Default::default()),
+            Self::Object => PBXValue::Object(Default::default()),
+        }
+    }
+}
+
+const REQUIRED_FIELDS: &[(&str, &str, FieldType)] = &[
+    ("PBXProject", "attributes", FieldType::Object),
+    ("PBXProject", "hasScannedForEncodings", FieldType::Number),
+    ("PBXProject", "knownRegions", FieldType::Vec),
+    ("PBXProject", "projectDirPath", FieldType::String),
+    ("PBXNativeTarget", "productType", FieldType::String),
+    ("PBXAggregateTarget", "productType", FieldType::String),
+    ("PBXLegacyTarget", "productType", FieldType::String),
+    (
+        "XCConfigurationList",
+        "defaultConfigurationIsVisible",
+        FieldType::Number,
+    ),
+    ("XCBuildConfiguration", "name", FieldType::String),
+    ("XCBuildConfiguration", "buildSettings", FieldType::Object),
+    ("PBXShellScriptBuildPhase", "inputPaths", FieldType::Vec),
+    ("PBXShellScriptBuildPhase", "outputPaths", FieldType::Vec),
+    (
+        "PBXContainerItemProxy",
+        "containerPortal",
+        FieldType::String,
+    ),
+    (
+        "XCSwiftPackageProductDependency",
+        "productName",
+        FieldType::String,
+    ),
+];
+
+static EMPTY_STRING: String = String::new();
+static EMPTY_VEC: PBXVec = PBXVec(Vec::new());
+static EMPTY_OBJECT: OnceLock<PBXHashMap> = OnceLock::new();
+
+impl PBXObjectCollection {
+    /// Get how typed objects treat missing required fields
+    pub fn decode_mode(&self) -> PBXDecodeMode {
+        self.decode_mode
+    }
+
+    /// Set how typed objects treat missing required fields
+    pub fn set_decode_mode(&mut self, mode: PBXDecodeMode) {
+        self.decode_mode = mode;
+    }
+
+    /// Get required fields missing from objects, which fail decoding in strict mode and are
+    /// defaulted in permissive mode. Sorted by reference and key.
+    pub fn decode_warnings(&self) -> Vec<PBXDecodeWarning> {
+        let mut warnings = self
+            .map
+            .iter()
+            .filter_map(|(reference, object)| {
+                let isa = object.get_kind("isa")?.to_string();
+                Some(
+                    REQUIRED_FIELDS
+                        .iter()
+                        .filter(|(field_isa, key, _)| {
+                            field_isa == &isa && object.get_value(key).is_none()
+                        })
+                        .map(|(_, key, field_type)| PBXDecodeWarning {
+                            reference: reference.clone(),
+                            isa: isa.clone(),
+                            key,
+                            default: field_type.default_value(),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        warnings.sort_by(|a, b| (&a.reference, a.key).cmp(&(&b.reference, b.key)));
+        warnings
+    }
+
+    /// Whether missing `key` of `value` should be defaulted
+    fn use_default(&self, value: &PBXHashMap, key: &str) -> bool {
+        self.decode_mode == PBXDecodeMode::Permissive && value.get_value(key).is_none()
+    }
+
+    /// Get required string field, see [`PBXDecodeMode`]
+    pub(crate) fn required_string<'a>(
+        &self,
+        value: &'a PBXHashMap,
+        key: &str,
+    ) -> Result<&'a String> {
+        if self.use_default(value, key) {
+            return Ok(&EMPTY_STRING);
+        }
+        value.try_get_string(key)
+    }
+
+    /// Get required number field, see [`PBXDecodeMode`]
+    pub(crate) fn required_number<'a>(
+        &self,
+        value: &'a PBXHashMap,
+        key: &str,
+    ) -> Result<&'a isize> {
+        if self.use_default(value, key) {
+            return Ok(&0);
+        }
+        value.try_get_number(key)
+    }
+
+    /// Get required vec field, see [`PBXDecodeMode`]
+    pub(crate) fn required_vec<'a>(&self, value: &'a PBXHashMap, key: &str) -> Result<&'a PBXVec> {
+        if self.use_default(value, key) {
+            return Ok(&EMPTY_VEC);
+        }
+        value.try_get_vec(key)
+    }
+
+    /// Get required object field, see [`PBXDecodeMode`]
+    pub(crate) fn required_object<'a>(
+        &self,
+        value: &'a PBXHashMap,
+        key: &str,
+    ) -> Result<&'a PBXHashMap> {
+        if self.use_default(value, key) {
+            return Ok(EMPTY_OBJECT.get_or_init(Default::default));
+        }
+        value.try_get_object(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn permissive_decoding() {
+        let mut root_object = test_demo_file!(demo1);
+        let list = root_object.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .id
+            .clone();
+        assert!(root_object.objects().decode_warnings().is_empty());
+
+        root_object
+            .objects_mut()
+            .get_object_mut(&list)
+            .unwrap()
+            .remove_value("defaultConfigurationIsVisible");
+        assert!(root_object.targets()[0].build_configuration_list.is_none());
+
+        let objects = root_object.objects_mut();
+        objects.set_decode_mode(PBXDecodeMode::Permissive);
+        let list = objects.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .id
+            .clone();
+        assert_eq!(
+            vec![PBXDecodeWarning {
+                reference: list,
+                isa: "XCConfigurationList".into(),
+                key: "defaultConfigurationIsVisible",
+                default: PBXValue::Number(0),
+            }],
+            objects.decode_warnings()
+        );
+    }
+}
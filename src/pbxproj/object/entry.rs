@@ -0,0 +1,255 @@
+use crate::pbxproj::*;
+
+/// Mutable view of an object of [`PBXObjectCollection`], returned by `_mut` getters such as
+/// [`PBXObjectCollection::get_target_mut`].
+///
+/// Derefs to the raw object, so fields are edited with [`PBXHashMap`] helpers, e.g.
+/// [`PBXHashMap::insert_value`] or [`PBXHashMap::vec_entry`]. Observers are notified of
+/// modifications when the view is dropped, see [`PBXRootObject::observe`].
+#[derive(Debug)]
+pub struct PBXObjectMut<'a> {
+    id: String,
+    kind: PBXObjectKind,
+    value: &'a mut PBXHashMap,
+    /// Object before edits and observers to notify, if any
+    observed: Option<(PBXHashMap, &'a PBXObservers)>,
+}
+
+impl<'a> PBXObjectMut<'a> {
+    /// Object reference
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Object kind
+    pub fn kind(&self) -> &PBXObjectKind {
+        &self.kind
+    }
+
+    /// Set string field `key`, removing it if `value` is `None`.
+    pub fn set_string<K: Into<SmolStr>>(&mut self, key: K, value: Option<&str>) {
+        let key = key.into();
+        match value {
+            Some(value) => {
+                self.value.insert_value(key, value);
+            }
+            None => {
+                self.value.remove_value(&key);
+            }
+        }
+    }
+
+    /// Get mutable build settings, for build configurations
+    pub fn build_settings_mut(&mut self) -> &mut PBXHashMap {
+        self.value.object_entry("buildSettings")
+    }
+
+    /// Get mutable children references, for groups
+    pub fn children_mut(&mut self) -> &mut PBXVec {
+        self.value.vec_entry("children")
+    }
+
+    /// Get mutable build file references, for build phases
+    pub fn files_mut(&mut self) -> &mut PBXVec {
+        self.value.vec_entry("files")
+    }
+
+    /// Get mutable build phase references, for targets
+    pub fn build_phases_mut(&mut self) -> &mut PBXVec {
+        self.value.vec_entry("buildPhases")
+    }
+
+    /// Get mutable target dependency references, for targets
+    pub fn dependencies_mut(&mut self) -> &mut PBXVec {
+        self.value.vec_entry("dependencies")
+    }
+
+    /// Get mutable build configuration references, for configuration lists
+    pub fn build_configurations_mut(&mut self) -> &mut PBXVec {
+        self.value.vec_entry("buildConfigurations")
+    }
+}
+
+impl<'a> std::ops::Deref for PBXObjectMut<'a> {
+    type Target = PBXHashMap;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'a> std::ops::DerefMut for PBXObjectMut<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
+impl<'a> Drop for PBXObjectMut<'a> {
+    fn drop(&mut self) {
+        if let Some((old, observers)) = &self.observed {
+            observers.notify_modified(&self.id, old, self.value);
+        }
+    }
+}
+
+impl PBXObjectCollection {
+    /// Get mutable object with the given reference if its kind matches `predicate`
+    pub(crate) fn get_mut_by<F>(&mut self, key: &str, predicate: F) -> Option<PBXObjectMut<'_>>
+    where
+        F: Fn(&PBXObjectKind) -> bool,
+    {
+        let kind = self
+            .map
+            .get(key)?
+            .get_kind("isa")
+            .filter(|k| predicate(k))?
+            .clone();
+        // Path or children edits may move fs references around
+        if kind.is_fs_reference() {
+            self.invalidate_path_index();
+        }
+        let value = self.map.get_mut(key)?;
+        let observed = match self.observers.is_empty() {
+            true => None,
+            false => Some((value.clone(), &self.observers)),
+        };
+        Some(PBXObjectMut {
+            id: key.to_string(),
+            kind,
+            value,
+            observed,
+        })
+    }
+
+    /// Get mutable PBXTarget
+    pub fn get_target_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, PBXObjectKind::is_target)
+    }
+
+    /// Get mutable PBXProject
+    pub fn get_project_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, PBXObjectKind::is_pbx_project)
+    }
+
+    /// Get mutable PBXBuildPhase
+    pub fn get_build_phase_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, PBXObjectKind::is_build_phase)
+    }
+
+    /// Get mutable file reference
+    pub fn get_file_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, |kind| {
+            kind.as_pbxfs_reference().map(|k| k.is_file()) == Some(true)
+        })
+    }
+
+    /// Get mutable group
+    pub fn get_group_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, |kind| {
+            kind.as_pbxfs_reference().map(|k| k.is_group()) == Some(true)
+        })
+    }
+
+    /// Get mutable XCConfigurationList
+    pub fn get_configuration_list_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, PBXObjectKind::is_xc_configuration_list)
+    }
+
+    /// Get mutable XCBuildConfiguration
+    pub fn get_build_configuration_mut(&mut self, key: &str) -> Option<PBXObjectMut<'_>> {
+        self.get_mut_by(key, PBXObjectKind::is_xc_build_configuration)
+    }
+
+    /// Get mutable build configurations of the configuration list with the given reference, or
+    /// of the configuration list of the target or project with the given reference.
+    pub fn get_build_configurations_mut(&mut self, key: &str) -> Vec<PBXObjectMut<'_>> {
+        let object = match self.map.get(key) {
+            Some(object) => object,
+            None => return vec![],
+        };
+        let list = match object.get_string("buildConfigurationList") {
+            Some(list) => self.map.get(list.as_str()),
+            None => Some(object),
+        };
+        let references = list
+            .and_then(|list| list.get_vec("buildConfigurations"))
+            .map(|references| {
+                references
+                    .as_vec_strings()
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let observers = (!self.observers.is_empty()).then_some(&self.observers);
+        let mut configurations = self
+            .map
+            .iter_mut()
+            .filter(|(id, _)| references.contains(id))
+            .filter_map(|(id, value)| {
+                let kind = value.get_kind("isa")?.clone();
+                kind.is_xc_build_configuration().then(|| PBXObjectMut {
+                    id: id.clone(),
+                    kind,
+                    observed: observers.map(|observers| (value.clone(), observers)),
+                    value,
+                })
+            })
+            .collect::<Vec<_>>();
+        configurations.sort_by_key(|c| references.iter().position(|r| r == &c.id));
+        configurations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn edit_typed_objects() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let group = root_object.find_group_by_path("Source").unwrap().id;
+        let objects = root_object.objects_mut();
+
+        objects
+            .get_target_mut(&target)
+            .unwrap()
+            .set_string("productName", Some("Renamed"));
+        assert!(objects.get_group_mut(&target).is_none());
+
+        let configurations = objects.get_build_configurations_mut(&target);
+        assert_eq!(2, configurations.len());
+        for mut configuration in configurations {
+            configuration
+                .build_settings_mut()
+                .insert_value("SWIFT_VERSION", "6.0");
+        }
+
+        objects
+            .get_group_mut(&group)
+            .unwrap()
+            .children_mut()
+            .push("AAAAAAAAAAAAAAAAAAAAAAAA");
+
+        let target = root_object.get_target(&target).unwrap();
+        assert_eq!(Some(&"Renamed".to_string()), target.product_name);
+        for configuration in target
+            .build_configuration_list
+            .unwrap()
+            .build_configurations
+        {
+            assert_eq!(
+                Some(&"6.0".to_string()),
+                configuration.build_settings.get_string("SWIFT_VERSION")
+            );
+        }
+        assert!(root_object
+            .get_object_mut(&group)
+            .unwrap()
+            .vec_entry("children")
+            .as_vec_strings()
+            .contains(&&"AAAAAAAAAAAAAAAAAAAAAAAA".to_string()));
+    }
+}
@@ -0,0 +1,253 @@
+use super::*;
+
+/// Text encoding of a file reference, stored as `fileEncoding`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PBXFileEncoding {
+    /// ASCII, `1`
+    Ascii,
+    /// UTF-8, `4`, Xcode's default
+    Utf8,
+    /// ISO Latin 1, `5`
+    IsoLatin1,
+    /// UTF-16, `10`
+    Utf16,
+    /// Windows Latin 1, `12`
+    WindowsLatin1,
+    /// Mac OS Roman, `30`
+    MacOsRoman,
+    /// UTF-16 big endian, `2415919360`
+    Utf16BigEndian,
+    /// UTF-16 little endian, `2483028224`
+    Utf16LittleEndian,
+    /// Any other encoding, by its raw value
+    Other(isize),
+}
+
+impl From<isize> for PBXFileEncoding {
+    fn from(value: isize) -> Self {
+        match value {
+            1 => Self::Ascii,
+            4 => Self::Utf8,
+            5 => Self::IsoLatin1,
+            10 => Self::Utf16,
+            12 => Self::WindowsLatin1,
+            30 => Self::MacOsRoman,
+            2415919360 => Self::Utf16BigEndian,
+            2483028224 => Self::Utf16LittleEndian,
+            value => Self::Other(value),
+        }
+    }
+}
+
+impl From<PBXFileEncoding> for isize {
+    fn from(encoding: PBXFileEncoding) -> Self {
+        match encoding {
+            PBXFileEncoding::Ascii => 1,
+            PBXFileEncoding::Utf8 => 4,
+            PBXFileEncoding::IsoLatin1 => 5,
+            PBXFileEncoding::Utf16 => 10,
+            PBXFileEncoding::WindowsLatin1 => 12,
+            PBXFileEncoding::MacOsRoman => 30,
+            PBXFileEncoding::Utf16BigEndian => 2415919360,
+            PBXFileEncoding::Utf16LittleEndian => 2483028224,
+            PBXFileEncoding::Other(value) => value,
+        }
+    }
+}
+
+/// Line endings of a file reference, stored as `lineEnding`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PBXLineEnding {
+    /// Unix, `\n`, `0`
+    Lf,
+    /// Classic Mac OS, `\r`, `1`
+    Cr,
+    /// Windows, `\r\n`, `2`
+    CrLf,
+    /// Any other line ending, by its raw value
+    Other(isize),
+}
+
+impl From<isize> for PBXLineEnding {
+    fn from(value: isize) -> Self {
+        match value {
+            0 => Self::Lf,
+            1 => Self::Cr,
+            2 => Self::CrLf,
+            value => Self::Other(value),
+        }
+    }
+}
+
+impl From<PBXLineEnding> for isize {
+    fn from(line_ending: PBXLineEnding) -> Self {
+        match line_ending {
+            PBXLineEnding::Lf => 0,
+            PBXLineEnding::Cr => 1,
+            PBXLineEnding::CrLf => 2,
+            PBXLineEnding::Other(value) => value,
+        }
+    }
+}
+
+/// Per file editor overrides of a file reference or group. `None` falls back to Xcode settings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PBXEditorAttributes {
+    /// Text encoding, `fileEncoding`
+    pub file_encoding: Option<PBXFileEncoding>,
+    /// Line endings, `lineEnding`
+    pub line_ending: Option<PBXLineEnding>,
+    /// Indent using tabs, `usesTabs`
+    pub uses_tabs: Option<bool>,
+    /// Indent width, `indentWidth`
+    pub indent_width: Option<isize>,
+    /// Tab width, `tabWidth`
+    pub tab_width: Option<isize>,
+    /// Wrap lines, `wrapsLines`
+    pub wraps_lines: Option<bool>,
+}
+
+impl<'a> PBXFSReference<'a> {
+    /// Get editor overrides of this fs reference
+    pub fn editor_attributes(&self) -> PBXEditorAttributes {
+        PBXEditorAttributes {
+            file_encoding: self.file_encoding,
+            line_ending: self.line_ending,
+            uses_tabs: self.uses_tabs,
+            indent_width: self.indent_width.copied(),
+            tab_width: self.tab_width.copied(),
+            wraps_lines: self.wraps_lines,
+        }
+    }
+}
+
+impl PBXObjectCollection {
+    /// Set editor overrides of the file reference or group with the given reference, removing
+    /// fields set to `None`.
+    pub fn set_editor_attributes(
+        &mut self,
+        reference: &str,
+        attributes: &PBXEditorAttributes,
+    ) -> Result<()> {
+        let mut object = self
+            .get_mut_by(reference, |kind| {
+                kind.as_pbxfs_reference()
+                    .map(|k| k.is_file() || k.is_group())
+                    == Some(true)
+            })
+            .ok_or_else(|| anyhow::anyhow!("{reference:?} is not a file reference or group"))?;
+        let flag = |value: Option<bool>| value.map(isize::from);
+        for (key, value) in [
+            ("fileEncoding", attributes.file_encoding.map(isize::from)),
+            ("lineEnding", attributes.line_ending.map(isize::from)),
+            ("usesTabs", flag(attributes.uses_tabs)),
+            ("indentWidth", attributes.indent_width),
+            ("tabWidth", attributes.tab_width),
+            ("wrapsLines", flag(attributes.wraps_lines)),
+        ] {
+            match value {
+                Some(value) => object.insert_value(key, value),
+                None => object.remove_value(key),
+            };
+        }
+        Ok(())
+    }
+
+    /// Set whether the file reference with the given reference is indexed, e.g. `false` to
+    /// exclude generated or vendored sources. `None` falls back to Xcode's default.
+    pub fn set_include_in_index(&mut self, reference: &str, include: Option<bool>) -> Result<()> {
+        let mut file = self
+            .get_file_mut(reference)
+            .ok_or_else(|| anyhow::anyhow!("{reference:?} is not a file reference"))?;
+        match include {
+            Some(include) => file.insert_value("includeInIndex", isize::from(include)),
+            None => file.remove_value("includeInIndex"),
+        };
+        Ok(())
+    }
+
+    /// Set language of the file reference with the given reference, e.g. `xcode.lang.swift`.
+    /// `None` falls back to the language derived from the file type.
+    pub fn set_language_specification_identifier(
+        &mut self,
+        reference: &str,
+        identifier: Option<&str>,
+    ) -> Result<()> {
+        self.get_file_mut(reference)
+            .ok_or_else(|| anyhow::anyhow!("{reference:?} is not a file reference"))?
+            .set_string("xcLanguageSpecificationIdentifier", identifier);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn editor_attributes() {
+        let mut root_object = test_demo_file!(demo1);
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        assert_eq!(
+            PBXEditorAttributes::default(),
+            root_object.get_file(&file).unwrap().editor_attributes()
+        );
+
+        let attributes = PBXEditorAttributes {
+            file_encoding: Some(PBXFileEncoding::Utf16LittleEndian),
+            line_ending: Some(PBXLineEnding::CrLf),
+            uses_tabs: Some(true),
+            indent_width: Some(2),
+            tab_width: Some(8),
+            wraps_lines: Some(false),
+        };
+        root_object
+            .objects_mut()
+            .set_editor_attributes(&file, &attributes)
+            .unwrap();
+        assert_eq!(
+            attributes,
+            root_object.get_file(&file).unwrap().editor_attributes()
+        );
+        assert_eq!(
+            Some(&2483028224),
+            root_object.objects()[&file].get_number("fileEncoding")
+        );
+        assert_eq!(PBXFileEncoding::Other(7), PBXFileEncoding::from(7));
+    }
+
+    #[test]
+    fn index_and_language() {
+        let mut root_object = test_demo_file!(demo1);
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        let group = root_object.find_group_by_path("Source").unwrap().id;
+        let objects = root_object.objects_mut();
+        objects.set_include_in_index(&file, Some(false)).unwrap();
+        objects
+            .set_language_specification_identifier(&file, Some("xcode.lang.swift"))
+            .unwrap();
+        assert!(objects.set_include_in_index(&group, Some(false)).is_err());
+
+        let reference = root_object.get_file(&file).unwrap();
+        assert_eq!(Some(false), reference.include_in_index);
+        assert_eq!(
+            Some(&"xcode.lang.swift".to_string()),
+            reference.xc_language_specification_identifier
+        );
+
+        let objects = root_object.objects_mut();
+        objects.set_include_in_index(&file, None).unwrap();
+        objects
+            .set_language_specification_identifier(&file, None)
+            .unwrap();
+        let reference = root_object.get_file(&file).unwrap();
+        assert_eq!(None, reference.include_in_index);
+        assert_eq!(None, reference.xc_language_specification_identifier);
+    }
+}
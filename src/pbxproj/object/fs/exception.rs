@@ -0,0 +1,260 @@
+use crate::pbxproj::*;
+
+/// Files of a synchronized folder built differently by a target,
+/// `PBXFileSystemSynchronizedBuildFileExceptionSet`.
+///
+/// Listed in `exceptions` of a [`PBXFileSystemSynchronizedRootGroup`]. Paths are relative to
+/// the folder, e.g. `Info.plist` for a file Xcode shouldn't copy as a resource.
+#[derive(Debug, derive_new::new)]
+pub struct PBXFileSystemSynchronizedBuildFileExceptionSet<'a> {
+    /// ID Reference
+    pub id: String,
+    /// Reference of the target the exceptions apply to
+    pub target_reference: &'a String,
+    /// Files excluded from the target, or included if the folder isn't a member of the target
+    pub membership_exceptions: Vec<&'a String>,
+    /// Headers made public
+    pub public_headers: Vec<&'a String>,
+    /// Headers made private
+    pub private_headers: Vec<&'a String>,
+    /// Extra compiler flags by file
+    pub additional_compiler_flags_by_relative_path: Option<&'a PBXHashMap>,
+    /// Build file attributes by file, e.g. `(Weak, )`
+    pub attributes_by_relative_path: Option<&'a PBXHashMap>,
+    /// Platforms files are built for by file
+    pub platform_filters_by_relative_path: Option<&'a PBXHashMap>,
+}
+
+impl<'a> AsPBXObject<'a> for PBXFileSystemSynchronizedBuildFileExceptionSet<'a> {
+    fn as_pbx_object(
+        id: String,
+        value: &'a PBXHashMap,
+        _objects: &'a PBXObjectCollection,
+    ) -> Result<Self>
+    where
+        Self: Sized + 'a,
+    {
+        if !value
+            .try_get_kind("isa")?
+            .is_pbx_file_system_synchronized_build_file_exception_set()
+        {
+            anyhow::bail!("isa isn't PBXFileSystemSynchronizedBuildFileExceptionSet");
+        }
+        let strings = |key| {
+            value
+                .get_vec(key)
+                .map(|v| v.as_vec_strings())
+                .unwrap_or_default()
+        };
+
+        Ok(Self {
+            id,
+            target_reference: value.try_get_string("target")?,
+            membership_exceptions: strings("membershipExceptions"),
+            public_headers: strings("publicHeaders"),
+            private_headers: strings("privateHeaders"),
+            additional_compiler_flags_by_relative_path: value
+                .get_object("additionalCompilerFlagsByRelativePath"),
+            attributes_by_relative_path: value.get_object("attributesByRelativePath"),
+            platform_filters_by_relative_path: value.get_object("platformFiltersByRelativePath"),
+        })
+    }
+}
+
+impl ToPBXHashMap for PBXFileSystemSynchronizedBuildFileExceptionSet<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value(
+            "isa",
+            PBXObjectKind::PBXFileSystemSynchronizedBuildFileExceptionSet,
+        );
+        value.insert_value("target", self.target_reference.as_str());
+        for (key, paths) in [
+            ("membershipExceptions", &self.membership_exceptions),
+            ("publicHeaders", &self.public_headers),
+            ("privateHeaders", &self.private_headers),
+        ] {
+            if !paths.is_empty() {
+                value.insert_value(key, paths.iter().map(|p| p.as_str()).collect::<Vec<_>>());
+            }
+        }
+        value.insert_some(
+            "additionalCompilerFlagsByRelativePath",
+            self.additional_compiler_flags_by_relative_path.cloned(),
+        );
+        value.insert_some(
+            "attributesByRelativePath",
+            self.attributes_by_relative_path.cloned(),
+        );
+        value.insert_some(
+            "platformFiltersByRelativePath",
+            self.platform_filters_by_relative_path.cloned(),
+        );
+        value
+    }
+}
+
+impl<'a> PBXFileSystemSynchronizedRootGroup<'a> {
+    /// Get exception set of the folder for the target with the given reference
+    pub fn exceptions_for_target(
+        &self,
+        target: &str,
+    ) -> Option<&PBXFileSystemSynchronizedBuildFileExceptionSet<'a>> {
+        self.build_file_exceptions
+            .iter()
+            .find(|exceptions| exceptions.target_reference == target)
+    }
+}
+
+impl PBXObjectCollection {
+    /// Add `path`, relative to the synchronized folder `group`, to the membership exceptions of
+    /// `target`, creating the folder exception set for the target if missing. Paths are kept
+    /// sorted like Xcode does. Returns the reference of the exception set.
+    pub fn add_membership_exception(
+        &mut self,
+        group: &str,
+        target: &str,
+        path: &str,
+    ) -> Result<String> {
+        let is_target = self
+            .map
+            .get(target)
+            .and_then(|t| t.get_kind("isa"))
+            .map(|k| k.is_target())
+            .unwrap_or_default();
+        if !is_target {
+            anyhow::bail!("{target:?} is not a target");
+        }
+        let exceptions = match self.find_exception_set(group, target)? {
+            Some(exceptions) => exceptions,
+            None => {
+                let mut exceptions = PBXHashMap::default();
+                exceptions.insert_value(
+                    "isa",
+                    PBXObjectKind::PBXFileSystemSynchronizedBuildFileExceptionSet,
+                );
+                exceptions.insert_value("target", target);
+                let reference = String::from(self.insert_object(exceptions, Some(group)));
+                self.get_object_mut(group)
+                    .unwrap()
+                    .vec_entry("exceptions")
+                    .push(reference.as_str());
+                reference
+            }
+        };
+
+        let mut object = self.get_object_mut(&exceptions).unwrap();
+        let paths = object.vec_entry("membershipExceptions");
+        if paths.position(path).is_none() {
+            let index = paths
+                .as_vec_strings()
+                .into_iter()
+                .take_while(|existing| existing.as_str() < path)
+                .count();
+            paths.insert(index, path);
+        }
+        Ok(exceptions)
+    }
+
+    /// Remove `path` from the membership exceptions of `target` in the synchronized folder
+    /// `group`, removing the exception set once it holds no exception. Returns whether `path`
+    /// was an exception.
+    pub fn remove_membership_exception(
+        &mut self,
+        group: &str,
+        target: &str,
+        path: &str,
+    ) -> Result<bool> {
+        let Some(exceptions) = self.find_exception_set(group, target)? else {
+            return Ok(false);
+        };
+        let is_empty = {
+            let mut object = self.get_object_mut(&exceptions).unwrap();
+            let Some(paths) = object.get_vec_mut("membershipExceptions") else {
+                return Ok(false);
+            };
+            let Some(index) = paths.position(path) else {
+                return Ok(false);
+            };
+            paths.0.remove(index);
+            if paths.is_empty() {
+                object.remove_value("membershipExceptions");
+            }
+            object
+                .keys()
+                .all(|key| matches!(key.as_str(), "isa" | "target"))
+        };
+        if is_empty {
+            self.remove_object(&exceptions);
+            let mut group = self.get_object_mut(group).unwrap();
+            if let Some(references) = group.get_vec_mut("exceptions") {
+                references.0.retain(|r| r.as_string() != Some(&exceptions));
+                if references.is_empty() {
+                    group.remove_value("exceptions");
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Get reference of the exception set of synchronized folder `group` for `target`
+    fn find_exception_set(&self, group: &str, target: &str) -> Result<Option<String>> {
+        let group = self
+            .get::<PBXFileSystemSynchronizedRootGroup, _>(group)
+            .ok_or_else(|| anyhow::anyhow!("{group:?} is not a synchronized folder"))?;
+        Ok(group
+            .exceptions_for_target(target)
+            .map(|exceptions| exceptions.id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn membership_exceptions() {
+        let mut root_object = test_demo_file!(demo13);
+        let group = root_object.synchronized_groups()[0].id.clone();
+        let target = root_object.get_target_by_name("Notes").unwrap().id;
+        let folder = root_object.synchronized_groups().remove(0);
+        let exceptions = folder.exceptions_for_target(&target).unwrap();
+        assert_eq!(vec!["Info.plist"], exceptions.membership_exceptions);
+        assert_eq!(
+            &root_object.objects()[&exceptions.id],
+            &exceptions.to_hashmap()
+        );
+        let existing = exceptions.id.clone();
+
+        let objects = root_object.objects_mut();
+        let added = objects
+            .add_membership_exception(&group, &target, "Debug.swift")
+            .unwrap();
+        assert_eq!(existing, added);
+        objects
+            .add_membership_exception(&group, &target, "Debug.swift")
+            .unwrap();
+        assert_eq!(
+            vec!["Debug.swift", "Info.plist"],
+            objects[&existing]
+                .get_vec("membershipExceptions")
+                .unwrap()
+                .as_vec_strings()
+        );
+        assert!(objects
+            .add_membership_exception(&group, &group, "Debug.swift")
+            .is_err());
+
+        assert!(objects
+            .remove_membership_exception(&group, &target, "Debug.swift")
+            .unwrap());
+        assert!(!objects
+            .remove_membership_exception(&group, &target, "Debug.swift")
+            .unwrap());
+        assert!(objects
+            .remove_membership_exception(&group, &target, "Info.plist")
+            .unwrap());
+        assert!(!objects.contains_key(&existing));
+        assert_eq!(None, objects[&group].get_value("exceptions"));
+    }
+}
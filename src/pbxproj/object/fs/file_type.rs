@@ -0,0 +1,153 @@
+use super::*;
+
+/// Field the type of a file reference is stored in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PBXFileTypeField {
+    /// `explicitFileType`, for target products and types picked in Xcode's file inspector
+    Explicit,
+    /// `lastKnownFileType`, for types Xcode detects from the file itself
+    LastKnown,
+}
+
+impl PBXFileTypeField {
+    /// Key of the field in file reference objects
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Explicit => "explicitFileType",
+            Self::LastKnown => "lastKnownFileType",
+        }
+    }
+}
+
+impl<'a> PBXFSReference<'a> {
+    /// Get file type Xcode uses for this file reference: `explicitFileType` when set,
+    /// `lastKnownFileType` otherwise.
+    pub fn file_type(&self) -> Option<&str> {
+        self.explicit_file_type
+            .or(self.last_known_file_type)
+            .map(|file_type| file_type.as_str())
+    }
+
+    /// Get field Xcode stores `file_type` of this file reference in: explicit for target
+    /// products, e.g. `wrapper.application` of `App.app`, and for types other than the one
+    /// detected from the file extension, last known otherwise.
+    pub fn file_type_field(&self, file_type: &str) -> PBXFileTypeField {
+        if self.source_tree == PBXSourceTree::BuildProductsDir
+            || self.detected_file_type().as_deref() != Some(file_type)
+        {
+            PBXFileTypeField::Explicit
+        } else {
+            PBXFileTypeField::LastKnown
+        }
+    }
+
+    /// File type detected from the extension of the file name
+    fn detected_file_type(&self) -> Option<String> {
+        Path::new(self.path.or(self.name)?)
+            .extension()
+            .and_then(|ext| xcode_file_type(ext.to_string_lossy()))
+    }
+}
+
+impl PBXObjectCollection {
+    /// Set type of the file reference with the given reference, e.g. `sourcecode.c.h` to treat
+    /// a `.pch` file as a header. The type is stored in the field Xcode uses for it, see
+    /// [`PBXFSReference::file_type_field`], and the other field is removed. `None` resets the
+    /// type to the one detected from the file extension, if any.
+    pub fn set_file_type(&mut self, reference: &str, file_type: Option<&str>) -> Result<()> {
+        let (field, file_type) = {
+            let file = self
+                .get_file(reference)
+                .ok_or_else(|| anyhow::anyhow!("{reference:?} is not a file reference"))?;
+            match file_type {
+                Some(file_type) => (file.file_type_field(file_type), Some(file_type.to_string())),
+                None => {
+                    let file_type = file.detected_file_type();
+                    let field = match file_type.as_deref() {
+                        Some(file_type) => file.file_type_field(file_type),
+                        None => PBXFileTypeField::LastKnown,
+                    };
+                    (field, file_type)
+                }
+            }
+        };
+
+        let mut file = self
+            .get_file_mut(reference)
+            .ok_or_else(|| anyhow::anyhow!("{reference:?} is not a file reference"))?;
+        for field in [PBXFileTypeField::Explicit, PBXFileTypeField::LastKnown] {
+            file.remove_value(field.key());
+        }
+        file.set_string(field.key(), file_type.as_deref());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn set_file_type() {
+        let mut root_object = test_demo_file!(demo1);
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        let fields = |root_object: &PBXRootObject, reference: &str| {
+            let file = root_object.get_file(reference).unwrap();
+            (
+                file.explicit_file_type.cloned(),
+                file.last_known_file_type.cloned(),
+            )
+        };
+
+        root_object
+            .objects_mut()
+            .set_file_type(&file, Some("text"))
+            .unwrap();
+        assert_eq!((Some("text".into()), None), fields(&root_object, &file));
+        assert_eq!(
+            Some("text"),
+            root_object.get_file(&file).unwrap().file_type()
+        );
+
+        root_object
+            .objects_mut()
+            .set_file_type(&file, None)
+            .unwrap();
+        assert_eq!(
+            (None, Some("sourcecode.swift".into())),
+            fields(&root_object, &file)
+        );
+        root_object
+            .objects_mut()
+            .set_file_type(&file, Some("sourcecode.swift"))
+            .unwrap();
+        assert_eq!(
+            (None, Some("sourcecode.swift".into())),
+            fields(&root_object, &file)
+        );
+
+        // Products keep their type in explicitFileType
+        let product = root_object
+            .targets()
+            .into_iter()
+            .find_map(|target| Some(target.product.as_ref()?.id.clone()))
+            .unwrap();
+        root_object
+            .objects_mut()
+            .set_file_type(&product, None)
+            .unwrap();
+        assert_eq!(
+            (Some("wrapper.application".into()), None),
+            fields(&root_object, &product)
+        );
+
+        let group = root_object.find_group_by_path("Source").unwrap().id;
+        assert!(root_object
+            .objects_mut()
+            .set_file_type(&group, None)
+            .is_err());
+    }
+}
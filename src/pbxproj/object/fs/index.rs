@@ -0,0 +1,221 @@
+use super::*;
+use std::collections::HashMap;
+use std::path::{Component, PathBuf};
+use std::sync::OnceLock;
+
+/// Lazily built index of fs references by normalized full path.
+///
+/// Full paths are relative to the project source root. References under other source trees,
+/// e.g. products, are keyed by `$(BUILT_PRODUCTS_DIR)/Wordle.app`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PBXPathIndex(OnceLock<PathIndex>);
+
+#[derive(Clone, Debug, Default)]
+struct PathIndex {
+    /// References at each full path, in project order
+    paths: HashMap<PathBuf, Vec<String>>,
+    /// Full path of each indexed reference
+    references: HashMap<String, PathBuf>,
+}
+
+impl PathIndex {
+    fn insert(&mut self, reference: &str, path: PathBuf) {
+        self.paths
+            .entry(path.clone())
+            .or_default()
+            .push(reference.to_string());
+        self.references.insert(reference.to_string(), path);
+    }
+}
+
+impl PBXObjectCollection {
+    fn path_index(&self) -> &PathIndex {
+        self.path_index.0.get_or_init(|| {
+            let mut index = PathIndex::default();
+            let main_groups = self
+                .projects()
+                .into_iter()
+                .map(|project| project.main_group.id)
+                .collect::<Vec<_>>();
+            for main_group in main_groups {
+                let path = self
+                    .map
+                    .get(&main_group)
+                    .and_then(|group| group.get_string("path"));
+                let path = normalize_path(Path::new(path.map(|p| p.as_str()).unwrap_or_default()));
+                self.index_children(&mut index, &main_group, &path);
+                index.references.insert(main_group, path);
+            }
+            index
+        })
+    }
+
+    fn index_children(&self, index: &mut PathIndex, group: &str, group_path: &Path) {
+        let children = match self
+            .map
+            .get(group)
+            .and_then(|group| group.get_vec("children"))
+        {
+            Some(children) => children.as_vec_strings(),
+            None => return,
+        };
+        for child in children {
+            let path = match self.map.get(child.as_str()) {
+                Some(object) => match resolve_path(object, group_path) {
+                    Some(path) => path,
+                    None => continue,
+                },
+                None => continue,
+            };
+            // Guard against cycles in malformed projects
+            if index.references.contains_key(child.as_str()) {
+                continue;
+            }
+            index.insert(child, path.clone());
+            self.index_children(index, child, &path);
+        }
+    }
+
+    /// Register `child` of `group` in the path index, if already built.
+    pub(crate) fn index_child(&mut self, group: &str, child: &str) {
+        if let Some(index) = self.path_index.0.get_mut() {
+            let path = index
+                .references
+                .get(group)
+                .zip(self.map.get(child))
+                .and_then(|(group_path, object)| resolve_path(object, group_path));
+            if let Some(path) = path {
+                index.insert(child, path);
+            }
+        }
+    }
+
+    /// Drop the path index, to be rebuilt on next lookup.
+    pub(crate) fn invalidate_path_index(&mut self) {
+        self.path_index.0.take();
+    }
+
+    /// Get references of all fs references at `path`, relative to the project source root.
+    pub fn get_fs_references_by_path<P: AsRef<Path>>(&self, path: P) -> &[String] {
+        self.path_index()
+            .paths
+            .get(&normalize_path(path.as_ref()))
+            .map(|references| references.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get file reference at `path`, relative to the project source root.
+    pub fn find_file_by_path<P: AsRef<Path>>(&self, path: P) -> Option<PBXFSReference<'_>> {
+        self.get_fs_references_by_path(path)
+            .iter()
+            .find_map(|reference| self.get_file(reference))
+    }
+
+    /// Get group at `path`, relative to the project source root.
+    pub fn find_group_by_path<P: AsRef<Path>>(&self, path: P) -> Option<PBXFSReference<'_>> {
+        self.get_fs_references_by_path(path)
+            .iter()
+            .find_map(|reference| self.get_group(reference))
+    }
+
+    /// Get normalized full path of fs reference with the given reference.
+    pub fn get_fs_reference_path(&self, reference: &str) -> Option<&Path> {
+        self.path_index()
+            .references
+            .get(reference)
+            .map(|path| path.as_path())
+    }
+
+    /// Get full paths referenced by more than one fs reference, along with their references.
+    pub fn duplicate_fs_references(&self) -> Vec<(&Path, &[String])> {
+        let mut duplicates = self
+            .path_index()
+            .paths
+            .iter()
+            .filter(|(_, references)| references.len() > 1)
+            .map(|(path, references)| (path.as_path(), references.as_slice()))
+            .collect::<Vec<_>>();
+        duplicates.sort();
+        duplicates
+    }
+}
+
+/// Full path of fs reference `object` whose parent group is at `group_path`
+pub(super) fn resolve_path(object: &PBXHashMap, group_path: &Path) -> Option<PathBuf> {
+    object.get_kind("isa")?.as_pbxfs_reference()?;
+    let path = Path::new(
+        object
+            .get_string("path")
+            .map(|p| p.as_str())
+            .unwrap_or_default(),
+    );
+    let source_tree = object
+        .get_string("sourceTree")
+        .map(|s| PBXSourceTree::from(s.as_str()))
+        .unwrap_or_default();
+
+    let path = match source_tree {
+        PBXSourceTree::Group => group_path.join(path),
+        PBXSourceTree::SourceRoot | PBXSourceTree::Absolute => path.to_path_buf(),
+        PBXSourceTree::None => return None,
+        source_tree => Path::new(&format!("$({source_tree})")).join(path),
+    };
+    Some(normalize_path(&path))
+}
+
+/// Remove `.` components and resolve `..` components lexically
+pub(super) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn find_by_path() {
+        let mut root_object = test_demo_file!(demo1);
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap();
+        assert_eq!(Some(&"GuessView.swift".to_string()), file.path);
+        assert!(root_object
+            .find_file_by_path("./Source/Views/../Views/GuessView.swift")
+            .is_some());
+        assert!(root_object.find_group_by_path("Source/Views").is_some());
+        assert!(root_object.find_file_by_path("Source/Views").is_none());
+        assert!(root_object.duplicate_fs_references().is_empty());
+
+        let reference = root_object
+            .get_or_add_file_reference("Source/Views/Kit.swift")
+            .unwrap();
+        assert_eq!(
+            Some(reference.as_str()),
+            root_object
+                .find_file_by_path("Source/Views/Kit.swift")
+                .map(|file| file.id)
+                .as_deref()
+        );
+
+        let group = root_object.find_group_by_path("Source").unwrap().id;
+        root_object
+            .objects_mut()
+            .add_file_reference(&group, "Views/Kit.swift")
+            .unwrap();
+        let duplicates = root_object.duplicate_fs_references();
+        assert_eq!(1, duplicates.len());
+        assert_eq!(Path::new("Source/Views/Kit.swift"), duplicates[0].0);
+    }
+}
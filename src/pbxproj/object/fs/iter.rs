@@ -0,0 +1,131 @@
+use super::index::{normalize_path, resolve_path};
+use super::*;
+use std::collections::HashSet;
+
+impl PBXRootObject {
+    /// Iterate fs references under the main group depth first, in children order.
+    ///
+    /// Each item is the reference, its navigator path made of each node name, or path when
+    /// unnamed, and its full path relative to the project source root, see
+    /// [`PBXObjectCollection::get_fs_reference_path`]. Objects are read as they are reached,
+    /// without decoding the group tree upfront.
+    pub fn iter_fs_references(&self) -> PBXFSIter<'_> {
+        let main_group = self
+            .objects
+            .map
+            .get(&self.root_object_reference)
+            .and_then(|project| project.get_string("mainGroup"));
+        let mut iter = PBXFSIter {
+            objects: &self.objects,
+            stack: vec![],
+            visited: HashSet::new(),
+        };
+        if let Some(main_group) = main_group {
+            let path = self
+                .objects
+                .map
+                .get(main_group)
+                .and_then(|group| group.get_string("path"))
+                .map(|path| normalize_path(Path::new(path)))
+                .unwrap_or_default();
+            iter.push_children(main_group, PathBuf::new(), path);
+        }
+        iter
+    }
+}
+
+/// Depth-first iterator over fs references, see [`PBXRootObject::iter_fs_references`]
+pub struct PBXFSIter<'a> {
+    objects: &'a PBXObjectCollection,
+    /// Remaining children of each group being iterated, with the group paths
+    stack: Vec<(std::vec::IntoIter<&'a String>, PathBuf, PathBuf)>,
+    /// Groups already iterated, guarding against cycles in malformed projects
+    visited: HashSet<&'a str>,
+}
+
+impl<'a> PBXFSIter<'a> {
+    fn push_children(&mut self, group: &'a str, navigator_path: PathBuf, path: PathBuf) {
+        if !self.visited.insert(group) {
+            return;
+        }
+        if let Some(children) = self
+            .objects
+            .map
+            .get(group)
+            .and_then(|group| group.get_vec("children"))
+        {
+            self.stack
+                .push((children.as_vec_strings().into_iter(), navigator_path, path));
+        }
+    }
+}
+
+impl<'a> Iterator for PBXFSIter<'a> {
+    type Item = (&'a str, PathBuf, PathBuf);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (children, group_navigator_path, group_path) = self.stack.last_mut()?;
+            let child = match children.next() {
+                Some(child) => child,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            let object = match self.objects.map.get(child) {
+                Some(object) => object,
+                None => continue,
+            };
+            let path = match resolve_path(object, group_path) {
+                Some(path) => path,
+                None => continue,
+            };
+            let navigator_path = match object
+                .get_string("name")
+                .or_else(|| object.get_string("path"))
+            {
+                Some(name) => group_navigator_path.join(name),
+                None => group_navigator_path.clone(),
+            };
+            self.push_children(child, navigator_path.clone(), path.clone());
+            return Some((child.as_str(), navigator_path, path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::path::Path;
+
+    #[test]
+    fn iter_fs_references() {
+        let root_object = test_demo_file!(demo1);
+        let items = root_object.iter_fs_references().collect::<Vec<_>>();
+        assert_eq!(
+            root_object.groups().len() - 1 + root_object.files().len(),
+            items.len()
+        );
+        for (reference, _, path) in items.iter() {
+            assert_eq!(
+                Some(path.as_path()),
+                root_object.get_fs_reference_path(reference)
+            );
+        }
+
+        let (_, navigator_path, path) = items
+            .iter()
+            .find(|(_, _, path)| path.ends_with("GuessView.swift"))
+            .unwrap();
+        assert_eq!(Path::new("Source/Views/GuessView.swift"), path);
+        assert!(navigator_path.ends_with("GuessView.swift"));
+
+        let (_, navigator_path, path) = items
+            .iter()
+            .find(|(_, navigator_path, _)| navigator_path.ends_with("Wordle.app"))
+            .unwrap();
+        assert_eq!(Path::new("Products/Wordle.app"), navigator_path);
+        assert_eq!(Path::new("$(BUILT_PRODUCTS_DIR)/Wordle.app"), path);
+    }
+}
@@ -1,14 +1,28 @@
+mod editor;
+mod exception;
+mod file_type;
+mod index;
+mod iter;
 mod kind;
 mod source_tree;
+mod synchronized;
+mod walk;
 use super::*;
-use crate::pbxproj::PBXHashMap;
+use crate::pbxproj::{PBXHashMap, PBXRootObject};
 use anyhow::Result;
+use std::path::{Path, PathBuf};
 
+pub use editor::*;
+pub use exception::*;
+pub use file_type::*;
+pub(crate) use index::PBXPathIndex;
+pub use iter::PBXFSIter;
 pub use kind::*;
 pub use source_tree::*;
+pub use synchronized::*;
+pub use walk::*;
 
-// mod full_path;
-// use crate::xcode::xcode_file_type;
+use crate::xcode::xcode_file_type;
 
 /// Abstraction over `PBXFileReference`, `PBXGroup`, `PBXVariantGroup`, and `XCVersionGroup`
 #[derive(Debug, Default)]
@@ -34,13 +48,13 @@ pub struct PBXFSReference<'a> {
     /// Element Kind.
     pub kind: PBXFSReferenceKind,
     /// Text encoding of file content (only relevant to PBXFileReference)
-    pub file_encoding: Option<&'a isize>,
+    pub file_encoding: Option<PBXFileEncoding>,
     /// User-specified file type. use `last_known_file_type` instead. (only relevant to PBXFileReference)
     pub explicit_file_type: Option<&'a String>,
     /// Derived file type. For a file named "foo.swift" this value would be "sourcecode.swift" (only relevant to PBXFileReference)
     pub last_known_file_type: Option<&'a String>,
     /// Line ending type for the file (only relevant to PBXFileReference)
-    pub line_ending: Option<&'a isize>,
+    pub line_ending: Option<PBXLineEnding>,
     /// Legacy programming language identifier (only relevant to PBXFileReference)
     pub language_specification_identifier: Option<&'a String>,
     /// Programming language identifier (only relevant to PBXFileReference)
@@ -55,6 +69,11 @@ pub struct PBXFSReference<'a> {
     pub parent: Option<Box<Self>>,
     /// Group children (only relevant to PBX*Group!!)
     pub children: Vec<Self>,
+    /// References of all group children, including objects other than fs references, e.g.
+    /// `PBXFileSystemSynchronizedRootGroup` (only relevant to PBX*Group)
+    pub children_references: Vec<&'a String>,
+    /// Synchronized folders among group children (only relevant to PBX*Group)
+    pub synchronized_groups: Vec<PBXFileSystemSynchronizedRootGroup<'a>>,
 }
 
 impl<'a> Eq for PBXFSReference<'a> {}
@@ -115,24 +134,91 @@ impl<'a> AsPBXObject<'a> for PBXFSReference<'a> {
             wraps_lines: value.get_number("wrapsLines").map(|v| v == &1),
             current_version_reference: value.get_string("currentVersion"),
             parent: None,
-            file_encoding: value.get_number("fileEncoding"),
+            file_encoding: value.get_number("fileEncoding").map(|v| (*v).into()),
             explicit_file_type: value.get_string("explicitFileType"),
             last_known_file_type: value.get_string("lastKnownFileType"),
-            line_ending: value.get_number("lineEnding"),
+            line_ending: value.get_number("lineEnding").map(|v| (*v).into()),
             language_specification_identifier: value.get_string("languageSpecificationIdentifier"),
             xc_language_specification_identifier: value
                 .get_string("xcLanguageSpecificationIdentifier"),
             plist_structure_definition_identifier: value
-                .get_string("xcLanguageSpecificationIdentifier"),
-            version_group_type: value.get_string("versioGroupType"),
+                .get_string("plistStructureDefinitionIdentifier"),
+            version_group_type: value.get_string("versionGroupType"),
             children: value
                 .get_vec("children")
                 .map(|v| objects.get_vec(v.as_vec_strings()))
                 .unwrap_or_default(),
+            children_references: value
+                .get_vec("children")
+                .map(|v| v.as_vec_strings())
+                .unwrap_or_default(),
+            synchronized_groups: value
+                .get_vec("children")
+                .map(|v| objects.get_vec(v.as_vec_strings()))
+                .unwrap_or_default(),
         })
     }
 }
 
+impl ToPBXHashMap for PBXFSReference<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXFSReference(self.kind.clone()));
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some("path", self.path.map(String::as_str));
+        if self.source_tree != PBXSourceTree::None {
+            value.insert_value("sourceTree", self.source_tree.to_string());
+        }
+        value.insert_some("includeInIndex", self.include_in_index.map(isize::from));
+        value.insert_some("usesTabs", self.uses_tabs.map(isize::from));
+        value.insert_some("indentWidth", self.indent_width.copied());
+        value.insert_some("tabWidth", self.tab_width.copied());
+        value.insert_some("wrapsLines", self.wraps_lines.map(isize::from));
+        value.insert_some(
+            "currentVersion",
+            self.current_version_reference.map(String::as_str),
+        );
+        value.insert_some("fileEncoding", self.file_encoding.map(isize::from));
+        value.insert_some(
+            "explicitFileType",
+            self.explicit_file_type.map(String::as_str),
+        );
+        value.insert_some(
+            "lastKnownFileType",
+            self.last_known_file_type.map(String::as_str),
+        );
+        value.insert_some("lineEnding", self.line_ending.map(isize::from));
+        value.insert_some(
+            "languageSpecificationIdentifier",
+            self.language_specification_identifier.map(String::as_str),
+        );
+        value.insert_some(
+            "xcLanguageSpecificationIdentifier",
+            self.xc_language_specification_identifier
+                .map(String::as_str),
+        );
+        value.insert_some(
+            "plistStructureDefinitionIdentifier",
+            self.plist_structure_definition_identifier
+                .map(String::as_str),
+        );
+        value.insert_some(
+            "versionGroupType",
+            self.version_group_type.map(String::as_str),
+        );
+        if self.kind.is_group() {
+            value.insert_value(
+                "children",
+                self.children_references
+                    .iter()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        value
+    }
+}
+
 impl<'a> PBXFSReference<'a> {
     /// Check whether this fs reference is group
     pub fn is_group(&self) -> bool {
@@ -159,232 +245,359 @@ impl<'a> PBXFSReference<'a> {
         self.kind.is_file()
     }
 
-    /*
-    /// Get group from children with given name
+    /// Check whether this fs reference is a folder reference, shown as a blue folder in Xcode.
     ///
-    /// NOTE: This will return None if self is file
-    pub fn get_subgroup(&self, name: &str) -> Option<Rc<RefCell<PBXFSReference>>> {
-        if self.is_file() {
-            return None;
+    /// Unlike groups, folder references mirror a directory on disk and are copied as a whole
+    /// into the product when added to the Resources phase.
+    pub fn is_folder_reference(&self) -> bool {
+        self.is_file() && self.file_type() == Some(FOLDER_FILE_TYPE)
+    }
+
+    /// Get child group with the given path or name, `None` if self is a file
+    pub fn get_subgroup(&self, name: &str) -> Option<&Self> {
+        self.children
+            .iter()
+            .filter(|child| child.is_group())
+            .find(|child| child.path.or(child.name).map(|n| n.as_str()) == Some(name))
+    }
+
+    /// Get child file with the given name or path, `None` if self is a file
+    pub fn get_file(&self, name: &str) -> Option<&Self> {
+        self.children
+            .iter()
+            .filter(|child| child.is_file())
+            .find(|child| child.name.or(child.path).map(|n| n.as_str()) == Some(name))
+    }
+}
+
+impl PBXRootObject {
+    /// Get group at `path` relative to the main group, creating any missing groups.
+    /// Returns the reference of the group.
+    pub fn get_or_add_group<P: AsRef<Path>>(&mut self, path: P) -> Result<String> {
+        let mut group = self.root_group().id;
+        for component in path.as_ref().iter() {
+            let component = component.to_string_lossy();
+            group = match self.objects.get_child_by_name_or_path(&group, &component) {
+                Some(child) => child,
+                None => self.objects.add_group(&group, component)?,
+            };
+        }
+        Ok(group)
+    }
+
+    /// Get file reference at `path` relative to the main group, creating it and any missing
+    /// groups. Returns the reference of the file reference.
+    pub fn get_or_add_file_reference<P: AsRef<Path>>(&mut self, path: P) -> Result<String> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("{path:?} has no file name"))?
+            .to_string_lossy();
+        let group = self.get_or_add_group(path.parent().unwrap_or_else(|| Path::new("")))?;
+
+        match self.objects.get_child_by_name_or_path(&group, &file_name) {
+            Some(file) => Ok(file),
+            None => self.objects.add_file_reference(&group, file_name),
         }
+    }
+}
 
-        self.children()
+impl PBXObjectCollection {
+    /// Get reference of a `group` child with the given name or path
+    pub fn get_child_by_name_or_path(&self, group: &str, name_or_path: &str) -> Option<String> {
+        self.map
+            .get(group)?
+            .get_vec("children")?
+            .as_vec_strings()
             .into_iter()
-            .filter(|v| v.borrow().is_group())
-            .find(|v| {
-                let group = v.borrow();
-                if let Some(group_path) = group.path() {
-                    group_path.eq(name)
-                } else if let Some(group_name) = group.name() {
-                    group_name.eq(name)
-                } else {
-                    false
-                }
+            .find(|key| {
+                self.map
+                    .get(key.as_str())
+                    .map(|child| {
+                        child.get_string("path").map(|p| p.as_str()) == Some(name_or_path)
+                            || child.get_string("name").map(|n| n.as_str()) == Some(name_or_path)
+                    })
+                    .unwrap_or_default()
             })
+            .cloned()
     }
-    */
 
-    /*
-    /// Get File from the group
-    ///
-    /// NOTE: This will return None if self is file
-    pub fn get_file<S: AsRef<str>>(&self, name: S) -> Option<Rc<RefCell<PBXFSReference>>> {
-        let name = name.as_ref();
-        self.children().into_iter().find(|o| {
-            if !o.borrow().is_file() {
-                return false;
-            }
-            let file = o.borrow();
-
-            if let Some(n) = file.name() {
-                n == name
-            } else if let Some(p) = file.path() {
-                p == name
-            } else {
-                false
-            }
-        })
+    /// Get absolute path of the fs reference with the given reference, with `source_root` being
+    /// the directory holding the xcodeproj bundle. `None` for references relative to source
+    /// trees other than the project, e.g. built products.
+    pub fn get_full_path<P: AsRef<Path>>(
+        &self,
+        reference: &str,
+        source_root: P,
+    ) -> Option<PathBuf> {
+        let path = self.get_fs_reference_path(reference)?;
+        if path.to_string_lossy().starts_with("$(") {
+            return None;
+        }
+        Some(source_root.as_ref().join(path))
     }
-    */
 
-    /*
-    /// Add file to a group
+    /// Add file at absolute `file_path` to `group`, relative to `source_tree`, `<group>` by
+    /// default. `source_root` is the directory holding the xcodeproj bundle.
     ///
-    /// NOTE: This will return None if self is file
+    /// Files already referenced at the same path aren't duplicated: the existing reference is
+    /// returned, and registered in `group` children only if it isn't in any group yet. Returns
+    /// the reference of the file reference.
     pub fn add_file<P: AsRef<Path>>(
         &mut self,
+        group: &str,
         file_path: P,
         source_root: P,
         source_tree: Option<PBXSourceTree>,
-    ) -> Result<Rc<RefCell<PBXFSReference>>> {
+    ) -> Result<String> {
         let (file_path, source_root) = (file_path.as_ref(), source_root.as_ref());
+        let group_path = self
+            .get_full_path(group, source_root)
+            .ok_or_else(|| anyhow::anyhow!("{group:?} is not a group in the project tree"))?;
+
+        if let Ok(relative) = file_path.strip_prefix(source_root) {
+            if let Some(existing) = self.find_file_by_path(relative).map(|f| f.id) {
+                let orphan = !self
+                    .groups()
+                    .iter()
+                    .any(|g| g.children.iter().any(|child| child.id == existing));
+                if orphan {
+                    self.register_child(group, &existing)?;
+                }
+                return Ok(existing);
+            }
+        }
 
-        // if !file_path.exists() {
-        //     bail!("Trying to add non-existing file {file_path:?}")
-        // }
-
-        let group_path = self.full_path(source_root)?;
-        let objects = self
-            .objects
-            .upgrade()
-            .ok_or_else(|| anyhow::anyhow!("objects already released!"))?;
-        let mut objects = objects.borrow_mut();
-
-        // TODO(fs): ensure we are not adding a duplication
-        //
-        // NOTE: This function error because self is already borrowed mutably
-        //
-        // if let Some((file_reference, existing_file)) =
-        //     objects.files().into_iter().find(|(_, file_reference)| {
-        //         let existing_file_ref = file_reference.borrow();
-        //         let existing_file_path = if let Some(path) = existing_file_ref.path() {
-        //             PathBuf::from(path)
-        //         } else {
-        //             return file_path
-        //                 == existing_file_ref
-        //                     .full_path(&source_root)
-        //                     .unwrap_or_default();
-        //         };
-        //         if existing_file_path.components().last() == file_path.components().last() {
-        //             file_path == existing_file_ref.full_path(source_root).unwrap_or_default()
-        //         } else {
-        //             false
-        //         }
-        //     })
-        // {
-        //     if !self
-        //         .children_references
-        //         .as_ref()
-        //         .map(|r| r.contains(&file_reference))
-        //         .unwrap_or_default()
-        //     {
-        //         // TODO: file exists but doesn't exists in self.
-        //     }
-        //     return Ok(existing_file);
-        // }
-
-        let source_tree = source_tree.unwrap_or_else(|| PBXSourceTree::Group);
-        let path: Option<PathBuf> = match &source_tree {
-            PBXSourceTree::Group => Some(file_path.strip_prefix(group_path)?.to_path_buf()),
-            PBXSourceTree::SourceRoot => Some(file_path.strip_prefix(source_root)?.to_path_buf()),
+        let source_tree = source_tree.unwrap_or(PBXSourceTree::Group);
+        let path = match &source_tree {
+            PBXSourceTree::Group => file_path.strip_prefix(&group_path)?,
+            PBXSourceTree::SourceRoot => file_path.strip_prefix(source_root)?,
             PBXSourceTree::Absolute | PBXSourceTree::SdkRoot | PBXSourceTree::DeveloperDir => {
-                Some(file_path.to_path_buf())
+                file_path
             }
-            _ => None,
+            source_tree => anyhow::bail!("Adding files relative to {source_tree} is not supported"),
         };
 
-        let mut file_reference = PBXFSReference::default();
-        file_reference.set_source_tree(source_tree);
-        file_reference.set_name(
-            file_path
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string()),
-        );
-
-        if let Some(path) = path {
-            let path = path.to_string_lossy().to_string().into();
-            file_reference.set_path(path);
+        let mut file = file_reference(&path.to_string_lossy(), source_tree);
+        if path.components().count() > 1 {
+            if let Some(name) = file_path.file_name() {
+                file.insert_value("name", name.to_string_lossy().as_ref());
+            }
         }
+        self.add_child(group, file)
+    }
 
-        let file_extension = file_path.extension().unwrap_or_default().to_string_lossy();
-        let file_extension = xcode_file_type(file_extension);
+    /// Add a new group with the given path as a child of `parent` group.
+    /// Returns the reference of the created group.
+    pub fn add_group<S: AsRef<str>>(&mut self, parent: &str, path: S) -> Result<String> {
+        let mut group = PBXHashMap::default();
+        group.insert_value("isa", PBXObjectKind::from("PBXGroup"));
+        group.insert_value("children", Vec::<String>::new());
+        group.insert_value("path", path.as_ref());
+        group.insert_value("sourceTree", PBXSourceTree::Group.to_string());
 
-        file_reference.set_explicit_file_type(file_extension.clone());
-        file_reference.set_last_known_file_type(file_extension);
-        file_reference.set_kind(PBXFSReferenceKind::File);
+        self.add_child(parent, group)
+    }
 
-        let file_reference = Rc::new(RefCell::new(file_reference));
-        let reference = objects.push(file_reference.clone());
+    /// Add a new file reference with the given path as a child of `group`.
+    /// Returns the reference of the created file reference.
+    pub fn add_file_reference<S: AsRef<str>>(&mut self, group: &str, path: S) -> Result<String> {
+        self.add_child(group, file_reference(path.as_ref(), PBXSourceTree::Group))
+    }
 
-        let children_references = self.children_references.get_or_insert(Default::default());
+    /// Add a new folder reference (blue folder) to the directory at `path` as a child of
+    /// `group`. Returns the reference of the created file reference.
+    pub fn add_folder_reference<S: AsRef<str>>(&mut self, group: &str, path: S) -> Result<String> {
+        let mut folder = file_reference(path.as_ref(), PBXSourceTree::Group);
+        folder.insert_value("lastKnownFileType", FOLDER_FILE_TYPE);
+        self.add_child(group, folder)
+    }
+
+    /// Add a new Swift playground reference to the `.playground` bundle at `path` as a child of
+    /// `group`. Playgrounds run in Xcode only and are never added to build phases. Returns the
+    /// reference of the created file reference.
+    pub fn add_playground<S: AsRef<str>>(&mut self, group: &str, path: S) -> Result<String> {
+        let mut playground = file_reference(path.as_ref(), PBXSourceTree::Group);
+        playground.insert_value("lastKnownFileType", PLAYGROUND_FILE_TYPE);
+        playground.insert_value("xcLanguageSpecificationIdentifier", "xcode.lang.swift");
+        self.add_child(group, playground)
+    }
 
-        if !children_references.contains(&reference) {
-            children_references.insert(reference.clone());
+    /// Add file or folder reference `file` to the Resources phase of the target with the given
+    /// reference, creating the phase if missing. Returns the reference of the build file.
+    pub fn add_resource(&mut self, target: &str, file: &str) -> Result<String> {
+        if !self
+            .get(file)
+            .map(|f: PBXFSReference| f.is_file())
+            .unwrap_or_default()
+        {
+            anyhow::bail!("{file:?} is not a file reference");
+        }
+        let phase = match self.get_target_build_phase(target, &PBXBuildPhaseKind::Resources) {
+            Some(phase) => phase,
+            None => self.add_build_phase(target, PBXBuildPhaseKind::Resources)?,
         };
+        self.add_build_file(&phase, file)
+    }
+
+    /// Insert a new file reference with the given path, without registering it in any group.
+    pub fn insert_file_reference<S: AsRef<str>>(
+        &mut self,
+        path: S,
+        source_tree: PBXSourceTree,
+    ) -> PBXObjectReference {
+        self.insert_object(file_reference(path.as_ref(), source_tree), None)
+    }
 
-        let mut build_file = PBXBuildFile::default();
-        build_file.set_file_reference(reference.into());
-        let _reference = objects.push(file_reference.clone());
+    /// Insert `child` object and register it in `group` children.
+    pub(crate) fn add_child(&mut self, group: &str, child: PBXHashMap) -> Result<String> {
+        let is_group = self
+            .map
+            .get(group)
+            .and_then(|g| g.get_kind("isa"))
+            .and_then(|k| k.as_pbxfs_reference())
+            .map(|k| k.is_group())
+            .unwrap_or_default();
+        if !is_group {
+            anyhow::bail!("{group:?} is not a group");
+        }
 
-        Ok(file_reference)
+        let reference = String::from(self.insert_object(child, Some(group)));
+        self.register_child(group, &reference)?;
+        Ok(reference)
+    }
+
+    /// Register existing `child` in `group` children.
+    pub(crate) fn register_child(&mut self, group: &str, child: &str) -> Result<()> {
+        self.get_group_mut(group)
+            .ok_or_else(|| anyhow::anyhow!("{group:?} is not a group"))?
+            .children_mut()
+            .push(child);
+        self.index_child(group, child);
+        Ok(())
     }
-    */
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use std::path::PathBuf;
-
-//     #[test]
-//     fn get_parent() {
-//         use crate::pbxproj::test_demo_file;
-//         let project = test_demo_file!(demo1);
-//         let main_group = project
-//             .objects()
-//             .projects()
-//             .first()
-//             .unwrap()
-//             .1
-//             .borrow()
-//             .main_group();
-
-//         let main_group = main_group.borrow();
-//         let source_group = main_group.get_subgroup("Source").unwrap();
-//         let source_group = source_group.borrow();
-//         let parent = source_group.parent();
-
-//         assert_eq!(
-//             parent.unwrap().borrow().children_references(),
-//             main_group.children_references()
-//         )
-//     }
-
-//     #[test]
-//     fn get_file() {
-//         use crate::pbxproj::test_demo_file;
-//         let project = test_demo_file!(demo1);
-//         let source_group = project
-//             .objects()
-//             .get_group_by_name_or_path("Source")
-//             .unwrap()
-//             .1;
-//         let source_group = source_group.borrow();
-//         let file = source_group.get_file("Log.swift");
-//         assert!(file.is_some())
-//     }
-
-//     #[test]
-//     fn add_file_full_path() {
-//         use crate::pbxproj::test_demo_file;
-//         let root = PathBuf::from("/path/to/project");
-//         let project = test_demo_file!(demo1);
-//         let source_group = project
-//             .objects()
-//             .get_group_by_name_or_path("Source")
-//             .unwrap()
-//             .1;
-//         let mut source_group = source_group.borrow_mut();
-//         let file = source_group
-//             .add_file(
-//                 root.join("Source").join("MyFile.swift").as_path(),
-//                 root.as_path(),
-//                 None,
-//             )
-//             .unwrap();
-
-//         assert_eq!(file.borrow().name(), Some(&String::from("MyFile.swift")));
-//         assert_eq!(file.borrow().path(), Some(&String::from("MyFile.swift")));
-
-//         drop(file);
-//         drop(source_group);
-
-//         let file = project.objects().files().into_iter().find(|(_, o)| {
-//             o.borrow()
-//                 .path()
-//                 .map(|n| n == "MyFile.swift")
-//                 .unwrap_or_default()
-//         });
-
-//         assert!(file.is_some());
-//     }
-// }
+/// File type of folder references
+const FOLDER_FILE_TYPE: &str = "folder";
+/// File type of Swift playground bundles
+pub(crate) const PLAYGROUND_FILE_TYPE: &str = "file.playground";
+
+/// File reference object with the given path, typed by its extension
+fn file_reference(path: &str, source_tree: PBXSourceTree) -> PBXHashMap {
+    let mut file = PBXHashMap::default();
+    file.insert_value("isa", PBXObjectKind::from("PBXFileReference"));
+    if let Some(file_type) = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| xcode_file_type(ext.to_string_lossy()))
+    {
+        file.insert_value("lastKnownFileType", file_type);
+    }
+    file.insert_value("path", path);
+    file.insert_value("sourceTree", source_tree.to_string());
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::path::Path;
+
+    #[test]
+    fn add_file() {
+        let mut root_object = test_demo_file!(demo1);
+        let root = Path::new("/tmp/Wordle");
+        let views = root_object.find_group_by_path("Source/Views").unwrap().id;
+        let existing = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        let objects = root_object.objects_mut();
+
+        let file = objects
+            .add_file(
+                &views,
+                &root.join("Source/Views/GuessView.swift"),
+                &root.into(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(existing, file);
+
+        let file = objects
+            .add_file(
+                &views,
+                &root.join("Source/Views/Kit/New.swift"),
+                &root.into(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            Some(root.join("Source/Views/Kit/New.swift")),
+            objects.get_full_path(&file, root)
+        );
+        assert_eq!(
+            file,
+            objects
+                .add_file(
+                    &views,
+                    &root.join("Source/Views/Kit/New.swift"),
+                    &root.into(),
+                    None
+                )
+                .unwrap()
+        );
+
+        let group = objects.get_group(&views).unwrap();
+        assert!(group.get_file("New.swift").is_some());
+        assert!(group.get_subgroup("GuessView.swift").is_none());
+        assert!(root_object.root_group().get_subgroup("Source").is_some());
+    }
+
+    #[test]
+    fn add_folder_reference() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let group = root_object.root_group().id;
+        let objects = root_object.objects_mut();
+
+        let folder = objects
+            .add_folder_reference(&group, "Assets/Levels")
+            .unwrap();
+        assert!(objects.get_file(&folder).unwrap().is_folder_reference());
+        assert!(!objects
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .is_folder_reference());
+
+        let build_file = objects.add_resource(&target, &folder).unwrap();
+        let phase = objects
+            .get_target_build_phase(&target, &PBXBuildPhaseKind::Resources)
+            .unwrap();
+        assert!(objects
+            .get_build_phase(&phase)
+            .unwrap()
+            .files
+            .iter()
+            .any(|file| file.id == build_file));
+        assert!(objects.add_resource(&target, &group).is_err());
+    }
+
+    #[test]
+    fn add_playground() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let group = root_object.root_group().id;
+        let objects = root_object.objects_mut();
+
+        let playground = objects
+            .add_playground(&group, "Explore.playground")
+            .unwrap();
+        let file = objects.get_file(&playground).unwrap();
+        assert_eq!(
+            Some("file.playground"),
+            file.last_known_file_type.map(|t| t.as_str())
+        );
+        assert!(objects.add_resource(&target, &playground).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 /// Helper Specifying source trees for files
 ///
 /// Corresponds to the "Location" dropdown in Xcode's File Inspector
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Default)]
 pub enum PBXSourceTree {
     /// No source tree
+    #[default]
     None,
     /// Absolute source tree
     Absolute,
@@ -21,15 +22,9 @@ pub enum PBXSourceTree {
     Custom(String),
 }
 
-impl Default for PBXSourceTree {
-    fn default() -> Self {
-        PBXSourceTree::None
-    }
-}
-
-impl ToString for PBXSourceTree {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for PBXSourceTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
             Self::None => "",
             Self::Absolute => "<absolute>",
             Self::Group => "<group>",
@@ -38,8 +33,8 @@ impl ToString for PBXSourceTree {
             Self::SdkRoot => "SDKROOT",
             Self::DeveloperDir => "DEVELOPER_DIR",
             Self::Custom(s) => s,
-        }
-        .into()
+        };
+        write!(f, "{value}")
     }
 }
 impl From<String> for PBXSourceTree {
@@ -0,0 +1,132 @@
+use crate::pbxproj::*;
+
+/// Folder whose content Xcode keeps in sync with the file system,
+/// `PBXFileSystemSynchronizedRootGroup`.
+///
+/// Introduced by Xcode 16 in place of groups listing every file: files found in the folder are
+/// members of the targets listing it in `fileSystemSynchronizedGroups`, unless excluded through
+/// its exception sets.
+#[derive(Debug, derive_new::new)]
+pub struct PBXFileSystemSynchronizedRootGroup<'a> {
+    /// ID Reference
+    pub id: String,
+    /// Folder name, if different from its path
+    pub name: Option<&'a String>,
+    /// Folder path, relative to the source tree
+    pub path: Option<&'a String>,
+    /// Folder source tree
+    pub source_tree: PBXSourceTree,
+    /// References of the folder exception sets
+    pub exception_references: Vec<&'a String>,
+    /// Exception sets of the folder by target, see
+    /// [`PBXFileSystemSynchronizedRootGroup::exceptions_for_target`]
+    pub build_file_exceptions: Vec<PBXFileSystemSynchronizedBuildFileExceptionSet<'a>>,
+    /// File types overriding the ones derived from extensions, by path relative to the folder
+    pub explicit_file_types: Option<&'a PBXHashMap>,
+    /// Paths of sub folders, relative to the folder, handled as a whole, e.g. bundles
+    pub explicit_folders: Option<&'a PBXVec>,
+}
+
+impl<'a> AsPBXObject<'a> for PBXFileSystemSynchronizedRootGroup<'a> {
+    fn as_pbx_object(
+        id: String,
+        value: &'a PBXHashMap,
+        objects: &'a PBXObjectCollection,
+    ) -> Result<Self>
+    where
+        Self: Sized + 'a,
+    {
+        if !value
+            .try_get_kind("isa")?
+            .is_pbx_file_system_synchronized_root_group()
+        {
+            anyhow::bail!("isa isn't PBXFileSystemSynchronizedRootGroup");
+        }
+
+        Ok(Self {
+            id,
+            name: value.get_string("name"),
+            path: value.get_string("path"),
+            source_tree: value
+                .get_string("sourceTree")
+                .map(|s| s.as_str().into())
+                .unwrap_or_default(),
+            exception_references: value
+                .get_vec("exceptions")
+                .map(|v| v.as_vec_strings())
+                .unwrap_or_default(),
+            build_file_exceptions: value
+                .get_vec("exceptions")
+                .map(|v| objects.get_vec(v.as_vec_strings()))
+                .unwrap_or_default(),
+            explicit_file_types: value.get_object("explicitFileTypes"),
+            explicit_folders: value.get_vec("explicitFolders"),
+        })
+    }
+}
+
+impl ToPBXHashMap for PBXFileSystemSynchronizedRootGroup<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXFileSystemSynchronizedRootGroup);
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some("path", self.path.map(String::as_str));
+        if self.source_tree != PBXSourceTree::None {
+            value.insert_value("sourceTree", self.source_tree.to_string());
+        }
+        if !self.exception_references.is_empty() {
+            value.insert_value(
+                "exceptions",
+                self.exception_references
+                    .iter()
+                    .map(|e| e.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        value.insert_some("explicitFileTypes", self.explicit_file_types.cloned());
+        value.insert_some("explicitFolders", self.explicit_folders.cloned());
+        value
+    }
+}
+
+impl PBXObjectCollection {
+    /// Get all synchronized folders, see [`PBXFileSystemSynchronizedRootGroup`]
+    pub fn synchronized_groups(&self) -> Vec<PBXFileSystemSynchronizedRootGroup<'_>> {
+        self.get_vec_by(|(_, v)| {
+            v.get_kind("isa")
+                .map(|k| k.is_pbx_file_system_synchronized_root_group())
+                .unwrap_or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synchronized_groups() {
+        let root_object = test_demo_file!(demo13);
+        let groups = root_object.synchronized_groups();
+        assert_eq!(1, groups.len());
+        let group = &groups[0];
+        assert_eq!(Some("Notes"), group.path.map(String::as_str));
+        assert_eq!(PBXSourceTree::Group, group.source_tree);
+        assert_eq!(1, group.exception_references.len());
+        assert_eq!(&root_object.objects()[&group.id], &group.to_hashmap());
+
+        let target = root_object.get_target_by_name("Notes").unwrap();
+        assert_eq!(
+            vec![group.id.as_str()],
+            target
+                .file_system_synchronized_groups
+                .iter()
+                .map(|g| g.id.as_str())
+                .collect::<Vec<_>>()
+        );
+        let main_group = root_object.root_group();
+        assert_eq!(1, main_group.synchronized_groups.len());
+        assert_eq!(2, main_group.children_references.len());
+        assert_eq!(1, main_group.children.len());
+    }
+}
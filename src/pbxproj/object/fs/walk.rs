@@ -0,0 +1,229 @@
+use super::*;
+
+/// What [`PBXFSReference::walk`] does after visiting a node
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PBXWalk {
+    /// Visit the node children, then its next sibling
+    #[default]
+    Continue,
+    /// Don't visit the node children
+    SkipChildren,
+    /// Stop walking
+    Stop,
+}
+
+impl From<()> for PBXWalk {
+    fn from(_: ()) -> Self {
+        Self::Continue
+    }
+}
+
+impl<'a> PBXFSReference<'a> {
+    /// Visit the reference and its descendants depth first, in children order.
+    ///
+    /// `visit` gets each node, its depth, the reference being at depth 0, and its path from the
+    /// reference made of each node name, or path when unnamed, as shown in Xcode navigator.
+    /// `visit` may return `()` to visit every node, or a [`PBXWalk`] to skip children or stop.
+    ///
+    /// ```
+    /// # use xcodeproj::pbxproj::*;
+    /// # fn print(root_object: &PBXRootObject) {
+    /// root_object.root_group().walk(|node, depth, path| {
+    ///     println!("{}{}", "  ".repeat(depth), path.display());
+    /// });
+    /// # }
+    /// ```
+    pub fn walk<F, R>(&self, visit: F)
+    where
+        F: FnMut(&PBXFSReference<'a>, usize, &Path) -> R,
+        R: Into<PBXWalk>,
+    {
+        self.walker().walk(visit)
+    }
+
+    /// Get a [`PBXFSWalker`] over the reference and its descendants, to visit only some of them.
+    pub fn walker(&self) -> PBXFSWalker<'_, 'a> {
+        PBXFSWalker {
+            root: self,
+            kinds: vec![],
+            max_depth: None,
+            filter: None,
+        }
+    }
+}
+
+type NodeFilter<'r, 'a> = Box<dyn Fn(&PBXFSReference<'a>) -> bool + 'r>;
+
+/// Filterable depth-first visitor of [`PBXFSReference`] trees, see [`PBXFSReference::walker`].
+///
+/// Filters only decide which nodes are visited: children of filtered out groups are still
+/// walked, unless beyond [`PBXFSWalker::max_depth`].
+pub struct PBXFSWalker<'r, 'a> {
+    root: &'r PBXFSReference<'a>,
+    kinds: Vec<PBXFSReferenceKind>,
+    max_depth: Option<usize>,
+    filter: Option<NodeFilter<'r, 'a>>,
+}
+
+impl<'r, 'a> PBXFSWalker<'r, 'a> {
+    /// Only visit nodes of the given kinds, every kind by default
+    pub fn kinds<I: IntoIterator<Item = PBXFSReferenceKind>>(mut self, kinds: I) -> Self {
+        self.kinds.extend(kinds);
+        self
+    }
+
+    /// Only visit file references
+    pub fn files(self) -> Self {
+        self.kinds([PBXFSReferenceKind::File])
+    }
+
+    /// Only visit groups, variant groups and version groups
+    pub fn groups(self) -> Self {
+        self.kinds([
+            PBXFSReferenceKind::FileGroup,
+            PBXFSReferenceKind::VariantGroup,
+            PBXFSReferenceKind::VersionGroup,
+        ])
+    }
+
+    /// Don't go deeper than `depth`, the walked reference being at depth 0
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Only visit nodes `filter` returns true for
+    pub fn filter<F: Fn(&PBXFSReference<'a>) -> bool + 'r>(mut self, filter: F) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Visit matching nodes, see [`PBXFSReference::walk`]. Returning [`PBXWalk::SkipChildren`]
+    /// skips children of the visited node.
+    pub fn walk<F, R>(&self, mut visit: F)
+    where
+        F: FnMut(&PBXFSReference<'a>, usize, &Path) -> R,
+        R: Into<PBXWalk>,
+    {
+        self.walk_node(self.root, 0, &PathBuf::new(), &mut |node, depth, path| {
+            visit(node, depth, path).into()
+        });
+    }
+
+    /// Get matching nodes with their depth and path, in walk order
+    pub fn collect(&self) -> Vec<(&'r PBXFSReference<'a>, usize, PathBuf)> {
+        let mut nodes = vec![];
+        self.walk_node(self.root, 0, &PathBuf::new(), &mut |node, depth, path| {
+            nodes.push((node, depth, path.to_path_buf()));
+            PBXWalk::Continue
+        });
+        nodes
+    }
+
+    fn matches(&self, node: &PBXFSReference<'a>) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&node.kind))
+            && self.filter.as_ref().map(|f| f(node)).unwrap_or(true)
+    }
+
+    /// Walk `node` at `depth`, returning false once stopped
+    fn walk_node<F>(
+        &self,
+        node: &'r PBXFSReference<'a>,
+        depth: usize,
+        path: &Path,
+        visit: &mut F,
+    ) -> bool
+    where
+        F: FnMut(&'r PBXFSReference<'a>, usize, &Path) -> PBXWalk,
+    {
+        let path = match (depth, node.name.or(node.path)) {
+            (0, _) | (_, None) => path.to_path_buf(),
+            (_, Some(name)) => path.join(name),
+        };
+        let control = if self.matches(node) {
+            visit(node, depth, &path)
+        } else {
+            PBXWalk::Continue
+        };
+        match control {
+            PBXWalk::Stop => return false,
+            PBXWalk::SkipChildren => return true,
+            PBXWalk::Continue => {}
+        }
+        if self.max_depth.map(|max| depth >= max).unwrap_or_default() {
+            return true;
+        }
+        node.children
+            .iter()
+            .all(|child| self.walk_node(child, depth + 1, &path, visit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn walk() {
+        let root_object = test_demo_file!(demo1);
+        let root_group = root_object.root_group();
+
+        let mut visited = 0;
+        let mut max_depth = 0;
+        root_group.walk(|_, depth, _| {
+            visited += 1;
+            max_depth = max_depth.max(depth);
+        });
+        assert_eq!(
+            root_object.groups().len() + root_object.files().len(),
+            visited
+        );
+        assert!(max_depth > 1);
+
+        let files = root_group.walker().files().collect();
+        assert_eq!(root_object.files().len(), files.len());
+        assert!(files
+            .iter()
+            .all(|(node, depth, _)| node.is_file() && *depth > 0));
+        let (_, depth, path) = files
+            .iter()
+            .find(|(node, _, _)| node.path.map(|p| p.as_str()) == Some("Wordle.app"))
+            .unwrap();
+        assert_eq!(2, *depth);
+        assert_eq!(PathBuf::from("Products/Wordle.app"), *path);
+
+        let top = root_group.walker().groups().max_depth(1).collect();
+        assert_eq!(
+            root_group.children.iter().filter(|c| c.is_group()).count() + 1,
+            top.len()
+        );
+
+        let swift = root_group
+            .walker()
+            .filter(|node| node.path.map(|p| p.ends_with(".swift")).unwrap_or_default())
+            .collect();
+        assert!(!swift.is_empty());
+
+        let mut names = vec![];
+        root_group.walk(|node, depth, _| {
+            if depth == 1 {
+                names.push(node.id.clone());
+                return PBXWalk::Stop;
+            }
+            PBXWalk::Continue
+        });
+        assert_eq!(1, names.len());
+
+        let mut depths = vec![];
+        root_group.walk(|_, depth, _| {
+            depths.push(depth);
+            if depth == 1 {
+                PBXWalk::SkipChildren
+            } else {
+                PBXWalk::Continue
+            }
+        });
+        assert!(depths.iter().all(|depth| *depth <= 1));
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// Seconds between the unix epoch and 2001-01-01, the epoch Xcode counts from
+const REFERENCE_DATE: u64 = 978_307_200;
+
+/// Object identifier laid out like the ones Xcode generates, 12 bytes written as 24 uppercase
+/// hex characters: user and process bytes, a sequence number, the creation time and a per
+/// process random value.
+///
+/// Uniqueness is only guaranteed within the generating process, use
+/// [`PBXObjectCollection::generate_reference`] to get one free within a collection.
+///
+/// [`PBXObjectCollection::generate_reference`]: crate::pbxproj::PBXObjectCollection::generate_reference
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PBXObjectId([u8; 12]);
+
+impl PBXObjectId {
+    /// Generate a new identifier, different from every other one generated by this process
+    pub fn generate() -> Self {
+        static SESSION: OnceLock<(u8, u32)> = OnceLock::new();
+        static SEQUENCE: OnceLock<AtomicU16> = OnceLock::new();
+
+        let (user, session) = *SESSION.get_or_init(|| {
+            let user = std::env::var("USER").unwrap_or_default();
+            let user = user
+                .bytes()
+                .fold(0u8, |hash, byte| hash.rotate_left(3) ^ byte);
+            (user, random() as u32)
+        });
+        let sequence = SEQUENCE
+            .get_or_init(|| AtomicU16::new(random() as u16))
+            .fetch_add(1, Ordering::Relaxed);
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(Duration::from_secs(REFERENCE_DATE))
+            .as_secs() as u32;
+
+        let mut bytes = [0; 12];
+        bytes[0] = user;
+        bytes[1] = std::process::id() as u8;
+        bytes[2..4].copy_from_slice(&sequence.to_be_bytes());
+        bytes[4..8].copy_from_slice(&time.to_be_bytes());
+        bytes[8..].copy_from_slice(&session.to_be_bytes());
+        Self(bytes)
+    }
+
+    /// Get raw bytes of the identifier
+    pub fn as_bytes(&self) -> &[u8; 12] {
+        &self.0
+    }
+}
+
+impl From<[u8; 12]> for PBXObjectId {
+    fn from(bytes: [u8; 12]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<PBXObjectId> for String {
+    fn from(id: PBXObjectId) -> Self {
+        id.to_string()
+    }
+}
+
+impl std::fmt::Display for PBXObjectId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+    }
+}
+
+fn random() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_object_id() {
+        let (first, second) = (PBXObjectId::generate(), PBXObjectId::generate());
+        assert_ne!(first, second);
+        // Same session, consecutive sequence numbers
+        assert_eq!(first.as_bytes()[8..], second.as_bytes()[8..]);
+        assert_eq!(first.as_bytes()[..2], second.as_bytes()[..2]);
+
+        let id = first.to_string();
+        assert_eq!(24, id.len());
+        assert!(id
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase()));
+        assert_eq!(
+            "C20D77EA24B665D7002E7AEA",
+            PBXObjectId::from([
+                0xC2, 0x0D, 0x77, 0xEA, 0x24, 0xB6, 0x65, 0xD7, 0x00, 0x2E, 0x7A, 0xEA
+            ])
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use super::copy::remap_references;
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Options of [`PBXRootObject::import_target`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PBXImportOptions {
+    /// Name of the imported target, the source name by default
+    pub name: Option<String>,
+    /// Point build files at existing file references of the destination at the same path,
+    /// relative to the project source root, instead of adding new ones
+    pub reuse_file_references: bool,
+}
+
+impl Default for PBXImportOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            reuse_file_references: true,
+        }
+    }
+}
+
+impl PBXRootObject {
+    /// Copy the target with the given name from `source`, e.g. another project, along with its
+    /// build phases, build files, rules, configurations and product. Every copied object gets a
+    /// fresh reference.
+    ///
+    /// Files of the target are added under the same group hierarchy as in `source`, reusing
+    /// existing groups, and existing file references at the same path unless disabled in
+    /// `options`. Swift package products are copied too, reusing packages of the destination
+    /// with the same url or path. Target dependencies refer to targets of `source` and are
+    /// dropped. Returns the reference of the imported target.
+    pub fn import_target(
+        &mut self,
+        source: &PBXRootObject,
+        name: &str,
+        options: &PBXImportOptions,
+    ) -> Result<String> {
+        let target = source
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("Target {name:?} not found"))?
+            .id;
+        let name = options.name.as_deref().unwrap_or(name);
+        if self.get_target_by_name(name).is_some() {
+            anyhow::bail!("Target with {name:?} already exists");
+        }
+
+        let mut subtree = source.objects.subtree(&target)?;
+        subtree.retain(|(_, object)| {
+            !matches!(
+                object.get_kind("isa"),
+                Some(PBXObjectKind::PBXTargetDependency | PBXObjectKind::PBXContainerItemProxy)
+            )
+        });
+
+        let mut importer = Importer {
+            source,
+            options,
+            groups: HashMap::new(),
+            shared: HashMap::new(),
+        };
+        for (reference, object) in subtree.iter() {
+            if reference == &target {
+                if let Some(product) = object.get_string("productReference") {
+                    let copy = importer.import_product(self, product)?;
+                    importer.shared.insert(product.clone(), copy);
+                }
+                for product in object
+                    .get_vec("packageProductDependencies")
+                    .map(|products| products.as_vec_strings())
+                    .unwrap_or_default()
+                {
+                    importer.import_package_product(self, product)?;
+                }
+            }
+            for key in ["fileRef", "baseConfigurationReference"] {
+                if let Some(file) = object.get_string(key) {
+                    importer.import_file(self, file)?;
+                }
+            }
+            if let Some(product) = object.get_string("productRef") {
+                importer.import_package_product(self, product)?;
+            }
+        }
+
+        for (reference, object) in subtree.iter_mut() {
+            let mut value = PBXValue::Object(std::mem::take(object));
+            remap_references(&mut value, &importer.shared);
+            if let PBXValue::Object(value) = value {
+                *object = value;
+            }
+            if reference == &target {
+                object.insert_value("name", name);
+                object.insert_value("dependencies", Vec::<String>::new());
+            }
+        }
+        let reference = self.objects.insert_subtree(&target, subtree);
+        self.objects.invalidate_path_index();
+
+        let mut project = self
+            .objects
+            .get_object_mut(&self.root_object_reference)
+            .ok_or_else(|| anyhow::anyhow!("Root project not found"))?;
+        match project.get_vec_mut("targets") {
+            Some(targets) => targets.push(reference.clone()),
+            None => {
+                project.insert_value("targets", vec![reference.clone()]);
+            }
+        }
+
+        Ok(reference)
+    }
+}
+
+/// State of a [`PBXRootObject::import_target`] call
+struct Importer<'a> {
+    source: &'a PBXRootObject,
+    options: &'a PBXImportOptions,
+    /// Destination group of each source group
+    groups: HashMap<String, String>,
+    /// Destination reference of each source object shared with other targets
+    shared: HashMap<String, String>,
+}
+
+impl<'a> Importer<'a> {
+    /// Copy the product file reference of the imported target to the destination products group
+    fn import_product(&mut self, destination: &mut PBXRootObject, product: &str) -> Result<String> {
+        let copy = destination
+            .objects
+            .deep_copy_from(&self.source.objects, product)?;
+        let group = destination
+            .objects
+            .map
+            .get(&destination.root_object_reference)
+            .and_then(|project| project.get_string("productRefGroup"))
+            .cloned();
+        if let Some(group) = group {
+            destination.objects.register_child(&group, &copy)?;
+        }
+        Ok(copy)
+    }
+
+    /// Map source fs reference `file` to an existing destination file reference at the same path,
+    /// or copy it under the same group hierarchy.
+    fn import_file(&mut self, destination: &mut PBXRootObject, file: &str) -> Result<()> {
+        if self.shared.contains_key(file) {
+            return Ok(());
+        }
+        let path = self.source.objects.get_fs_reference_path(file);
+        let existing = path
+            .filter(|_| self.options.reuse_file_references)
+            .and_then(|path| destination.objects.get_fs_references_by_path(path).first())
+            .cloned();
+        let copy = match existing {
+            Some(existing) => existing,
+            None => {
+                let group = self.import_group_chain(destination, file)?;
+                let copy = destination
+                    .objects
+                    .deep_copy_from(&self.source.objects, file)?;
+                destination.objects.register_child(&group, &copy)?;
+                copy
+            }
+        };
+        self.shared.insert(file.to_string(), copy);
+        Ok(())
+    }
+
+    /// Get destination group matching the parent group of source fs reference `child`, creating
+    /// missing groups of the hierarchy.
+    fn import_group_chain(
+        &mut self,
+        destination: &mut PBXRootObject,
+        child: &str,
+    ) -> Result<String> {
+        let source = &self.source.objects;
+        let parents = source
+            .map
+            .iter()
+            .filter_map(|(group, object)| {
+                let children = object.get_vec("children")?.as_vec_strings();
+                Some(
+                    children
+                        .into_iter()
+                        .map(move |c| (c.as_str(), group.as_str())),
+                )
+            })
+            .flatten()
+            .collect::<HashMap<_, _>>();
+        let main_group = self.source.root_group().id;
+        let mut chain = vec![];
+        let mut current = child;
+        while let Some(parent) = parents.get(current) {
+            if *parent == main_group || chain.contains(parent) {
+                break;
+            }
+            chain.push(*parent);
+            current = parent;
+        }
+
+        let mut group = destination.root_group().id;
+        for source_group in chain.into_iter().rev() {
+            if let Some(existing) = self.groups.get(source_group) {
+                group = existing.clone();
+                continue;
+            }
+            let object = &source.map[source_group];
+            let existing = object
+                .get_string("path")
+                .or_else(|| object.get_string("name"))
+                .and_then(|key| destination.objects.get_child_by_name_or_path(&group, key))
+                .filter(|existing| destination.objects.get_group(existing).is_some());
+            group = match existing {
+                Some(existing) => existing,
+                None => {
+                    let mut copy = object.clone();
+                    copy.insert_value("children", Vec::<String>::new());
+                    destination.objects.add_child(&group, copy)?
+                }
+            };
+            self.groups.insert(source_group.to_string(), group.clone());
+        }
+        Ok(group)
+    }
+
+    /// Copy source swift package product dependency `product`, reusing the destination package
+    /// with the same url or path, or adding the source package to the destination project.
+    fn import_package_product(
+        &mut self,
+        destination: &mut PBXRootObject,
+        product: &str,
+    ) -> Result<()> {
+        if self.shared.contains_key(product) {
+            return Ok(());
+        }
+        let mut object = self
+            .source
+            .objects
+            .map
+            .get(product)
+            .ok_or_else(|| anyhow::anyhow!("Object {product:?} not found"))?
+            .clone();
+
+        if let Some(package) = object.get_string("package").cloned() {
+            let package = match self.shared.get(&package) {
+                Some(copy) => copy.clone(),
+                None => {
+                    let copy = self.import_package(destination, &package)?;
+                    self.shared.insert(package, copy.clone());
+                    copy
+                }
+            };
+            object.insert_value("package", package);
+        }
+
+        let copy = String::from(destination.objects.insert_object(object, None));
+        self.shared.insert(product.to_string(), copy);
+        Ok(())
+    }
+
+    /// Get destination package matching source package `package`, adding a copy if missing
+    fn import_package(&self, destination: &mut PBXRootObject, package: &str) -> Result<String> {
+        let object = self
+            .source
+            .objects
+            .map
+            .get(package)
+            .ok_or_else(|| anyhow::anyhow!("Object {package:?} not found"))?;
+        let location = |object: &PBXHashMap| {
+            object
+                .get_string("repositoryURL")
+                .or_else(|| object.get_string("relativePath"))
+                .cloned()
+        };
+
+        let project = destination
+            .objects
+            .map
+            .get(&destination.root_object_reference)
+            .ok_or_else(|| anyhow::anyhow!("Root project not found"))?;
+        let packages = project
+            .get_vec("packageReferences")
+            .map(|packages| packages.as_vec_strings())
+            .unwrap_or_default();
+        let existing = packages.into_iter().find(|existing| {
+            let existing = destination.objects.map.get(existing.as_str());
+            location(object).is_some() && existing.and_then(location) == location(object)
+        });
+        if let Some(existing) = existing {
+            return Ok(existing.clone());
+        }
+
+        let copy = String::from(
+            destination
+                .objects
+                .insert_object(object.clone(), Some(&destination.root_object_reference)),
+        );
+        let mut project = destination
+            .objects
+            .get_object_mut(&destination.root_object_reference)
+            .ok_or_else(|| anyhow::anyhow!("Root project not found"))?;
+        match project.get_vec_mut("packageReferences") {
+            Some(packages) => packages.push(copy.clone()),
+            None => {
+                project.insert_value("packageReferences", vec![copy.clone()]);
+            }
+        }
+        Ok(copy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn import_target() {
+        let source = test_demo_file!(demo1);
+        let mut root_object = test_demo_file!(demo2);
+        let options = PBXImportOptions::default();
+        assert!(root_object
+            .import_target(&source, "Missing", &options)
+            .is_err());
+
+        let reference = root_object
+            .import_target(&source, "Wordle", &options)
+            .unwrap();
+        let original = source.get_target_by_name("Wordle").unwrap();
+        let target = root_object.get_target(&reference).unwrap();
+        assert_eq!(Some("Wordle"), target.name.map(|n| n.as_str()));
+        assert_eq!(original.build_phases.len(), target.build_phases.len());
+        assert!(root_object
+            .root_project()
+            .targets
+            .iter()
+            .any(|t| t.id == reference));
+
+        let file_paths = |root_object: &PBXRootObject, target: &PBXTarget| {
+            target
+                .build_phases
+                .iter()
+                .flat_map(|phase| phase.files.iter())
+                .filter_map(|file| file.file.as_ref())
+                .map(|file| {
+                    root_object
+                        .objects()
+                        .get_fs_reference_path(&file.id)
+                        .map(|path| path.to_path_buf())
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            file_paths(&source, &original),
+            file_paths(&root_object, &target)
+        );
+        assert_eq!(1, target.package_product_dependencies.len());
+        let product = target.product.as_ref().unwrap().id.clone();
+
+        let files = root_object.files().len();
+        let packages = root_object.root_project().packages.len();
+        let options = PBXImportOptions {
+            name: Some("WordleCopy".into()),
+            ..Default::default()
+        };
+        let copy = root_object
+            .import_target(&source, "Wordle", &options)
+            .unwrap();
+        assert!(root_object
+            .import_target(&source, "Wordle", &options)
+            .is_err());
+        // Only the product is added, files and packages are reused
+        assert_eq!(files + 1, root_object.files().len());
+        assert_eq!(packages, root_object.root_project().packages.len());
+        let copy = root_object.get_target(&copy).unwrap();
+        assert_ne!(product, copy.product.as_ref().unwrap().id);
+    }
+}
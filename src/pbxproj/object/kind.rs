@@ -4,7 +4,7 @@ use enum_as_inner::EnumAsInner;
 use super::{PBXBuildPhaseKind, PBXFSReferenceKind};
 
 /// Representation of all Target kinds
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, is_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, is_enum_variant)]
 pub enum PBXTargetKind {
     /// A build target that produces a binary content (application or library).
     Native,
@@ -14,7 +14,7 @@ pub enum PBXTargetKind {
     Aggregate,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, EnumAsInner)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumAsInner)]
 /// Pbxproj object kinds
 pub enum PBXObjectKind {
     /// An abstraction over targets, including:
@@ -27,6 +27,8 @@ pub enum PBXObjectKind {
     /// - PBXFrameworksBuildPhase: Framework link build phase.
     /// - PBXHeadersBuildPhase: Headers link build phase.
     /// - PBXRezBuildPhase: Build Carbon Resources build phase.
+    /// - PBXAppleScriptBuildPhase: Legacy AppleScript compilation build phase.
+    /// - PBXJavaArchiveBuildPhase: Legacy Java archive build phase.
     /// - PBXResourcesBuildPhase: Resources copy build phase.
     /// - PBXShellScriptBuildPhase: Shell Script build phase.
     /// - PBXSourcesBuildPhase: A Kind representing the sources compilation build phase.
@@ -57,8 +59,18 @@ pub enum PBXObjectKind {
     XCSwiftPackageProductDependency,
     /// A Kind representing a reference to other targets through content proxies.
     PBXTargetDependency,
-    /// UnknownPBXObjectKind
-    Unknown(String),
+    /// A Kind representing a reference to a product of another project.
+    PBXReferenceProxy,
+    /// A Kind representing a local swift package, referenced by path.
+    XCLocalSwiftPackageReference,
+    /// A Kind representing a folder whose content Xcode keeps in sync with the file system.
+    PBXFileSystemSynchronizedRootGroup,
+    /// A Kind representing files of a synchronized folder excluded from a target.
+    PBXFileSystemSynchronizedBuildFileExceptionSet,
+    /// A Kind representing files of a synchronized folder excluded from a build phase.
+    PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet,
+    /// Any other isa, kept as is.
+    Other(String),
 }
 
 impl PBXObjectKind {
@@ -112,6 +124,42 @@ impl PBXObjectKind {
     pub fn is_pbx_fsreference(&self) -> bool {
         matches!(self, Self::PBXFSReference(..))
     }
+
+    /// Returns `true` if the kind is a native, legacy or aggregate target.
+    #[must_use]
+    pub fn is_target(&self) -> bool {
+        self.is_pbx_target()
+    }
+
+    /// Returns `true` if the kind is a build phase.
+    #[must_use]
+    pub fn is_build_phase(&self) -> bool {
+        self.is_pbx_build_phase()
+    }
+
+    /// Returns `true` if the kind is an element of the file system tree, i.e. a file reference,
+    /// a group, a reference proxy or a synchronized folder.
+    #[must_use]
+    pub fn is_fs_reference(&self) -> bool {
+        matches!(
+            self,
+            Self::PBXFSReference(..)
+                | Self::PBXReferenceProxy
+                | Self::PBXFileSystemSynchronizedRootGroup
+        )
+    }
+
+    /// Returns `true` if the kind is a remote or local swift package reference, or a swift
+    /// package product dependency.
+    #[must_use]
+    pub fn is_swift_package_kind(&self) -> bool {
+        matches!(
+            self,
+            Self::XCRemoteSwiftPackageReference
+                | Self::XCLocalSwiftPackageReference
+                | Self::XCSwiftPackageProductDependency
+        )
+    }
 }
 
 impl From<&str> for PBXObjectKind {
@@ -131,6 +179,8 @@ impl From<&str> for PBXObjectKind {
             "PBXSourcesBuildPhase" => Self::PBXBuildPhase(PBXBuildPhaseKind::Sources),
             "PBXCopyFilesBuildPhase" => Self::PBXBuildPhase(PBXBuildPhaseKind::CopyFiles),
             "PBXRezBuildPhase" => Self::PBXBuildPhase(PBXBuildPhaseKind::CarbonResources),
+            "PBXAppleScriptBuildPhase" => Self::PBXBuildPhase(PBXBuildPhaseKind::AppleScript),
+            "PBXJavaArchiveBuildPhase" => Self::PBXBuildPhase(PBXBuildPhaseKind::JavaArchive),
             "XCConfigurationList" => Self::XCConfigurationList,
             "PBXTargetDependency" => Self::PBXTargetDependency,
             "PBXVariantGroup" => Self::PBXFSReference(PBXFSReferenceKind::VariantGroup),
@@ -140,14 +190,23 @@ impl From<&str> for PBXObjectKind {
             "PBXBuildRule" => Self::PBXBuildRule,
             "XCRemoteSwiftPackageReference" => Self::XCRemoteSwiftPackageReference,
             "XCSwiftPackageProductDependency" => Self::XCSwiftPackageProductDependency,
-            str => Self::Unknown(str.to_string()),
+            "PBXReferenceProxy" => Self::PBXReferenceProxy,
+            "XCLocalSwiftPackageReference" => Self::XCLocalSwiftPackageReference,
+            "PBXFileSystemSynchronizedRootGroup" => Self::PBXFileSystemSynchronizedRootGroup,
+            "PBXFileSystemSynchronizedBuildFileExceptionSet" => {
+                Self::PBXFileSystemSynchronizedBuildFileExceptionSet
+            }
+            "PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet" => {
+                Self::PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet
+            }
+            str => Self::Other(str.to_string()),
         }
     }
 }
 
-impl ToString for PBXObjectKind {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for PBXObjectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
             Self::PBXBuildFile => "PBXBuildFile",
             Self::PBXProject => "PBXProject",
             Self::XCConfigurationList => "XCConfigurationList",
@@ -157,6 +216,15 @@ impl ToString for PBXObjectKind {
             Self::PBXBuildRule => "PBXBuildRule",
             Self::XCRemoteSwiftPackageReference => "XCRemoteSwiftPackageReference",
             Self::XCSwiftPackageProductDependency => "XCSwiftPackageProductDependency",
+            Self::PBXReferenceProxy => "PBXReferenceProxy",
+            Self::XCLocalSwiftPackageReference => "XCLocalSwiftPackageReference",
+            Self::PBXFileSystemSynchronizedRootGroup => "PBXFileSystemSynchronizedRootGroup",
+            Self::PBXFileSystemSynchronizedBuildFileExceptionSet => {
+                "PBXFileSystemSynchronizedBuildFileExceptionSet"
+            }
+            Self::PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet => {
+                "PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet"
+            }
             Self::PBXFSReference(kind) => kind.as_isa(),
             Self::PBXTarget(kind) => match kind {
                 PBXTargetKind::Native => "PBXNativeTarget",
@@ -164,8 +232,68 @@ impl ToString for PBXObjectKind {
                 PBXTargetKind::Aggregate => "PBXAggregateTarget",
             },
             PBXObjectKind::PBXBuildPhase(kind) => kind.as_isa(),
-            Self::Unknown(str) => str,
+            Self::Other(str) => str,
+        };
+        write!(f, "{value}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isa_round_trip() {
+        let isas = [
+            "PBXAggregateTarget",
+            "PBXAppleScriptBuildPhase",
+            "PBXBuildFile",
+            "PBXBuildRule",
+            "PBXContainerItemProxy",
+            "PBXCopyFilesBuildPhase",
+            "PBXFileReference",
+            "PBXFileSystemSynchronizedBuildFileExceptionSet",
+            "PBXFileSystemSynchronizedGroupBuildPhaseMembershipExceptionSet",
+            "PBXFileSystemSynchronizedRootGroup",
+            "PBXFrameworksBuildPhase",
+            "PBXGroup",
+            "PBXHeadersBuildPhase",
+            "PBXJavaArchiveBuildPhase",
+            "PBXLegacyTarget",
+            "PBXNativeTarget",
+            "PBXProject",
+            "PBXReferenceProxy",
+            "PBXResourcesBuildPhase",
+            "PBXRezBuildPhase",
+            "PBXShellScriptBuildPhase",
+            "PBXSourcesBuildPhase",
+            "PBXTargetDependency",
+            "PBXVariantGroup",
+            "XCBuildConfiguration",
+            "XCConfigurationList",
+            "XCLocalSwiftPackageReference",
+            "XCRemoteSwiftPackageReference",
+            "XCSwiftPackageProductDependency",
+            "XCVersionGroup",
+        ];
+        for isa in isas {
+            let kind = PBXObjectKind::from(isa);
+            assert!(
+                !matches!(kind, PBXObjectKind::Other(_)),
+                "{isa} is not known"
+            );
+            assert_eq!(isa, kind.to_string());
         }
-        .into()
+        assert_eq!(
+            PBXObjectKind::Other("PBXFutureObject".into()),
+            PBXObjectKind::from("PBXFutureObject")
+        );
+
+        let kind = PBXObjectKind::from;
+        assert!(kind("PBXLegacyTarget").is_target());
+        assert!(kind("PBXRezBuildPhase").is_build_phase());
+        assert!(kind("PBXReferenceProxy").is_fs_reference());
+        assert!(!kind("PBXBuildFile").is_fs_reference());
+        assert!(kind("XCLocalSwiftPackageReference").is_swift_package_kind());
     }
 }
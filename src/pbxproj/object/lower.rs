@@ -0,0 +1,102 @@
+use crate::pbxproj::*;
+use std::collections::HashMap;
+
+impl PBXObjectCollection {
+    /// Lower the object with the given reference into [`PBXHashMap`] through its pbx object,
+    /// see [`ToPBXHashMap`]. Required fields missing in permissive mode are lowered with
+    /// their default, see [`PBXDecodeMode`]. Objects without pbx object, e.g. without isa, or failing to decode are cloned as
+    /// is.
+    pub fn to_hashmap(&self, reference: &str) -> Option<PBXHashMap> {
+        let object = self.map.get(reference)?;
+        let lowered = object.get_kind("isa").and_then(|kind| match kind {
+            PBXObjectKind::PBXTarget(_) => self.lower::<PBXTarget>(reference),
+            PBXObjectKind::PBXBuildPhase(_) => self.lower::<PBXBuildPhase>(reference),
+            PBXObjectKind::PBXFSReference(_) => self.lower::<PBXFSReference>(reference),
+            PBXObjectKind::XCBuildConfiguration => self.lower::<XCBuildConfiguration>(reference),
+            PBXObjectKind::PBXBuildFile => self.lower::<PBXBuildFile>(reference),
+            PBXObjectKind::PBXBuildRule => self.lower::<PBXBuildRule>(reference),
+            PBXObjectKind::XCConfigurationList => self.lower::<XCConfigurationList>(reference),
+            PBXObjectKind::PBXContainerItemProxy => self.lower::<PBXContainerItemProxy>(reference),
+            PBXObjectKind::PBXProject => self.lower::<PBXProject>(reference),
+            PBXObjectKind::XCRemoteSwiftPackageReference => {
+                self.lower::<XCRemoteSwiftPackageReference>(reference)
+            }
+            PBXObjectKind::XCSwiftPackageProductDependency => {
+                self.lower::<XCSwiftPackageProductDependency>(reference)
+            }
+            PBXObjectKind::PBXTargetDependency => self.lower::<PBXTargetDependency>(reference),
+            PBXObjectKind::PBXFileSystemSynchronizedRootGroup => {
+                self.lower::<PBXFileSystemSynchronizedRootGroup>(reference)
+            }
+            PBXObjectKind::PBXFileSystemSynchronizedBuildFileExceptionSet => {
+                self.lower::<PBXFileSystemSynchronizedBuildFileExceptionSet>(reference)
+            }
+            _ => None,
+        });
+        Some(lowered.unwrap_or_else(|| object.clone()))
+    }
+
+    /// Lower every object into [`PBXHashMap`] by reference, see
+    /// [`PBXObjectCollection::to_hashmap`]
+    pub fn to_hashmaps(&self) -> HashMap<String, PBXHashMap> {
+        self.map
+            .keys()
+            .filter_map(|reference| Some((reference.clone(), self.to_hashmap(reference)?)))
+            .collect()
+    }
+
+    fn lower<'a, T>(&'a self, reference: &str) -> Option<PBXHashMap>
+    where
+        T: AsPBXObject<'a> + ToPBXHashMap + 'a,
+    {
+        self.get::<T, _>(reference)
+            .map(|object| object.to_hashmap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn lower_objects() {
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        for entry in std::fs::read_dir(samples).unwrap().flatten() {
+            let root_object = PBXRootObject::try_from(entry.path()).unwrap();
+            let objects = root_object.objects();
+            let lowered = objects.to_hashmaps();
+            let defaulted = objects
+                .decode_warnings()
+                .into_iter()
+                .map(|w| (w.reference, w.key))
+                .collect::<Vec<_>>();
+            assert_eq!(objects.len(), lowered.len());
+            for (reference, object) in lowered {
+                let raw = &objects[&reference];
+                let is_fully_modeled = raw.get_kind("isa").is_some_and(|kind| {
+                    kind.is_xc_build_configuration()
+                        || kind.is_xc_configuration_list()
+                        || kind.is_pbx_container_item_proxy()
+                        || kind.is_xc_swift_package_product_dependency()
+                        || kind.is_xc_remote_swift_package_reference()
+                        || kind.is_pbx_file_system_synchronized_root_group()
+                        || kind.is_pbx_file_system_synchronized_build_file_exception_set()
+                });
+                if is_fully_modeled {
+                    assert_eq!(raw, &object, "{:?} {reference}", entry.path());
+                }
+                for (key, value) in object.iter() {
+                    if defaulted.contains(&(reference.clone(), key.as_str())) {
+                        continue;
+                    }
+                    assert_eq!(
+                        raw.get_value(key),
+                        Some(value),
+                        "{:?} {reference} {key}",
+                        entry.path()
+                    );
+                }
+            }
+        }
+    }
+}
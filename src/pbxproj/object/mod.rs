@@ -5,17 +5,30 @@ mod target;
 
 mod collection;
 mod container_item_proxy;
+mod copy;
+mod decode;
+mod entry;
 mod fs;
+mod id;
+mod import;
 mod kind;
+mod lower;
 mod product_type;
+mod referrers;
+mod rename;
 
 pub use fs::*;
+pub use id::*;
 pub use kind::*;
 pub use product_type::*;
 
 pub use build::*;
 pub use container_item_proxy::*;
+pub use decode::*;
+pub use entry::*;
+pub use import::*;
 pub use project::*;
+pub use referrers::*;
 
 pub use swift_package::*;
 pub use target::*;
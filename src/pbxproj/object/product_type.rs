@@ -1,7 +1,7 @@
 use tap::Pipe;
 
 /// Target Product Type
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
 pub enum PBXProductType {
     /// Application
     Application,
@@ -62,15 +62,10 @@ pub enum PBXProductType {
     /// SystemExtension
     SystemExtension,
     /// None Identified
+    #[default]
     None,
 }
 
-impl Default for PBXProductType {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl PBXProductType {
     /// Return file extension for product type
     pub fn file_extension(&self) -> Option<&str> {
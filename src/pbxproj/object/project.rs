@@ -10,8 +10,14 @@ pub struct PBXProject<'a> {
     pub id: String,
     /// Project name
     pub name: Option<&'a String>,
-    /// A string representation of the XcodeCompatibilityVersion.
-    pub compatibility_version: &'a String,
+    /// A string representation of the XcodeCompatibilityVersion, e.g. `Xcode 14.0`. Dropped
+    /// from objectVersion 70, Xcode 16.
+    pub compatibility_version: Option<&'a String>,
+    /// Object version Xcode upgrades the project to, from objectVersion 70, Xcode 16.
+    pub preferred_project_object_version: Option<&'a isize>,
+    /// Whether proxies of references to other projects are written minimized, from
+    /// objectVersion 70, Xcode 16.
+    pub minimized_project_reference_proxies: Option<bool>,
     /// The region of development.
     pub development_region: Option<&'a String>,
     /// Whether file encodings have been scanned.
@@ -27,7 +33,7 @@ pub struct PBXProject<'a> {
     /// Project attributes.
     pub attributes: &'a PBXHashMap,
     /// Project's Targets attributes by target reference key
-    pub target_attributes: HashMap<&'a String, &'a PBXHashMap>,
+    pub target_attributes: HashMap<&'a str, &'a PBXHashMap>,
     /// Project's Package references.
     pub packages: Vec<XCRemoteSwiftPackageReference<'a>>,
     /// Project's Build configuration list
@@ -56,23 +62,28 @@ impl<'a> AsPBXObject<'a> for PBXProject<'a> {
                 vec![]
             };
 
-        let attributes = value.try_get_object("attributes")?;
+        let attributes = objects.required_object(value, "attributes")?;
         Ok(Self {
             id,
             name: value.get_string("name"),
-            compatibility_version: value.try_get_string("compatibilityVersion")?,
+            compatibility_version: value.get_string("compatibilityVersion"),
+            preferred_project_object_version: value.get_number("preferredProjectObjectVersion"),
+            minimized_project_reference_proxies: value
+                .get_number("minimizedProjectReferenceProxies")
+                .map(|v| v == &1),
             development_region: value.get_string("developmentRegion"),
 
-            has_scanned_for_encodings: value.try_get_number("hasScannedForEncodings")?,
-            known_regions: value.try_get_vec("knownRegions")?.as_vec_strings(),
-            project_dir_path: value.try_get_string("projectDirPath")?,
+            has_scanned_for_encodings: objects.required_number(value, "hasScannedForEncodings")?,
+            known_regions: objects
+                .required_vec(value, "knownRegions")?
+                .as_vec_strings(),
+            project_dir_path: objects.required_string(value, "projectDirPath")?,
             project_roots,
             target_attributes: attributes
                 .get_object("TargetAttributes")
                 .map(|v| {
                     v.iter()
-                        .map(|(k, value)| Some((k, value.as_object()?)))
-                        .flatten()
+                        .filter_map(|(k, value)| Some((k.as_str(), value.as_object()?)))
                         .collect::<HashMap<_, _>>()
                 })
                 .unwrap_or_default(),
@@ -98,12 +109,80 @@ impl<'a> AsPBXObject<'a> for PBXProject<'a> {
         })
     }
 }
+
+impl ToPBXHashMap for PBXProject<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXProject);
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some(
+            "compatibilityVersion",
+            self.compatibility_version.map(String::as_str),
+        );
+        value.insert_some(
+            "preferredProjectObjectVersion",
+            self.preferred_project_object_version.copied(),
+        );
+        value.insert_some(
+            "minimizedProjectReferenceProxies",
+            self.minimized_project_reference_proxies.map(isize::from),
+        );
+        value.insert_some(
+            "developmentRegion",
+            self.development_region.map(String::as_str),
+        );
+        value.insert_value("hasScannedForEncodings", *self.has_scanned_for_encodings);
+        value.insert_value(
+            "knownRegions",
+            self.known_regions
+                .iter()
+                .map(|r| r.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value.insert_value("projectDirPath", self.project_dir_path.as_str());
+        match self.project_roots.as_slice() {
+            [] => {}
+            [root] => {
+                value.insert_value("projectRoot", root.as_str());
+            }
+            roots => {
+                value.insert_value(
+                    "projectRoots",
+                    roots.iter().map(|r| r.as_str()).collect::<Vec<_>>(),
+                );
+            }
+        }
+        value.insert_value("mainGroup", self.main_group.id.as_str());
+        value.insert_value("attributes", self.attributes.clone());
+        if !self.packages.is_empty() {
+            value.insert_value(
+                "packageReferences",
+                self.packages
+                    .iter()
+                    .map(|p| p.id.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        value.insert_value(
+            "buildConfigurationList",
+            self.build_configuration_list.id.as_str(),
+        );
+        value.insert_value(
+            "targets",
+            self.targets
+                .iter()
+                .map(|t| t.id.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value
+    }
+}
 impl<'a> PBXProject<'a> {
     /// Returns the attributes of a given target.
     #[must_use]
     pub fn get_attributes_for_target_reference(
         &self,
-        target_reference: &String,
+        target_reference: &str,
     ) -> Option<&&PBXHashMap> {
         self.target_attributes.get(target_reference)
     }
@@ -0,0 +1,131 @@
+use crate::pbxproj::*;
+use std::collections::HashMap;
+
+/// Objects referring to each object of a collection, see
+/// [`PBXObjectCollection::referrer_index`].
+///
+/// A snapshot: it isn't updated as the collection changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PBXReferrerIndex(HashMap<String, Vec<String>>);
+
+impl PBXReferrerIndex {
+    /// Get references of objects referring to `reference`, sorted
+    pub fn referrers(&self, reference: &str) -> &[String] {
+        self.0
+            .get(reference)
+            .map(|referrers| referrers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Whether no object refers to `reference`
+    pub fn is_unreferenced(&self, reference: &str) -> bool {
+        self.referrers(reference).is_empty()
+    }
+}
+
+impl PBXObjectCollection {
+    /// Get references of objects held by the object with the given reference in any field,
+    /// including keys of nested objects, e.g. targets in `TargetAttributes`. Sorted, without
+    /// duplicates.
+    pub fn get_references(&self, reference: &str) -> Vec<&str> {
+        let mut references = vec![];
+        if let Some(object) = self.map.get(reference) {
+            self.collect_references(object, &mut references);
+        }
+        references.sort_unstable();
+        references.dedup();
+        references
+    }
+
+    /// Get references of objects holding `reference` in any field, e.g. build files of a file
+    /// reference, or its parent group. Sorted.
+    ///
+    /// Every object is scanned: use [`PBXObjectCollection::referrer_index`] for many lookups.
+    pub fn referrers(&self, reference: &str) -> Vec<&str> {
+        let mut referrers = self
+            .map
+            .keys()
+            .filter(|referrer| self.get_references(referrer).contains(&reference))
+            .map(|referrer| referrer.as_str())
+            .collect::<Vec<_>>();
+        referrers.sort_unstable();
+        referrers
+    }
+
+    /// Index objects referring to each object in a single scan, see
+    /// [`PBXObjectCollection::referrers`].
+    pub fn referrer_index(&self) -> PBXReferrerIndex {
+        let mut index = HashMap::<String, Vec<String>>::new();
+        for referrer in self.map.keys() {
+            for reference in self.get_references(referrer) {
+                index
+                    .entry(reference.to_string())
+                    .or_default()
+                    .push(referrer.clone());
+            }
+        }
+        index.values_mut().for_each(|referrers| referrers.sort());
+        PBXReferrerIndex(index)
+    }
+
+    /// Push references held by `object` to objects of the collection
+    fn collect_references<'a>(&'a self, object: &'a PBXHashMap, references: &mut Vec<&'a str>) {
+        for (key, value) in object.0.iter() {
+            if let Some((key, _)) = self.map.get_key_value(key.as_str()) {
+                references.push(key);
+            }
+            self.collect_value_references(value, references);
+        }
+    }
+
+    fn collect_value_references<'a>(&'a self, value: &'a PBXValue, references: &mut Vec<&'a str>) {
+        match value {
+            PBXValue::String(reference) if self.map.contains_key(reference) => {
+                references.push(reference)
+            }
+            PBXValue::Object(object) => self.collect_references(object, references),
+            PBXValue::Vec(values) => values
+                .0
+                .iter()
+                .for_each(|value| self.collect_value_references(value, references)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn referrers() {
+        let root_object = test_demo_file!(demo1);
+        let file = root_object
+            .find_file_by_path("Source/Views/GuessView.swift")
+            .unwrap()
+            .id;
+        let group = root_object.find_group_by_path("Source/Views").unwrap().id;
+        let build_files = root_object
+            .build_files()
+            .into_iter()
+            .filter(|b| b.file.as_ref().map(|f| &f.id) == Some(&file))
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+        assert!(!build_files.is_empty());
+
+        let mut expected = build_files.iter().map(String::as_str).collect::<Vec<_>>();
+        expected.push(group.as_str());
+        expected.sort();
+        assert_eq!(expected, root_object.referrers(&file));
+        assert!(root_object.get_references(&group).contains(&file.as_str()));
+
+        let index = root_object.referrer_index();
+        assert_eq!(expected, index.referrers(&file));
+        assert!(index.is_unreferenced(root_object.root_object_reference()));
+        // Targets are referenced by the project, including `TargetAttributes` keys
+        let target = root_object.targets()[0].id.clone();
+        assert!(index
+            .referrers(&target)
+            .contains(&root_object.root_object_reference().to_string()));
+    }
+}
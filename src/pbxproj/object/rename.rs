@@ -0,0 +1,113 @@
+use super::copy::remap_references;
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::collections::HashMap;
+
+impl PBXObjectCollection {
+    /// Rename the object with reference `old` to `new`, rewriting every field of every object
+    /// pointing at it, e.g. build files, group children, container proxies and configuration
+    /// lists. Fails if `old` doesn't exist or `new` is already taken.
+    pub fn rename_reference(&mut self, old: &str, new: &str) -> Result<()> {
+        self.rename_references(HashMap::from([(old.to_string(), new.to_string())]))
+    }
+
+    /// Rename objects by old to new reference at once, see
+    /// [`PBXObjectCollection::rename_reference`]. References can be swapped or rotated.
+    pub fn rename_references(&mut self, references: HashMap<String, String>) -> Result<()> {
+        for (old, new) in references.iter() {
+            if !self.map.contains_key(old) {
+                anyhow::bail!("Object {old:?} not found");
+            }
+            if new.is_empty() || new.contains(|c: char| c.is_whitespace() || c == '"') {
+                anyhow::bail!("{new:?} is not a valid reference");
+            }
+            if self.map.contains_key(new) && !references.contains_key(new) {
+                anyhow::bail!("Reference {new:?} already exists");
+            }
+        }
+        let mut targets = references.values().collect::<Vec<_>>();
+        targets.sort();
+        if targets.windows(2).any(|pair| pair[0] == pair[1]) {
+            anyhow::bail!("Multiple objects renamed to the same reference");
+        }
+
+        let remap = |object: &mut PBXHashMap| {
+            let mut value = PBXValue::Object(std::mem::take(object));
+            remap_references(&mut value, &references);
+            if let PBXValue::Object(value) = value {
+                *object = value;
+            }
+        };
+        let mut objects = references
+            .iter()
+            .map(|(old, new)| (new.clone(), self.remove_object(old).unwrap()))
+            .collect::<Vec<_>>();
+        self.edit_objects(|_, object| remap(object));
+        for (reference, mut object) in objects.drain(..) {
+            remap(&mut object);
+            self.insert_at(reference, object);
+        }
+        self.invalidate_path_index();
+
+        Ok(())
+    }
+}
+
+impl PBXRootObject {
+    /// Rename the object with reference `old` to `new`, including the root object reference,
+    /// see [`PBXObjectCollection::rename_reference`].
+    pub fn rename_reference(&mut self, old: &str, new: &str) -> Result<()> {
+        self.objects.rename_reference(old, new)?;
+        if self.root_object_reference == old {
+            self.root_object_reference = new.to_string();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn rename_reference() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let project = root_object.root_object_reference().to_string();
+        let file = root_object
+            .build_files()
+            .into_iter()
+            .find_map(|build_file| build_file.file)
+            .unwrap()
+            .id;
+
+        root_object
+            .rename_reference(&target, "AAAAAAAAAAAAAAAAAAAAAAAA")
+            .unwrap();
+        root_object
+            .rename_reference(&file, "BBBBBBBBBBBBBBBBBBBBBBBB")
+            .unwrap();
+        root_object
+            .rename_reference(&project, "CCCCCCCCCCCCCCCCCCCCCCCC")
+            .unwrap();
+
+        assert!(root_object.get_target(&target).is_none());
+        let project = root_object.root_project();
+        assert_eq!("CCCCCCCCCCCCCCCCCCCCCCCC", project.id);
+        assert_eq!("AAAAAAAAAAAAAAAAAAAAAAAA", project.targets[0].id);
+        assert!(project
+            .get_attributes_for_target_reference("AAAAAAAAAAAAAAAAAAAAAAAA")
+            .is_some());
+        assert!(root_object
+            .build_files()
+            .iter()
+            .any(|f| f.file.as_ref().map(|f| f.id.as_str()) == Some("BBBBBBBBBBBBBBBBBBBBBBBB")));
+
+        assert!(root_object
+            .rename_reference("missing", "DDDDDDDDDDDDDDDDDDDDDDDD")
+            .is_err());
+        assert!(root_object
+            .rename_reference("AAAAAAAAAAAAAAAAAAAAAAAA", "BBBBBBBBBBBBBBBBBBBBBBBB")
+            .is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::pbxproj::*;
+use anyhow::Result;
 
 /// [`PBXObject`] represents swift package dependency
 ///
@@ -24,8 +25,123 @@ impl<'a> AsPBXObject<'a> for XCSwiftPackageProductDependency<'a> {
     {
         Ok(Self {
             id,
-            product_name: value.try_get_string("productName")?,
+            product_name: objects.required_string(value, "productName")?,
             package: value.get_string("package").and_then(|key| objects.get(key)),
         })
     }
 }
+
+impl ToPBXHashMap for XCSwiftPackageProductDependency<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::XCSwiftPackageProductDependency);
+        value.insert_value("productName", self.product_name.as_str());
+        value.insert_some("package", self.package.as_ref().map(|p| p.id.as_str()));
+        value
+    }
+}
+
+impl PBXRootObject {
+    /// Link product `product_name` of the remote swift package at `repository_url` to the target
+    /// with the given reference.
+    ///
+    /// The package is added to the root project unless a package with the same url exists, in
+    /// which case its requirement is updated. The product is added to the target package
+    /// products and linked in the frameworks build phase. Returns the reference of the
+    /// `XCSwiftPackageProductDependency`, the existing one if the target already uses the product.
+    pub fn add_swift_package(
+        &mut self,
+        target: &str,
+        repository_url: &str,
+        product_name: &str,
+        requirement: XCVersionRequirement,
+    ) -> Result<String> {
+        if self.get_target(target).is_none() {
+            anyhow::bail!("Target {target:?} not found");
+        }
+        let package = self.add_remote_swift_package(repository_url, requirement)?;
+
+        let existing = self
+            .get_target(target)
+            .into_iter()
+            .flat_map(|t| t.package_product_dependencies)
+            .find(|product| {
+                product.product_name == product_name
+                    && product.package.as_ref().map(|p| p.id.as_str()) == Some(package.as_str())
+            })
+            .map(|product| product.id);
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+
+        let mut product = PBXHashMap::default();
+        product.insert_value("isa", PBXObjectKind::XCSwiftPackageProductDependency);
+        product.insert_value("package", package);
+        product.insert_value("productName", product_name);
+        let product = String::from(self.objects.insert_object(product, Some(target)));
+        self.objects
+            .get_object_mut(target)
+            .unwrap()
+            .vec_entry("packageProductDependencies")
+            .push(product.clone());
+
+        let phase = match self
+            .objects
+            .get_target_build_phase(target, &PBXBuildPhaseKind::Frameworks)
+        {
+            Some(phase) => phase,
+            None => self
+                .objects
+                .add_build_phase(target, PBXBuildPhaseKind::Frameworks)?,
+        };
+        let mut build_file = PBXHashMap::default();
+        build_file.insert_value("isa", PBXObjectKind::PBXBuildFile);
+        build_file.insert_value("productRef", product.as_str());
+        let build_file = String::from(self.objects.insert_object(build_file, Some(&phase)));
+        self.objects
+            .get_object_mut(&phase)
+            .unwrap()
+            .vec_entry("files")
+            .push(build_file);
+
+        Ok(product)
+    }
+
+    /// Get reference of the root project package at `repository_url` with its requirement set to
+    /// `requirement`, adding the package if missing.
+    fn add_remote_swift_package(
+        &mut self,
+        repository_url: &str,
+        requirement: XCVersionRequirement,
+    ) -> Result<String> {
+        let existing = self
+            .root_project()
+            .packages
+            .into_iter()
+            .find(|p| p.repository_url.map(|url| url.as_str()) == Some(repository_url))
+            .map(|p| p.id);
+        let package = match existing {
+            Some(package) => package,
+            None => {
+                let mut package = PBXHashMap::default();
+                package.insert_value("isa", PBXObjectKind::XCRemoteSwiftPackageReference);
+                package.insert_value("repositoryURL", repository_url);
+                let package = String::from(
+                    self.objects
+                        .insert_object(package, Some(&self.root_object_reference)),
+                );
+                self.objects
+                    .get_object_mut(&self.root_object_reference)
+                    .ok_or_else(|| anyhow::anyhow!("Root project not found"))?
+                    .vec_entry("packageReferences")
+                    .push(package.clone());
+                package
+            }
+        };
+        self.objects
+            .get_object_mut(&package)
+            .unwrap()
+            .insert_value("requirement", requirement);
+        Ok(package)
+    }
+}
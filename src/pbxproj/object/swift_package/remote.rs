@@ -28,8 +28,7 @@ impl<'a> XCRemoteSwiftPackageReference<'a> {
     pub fn name(&self) -> Option<&str> {
         self.repository_url
             .as_ref()
-            .map(|s| s.split("/").last())
-            .flatten()
+            .and_then(|s| s.split('/').next_back())
     }
 
     /// Get a reference to the xcremote swift package reference's version requirement.
@@ -58,8 +57,71 @@ impl<'a> AsPBXObject<'a> for XCRemoteSwiftPackageReference<'a> {
             repository_url: value.get_string("repositoryURL"),
             version_requirement: value
                 .get_value("requirement")
-                .map(|v| v.try_into().ok())
-                .flatten(),
+                .and_then(|v| v.try_into().ok()),
         })
     }
 }
+
+impl ToPBXHashMap for XCRemoteSwiftPackageReference<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::XCRemoteSwiftPackageReference);
+        value.insert_some("repositoryURL", self.repository_url.map(String::as_str));
+        value.insert_some("requirement", self.version_requirement.clone());
+        value
+    }
+}
+
+impl PBXObjectCollection {
+    /// Set `repositoryURL` of every XCRemoteSwiftPackageReference to the url returned by
+    /// `rewrite`, e.g. to redirect packages to a mirror. Returns references of the changed
+    /// package references with their previous url, sorted by reference.
+    pub fn rewrite_package_urls<F>(&mut self, mut rewrite: F) -> Vec<(String, String)>
+    where
+        F: FnMut(&str) -> String,
+    {
+        let mut changed = vec![];
+        self.edit_objects(|reference, object| {
+            let is_remote = object
+                .get_kind("isa")
+                .map(|k| k.is_xc_remote_swift_package_reference())
+                .unwrap_or_default();
+            let url = match object.get_string("repositoryURL") {
+                Some(url) if is_remote => url.clone(),
+                _ => return,
+            };
+            let rewritten = rewrite(&url);
+            if rewritten != url {
+                object.insert_value("repositoryURL", rewritten);
+                changed.push((reference.to_string(), url));
+            }
+        });
+        changed.sort();
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn rewrite_package_urls() {
+        let mut root_object = test_demo_file!(demo1);
+        let changed = root_object
+            .objects_mut()
+            .rewrite_package_urls(|url| url.replace("github.com", "mirror.example.com"));
+        assert_eq!(1, changed.len());
+        assert_eq!("https://github.com/apple/swift-log.git", changed[0].1);
+
+        let package = root_object.get_swift_package_reference(&changed[0].0);
+        assert_eq!(
+            Some(&"https://mirror.example.com/apple/swift-log.git".to_string()),
+            package.and_then(|p| p.repository_url)
+        );
+        assert!(root_object
+            .objects_mut()
+            .rewrite_package_urls(|url| url.to_string())
+            .is_empty());
+    }
+}
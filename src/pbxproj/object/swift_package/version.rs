@@ -6,7 +6,7 @@ use tap::Pipe;
 /// [`XCRemoteSwiftPackageReference`] version rules.
 ///
 /// [`XCRemoteSwiftPackageReference`]: crate::pbxproj::XCRemoteSwiftPackageReference
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum XCVersionRequirement {
     /// Version can be bumped up to the next major version.
     UpToNextMajorVersion(String),
@@ -31,8 +31,8 @@ impl TryFrom<&PBXValue> for XCVersionRequirement {
             .ok_or_else(|| anyhow::anyhow!("Can get XCVersionRequirement for non object type"))?;
         let key = map.try_get_string("kind")?;
         match key.as_str() {
-            "bracnh" => Self::Branch(map.try_get_string(&key)?.to_string()),
-            "revision" => Self::Revision(map.try_get_string(&key)?.to_string()),
+            "branch" => Self::Branch(map.try_get_string(key)?.to_string()),
+            "revision" => Self::Revision(map.try_get_string(key)?.to_string()),
             "exactVersion" => Self::Exact(map.try_get_string("version")?.to_string()),
             "versionRange" => {
                 let min = map.try_get_string("minimumVersion")?;
@@ -44,8 +44,8 @@ impl TryFrom<&PBXValue> for XCVersionRequirement {
                 Self::UpToNextMinorVersion(min.to_string())
             }
             "upToNextMajorVersion" => {
-                let max = map.try_get_string("maximumVersion")?;
-                Self::UpToNextMajorVersion(max.to_string())
+                let min = map.try_get_string("minimumVersion")?;
+                Self::UpToNextMajorVersion(min.to_string())
             }
             k => bail!("Unkown kind {k}"),
         }
@@ -58,29 +58,29 @@ impl From<XCVersionRequirement> for PBXValue {
         let mut collect = HashMap::default();
         match value {
             XCVersionRequirement::UpToNextMajorVersion(v) => {
-                collect.insert("kind".to_string(), "upToNextMajorVersion".into());
-                collect.insert("maximumVersion".to_string(), v.into());
+                collect.insert("kind".into(), "upToNextMajorVersion".into());
+                collect.insert("minimumVersion".into(), v.into());
             }
             XCVersionRequirement::UpToNextMinorVersion(v) => {
-                collect.insert("kind".to_string(), "upToNextMinorVersion".into());
-                collect.insert("minimumVersion".to_string(), v.into());
+                collect.insert("kind".into(), "upToNextMinorVersion".into());
+                collect.insert("minimumVersion".into(), v.into());
             }
             XCVersionRequirement::Range(s, e) => {
-                collect.insert("kind".to_string(), "versionRange".into());
-                collect.insert("minimumVersion".to_string(), e.into());
-                collect.insert("maximumVersion".to_string(), s.into());
+                collect.insert("kind".into(), "versionRange".into());
+                collect.insert("minimumVersion".into(), s.into());
+                collect.insert("maximumVersion".into(), e.into());
             }
             XCVersionRequirement::Exact(v) => {
-                collect.insert("kind".to_string(), "exactVersion".into());
-                collect.insert("version".to_string(), v.into());
+                collect.insert("kind".into(), "exactVersion".into());
+                collect.insert("version".into(), v.into());
             }
             XCVersionRequirement::Branch(v) => {
-                collect.insert("kind".to_string(), "branch".into());
-                collect.insert("branch".to_string(), v.into());
+                collect.insert("kind".into(), "branch".into());
+                collect.insert("branch".into(), v.into());
             }
             XCVersionRequirement::Revision(v) => {
-                collect.insert("kind".to_string(), "revision".into());
-                collect.insert("revision".to_string(), v.into());
+                collect.insert("kind".into(), "revision".into());
+                collect.insert("revision".into(), v.into());
             }
         }
 
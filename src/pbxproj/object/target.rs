@@ -1,7 +1,16 @@
+mod arch;
+mod builder;
 mod dependency;
+mod graph;
 mod info;
+mod info_plist;
+mod linkage;
 mod platform;
+mod settings;
+pub use builder::PBXTargetBuilder;
 pub use dependency::*;
+pub use info_plist::XCInfoPlistKey;
+pub use linkage::*;
 
 use anyhow::Result;
 
@@ -35,6 +44,8 @@ pub struct PBXTarget<'a> {
     pub product: Option<PBXFSReference<'a>>,
     /// Swift package product references.
     pub package_product_dependencies: Vec<XCSwiftPackageProductDependency<'a>>,
+    /// Synchronized folders whose files are members of the target.
+    pub file_system_synchronized_groups: Vec<PBXFileSystemSynchronizedRootGroup<'a>>,
     /// Target Kind
     pub kind: &'a PBXTargetKind,
     /// Target product install path. (relevant only for `PBXNativeTarget`)
@@ -74,7 +85,17 @@ impl<'a> AsPBXObject<'a> for PBXTarget<'a> {
             id,
             name: value.get_string("name"),
             product_name: value.get_string("productName"),
-            product_type: value.try_get_string("productType")?.as_str().into(),
+            // Aggregate and legacy targets build no product
+            product_type: match kind.is_native() {
+                true => objects
+                    .required_string(value, "productType")?
+                    .as_str()
+                    .into(),
+                false => value
+                    .get_string("productType")
+                    .map(|t| t.as_str().into())
+                    .unwrap_or_default(),
+            },
             build_configuration_list: value
                 .get_string("buildConfigurationList")
                 .and_then(|key| objects.get(key)),
@@ -97,6 +118,10 @@ impl<'a> AsPBXObject<'a> for PBXTarget<'a> {
                 .get_vec("packageProductDependencies")
                 .map(|v| objects.get_vec(v.as_vec_strings()))
                 .unwrap_or_default(),
+            file_system_synchronized_groups: value
+                .get_vec("fileSystemSynchronizedGroups")
+                .map(|v| objects.get_vec(v.as_vec_strings()))
+                .unwrap_or_default(),
             product_install_path: if kind.is_native() {
                 value.get_string("productInstallPath")
             } else {
@@ -113,6 +138,175 @@ impl<'a> AsPBXObject<'a> for PBXTarget<'a> {
     }
 }
 
+impl ToPBXHashMap for PBXTarget<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXTarget(self.kind.clone()));
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some("productName", self.product_name.map(String::as_str));
+        if self.product_type != PBXProductType::None {
+            value.insert_value("productType", self.product_type.to_string());
+        }
+        value.insert_some(
+            "buildConfigurationList",
+            self.build_configuration_list
+                .as_ref()
+                .map(|l| l.id.as_str()),
+        );
+        value.insert_value(
+            "buildPhases",
+            self.build_phases
+                .iter()
+                .map(|p| p.id.as_str())
+                .collect::<Vec<_>>(),
+        );
+        if !self.build_rules.is_empty() {
+            value.insert_value(
+                "buildRules",
+                self.build_rules
+                    .iter()
+                    .map(|r| r.id.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        value.insert_value(
+            "dependencies",
+            self.target_dependencies
+                .iter()
+                .map(|d| d.id.as_str())
+                .collect::<Vec<_>>(),
+        );
+        value.insert_some(
+            "productReference",
+            self.product.as_ref().map(|p| p.id.as_str()),
+        );
+        if !self.package_product_dependencies.is_empty() {
+            value.insert_value(
+                "packageProductDependencies",
+                self.package_product_dependencies
+                    .iter()
+                    .map(|d| d.id.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        if !self.file_system_synchronized_groups.is_empty() {
+            value.insert_value(
+                "fileSystemSynchronizedGroups",
+                self.file_system_synchronized_groups
+                    .iter()
+                    .map(|g| g.id.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        value.insert_some(
+            "productInstallPath",
+            self.product_install_path.map(String::as_str),
+        );
+        value.insert_some("buildToolPath", self.build_tool_path.map(String::as_str));
+        value.insert_some(
+            "buildArgumentsString",
+            self.build_arguments_string.map(String::as_str),
+        );
+        value.insert_some(
+            "passBuildSettingsInEnvironment",
+            self.pass_build_settings_in_environment.map(isize::from),
+        );
+        value.insert_some(
+            "buildWorkingDirectory",
+            self.build_working_directory.map(String::as_str),
+        );
+        value
+    }
+}
+
+impl PBXRootObject {
+    /// Add a new native target to the root project with Xcode's default build phases and
+    /// configurations for the given product type. Returns the reference of the created target.
+    pub fn add_native_target<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        product_type: PBXProductType,
+        platform: PBXTargetPlatform,
+    ) -> Result<String> {
+        let name = name.as_ref();
+        if self.get_target_by_name(name).is_some() {
+            anyhow::bail!("Target with {name:?} already exists");
+        }
+
+        let product_group = self
+            .objects
+            .map
+            .get(&self.root_object_reference)
+            .and_then(|p| p.get_string("productRefGroup"))
+            .cloned();
+
+        let product_path = match product_type.file_extension() {
+            Some(ext) => format!("{name}.{ext}"),
+            None => name.to_string(),
+        };
+        let mut product = PBXHashMap::default();
+        product.insert_value("isa", PBXObjectKind::from("PBXFileReference"));
+        if let Some(file_type) = product_type
+            .file_extension()
+            .and_then(crate::xcode::xcode_file_type)
+        {
+            product.insert_value("explicitFileType", file_type);
+        }
+        product.insert_value("includeInIndex", 0);
+        product.insert_value("path", product_path);
+        product.insert_value("sourceTree", PBXSourceTree::BuildProductsDir.to_string());
+        let product_reference = String::from(
+            self.objects
+                .insert_object(product, product_group.as_deref()),
+        );
+
+        if let Some(mut group) = product_group.and_then(|key| self.objects.get_object_mut(key)) {
+            if let Some(children) = group.get_vec_mut("children") {
+                children.push(product_reference.clone());
+            }
+        }
+
+        let preset = XCBuildSettingsPreset::from_product_type(&product_type);
+        let build_configuration_list = self.objects.add_default_configurations(preset, &platform);
+
+        let mut target = PBXHashMap::default();
+        target.insert_value("isa", PBXObjectKind::PBXTarget(PBXTargetKind::Native));
+        target.insert_value("buildConfigurationList", build_configuration_list);
+        target.insert_value("buildPhases", Vec::<String>::new());
+        target.insert_value("buildRules", Vec::<String>::new());
+        target.insert_value("dependencies", Vec::<String>::new());
+        target.insert_value("name", name);
+        target.insert_value("productName", name);
+        target.insert_value("productReference", product_reference);
+        target.insert_value("productType", product_type.to_string());
+        let reference = String::from(
+            self.objects
+                .insert_object(target, Some(&self.root_object_reference)),
+        );
+
+        for kind in [
+            PBXBuildPhaseKind::Sources,
+            PBXBuildPhaseKind::Frameworks,
+            PBXBuildPhaseKind::Resources,
+        ] {
+            self.objects.add_build_phase(&reference, kind)?;
+        }
+
+        let mut project = self
+            .objects
+            .get_object_mut(&self.root_object_reference)
+            .ok_or_else(|| anyhow::anyhow!("Root project not found"))?;
+        match project.get_vec_mut("targets") {
+            Some(targets) => targets.push(reference.clone()),
+            None => {
+                project.insert_value("targets", vec![reference.clone()]);
+            }
+        }
+
+        Ok(reference)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pbxproj::test_demo_file;
@@ -131,5 +325,120 @@ mod tests {
         };
     }
 
-    test_samples![demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11];
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo13,
+        legacy1
+    ];
+
+    #[test]
+    fn add_native_target() {
+        use crate::pbxproj::*;
+        let mut root_object = test_demo_file!(demo1);
+        let reference = root_object
+            .add_native_target("Widget", PBXProductType::Framework, PBXTargetPlatform::IOS)
+            .unwrap();
+
+        let target = root_object.get_target(&reference).unwrap();
+        let list = target.build_configuration_list.as_ref().unwrap();
+        assert_eq!(Some(&String::from("Widget")), target.name);
+        assert_eq!(3, target.build_phases.len());
+        assert_eq!(2, list.build_configurations.len());
+        assert_eq!(
+            Some(&true),
+            list.build_configurations[0]
+                .build_settings
+                .get_bool("DEFINES_MODULE")
+        );
+        assert_eq!(
+            Some(&String::from("Widget.framework")),
+            target.product.as_ref().and_then(|p| p.path)
+        );
+        assert_eq!(2, root_object.root_project().targets.len());
+        assert!(root_object
+            .add_native_target("Widget", PBXProductType::Framework, PBXTargetPlatform::IOS)
+            .is_err());
+    }
+
+    #[test]
+    fn set_bridging_header() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let header = root_object
+            .set_bridging_header(&target, "Source/Wordle-Bridging-Header.h")
+            .unwrap();
+
+        let file = root_object.get_file(&header).unwrap();
+        assert_eq!(Some(&"Wordle-Bridging-Header.h".to_string()), file.path);
+        assert_eq!(
+            Some(header),
+            root_object
+                .get_group_by_name_or_path("Source")
+                .and_then(|g| g
+                    .children
+                    .into_iter()
+                    .map(|c| c.id)
+                    .find(|id| id == &file.id))
+        );
+
+        let settings = |root_object: &crate::pbxproj::PBXRootObject| {
+            root_object.targets()[0]
+                .build_configuration_list
+                .as_ref()
+                .unwrap()
+                .build_configurations
+                .iter()
+                .map(|c| {
+                    c.build_settings
+                        .get_string("SWIFT_OBJC_BRIDGING_HEADER")
+                        .cloned()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![Some("Source/Wordle-Bridging-Header.h".to_string()); 2],
+            settings(&root_object)
+        );
+
+        root_object.remove_bridging_header(&target).unwrap();
+        assert_eq!(vec![None; 2], settings(&root_object));
+    }
+
+    #[test]
+    fn set_module_map() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let module_map = root_object
+            .set_module_map(&target, "Support/module.modulemap")
+            .unwrap();
+        root_object.set_defines_module(&target, true).unwrap();
+        root_object
+            .set_product_module_name(&target, Some("WordleKit"))
+            .unwrap();
+
+        let file = root_object.get_file(&module_map).unwrap();
+        assert_eq!(
+            Some(&"sourcecode.module-map".to_string()),
+            file.last_known_file_type
+        );
+        assert!(root_object.get_group_by_name_or_path("Support").is_some());
+
+        for configuration in root_object.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations
+            .iter()
+        {
+            let settings = configuration.build_settings;
+            assert_eq!(
+                Some(&"Support/module.modulemap".to_string()),
+                settings.get_string("MODULEMAP_FILE")
+            );
+            assert_eq!(Some(&true), settings.get_bool("DEFINES_MODULE"));
+            assert_eq!(
+                Some(&"WordleKit".to_string()),
+                settings.get_string("PRODUCT_MODULE_NAME")
+            );
+        }
+    }
 }
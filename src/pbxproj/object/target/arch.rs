@@ -0,0 +1,234 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+const SIMULATOR_SDK: &str = "iphonesimulator*";
+
+/// Build setting key, conditioned on `sdk` if any, e.g. `EXCLUDED_ARCHS[sdk=iphonesimulator*]`.
+fn conditional_key(key: &str, sdk: Option<&str>) -> String {
+    match sdk {
+        Some(sdk) => format!("{key}[sdk={sdk}]"),
+        None => key.to_string(),
+    }
+}
+
+/// Remove setting `key` from `settings`, also when inserted quoted or in another case, see
+/// [`PBXHashMap::find_key`].
+fn remove_setting(settings: &mut PBXHashMap, key: &str) -> Option<PBXValue> {
+    let key = settings.find_key(key)?.clone();
+    settings.remove_value(&key)
+}
+
+/// Set setting `key` of `settings`, replacing any quoted spelling of it, see [`remove_setting`].
+fn set_setting<V: Into<PBXValue>>(settings: &mut PBXHashMap, key: &str, value: V) {
+    remove_setting(settings, key);
+    settings.insert_value(key, value);
+}
+
+impl PBXRootObject {
+    /// Set `ARCHS` across all configurations of the target with the given reference, optionally
+    /// conditioned on `sdk`, e.g. `iphoneos*`. Empty `archs` removes the setting.
+    pub fn set_archs(&mut self, target: &str, sdk: Option<&str>, archs: &[&str]) -> Result<()> {
+        self.set_archs_setting(target, "ARCHS", sdk, archs)
+    }
+
+    /// Set `EXCLUDED_ARCHS` across all configurations of the target with the given reference,
+    /// optionally conditioned on `sdk`. Empty `archs` removes the setting.
+    pub fn set_excluded_archs(
+        &mut self,
+        target: &str,
+        sdk: Option<&str>,
+        archs: &[&str],
+    ) -> Result<()> {
+        self.set_archs_setting(target, "EXCLUDED_ARCHS", sdk, archs)
+    }
+
+    /// Set `VALID_ARCHS` across all configurations of the target with the given reference,
+    /// optionally conditioned on `sdk`. Empty `archs` removes the setting.
+    ///
+    /// NOTE: `VALID_ARCHS` is deprecated since Xcode 12 in favor of `EXCLUDED_ARCHS`.
+    pub fn set_valid_archs(
+        &mut self,
+        target: &str,
+        sdk: Option<&str>,
+        archs: &[&str],
+    ) -> Result<()> {
+        self.set_archs_setting(target, "VALID_ARCHS", sdk, archs)
+    }
+
+    /// Set `ONLY_ACTIVE_ARCH` in the configuration named `configuration` of the target with the
+    /// given reference, optionally conditioned on `sdk`. `None` removes the setting.
+    pub fn set_only_active_arch(
+        &mut self,
+        target: &str,
+        configuration: &str,
+        sdk: Option<&str>,
+        only_active_arch: Option<bool>,
+    ) -> Result<()> {
+        let key = conditional_key("ONLY_ACTIVE_ARCH", sdk);
+        let configuration = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?
+            .build_configuration_list
+            .and_then(|list| {
+                list.build_configurations
+                    .into_iter()
+                    .find(|c| c.name.as_str() == configuration)
+                    .map(|c| c.id)
+            })
+            .ok_or_else(|| anyhow::anyhow!("Configuration {configuration:?} not found"))?;
+
+        let mut configuration = self.objects.get_object_mut(&configuration);
+        if let Some(settings) = configuration
+            .as_mut()
+            .and_then(|c| c.get_object_mut("buildSettings"))
+        {
+            match only_active_arch {
+                Some(value) => set_setting(settings, &key, value),
+                None => {
+                    remove_setting(settings, &key);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exclude `arm64` from iOS simulator builds of the target with the given reference, the
+    /// usual workaround for dependencies that ship no arm64 simulator slice.
+    pub fn exclude_simulator_arm64(&mut self, target: &str) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                let key = conditional_key("EXCLUDED_ARCHS", Some(SIMULATOR_SDK));
+                let mut archs = archs_value(remove_setting(settings, &key).as_ref());
+                if !archs.iter().any(|a| a == "arm64") {
+                    archs.push("arm64".into());
+                }
+                settings.insert_value(key, archs.join(" "));
+            })
+    }
+
+    /// Revert [`PBXRootObject::exclude_simulator_arm64`] and drop deprecated `VALID_ARCHS`
+    /// settings of the target with the given reference, so it builds natively on Apple Silicon.
+    pub fn include_simulator_arm64(&mut self, target: &str) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                let key = conditional_key("EXCLUDED_ARCHS", Some(SIMULATOR_SDK));
+                let archs = archs_value(remove_setting(settings, &key).as_ref())
+                    .into_iter()
+                    .filter(|a| a != "arm64")
+                    .collect::<Vec<_>>();
+                if !archs.is_empty() {
+                    settings.insert_value(key, archs.join(" "));
+                }
+
+                settings
+                    .0
+                    .retain(|key, _| !key.trim_start_matches('"').starts_with("VALID_ARCHS"));
+            })
+    }
+
+    fn set_archs_setting(
+        &mut self,
+        target: &str,
+        key: &str,
+        sdk: Option<&str>,
+        archs: &[&str],
+    ) -> Result<()> {
+        let key = conditional_key(key, sdk);
+        let value = archs.join(" ");
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                if value.is_empty() {
+                    remove_setting(settings, &key);
+                } else {
+                    set_setting(settings, &key, value.as_str());
+                }
+            })
+    }
+}
+
+/// Architectures of a build setting value, which can be either space separated or a list.
+fn archs_value(value: Option<&PBXValue>) -> Vec<String> {
+    match value {
+        Some(PBXValue::String(value)) => value.split_whitespace().map(String::from).collect(),
+        Some(PBXValue::Vec(values)) => values
+            .as_vec_strings()
+            .into_iter()
+            .flat_map(|v| v.split_whitespace())
+            .map(String::from)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn exclude_and_include_simulator_arm64() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let settings = |root_object: &PBXRootObject, key: &str| {
+            root_object.targets()[0]
+                .build_configuration_list
+                .as_ref()
+                .unwrap()
+                .build_configurations[0]
+                .build_settings
+                .get_value(key)
+                .cloned()
+        };
+
+        root_object.exclude_simulator_arm64(&target).unwrap();
+        root_object.exclude_simulator_arm64(&target).unwrap();
+        root_object
+            .set_valid_archs(&target, None, &["arm64", "x86_64"])
+            .unwrap();
+        assert_eq!(
+            Some(PBXValue::from("arm64")),
+            settings(&root_object, "EXCLUDED_ARCHS[sdk=iphonesimulator*]")
+        );
+        assert_eq!(
+            Some(PBXValue::from("arm64 x86_64")),
+            settings(&root_object, "VALID_ARCHS")
+        );
+
+        root_object.include_simulator_arm64(&target).unwrap();
+        assert_eq!(
+            None,
+            settings(&root_object, "EXCLUDED_ARCHS[sdk=iphonesimulator*]")
+        );
+        assert_eq!(None, settings(&root_object, "VALID_ARCHS"));
+
+        root_object
+            .set_only_active_arch(&target, "Debug", None, Some(true))
+            .unwrap();
+        assert_eq!(
+            Some(PBXValue::Bool(true)),
+            settings(&root_object, "ONLY_ACTIVE_ARCH")
+        );
+    }
+
+    #[test]
+    fn simulator_arm64_after_reparse() {
+        let key = "EXCLUDED_ARCHS[sdk=iphonesimulator*]";
+        let reparse = |root_object: &PBXRootObject| {
+            PBXRootObject::try_from(root_object.to_pbxproj_string().as_str()).unwrap()
+        };
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+
+        root_object.exclude_simulator_arm64(&target).unwrap();
+        let mut root_object = reparse(&root_object);
+        root_object.exclude_simulator_arm64(&target).unwrap();
+        let content = root_object.to_pbxproj_string();
+        assert_eq!(2, content.matches(&format!("\"{key}\" = arm64;")).count());
+
+        let mut root_object = reparse(&root_object);
+        root_object.include_simulator_arm64(&target).unwrap();
+        assert!(!reparse(&root_object)
+            .to_pbxproj_string()
+            .contains("EXCLUDED_ARCHS"));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+impl<'a> PBXTarget<'a> {
+    /// Start building a new native target with the given name, an iOS application by default.
+    ///
+    /// ```
+    /// use xcodeproj::pbxproj::*;
+    ///
+    /// # fn build(root_object: &mut PBXRootObject) -> anyhow::Result<()> {
+    /// let target = PBXTarget::builder("Widget")
+    ///     .product_type(PBXProductType::Framework)
+    ///     .deployment_target("16.0")
+    ///     .source("Widget/Widget.swift")
+    ///     .resource("Widget/Assets.xcassets")
+    ///     .framework("SwiftUI.framework")
+    ///     .dependency("Core")
+    ///     .build(root_object)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder<S: Into<String>>(name: S) -> PBXTargetBuilder {
+        PBXTargetBuilder::new(name)
+    }
+}
+
+/// Builder of native targets, see [`PBXTarget::builder`].
+///
+/// Nothing is added to the project until [`PBXTargetBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PBXTargetBuilder {
+    name: String,
+    product_type: PBXProductType,
+    platform: PBXTargetPlatform,
+    deployment_target: Option<String>,
+    build_settings: PBXHashMap,
+    sources: Vec<PathBuf>,
+    resources: Vec<PathBuf>,
+    frameworks: Vec<PathBuf>,
+    packages: Vec<(String, String, XCVersionRequirement)>,
+    dependencies: Vec<String>,
+}
+
+impl PBXTargetBuilder {
+    /// iOS application target with the given name
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            product_type: PBXProductType::Application,
+            platform: PBXTargetPlatform::IOS,
+            deployment_target: None,
+            build_settings: PBXHashMap::default(),
+            sources: vec![],
+            resources: vec![],
+            frameworks: vec![],
+            packages: vec![],
+            dependencies: vec![],
+        }
+    }
+
+    /// Set target product type
+    pub fn product_type(mut self, product_type: PBXProductType) -> Self {
+        self.product_type = product_type;
+        self
+    }
+
+    /// Set target platform
+    pub fn platform(mut self, platform: PBXTargetPlatform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Set minimum OS version of the target platform, e.g. `16.0`
+    pub fn deployment_target<S: Into<String>>(mut self, version: S) -> Self {
+        self.deployment_target = Some(version.into());
+        self
+    }
+
+    /// Set build setting `key` to `value` in every target configuration
+    pub fn setting<K: Into<smol_str::SmolStr>, V: Into<PBXValue>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        self.build_settings.insert_value(key, value);
+        self
+    }
+
+    /// Compile the file at `path`, relative to the main group
+    pub fn source<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.sources.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Copy the file at `path`, relative to the main group, to the product resources
+    pub fn resource<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.resources.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Link a framework. A bare name, e.g. `UIKit.framework`, is a SDK framework; an
+    /// `.xcframework` is added with [`PBXRootObject::add_xcframework`] without embedding;
+    /// any other path is relative to the main group.
+    pub fn framework<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.frameworks.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Link `product` of the remote swift package at `repository_url`, see
+    /// [`PBXRootObject::add_swift_package`]
+    pub fn package<U: Into<String>, P: Into<String>>(
+        mut self,
+        repository_url: U,
+        product: P,
+        requirement: XCVersionRequirement,
+    ) -> Self {
+        self.packages
+            .push((repository_url.into(), product.into(), requirement));
+        self
+    }
+
+    /// Depend on the project target with the given name
+    pub fn dependency<S: Into<String>>(mut self, target: S) -> Self {
+        self.dependencies.push(target.into());
+        self
+    }
+
+    /// Add the target and every object it needs to `root_object`: product, configurations,
+    /// build phases, file references, packages and dependencies. Either everything is added or,
+    /// on error, nothing is. Returns the reference of the created target.
+    pub fn build(self, root_object: &mut PBXRootObject) -> Result<String> {
+        root_object.transaction(|root_object| self.materialize(root_object))
+    }
+
+    fn materialize(self, root_object: &mut PBXRootObject) -> Result<String> {
+        let target =
+            root_object.add_native_target(&self.name, self.product_type, self.platform.clone())?;
+
+        let deployment_target = match (
+            self.platform.deployment_target_key(),
+            self.deployment_target,
+        ) {
+            (Some(key), Some(version)) => Some((key, version)),
+            (None, Some(_)) => anyhow::bail!("{} has no deployment target", self.platform),
+            _ => None,
+        };
+        let build_settings = self.build_settings;
+        root_object
+            .objects
+            .update_target_build_settings(&target, |settings| {
+                settings.0.extend(build_settings.0.clone());
+                if let Some((key, version)) = &deployment_target {
+                    settings.insert_value(*key, version.as_str());
+                }
+            })?;
+
+        for (paths, kind) in [
+            (self.sources, PBXBuildPhaseKind::Sources),
+            (self.resources, PBXBuildPhaseKind::Resources),
+        ] {
+            let phase = root_object
+                .objects
+                .get_target_build_phase(&target, &kind)
+                .ok_or_else(|| anyhow::anyhow!("{kind:?} build phase not found"))?;
+            for path in paths {
+                let file = root_object.get_or_add_file_reference(&path)?;
+                root_object.objects.add_build_file(&phase, &file)?;
+            }
+        }
+
+        for path in self.frameworks {
+            add_framework(root_object, &target, &path)?;
+        }
+
+        for (repository_url, product, requirement) in self.packages {
+            root_object.add_swift_package(&target, &repository_url, &product, requirement)?;
+        }
+
+        for name in self.dependencies {
+            let dependency = root_object
+                .get_target_by_name(&name)
+                .ok_or_else(|| anyhow::anyhow!("Target {name:?} not found"))?
+                .id;
+            root_object.add_target_dependency(&target, &dependency)?;
+        }
+
+        Ok(target)
+    }
+}
+
+/// Link framework at `path` to `target`, see [`PBXTargetBuilder::framework`]
+fn add_framework(root_object: &mut PBXRootObject, target: &str, path: &Path) -> Result<()> {
+    if path.extension().map(|ext| ext == "xcframework") == Some(true) {
+        root_object.add_xcframework(target, path, false)?;
+        return Ok(());
+    }
+
+    let file = if path.components().count() == 1 {
+        let group = root_object.get_or_add_group("Frameworks")?;
+        let sdk_path = Path::new("System/Library/Frameworks").join(path);
+        let sdk_path = sdk_path.to_string_lossy();
+        let existing = root_object
+            .objects
+            .get_fs_references_by_path(sdk_path.as_ref())
+            .first()
+            .cloned();
+        match existing {
+            Some(file) => file,
+            None => {
+                let file = String::from(
+                    root_object
+                        .objects
+                        .insert_file_reference(sdk_path.as_ref(), PBXSourceTree::SdkRoot),
+                );
+                if let Some(mut object) = root_object.objects.get_object_mut(&file) {
+                    object.insert_value("name", path.to_string_lossy().as_ref());
+                }
+                root_object.objects.register_child(&group, &file)?;
+                file
+            }
+        }
+    } else {
+        root_object.get_or_add_file_reference(path)?
+    };
+
+    let phase = root_object
+        .objects
+        .get_target_build_phase(target, &PBXBuildPhaseKind::Frameworks)
+        .ok_or_else(|| anyhow::anyhow!("Frameworks build phase not found"))?;
+    root_object.objects.add_build_file(&phase, &file)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn target_builder() {
+        let mut root_object = test_demo_file!(demo1);
+        let url = "https://github.com/apple/swift-collections";
+        let reference = PBXTarget::builder("Widget")
+            .product_type(PBXProductType::Framework)
+            .deployment_target("16.4")
+            .setting("SWIFT_VERSION", "5.10")
+            .source("Widget/Widget.swift")
+            .resource("Widget/Assets.xcassets")
+            .framework("SwiftUI.framework")
+            .package(
+                url,
+                "Collections",
+                XCVersionRequirement::UpToNextMajorVersion("1.0.0".into()),
+            )
+            .dependency("Wordle")
+            .build(&mut root_object)
+            .unwrap();
+
+        let target = root_object.get_target(&reference).unwrap();
+        let files = |kind: PBXBuildPhaseKind| {
+            target
+                .build_phases
+                .iter()
+                .find(|p| p.kind == kind)
+                .unwrap()
+                .files
+                .iter()
+                .map(|f| {
+                    f.file
+                        .as_ref()
+                        .and_then(|f| f.name.or(f.path))
+                        .cloned()
+                        .or_else(|| f.product.as_ref().map(|p| p.product_name.clone()))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["Widget.swift"], files(PBXBuildPhaseKind::Sources));
+        assert_eq!(vec!["Assets.xcassets"], files(PBXBuildPhaseKind::Resources));
+        let mut frameworks = files(PBXBuildPhaseKind::Frameworks);
+        frameworks.sort();
+        assert_eq!(vec!["Collections", "SwiftUI.framework"], frameworks);
+
+        assert_eq!(1, target.package_product_dependencies.len());
+        assert_eq!(
+            Some(url),
+            target.package_product_dependencies[0]
+                .package
+                .as_ref()
+                .and_then(|p| p.repository_url)
+                .map(|u| u.as_str())
+        );
+        assert_eq!(
+            Some("Wordle"),
+            target.target_dependencies[0]
+                .target
+                .as_ref()
+                .and_then(|t| t.name)
+                .map(|n| n.as_str())
+        );
+        for configuration in target
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations
+            .iter()
+        {
+            let settings = configuration.build_settings;
+            assert_eq!(
+                Some(&"16.4".to_string()),
+                settings.get_string("IPHONEOS_DEPLOYMENT_TARGET")
+            );
+            assert_eq!(
+                Some(&"5.10".to_string()),
+                settings.get_string("SWIFT_VERSION")
+            );
+        }
+
+        // Failing builds leave the project untouched
+        let targets = root_object.targets().len();
+        let objects = root_object.objects().map.len();
+        assert!(PBXTarget::builder("Broken")
+            .source("Broken/Broken.swift")
+            .dependency("Missing")
+            .build(&mut root_object)
+            .is_err());
+        assert_eq!(targets, root_object.targets().len());
+        assert_eq!(objects, root_object.objects().map.len());
+    }
+}
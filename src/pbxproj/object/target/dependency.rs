@@ -0,0 +1,107 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+/// [`PBXObject`] referencing other targets through content proxies.
+#[derive(Debug, derive_new::new)]
+pub struct PBXTargetDependency<'a> {
+    /// ID Reference
+    pub id: String,
+    /// Target name.
+    pub name: Option<&'a String>,
+    /// Platform filter attribute.
+    pub platform_filter: Option<&'a String>,
+    /// Target
+    pub target: Option<PBXTarget<'a>>,
+    /// Target proxy
+    pub target_proxy: Option<PBXContainerItemProxy<'a>>,
+    /// Product reference.
+    pub product: Option<XCSwiftPackageProductDependency<'a>>,
+}
+
+impl<'a> AsPBXObject<'a> for PBXTargetDependency<'a> {
+    fn as_pbx_object(
+        id: String,
+        value: &'a PBXHashMap,
+        objects: &'a PBXObjectCollection,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized + 'a,
+    {
+        Ok(Self {
+            id,
+            name: value.get_string("name"),
+            platform_filter: value.get_string("platformFilter"),
+            target: value.get_string("target").and_then(|key| objects.get(key)),
+            target_proxy: value
+                .get_string("targetProxy")
+                .and_then(|key| objects.get(key)),
+            product: value
+                .get_string("productRef")
+                .and_then(|key| objects.get(key)),
+        })
+    }
+}
+
+impl ToPBXHashMap for PBXTargetDependency<'_> {
+    fn to_hashmap(&self) -> PBXHashMap {
+        let mut value = PBXHashMap::default();
+        value.insert_value("isa", PBXObjectKind::PBXTargetDependency);
+        value.insert_some("name", self.name.map(String::as_str));
+        value.insert_some("platformFilter", self.platform_filter.map(String::as_str));
+        value.insert_some("target", self.target.as_ref().map(|t| t.id.as_str()));
+        value.insert_some(
+            "targetProxy",
+            self.target_proxy.as_ref().map(|p| p.id.as_str()),
+        );
+        value.insert_some("productRef", self.product.as_ref().map(|p| p.id.as_str()));
+        value
+    }
+}
+
+impl PBXRootObject {
+    /// Make the target with the given reference depend on target `dependency` of the same
+    /// project, so Xcode builds `dependency` first. Returns the reference of the
+    /// `PBXTargetDependency`, the existing one if the target already depends on `dependency`.
+    pub fn add_target_dependency(&mut self, target: &str, dependency: &str) -> Result<String> {
+        if target == dependency {
+            anyhow::bail!("Target {target:?} can't depend on itself");
+        }
+        let existing = self
+            .get_target(target)
+            .ok_or_else(|| anyhow::anyhow!("Target {target:?} not found"))?
+            .target_dependencies
+            .into_iter()
+            .find(|d| d.target.as_ref().map(|t| t.id.as_str()) == Some(dependency))
+            .map(|d| d.id);
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+        let name = self
+            .get_target(dependency)
+            .ok_or_else(|| anyhow::anyhow!("Target {dependency:?} not found"))?
+            .name
+            .cloned()
+            .unwrap_or_default();
+
+        let mut proxy = PBXHashMap::default();
+        proxy.insert_value("isa", PBXObjectKind::PBXContainerItemProxy);
+        proxy.insert_value("containerPortal", self.root_object_reference.as_str());
+        proxy.insert_value("proxyType", 1);
+        proxy.insert_value("remoteGlobalIDString", dependency);
+        proxy.insert_value("remoteInfo", name);
+        let proxy = String::from(self.objects.insert_object(proxy, Some(target)));
+
+        let mut object = PBXHashMap::default();
+        object.insert_value("isa", PBXObjectKind::PBXTargetDependency);
+        object.insert_value("target", dependency);
+        object.insert_value("targetProxy", proxy);
+        let reference = String::from(self.objects.insert_object(object, Some(target)));
+
+        self.objects
+            .get_object_mut(target)
+            .unwrap()
+            .vec_entry("dependencies")
+            .push(reference.clone());
+        Ok(reference)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+impl PBXRootObject {
+    /// Get targets depending on the target with the given name, directly or transitively,
+    /// sorted by name, e.g. to find what to rebuild or retest when a module changes.
+    ///
+    /// A target depends on another through a `PBXTargetDependency`, or by linking its product
+    /// in a frameworks build phase.
+    pub fn dependents_of(&self, name: &str) -> Result<Vec<PBXTarget<'_>>> {
+        let target = self
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("Target {name:?} not found"))?
+            .id;
+
+        let mut dependents = HashMap::<String, Vec<String>>::new();
+        for (dependent, dependencies) in self.target_dependency_graph() {
+            for dependency in dependencies {
+                dependents
+                    .entry(dependency)
+                    .or_default()
+                    .push(dependent.clone());
+            }
+        }
+
+        let mut found = HashSet::new();
+        let mut pending = vec![target.clone()];
+        while let Some(reference) = pending.pop() {
+            for dependent in dependents.get(&reference).into_iter().flatten() {
+                if dependent != &target && found.insert(dependent.clone()) {
+                    pending.push(dependent.clone());
+                }
+            }
+        }
+
+        let mut targets = self
+            .targets()
+            .into_iter()
+            .filter(|t| found.contains(&t.id))
+            .collect::<Vec<_>>();
+        targets.sort_by(|a, b| (a.name, &a.id).cmp(&(b.name, &b.id)));
+        Ok(targets)
+    }
+
+    /// Get references of targets each target directly depends on
+    fn target_dependency_graph(&self) -> HashMap<String, HashSet<String>> {
+        let targets = self.targets();
+        let products = targets
+            .iter()
+            .filter_map(|t| Some((t.product.as_ref()?.id.as_str(), t.id.as_str())))
+            .collect::<HashMap<_, _>>();
+
+        let mut graph = HashMap::new();
+        for target in targets.iter() {
+            let declared = target.target_dependencies.iter().filter_map(|dependency| {
+                match (&dependency.target, &dependency.target_proxy) {
+                    (Some(target), _) => Some(target.id.as_str()),
+                    (None, Some(proxy))
+                        if proxy.container_portal_reference == &self.root_object_reference =>
+                    {
+                        proxy.remote_global_id_reference.map(|r| r.as_str())
+                    }
+                    _ => None,
+                }
+            });
+            let linked = target
+                .build_phases
+                .iter()
+                .filter(|phase| phase.is_frameworks())
+                .flat_map(|phase| phase.files.iter())
+                .filter_map(|file| products.get(file.file.as_ref()?.id.as_str()).copied());
+            let dependencies = declared
+                .chain(linked)
+                .filter(|dependency| dependency != &target.id)
+                .map(String::from)
+                .collect();
+            graph.insert(target.id.clone(), dependencies);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn dependents_of() {
+        let mut root_object = test_demo_file!(demo1);
+        assert!(root_object.dependents_of("Missing").is_err());
+        assert!(root_object.dependents_of("Wordle").unwrap().is_empty());
+
+        PBXTarget::builder("Core")
+            .product_type(PBXProductType::Framework)
+            .build(&mut root_object)
+            .unwrap();
+        let kit = PBXTarget::builder("Kit")
+            .product_type(PBXProductType::Framework)
+            .dependency("Core")
+            .build(&mut root_object)
+            .unwrap();
+        let wordle = root_object.get_target_by_name("Wordle").unwrap().id;
+        let product = root_object.get_target(&kit).unwrap().product.unwrap().id;
+        let phase = root_object
+            .objects()
+            .get_target_build_phase(&wordle, &PBXBuildPhaseKind::Frameworks)
+            .unwrap();
+        root_object
+            .objects_mut()
+            .add_build_file(&phase, &product)
+            .unwrap();
+
+        let names = |targets: Vec<PBXTarget>| {
+            targets
+                .into_iter()
+                .map(|t| t.name.cloned().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec!["Kit", "Wordle"],
+            names(root_object.dependents_of("Core").unwrap())
+        );
+        assert_eq!(
+            vec!["Wordle"],
+            names(root_object.dependents_of("Kit").unwrap())
+        );
+        assert!(root_object.dependents_of("Wordle").unwrap().is_empty());
+    }
+}
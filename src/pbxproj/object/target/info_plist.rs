@@ -0,0 +1,318 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+/// Info.plist key Xcode generates from an `INFOPLIST_KEY_*` build setting when
+/// `GENERATE_INFOPLIST_FILE` is enabled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCInfoPlistKey {
+    /// `CFBundleDisplayName`
+    DisplayName,
+    /// `LSApplicationCategoryType`
+    ApplicationCategoryType,
+    /// `NSHumanReadableCopyright`
+    HumanReadableCopyright,
+    /// `NSPrincipalClass`
+    PrincipalClass,
+    /// `NSMainStoryboardFile`, macOS
+    MainStoryboardFile,
+    /// `NSMainNibFile`, macOS
+    MainNibFile,
+    /// `UIMainStoryboardFile`
+    UIMainStoryboardFile,
+    /// `UILaunchStoryboardName`
+    LaunchStoryboardName,
+    /// Generate an empty `UILaunchScreen`, `UILaunchScreen_Generation`
+    LaunchScreenGeneration,
+    /// Generate a `UIApplicationSceneManifest` supporting multiple scenes,
+    /// `UIApplicationSceneManifest_Generation`
+    SceneManifestGeneration,
+    /// `UIApplicationSupportsIndirectInputEvents`
+    SupportsIndirectInputEvents,
+    /// `UISupportedInterfaceOrientations`, space separated
+    SupportedInterfaceOrientations,
+    /// `UISupportedInterfaceOrientations~ipad`, space separated
+    SupportedInterfaceOrientationsIPad,
+    /// `UISupportedInterfaceOrientations~iphone`, space separated
+    SupportedInterfaceOrientationsIPhone,
+    /// `UIRequiresFullScreen`
+    RequiresFullScreen,
+    /// `UIStatusBarStyle`
+    StatusBarStyle,
+    /// `UIUserInterfaceStyle`
+    UserInterfaceStyle,
+    /// `UIFileSharingEnabled`
+    FileSharingEnabled,
+    /// `LSSupportsOpeningDocumentsInPlace`
+    SupportsOpeningDocumentsInPlace,
+    /// `ITSAppUsesNonExemptEncryption`
+    UsesNonExemptEncryption,
+    /// `NSCameraUsageDescription`
+    CameraUsageDescription,
+    /// `NSMicrophoneUsageDescription`
+    MicrophoneUsageDescription,
+    /// `NSPhotoLibraryUsageDescription`
+    PhotoLibraryUsageDescription,
+    /// `NSPhotoLibraryAddUsageDescription`
+    PhotoLibraryAddUsageDescription,
+    /// `NSLocationWhenInUseUsageDescription`
+    LocationWhenInUseUsageDescription,
+    /// `NSLocationAlwaysAndWhenInUseUsageDescription`
+    LocationAlwaysAndWhenInUseUsageDescription,
+    /// `NSContactsUsageDescription`
+    ContactsUsageDescription,
+    /// `NSCalendarsUsageDescription`
+    CalendarsUsageDescription,
+    /// `NSRemindersUsageDescription`
+    RemindersUsageDescription,
+    /// `NSBluetoothAlwaysUsageDescription`
+    BluetoothAlwaysUsageDescription,
+    /// `NSFaceIDUsageDescription`
+    FaceIDUsageDescription,
+    /// `NSMotionUsageDescription`
+    MotionUsageDescription,
+    /// `NSSpeechRecognitionUsageDescription`
+    SpeechRecognitionUsageDescription,
+    /// `NSHealthShareUsageDescription`
+    HealthShareUsageDescription,
+    /// `NSHealthUpdateUsageDescription`
+    HealthUpdateUsageDescription,
+    /// `NSLocalNetworkUsageDescription`
+    LocalNetworkUsageDescription,
+    /// `NSUserTrackingUsageDescription`
+    UserTrackingUsageDescription,
+    /// `NSAppleMusicUsageDescription`
+    AppleMusicUsageDescription,
+    /// `NSSiriUsageDescription`
+    SiriUsageDescription,
+}
+
+impl XCInfoPlistKey {
+    /// Every key with its Info.plist name
+    pub const ALL: [(Self, &'static str); 39] = [
+        (Self::DisplayName, "CFBundleDisplayName"),
+        (Self::ApplicationCategoryType, "LSApplicationCategoryType"),
+        (Self::HumanReadableCopyright, "NSHumanReadableCopyright"),
+        (Self::PrincipalClass, "NSPrincipalClass"),
+        (Self::MainStoryboardFile, "NSMainStoryboardFile"),
+        (Self::MainNibFile, "NSMainNibFile"),
+        (Self::UIMainStoryboardFile, "UIMainStoryboardFile"),
+        (Self::LaunchStoryboardName, "UILaunchStoryboardName"),
+        (Self::LaunchScreenGeneration, "UILaunchScreen_Generation"),
+        (
+            Self::SceneManifestGeneration,
+            "UIApplicationSceneManifest_Generation",
+        ),
+        (
+            Self::SupportsIndirectInputEvents,
+            "UIApplicationSupportsIndirectInputEvents",
+        ),
+        (
+            Self::SupportedInterfaceOrientations,
+            "UISupportedInterfaceOrientations",
+        ),
+        (
+            Self::SupportedInterfaceOrientationsIPad,
+            "UISupportedInterfaceOrientations~ipad",
+        ),
+        (
+            Self::SupportedInterfaceOrientationsIPhone,
+            "UISupportedInterfaceOrientations~iphone",
+        ),
+        (Self::RequiresFullScreen, "UIRequiresFullScreen"),
+        (Self::StatusBarStyle, "UIStatusBarStyle"),
+        (Self::UserInterfaceStyle, "UIUserInterfaceStyle"),
+        (Self::FileSharingEnabled, "UIFileSharingEnabled"),
+        (
+            Self::SupportsOpeningDocumentsInPlace,
+            "LSSupportsOpeningDocumentsInPlace",
+        ),
+        (
+            Self::UsesNonExemptEncryption,
+            "ITSAppUsesNonExemptEncryption",
+        ),
+        (Self::CameraUsageDescription, "NSCameraUsageDescription"),
+        (
+            Self::MicrophoneUsageDescription,
+            "NSMicrophoneUsageDescription",
+        ),
+        (
+            Self::PhotoLibraryUsageDescription,
+            "NSPhotoLibraryUsageDescription",
+        ),
+        (
+            Self::PhotoLibraryAddUsageDescription,
+            "NSPhotoLibraryAddUsageDescription",
+        ),
+        (
+            Self::LocationWhenInUseUsageDescription,
+            "NSLocationWhenInUseUsageDescription",
+        ),
+        (
+            Self::LocationAlwaysAndWhenInUseUsageDescription,
+            "NSLocationAlwaysAndWhenInUseUsageDescription",
+        ),
+        (Self::ContactsUsageDescription, "NSContactsUsageDescription"),
+        (
+            Self::CalendarsUsageDescription,
+            "NSCalendarsUsageDescription",
+        ),
+        (
+            Self::RemindersUsageDescription,
+            "NSRemindersUsageDescription",
+        ),
+        (
+            Self::BluetoothAlwaysUsageDescription,
+            "NSBluetoothAlwaysUsageDescription",
+        ),
+        (Self::FaceIDUsageDescription, "NSFaceIDUsageDescription"),
+        (Self::MotionUsageDescription, "NSMotionUsageDescription"),
+        (
+            Self::SpeechRecognitionUsageDescription,
+            "NSSpeechRecognitionUsageDescription",
+        ),
+        (
+            Self::HealthShareUsageDescription,
+            "NSHealthShareUsageDescription",
+        ),
+        (
+            Self::HealthUpdateUsageDescription,
+            "NSHealthUpdateUsageDescription",
+        ),
+        (
+            Self::LocalNetworkUsageDescription,
+            "NSLocalNetworkUsageDescription",
+        ),
+        (
+            Self::UserTrackingUsageDescription,
+            "NSUserTrackingUsageDescription",
+        ),
+        (
+            Self::AppleMusicUsageDescription,
+            "NSAppleMusicUsageDescription",
+        ),
+        (Self::SiriUsageDescription, "NSSiriUsageDescription"),
+    ];
+
+    /// Get key from its Info.plist name, e.g. `NSCameraUsageDescription`
+    pub fn from_plist_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, name)| *name == key)
+            .map(|(k, _)| *k)
+    }
+
+    /// Info.plist name, e.g. `UISupportedInterfaceOrientations~ipad`
+    pub fn plist_key(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(k, _)| k == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+
+    /// Build setting the key is generated from, e.g.
+    /// `INFOPLIST_KEY_UISupportedInterfaceOrientations_iPad`
+    pub fn build_setting(&self) -> String {
+        let key = self
+            .plist_key()
+            .replace("~ipad", "_iPad")
+            .replace("~iphone", "_iPhone");
+        format!("INFOPLIST_KEY_{key}")
+    }
+}
+
+impl PBXObjectCollection {
+    /// Enable or disable Info.plist generation from build settings for every configuration of
+    /// the target with the given reference, `GENERATE_INFOPLIST_FILE`. Keys of an
+    /// `INFOPLIST_FILE` still set are merged into the generated file.
+    pub fn set_generate_info_plist(&mut self, target: &str, generate: bool) -> Result<()> {
+        self.update_target_build_settings(target, |settings| {
+            settings.insert_value("GENERATE_INFOPLIST_FILE", generate);
+        })
+    }
+
+    /// Set `key` of the generated Info.plist for every configuration of the target with the
+    /// given reference, e.g. `true` for [`XCInfoPlistKey::LaunchScreenGeneration`] or a
+    /// sentence for [`XCInfoPlistKey::CameraUsageDescription`]. `None` removes the setting.
+    pub fn set_info_plist_key(
+        &mut self,
+        target: &str,
+        key: XCInfoPlistKey,
+        value: Option<PBXValue>,
+    ) -> Result<()> {
+        let setting = key.build_setting();
+        self.update_target_build_settings(target, |settings| match value.clone() {
+            Some(value) => {
+                settings.insert_value(setting.as_str(), value);
+            }
+            None => {
+                settings.remove_value(&setting);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_info_plist_key() {
+        let mut root_object = test_demo_file!(demo1);
+        let target = root_object.targets()[0].id.clone();
+        let objects = root_object.objects_mut();
+        objects.set_generate_info_plist(&target, true).unwrap();
+        objects
+            .set_info_plist_key(
+                &target,
+                XCInfoPlistKey::CameraUsageDescription,
+                Some("Scan words".into()),
+            )
+            .unwrap();
+        objects
+            .set_info_plist_key(
+                &target,
+                XCInfoPlistKey::SupportedInterfaceOrientationsIPad,
+                Some("UIInterfaceOrientationPortrait".into()),
+            )
+            .unwrap();
+        objects
+            .set_info_plist_key(
+                &target,
+                XCInfoPlistKey::LaunchScreenGeneration,
+                Some(true.into()),
+            )
+            .unwrap();
+        objects
+            .set_info_plist_key(&target, XCInfoPlistKey::LaunchScreenGeneration, None)
+            .unwrap();
+        assert!(objects
+            .set_info_plist_key("missing", XCInfoPlistKey::DisplayName, None)
+            .is_err());
+
+        for configuration in root_object.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations
+            .iter()
+        {
+            let settings = &configuration.build_settings;
+            assert_eq!(Some(&true), settings.get_bool("GENERATE_INFOPLIST_FILE"));
+            assert_eq!(
+                Some(&"Scan words".to_string()),
+                settings.get_string("INFOPLIST_KEY_NSCameraUsageDescription")
+            );
+            assert!(settings
+                .get_string("INFOPLIST_KEY_UISupportedInterfaceOrientations_iPad")
+                .is_some());
+            assert!(settings
+                .get_value("INFOPLIST_KEY_UILaunchScreen_Generation")
+                .is_none());
+        }
+        assert_eq!(
+            Some(XCInfoPlistKey::SupportedInterfaceOrientationsIPhone),
+            XCInfoPlistKey::from_plist_key("UISupportedInterfaceOrientations~iphone")
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::pbxproj::*;
+use std::path::Path;
+
+/// How a target uses a framework or library, see [`PBXRootObject::targets_linking`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PBXLinkKind {
+    /// Linked by the frameworks build phase
+    Linked,
+    /// Swift package product of the target, `packageProductDependencies`
+    PackageProduct,
+    /// Copied to the product by a copy files phase, e.g. `Embed Frameworks`
+    Embedded,
+}
+
+/// Target using a framework or library, see [`PBXRootObject::targets_linking`]
+#[derive(Debug)]
+pub struct PBXTargetLinkage<'a> {
+    /// Target using the framework or library
+    pub target: PBXTarget<'a>,
+    /// Ways the target uses it, sorted
+    pub kinds: Vec<PBXLinkKind>,
+}
+
+impl PBXRootObject {
+    /// Get targets using the framework, library or swift package product with the given name,
+    /// e.g. `Alamofire` for `Alamofire.xcframework`, `libAlamofire.a` or the `Alamofire`
+    /// package product, with how each one uses it. Sorted by target name. Useful before
+    /// removing a dependency.
+    pub fn targets_linking(&self, name: &str) -> Vec<PBXTargetLinkage<'_>> {
+        let mut linkages = self
+            .targets()
+            .into_iter()
+            .filter_map(|target| {
+                let mut kinds = vec![];
+                if target
+                    .package_product_dependencies
+                    .iter()
+                    .any(|product| product.product_name == name)
+                {
+                    kinds.push(PBXLinkKind::PackageProduct);
+                }
+                for phase in target.build_phases.iter() {
+                    let kind = match phase.kind {
+                        PBXBuildPhaseKind::Frameworks => PBXLinkKind::Linked,
+                        PBXBuildPhaseKind::CopyFiles => PBXLinkKind::Embedded,
+                        _ => continue,
+                    };
+                    if phase.files.iter().any(|file| builds(file, name)) {
+                        kinds.push(kind);
+                    }
+                }
+                kinds.sort();
+                kinds.dedup();
+                (!kinds.is_empty()).then_some(PBXTargetLinkage { target, kinds })
+            })
+            .collect::<Vec<_>>();
+        linkages.sort_by(|a, b| (a.target.name, &a.target.id).cmp(&(b.target.name, &b.target.id)));
+        linkages
+    }
+}
+
+/// Whether build file `file` builds the framework, library or package product `name`
+fn builds(file: &PBXBuildFile, name: &str) -> bool {
+    if let Some(product) = &file.product {
+        return product.product_name == name;
+    }
+    let path = match file.file.as_ref().and_then(|file| file.name.or(file.path)) {
+        Some(path) => Path::new(path),
+        None => return false,
+    };
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let is_library = path
+        .extension()
+        .map(|ext| ext == "a" || ext == "dylib" || ext == "tbd")
+        .unwrap_or_default();
+    stem == name || (is_library && stem.strip_prefix("lib") == Some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn targets_linking() {
+        let mut root_object = test_demo_file!(demo1);
+        assert!(root_object.targets_linking("Alamofire").is_empty());
+
+        let url = "https://github.com/Alamofire/Alamofire";
+        let requirement = XCVersionRequirement::UpToNextMajorVersion("5.0.0".into());
+        PBXTarget::builder("Network")
+            .product_type(PBXProductType::Framework)
+            .package(url, "Alamofire", requirement)
+            .build(&mut root_object)
+            .unwrap();
+        let wordle = root_object.get_target_by_name("Wordle").unwrap().id;
+        root_object
+            .add_xcframework(&wordle, "Vendor/Alamofire.xcframework", true)
+            .unwrap();
+        PBXTarget::builder("Legacy")
+            .framework("Vendor/libAlamofire.a")
+            .framework("Vendor/AlamofireImage.xcframework")
+            .build(&mut root_object)
+            .unwrap();
+
+        let linkages = root_object
+            .targets_linking("Alamofire")
+            .into_iter()
+            .map(|l| (l.target.name.cloned().unwrap_or_default(), l.kinds))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Legacy".to_string(), vec![PBXLinkKind::Linked]),
+                (
+                    "Network".to_string(),
+                    vec![PBXLinkKind::Linked, PBXLinkKind::PackageProduct]
+                ),
+                (
+                    "Wordle".to_string(),
+                    vec![PBXLinkKind::Linked, PBXLinkKind::Embedded]
+                ),
+            ],
+            linkages
+        );
+    }
+}
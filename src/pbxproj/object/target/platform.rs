@@ -0,0 +1,88 @@
+use crate::macros::log;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use derive_is_enum_variant::is_enum_variant;
+#[derive(Clone, Debug, Deserialize, Serialize, Hash, PartialEq, Eq, is_enum_variant, Default)]
+/// Target Platform based
+pub enum PBXTargetPlatform {
+    /// iOS Platform
+    #[serde(rename = "iOS")]
+    IOS,
+    /// watchOS Platform
+    #[serde(rename = "watchOS")]
+    WatchOS,
+    /// tvOs Platform
+    #[serde(rename = "tvOS")]
+    TvOS,
+    /// macOS Platform
+    #[serde(rename = "macOS")]
+    MacOS,
+    /// Unknown or not support platform
+    #[default]
+    Unknown,
+}
+
+impl PBXTargetPlatform {
+    /// Get Target Platfrom from sdkroot
+    pub fn from_sdk_root(sdk_root: &str) -> Self {
+        match sdk_root {
+            "iphoneos" => Self::IOS,
+            "macosx" => Self::MacOS,
+            "appletvos" => Self::TvOS,
+            "watchos" => Self::WatchOS,
+            _ => Self::Unknown,
+        }
+    }
+    /// Get build setting holding the minimum OS version of the platform, e.g.
+    /// `IPHONEOS_DEPLOYMENT_TARGET`
+    pub fn deployment_target_key(&self) -> Option<&'static str> {
+        match self {
+            Self::IOS => Some("IPHONEOS_DEPLOYMENT_TARGET"),
+            Self::MacOS => Some("MACOSX_DEPLOYMENT_TARGET"),
+            Self::TvOS => Some("TVOS_DEPLOYMENT_TARGET"),
+            Self::WatchOS => Some("WATCHOS_DEPLOYMENT_TARGET"),
+            Self::Unknown => None,
+        }
+    }
+
+    /// Get PBXTargetPlatform from simulator identifer
+    pub fn from_identifer(identifer: &str) -> Self {
+        let name = identifer.replace("com.apple.CoreSimulator.SimRuntime.", "");
+        let platform_str = name.split("-").next().unwrap().to_string();
+        match Self::from_str(&platform_str) {
+            Ok(res) => res,
+            Err(e) => {
+                log!(error, "Platfrom from str: {e}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl FromStr for PBXTargetPlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "iOS" => Ok(Self::IOS),
+            "watchOS" => Ok(Self::WatchOS),
+            "tvOS" => Ok(Self::TvOS),
+            "macOS" => Ok(Self::MacOS),
+            _ => Ok(Self::Unknown),
+        }
+    }
+}
+
+impl std::fmt::Display for PBXTargetPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::IOS => "iOS",
+            Self::WatchOS => "watchOS",
+            Self::TvOS => "tvOS",
+            Self::MacOS => "macOS",
+            _ => "",
+        };
+        write!(f, "{value}")
+    }
+}
@@ -0,0 +1,132 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::path::Path;
+
+impl PBXHashMap {
+    /// Append `value` to list build setting `key` if not already included. Missing settings are
+    /// initialized with `$(inherited)`.
+    pub(crate) fn append_setting_value(&mut self, key: &str, value: &str) {
+        let mut values = match self.remove_value(key) {
+            Some(PBXValue::Vec(values)) => values,
+            Some(PBXValue::String(existing)) => PBXVec::new(vec![existing.into()]),
+            Some(other) => PBXVec::new(vec![other]),
+            None => PBXVec::new(vec!["$(inherited)".into()]),
+        };
+
+        if !values.as_vec_strings().iter().any(|v| v.as_str() == value) {
+            values.push(value);
+        }
+
+        self.insert_value(key, values);
+    }
+}
+
+impl PBXObjectCollection {
+    /// Apply `update` to build settings of every configuration of the target with the given
+    /// reference.
+    pub(crate) fn update_target_build_settings<F>(
+        &mut self,
+        target: &str,
+        mut update: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&mut PBXHashMap),
+    {
+        if self.get_target(target).is_none() {
+            anyhow::bail!("Target {target:?} not found");
+        }
+
+        for mut configuration in self.get_build_configurations_mut(target) {
+            if let Some(settings) = configuration.get_object_mut("buildSettings") {
+                update(settings);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PBXRootObject {
+    /// Set Objective-C bridging header of the target with the given reference.
+    ///
+    /// `path` is relative to project root. The header file reference is created if it doesn't
+    /// exist already, and `SWIFT_OBJC_BRIDGING_HEADER` is set across all target configurations.
+    /// Returns the reference of the header file reference.
+    pub fn set_bridging_header<P: AsRef<Path>>(&mut self, target: &str, path: P) -> Result<String> {
+        let path = path.as_ref();
+        if self.get_target(target).is_none() {
+            anyhow::bail!("Target {target:?} not found");
+        }
+
+        let file = self.get_or_add_file_reference(path)?;
+        let value = path.to_string_lossy().to_string();
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.insert_value("SWIFT_OBJC_BRIDGING_HEADER", value.as_str());
+            })?;
+
+        Ok(file)
+    }
+
+    /// Remove Objective-C bridging header from all configurations of the target with the given
+    /// reference, e.g. when the target becomes swift only.
+    pub fn remove_bridging_header(&mut self, target: &str) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.remove_value("SWIFT_OBJC_BRIDGING_HEADER");
+            })
+    }
+}
+
+impl PBXRootObject {
+    /// Set custom module map of the target with the given reference.
+    ///
+    /// `path` is relative to project root. The module map file reference is created if it
+    /// doesn't exist already, and `MODULEMAP_FILE` is set across all target configurations.
+    /// Returns the reference of the module map file reference.
+    pub fn set_module_map<P: AsRef<Path>>(&mut self, target: &str, path: P) -> Result<String> {
+        let path = path.as_ref();
+        if self.get_target(target).is_none() {
+            anyhow::bail!("Target {target:?} not found");
+        }
+
+        let file = self.get_or_add_file_reference(path)?;
+        let value = path.to_string_lossy().to_string();
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.insert_value("MODULEMAP_FILE", value.as_str());
+            })?;
+
+        Ok(file)
+    }
+
+    /// Remove custom module map from all configurations of the target with the given reference.
+    pub fn remove_module_map(&mut self, target: &str) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.remove_value("MODULEMAP_FILE");
+            })
+    }
+
+    /// Set `DEFINES_MODULE` across all configurations of the target with the given reference.
+    pub fn set_defines_module(&mut self, target: &str, defines_module: bool) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| {
+                settings.insert_value("DEFINES_MODULE", defines_module);
+            })
+    }
+
+    /// Set `PRODUCT_MODULE_NAME` across all configurations of the target with the given
+    /// reference. `None` removes the setting, falling back to Xcode's default module name.
+    pub fn set_product_module_name(&mut self, target: &str, name: Option<&str>) -> Result<()> {
+        self.objects
+            .update_target_build_settings(target, |settings| match name {
+                Some(name) => {
+                    settings.insert_value("PRODUCT_MODULE_NAME", name);
+                }
+                None => {
+                    settings.remove_value("PRODUCT_MODULE_NAME");
+                }
+            })
+    }
+}
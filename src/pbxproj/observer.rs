@@ -0,0 +1,219 @@
+use crate::pbxproj::*;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Mutex, MutexGuard};
+
+/// Callback fired for every object inserted, removed or modified, see
+/// [`PBXRootObject::observe`]
+pub type PBXObserver = Box<dyn FnMut(&PBXObjectChange) + Send>;
+
+/// Registered [`PBXObserver`]s. Observers are bound to a project instance and are not cloned
+/// with it. They are shared by every mutable object view, see [`PBXObjectMut`].
+#[derive(Default)]
+pub(crate) struct PBXObservers(Mutex<Vec<PBXObserver>>);
+
+impl PBXObservers {
+    fn observers(&self) -> MutexGuard<'_, Vec<PBXObserver>> {
+        self.0.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers().is_empty()
+    }
+
+    pub(crate) fn notify(&self, changes: &[PBXObjectChange]) {
+        let mut observers = self.observers();
+        for change in changes {
+            for observer in observers.iter_mut() {
+                observer(change);
+            }
+        }
+    }
+
+    /// Notify of `object` modifications, `old` being the object before they were made
+    pub(crate) fn notify_modified(&self, reference: &str, old: &PBXHashMap, object: &PBXHashMap) {
+        if old != object {
+            self.notify(&[PBXObjectChange::Modified {
+                reference: reference.to_string(),
+                fields: field_changes(old, object),
+            }]);
+        }
+    }
+}
+
+impl Clone for PBXObservers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for PBXObservers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PBXObservers")
+            .field(&self.observers().len())
+            .finish()
+    }
+}
+
+impl PBXObjectCollection {
+    /// Insert `object` under `reference`, notifying observers
+    pub(crate) fn insert_at(&mut self, reference: String, object: PBXHashMap) {
+        if self.observers.is_empty() {
+            self.map.insert(reference, object);
+            return;
+        }
+        let change = match self.map.insert(reference.clone(), object) {
+            Some(old) => {
+                let object = &self.map[&reference];
+                if &old == object {
+                    return;
+                }
+                PBXObjectChange::Modified {
+                    fields: field_changes(&old, object),
+                    reference,
+                }
+            }
+            None => PBXObjectChange::Added { reference },
+        };
+        self.observers.notify(&[change]);
+    }
+
+    /// Remove object with the given reference, notifying observers
+    pub(crate) fn remove_object(&mut self, reference: &str) -> Option<PBXHashMap> {
+        let object = self.map.remove(reference)?;
+        if !self.observers.is_empty() {
+            self.observers.notify(&[PBXObjectChange::Removed {
+                reference: reference.to_string(),
+            }]);
+        }
+        Some(object)
+    }
+
+    /// Edit every object with `edit`, notifying observers of modified ones
+    pub(crate) fn edit_objects<F>(&mut self, mut edit: F)
+    where
+        F: FnMut(&str, &mut PBXHashMap),
+    {
+        if self.observers.is_empty() {
+            for (reference, object) in self.map.iter_mut() {
+                edit(reference, object);
+            }
+            return;
+        }
+        let mut references = self.map.keys().cloned().collect::<Vec<_>>();
+        references.sort();
+        for reference in references {
+            let object = self.map.get_mut(&reference).unwrap();
+            let old = object.clone();
+            edit(&reference, object);
+            self.observers.notify_modified(&reference, &old, object);
+        }
+    }
+}
+
+impl PBXRootObject {
+    /// Register `observer` to be called with every object inserted, removed or modified, once
+    /// the change is made.
+    ///
+    /// Edits made through [`PBXRootObject::transaction`] are reported when the transaction
+    /// commits, along with changes reverted by [`PBXRootObject::restore`] or applied by a
+    /// reload, see [`PBXRootObject::reload_from_str`]. Other edits are reported by the
+    /// primitive making them: insertion, removal, or dropping the mutable object returned by
+    /// [`PBXObjectCollection::get_object_mut`] and other `_mut` getters.
+    pub fn observe<F>(&mut self, observer: F)
+    where
+        F: FnMut(&PBXObjectChange) + Send + 'static,
+    {
+        self.objects.observers.observers().push(Box::new(observer));
+    }
+
+    /// Subscribe to object changes as an event stream, see [`PBXRootObject::observe`] for when
+    /// changes are reported. The subscription ends when the receiver is dropped.
+    pub fn subscribe(&mut self) -> Receiver<PBXObjectChange> {
+        let (sender, receiver) = channel();
+        self.observe(move |change| {
+            sender.send(change.clone()).ok();
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn observe_transaction_changes() {
+        let mut root_object = test_demo_file!(demo1);
+        let receiver = root_object.subscribe();
+        let added = Arc::new(Mutex::new(vec![]));
+        {
+            let added = added.clone();
+            root_object.observe(move |change| {
+                if let PBXObjectChange::Added { reference } = change {
+                    added.lock().unwrap().push(reference.clone());
+                }
+            });
+        }
+
+        let group = root_object
+            .transaction(|tx| tx.get_or_add_group("Kit"))
+            .unwrap();
+        root_object
+            .transaction(|tx| -> anyhow::Result<()> {
+                tx.get_or_add_group("Kit/Sources")?;
+                anyhow::bail!("Abort")
+            })
+            .unwrap_err();
+
+        assert_eq!(vec![group], *added.lock().unwrap());
+        assert_eq!(2, receiver.try_iter().count());
+    }
+
+    #[test]
+    fn observe_direct_changes() {
+        let mut root_object = test_demo_file!(demo1);
+        let receiver = root_object.subscribe();
+        let target = root_object.targets()[0].id.clone();
+
+        let group = root_object.get_or_add_group("Utils").unwrap();
+        let main_group = root_object.root_group().id;
+        let changes = receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(
+            PBXObjectChange::Added {
+                reference: group.clone()
+            },
+            changes[0]
+        );
+        assert!(matches!(
+            &changes[1],
+            PBXObjectChange::Modified { reference, fields }
+                if reference == &main_group && fields[0].key == "children"
+        ));
+        assert_eq!(2, changes.len());
+
+        let objects = root_object.objects_mut();
+        objects
+            .get_object_mut(&target)
+            .unwrap()
+            .insert_value("productName", "Renamed");
+        objects.get_target_mut(&target).unwrap();
+        let changes = receiver.try_iter().collect::<Vec<_>>();
+        assert!(matches!(
+            &changes[..],
+            [PBXObjectChange::Modified { reference, fields }]
+                if reference == &target && fields[0].key == "productName"
+        ));
+
+        let build_file = root_object.build_files()[0].id.clone();
+        root_object.objects_mut().remove_build_file(&build_file);
+        let changes = receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(
+            Some(&PBXObjectChange::Removed {
+                reference: build_file
+            }),
+            changes.last()
+        );
+        assert_eq!(2, changes.len());
+    }
+}
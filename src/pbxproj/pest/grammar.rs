@@ -0,0 +1,185 @@
+#![allow(clippy::result_large_err)]
+use super::PBXProjectParser;
+use crate::pbxproj::{PBXDecimal, PBXHashMap, PBXObjectKind, PBXValue, PBXVec, SmolStr};
+use anyhow::{Context, Result};
+use std::{collections::HashMap, num::ParseIntError};
+
+use pest_consume::*;
+use tap::Pipe;
+
+/// Pest Parser
+#[derive(Parser)]
+#[grammar = "pbxproj/pest/grammar.pest"]
+pub(crate) struct PBXGrammarParser;
+pub(crate) type NodeResult<T> = std::result::Result<T, Error<Rule>>;
+pub(crate) type Node<'i> = pest_consume::Node<'i, Rule, ()>;
+
+#[parser]
+impl PBXGrammarParser {
+    fn key(input: Node) -> NodeResult<SmolStr> {
+        let inner = input.into_children().next().unwrap();
+        Ok(SmolStr::new(super::scanner::unquote_key(inner.as_str())))
+    }
+
+    fn string(input: Node) -> NodeResult<PBXValue> {
+        let value = input.as_str();
+        super::scanner::unescape(&value[1..value.len() - 1])
+            .pipe(PBXValue::String)
+            .pipe(Ok)
+    }
+
+    fn data(input: Node) -> NodeResult<PBXValue> {
+        let digits = input
+            .as_str()
+            .chars()
+            .filter_map(|char| char.to_digit(16))
+            .collect::<Vec<_>>();
+        if digits.len() % 2 != 0 {
+            return Err(input.error("Data has an odd number of hex digits"));
+        }
+        digits
+            .chunks(2)
+            .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+            .collect::<Vec<_>>()
+            .pipe(PBXValue::Data)
+            .pipe(Ok)
+    }
+
+    fn kind(input: Node) -> NodeResult<PBXValue> {
+        let value = PBXObjectKind::from(input.as_str());
+        value.pipe(PBXValue::Kind).pipe(Ok)
+    }
+
+    fn ident(input: Node) -> NodeResult<PBXValue> {
+        input.as_str().to_string().pipe(PBXValue::String).pipe(Ok)
+    }
+
+    fn uuid(input: Node) -> NodeResult<PBXValue> {
+        input.as_str().to_string().pipe(PBXValue::String).pipe(Ok)
+    }
+
+    fn number(input: Node) -> NodeResult<PBXValue> {
+        // Versions, e.g. `1.2.0`, are kept as strings
+        let value = input.as_str();
+        if value.contains(".") {
+            return Ok(PBXDecimal::parse(value)
+                .map(PBXValue::Float)
+                .unwrap_or_else(|| PBXValue::String(value.into())));
+        }
+        value
+            .parse()
+            .map_err(|e: ParseIntError| input.error(e))
+            .map(PBXValue::Number)
+    }
+
+    fn bool(input: Node) -> NodeResult<PBXValue> {
+        match input.as_str() {
+            "YES" => Ok(true),
+            "NO" => Ok(false),
+            value => input
+                .error(format!("{value:?} is not parseable as boolean!"))
+                .pipe(Err),
+        }?
+        .pipe(PBXValue::Bool)
+        .pipe(Ok)
+    }
+
+    fn array(input: Node) -> NodeResult<PBXValue> {
+        // Pest iterators carry no size hint, reserve upfront to avoid growing while collecting
+        let mut values = Vec::with_capacity(input.children().count());
+        for node in input.into_children() {
+            values.push(Self::value(node)?);
+        }
+        values.pipe(PBXVec::new).pipe(PBXValue::Vec).pipe(Ok)
+    }
+
+    fn value(input: Node) -> NodeResult<PBXValue> {
+        // Dispatch on the single child rule directly, `match_nodes!` allocates per call.
+        let node = input.into_children().next().unwrap();
+        match node.as_rule() {
+            Rule::array => Self::array(node),
+            Rule::object => Self::object(node),
+            Rule::string => Self::string(node),
+            Rule::data => Self::data(node),
+            Rule::bool => Self::bool(node),
+            Rule::kind => Self::kind(node),
+            Rule::number => Self::number(node),
+            Rule::uuid => Self::uuid(node),
+            Rule::ident => Self::ident(node),
+            rule => Err(node.error(format!("Unexpected {rule:?} value"))),
+        }
+    }
+
+    fn field(node: Node) -> NodeResult<(SmolStr, PBXValue)> {
+        let mut children = node.into_children();
+        let key = Self::key(children.next().unwrap())?;
+        let value = match children.next() {
+            Some(value) => Self::value(value)?,
+            None => PBXValue::String(String::new()),
+        };
+
+        Ok((key, value))
+    }
+
+    fn object(input: Node) -> NodeResult<PBXValue> {
+        let mut fields = HashMap::with_capacity(input.children().count());
+        for node in input.into_children() {
+            let (key, value) = Self::field(node)?;
+            fields.insert(key, value);
+        }
+        fields.pipe(PBXHashMap::new).pipe(PBXValue::Object).pipe(Ok)
+    }
+
+    pub fn file(input: Node) -> NodeResult<PBXHashMap> {
+        let node = input.into_children().next().unwrap();
+        Self::object(node)?.try_into_object().unwrap().pipe(Ok)
+    }
+}
+
+impl PBXProjectParser {
+    /// Parse content with the pest grammar, the reference [`super::Scanner`] output is checked against.
+    pub fn try_from_str_with_grammar<S>(content: S) -> Result<PBXHashMap>
+    where
+        S: AsRef<str>,
+    {
+        PBXGrammarParser::parse(Rule::file, content.as_ref())
+            .context("Parse content")?
+            .pipe(|n| n.single().context("nodes to single node"))?
+            .pipe(PBXGrammarParser::file)
+            .context("parse into PBXHashMap")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scanner_matches_grammar() {
+        let samples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        for entry in std::fs::read_dir(samples).unwrap().flatten() {
+            let content = std::fs::read_to_string(entry.path()).unwrap();
+            assert_eq!(
+                PBXProjectParser::try_from_str_with_grammar(&content).unwrap(),
+                PBXProjectParser::try_from_str(&content).unwrap(),
+                "{:?}",
+                entry.path()
+            );
+        }
+
+        let content = "// !$*UTF8*$!\n{\n\tuserData = <0fbd77 1c2735ae>;\n\tempty = <>;\n}\n";
+        let parsed = PBXProjectParser::try_from_str(content).unwrap();
+        assert_eq!(
+            Some(&PBXValue::Data(vec![
+                0x0f, 0xbd, 0x77, 0x1c, 0x27, 0x35, 0xae
+            ])),
+            parsed.get_value("userData")
+        );
+        assert_eq!(Some(&PBXValue::Data(vec![])), parsed.get_value("empty"));
+        assert_eq!(
+            PBXProjectParser::try_from_str_with_grammar(content).unwrap(),
+            parsed
+        );
+        assert!(PBXProjectParser::try_from_str("// !$*UTF8*$!\n{ a = <0fb>; }").is_err());
+    }
+}
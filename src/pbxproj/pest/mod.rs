@@ -1,117 +1,18 @@
 #![allow(missing_docs)]
-use super::object::PBXObjectKind;
-use super::{PBXHashMap, PBXVec};
-use crate::pbxproj::PBXValue;
-use anyhow::{anyhow, Context, Result};
-use itertools::Itertools;
+#![allow(clippy::result_large_err)]
+use super::PBXHashMap;
+use anyhow::{anyhow, Result};
 use std::path::Path;
-use std::{collections::HashMap, num::ParseIntError};
-
-use pest_consume::*;
 use tap::Pipe;
 
-/// Pest Parser
-#[derive(Parser)]
-#[grammar = "pbxproj/pest/grammar.pest"]
-pub(crate) struct PBXProjectParser;
-pub(crate) type NodeResult<T> = std::result::Result<T, Error<Rule>>;
-pub(crate) type Node<'i> = pest_consume::Node<'i, Rule, ()>;
-
-#[parser]
-impl PBXProjectParser {
-    fn key(input: Node) -> NodeResult<String> {
-        let inner = input.into_children().next().unwrap();
-        Ok(inner.as_str().to_string())
-    }
-
-    fn string(input: Node) -> NodeResult<PBXValue> {
-        let value = input.as_str().replace("\"", "");
-        // println!("string value: `{value}`");
-        value.pipe(PBXValue::String).pipe(Ok)
-    }
-
-    fn kind(input: Node) -> NodeResult<PBXValue> {
-        let value = PBXObjectKind::from(input.as_str());
-        value.pipe(PBXValue::Kind).pipe(Ok)
-    }
-
-    fn ident(input: Node) -> NodeResult<PBXValue> {
-        input.as_str().to_string().pipe(PBXValue::String).pipe(Ok)
-    }
+#[cfg(feature = "grammar")]
+mod grammar;
+mod scanner;
+pub(crate) use scanner::{unquote_key, unquoted_value, Scanner};
+pub use scanner::{PBXMergeConflictError, PBXParseError};
 
-    fn uuid(input: Node) -> NodeResult<PBXValue> {
-        input.as_str().to_string().pipe(PBXValue::String).pipe(Ok)
-    }
-
-    fn number(input: Node) -> NodeResult<PBXValue> {
-        // TODO: identify versions as string instead of number or as ident!
-        let value = input.as_str();
-        if value.contains(".") {
-            return Ok(PBXValue::String(value.into()));
-        }
-        value
-            .parse()
-            .map_err(|e: ParseIntError| input.error(e))
-            .map(PBXValue::Number)
-    }
-
-    fn bool(input: Node) -> NodeResult<PBXValue> {
-        match input.as_str() {
-            "YES" => Ok(true),
-            "NO" => Ok(false),
-            value => input
-                .error(format!("{value:?} is not parseable as boolean!"))
-                .pipe(Err),
-        }?
-        .pipe(PBXValue::Bool)
-        .pipe(Ok)
-    }
-
-    fn array(input: Node) -> NodeResult<PBXValue> {
-        match_nodes!(input.into_children();
-            [value(values)..] => values.collect::<Vec<PBXValue>>()
-        )
-        .pipe(PBXVec::new)
-        .pipe(PBXValue::Vec)
-        .pipe(Ok)
-    }
-
-    fn value(input: Node) -> NodeResult<PBXValue> {
-        match_nodes!(input.into_children();
-         [array(value)] => value,
-         [object(value)] => value,
-         [string(value)] => value,
-         [bool(value)] => value,
-         [kind(value)] => value,
-         [number(value)] => value,
-         [uuid(value)] => value,
-         [ident(value)] => value
-        )
-        .pipe(Ok)
-    }
-
-    fn field(node: Node) -> NodeResult<(String, PBXValue)> {
-        let (k, v) = node.into_children().collect_tuple().unwrap();
-        let key = Self::key(k)?;
-        let value = Self::value(v)?;
-
-        Ok((key, value))
-    }
-
-    fn object(input: Node) -> NodeResult<PBXValue> {
-        match_nodes!(input.into_children();
-            [field(fields)..] => fields.collect::<HashMap<String, PBXValue>>(),
-        )
-        .pipe(PBXHashMap::new)
-        .pipe(PBXValue::Object)
-        .pipe(Ok)
-    }
-
-    pub fn file(input: Node) -> NodeResult<PBXHashMap> {
-        let node = input.into_children().next().unwrap();
-        Self::object(node)?.try_into_object().unwrap().pipe(Ok)
-    }
-}
+/// Project content parser, see [`Scanner`]
+pub(crate) struct PBXProjectParser;
 
 impl PBXProjectParser {
     pub fn try_parse_from_file<P>(path: P) -> Result<PBXHashMap>
@@ -127,11 +28,15 @@ impl PBXProjectParser {
     where
         S: AsRef<str>,
     {
-        PBXProjectParser::parse(Rule::file, content.as_ref())
-            .context("Parse content")?
-            .pipe(|n| n.single().context("nodes to single node"))?
-            .pipe(PBXProjectParser::file)
-            .context("parse into PBXHashMap")
+        Scanner::new(content.as_ref()).parse_file()
+    }
+
+    /// Parse a standalone `{ key = value; ... }` object, e.g. a subset of the objects section.
+    pub fn try_object_from_str<S>(content: S) -> Result<PBXHashMap>
+    where
+        S: AsRef<str>,
+    {
+        Scanner::new(content.as_ref()).parse_document()
     }
 }
 
@@ -161,5 +66,48 @@ mod parse_tests {
         };
     }
 
-    test_samples![demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12];
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12,
+        demo13, legacy1
+    ];
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_diagnostics() {
+        let content = "// !$*UTF8*$!\n{\n\tarchiveVersion = 1;\n\tclasses = {\n\t};\n\tobjectVersion 56;\n}\n";
+        let error = PBXProjectParser::try_from_str(content).unwrap_err();
+        let error = error.downcast_ref::<PBXParseError>().unwrap();
+        assert_eq!(Some((6, 16)), error.location);
+        assert_eq!(Some("56"), error.token.as_deref());
+        assert_eq!(
+            Some("\tobjectVersion 56;\n\t              ^"),
+            error.snippet.as_deref()
+        );
+        assert_eq!("Expected `=` at 6:16 near `56`", error.to_string());
+        assert!(format!("{error:#}").ends_with("\n\tobjectVersion 56;\n\t              ^"));
+    }
+
+    #[test]
+    fn merge_conflict_markers() {
+        let content = "// !$*UTF8*$!\n{\n\tarchiveVersion = 1;\n<<<<<<< HEAD\n\tobjectVersion = 56;\n=======\n\tobjectVersion = 60;\n>>>>>>> feature\n}\n";
+        let error = PBXProjectParser::try_from_str(content).unwrap_err();
+        assert_eq!(
+            Some(&PBXMergeConflictError { line: 4 }),
+            error.downcast_ref::<PBXMergeConflictError>()
+        );
+        let error = PBXProjectParser::try_from_str(content.replace("<<<<<<< HEAD\n", ""));
+        assert_eq!(
+            "Unresolved merge conflict at line 5, resolve the conflict and try again",
+            error.unwrap_err().to_string()
+        );
+
+        let content = content
+            .replace("<<<<<<< HEAD\n", "")
+            .replace("=======\n\tobjectVersion = 60;\n>>>>>>> feature\n", "");
+        assert!(PBXProjectParser::try_from_str(content).is_ok());
+    }
 }
@@ -0,0 +1,749 @@
+use crate::pbxproj::PBXObjectKind;
+use crate::pbxproj::{
+    PBXComment, PBXCommentKind, PBXDecimal, PBXHashMap, PBXSkippedEntry, PBXValue, PBXVec, SmolStr,
+};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Maximum nesting of objects and arrays, deeper content is rejected instead of overflowing
+/// the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Error found while parsing pbxproj content, returned by parsing functions and retrieved
+/// with `error.downcast_ref::<PBXParseError>()`.
+///
+/// Displayed as `message at line:column near `token``, followed by the snippet with the
+/// alternate flag, `{:#}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXParseError {
+    /// Error description
+    pub message: String,
+    /// 1-based line and column of the error, if it relates to a position in the content
+    pub location: Option<(usize, usize)>,
+    /// Token found at the error location, `None` at the end of content
+    pub token: Option<String>,
+    /// Line of the error location, with a caret pointing at the column on the next line
+    pub snippet: Option<String>,
+}
+
+impl std::fmt::Display for PBXParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some((line, column)) = self.location {
+            write!(f, " at {line}:{column}")?;
+        }
+        if let Some(token) = &self.token {
+            write!(f, " near `{token}`")?;
+        }
+        match &self.snippet {
+            Some(snippet) if f.alternate() => write!(f, "\n{snippet}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for PBXParseError {}
+
+impl PBXParseError {
+    /// Create error unrelated to a position in the content
+    pub(crate) fn new<S: ToString>(message: S) -> Self {
+        Self {
+            message: message.to_string(),
+            location: None,
+            token: None,
+            snippet: None,
+        }
+    }
+
+    /// Parse error from `error`, keeping its location if it is a [`PBXParseError`].
+    pub(crate) fn from_error(error: anyhow::Error) -> Self {
+        error
+            .downcast::<Self>()
+            .unwrap_or_else(|error| Self::new(format!("{error:#}")))
+    }
+}
+
+/// Error returned when parsing content with unresolved git merge conflict markers, e.g.
+/// `<<<<<<< HEAD`, retrieved with `error.downcast_ref::<PBXMergeConflictError>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PBXMergeConflictError {
+    /// 1-based line of the first conflict marker
+    pub line: usize,
+}
+
+impl std::fmt::Display for PBXMergeConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unresolved merge conflict at line {}, resolve the conflict and try again",
+            self.line
+        )
+    }
+}
+
+impl std::error::Error for PBXMergeConflictError {}
+
+/// Get 1-based line of the first merge conflict marker in `content`: seven `<`, `|`, `=` or
+/// `>` at the start of a line, followed by a space or the line end
+fn find_conflict_marker(content: &str) -> Option<usize> {
+    let is_marker = |line: &str| {
+        ["<<<<<<<", "|||||||", "=======", ">>>>>>>"]
+            .iter()
+            .any(|marker| match line.strip_prefix(marker) {
+                Some(rest) => rest.is_empty() || rest.starts_with(' '),
+                None => false,
+            })
+    };
+    content.lines().position(is_marker).map(|index| index + 1)
+}
+
+/// Longest token kept in a [`PBXParseError`]
+const MAX_TOKEN: usize = 40;
+
+/// Hand written single pass reader of pbxproj content.
+///
+/// Produces the same values as the pest grammar, which is kept as reference, at a fraction of
+/// the cost since no intermediate parse tree is built.
+pub(crate) struct Scanner<'a> {
+    content: &'a str,
+    bytes: &'a [u8],
+    pub(crate) position: usize,
+    depth: usize,
+    /// Errors of skipped malformed fields, `None` unless parsing lossy
+    errors: Option<Vec<PBXParseError>>,
+    /// Malformed entries of the objects section skipped as a whole, `None` unless recovering
+    skipped: Option<Vec<PBXSkippedEntry>>,
+    /// Objects shadowed by an earlier object with the same reference
+    duplicates: Vec<(String, PBXHashMap)>,
+    /// Hand written comments found so far, `None` unless collecting them
+    comments: Option<CommentCollector>,
+}
+
+/// Comments found by a scanner, each attached to the field or element following it
+#[derive(Default)]
+struct CommentCollector {
+    /// Keys and array indices leading to the value being parsed
+    path: Vec<String>,
+    /// Comments waiting for the next field, element or closing delimiter
+    pending: Vec<(String, PBXCommentKind)>,
+    comments: Vec<PBXComment>,
+}
+
+impl CommentCollector {
+    /// Attach pending comments to the value at `path`
+    fn attach(&mut self, trailing: bool) {
+        for (text, kind) in self.pending.drain(..) {
+            self.comments.push(PBXComment {
+                text,
+                kind,
+                path: self.path.clone(),
+                trailing,
+            });
+        }
+    }
+}
+
+const HEADER: &str = "// !$*UTF8*$!";
+
+impl<'a> Scanner<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        Self {
+            content,
+            bytes: content.as_bytes(),
+            position: 0,
+            depth: 0,
+            errors: None,
+            skipped: None,
+            duplicates: vec![],
+            comments: None,
+        }
+    }
+
+    /// Scanner skipping malformed fields instead of failing, see [`Scanner::errors`].
+    pub(crate) fn lossy(content: &'a str) -> Self {
+        Self {
+            errors: Some(vec![]),
+            ..Self::new(content)
+        }
+    }
+
+    /// Scanner skipping malformed entries of the objects section as a whole instead of failing,
+    /// see [`Scanner::take_skipped`]. Errors elsewhere still fail.
+    pub(crate) fn recovering(content: &'a str) -> Self {
+        Self {
+            skipped: Some(vec![]),
+            ..Self::new(content)
+        }
+    }
+
+    /// Take entries skipped by a recovering scanner
+    pub(crate) fn take_skipped(&mut self) -> Vec<PBXSkippedEntry> {
+        self.skipped.take().unwrap_or_default()
+    }
+
+    /// Scanner collecting hand written comments, see [`Scanner::take_comments`]
+    pub(crate) fn with_comments(content: &'a str) -> Self {
+        Self {
+            comments: Some(Default::default()),
+            ..Self::new(content)
+        }
+    }
+
+    /// Take comments found by a scanner collecting them, in content order
+    pub(crate) fn take_comments(&mut self) -> Vec<PBXComment> {
+        self.comments
+            .take()
+            .map(|collector| collector.comments)
+            .unwrap_or_default()
+    }
+
+    /// Errors of malformed fields skipped by a lossy scanner
+    pub(crate) fn errors(self) -> Vec<PBXParseError> {
+        self.errors.unwrap_or_default()
+    }
+
+    /// Take objects of the objects section whose reference was already taken by an earlier
+    /// object. The first object keeps the reference, as Xcode mostly does.
+    pub(crate) fn take_duplicates(&mut self) -> Vec<(String, PBXHashMap)> {
+        std::mem::take(&mut self.duplicates)
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    pub(crate) fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| self.position += 1)
+    }
+
+    /// Skip the `// !$*UTF8*$!` header line
+    pub(crate) fn skip_line_comment(&mut self) {
+        if self.content[self.position..].starts_with("//") {
+            while let Some(byte) = self.peek() {
+                self.position += 1;
+                if byte == b'\n' {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Skip whitespace, block and line comments
+    pub(crate) fn skip_trivia(&mut self) {
+        loop {
+            let start = self.position;
+            let kind = match self.peek() {
+                Some(byte) if is_whitespace(byte) => {
+                    self.position += 1;
+                    continue;
+                }
+                Some(b'/') if self.bytes.get(self.position + 1) == Some(&b'*') => {
+                    match self.content[self.position + 2..].find("*/") {
+                        Some(end) => self.position += end + 4,
+                        None => self.position = self.bytes.len(),
+                    }
+                    PBXCommentKind::Block
+                }
+                Some(b'/') if self.bytes.get(self.position + 1) == Some(&b'/') => {
+                    match self.content[self.position..].find('\n') {
+                        Some(end) => self.position += end,
+                        None => self.position = self.bytes.len(),
+                    }
+                    PBXCommentKind::Line
+                }
+                _ => return,
+            };
+            if self.comments.is_some() {
+                self.collect_comment(start, kind);
+            }
+        }
+    }
+
+    /// Keep the comment starting at `start` unless generated by Xcode: section markers and
+    /// annotations following a reference on the same line, e.g. `/* main.swift */`.
+    fn collect_comment(&mut self, start: usize, kind: PBXCommentKind) {
+        let raw = &self.content[start..self.position];
+        let text = match kind {
+            PBXCommentKind::Block => raw.trim_start_matches("/*").trim_end_matches("*/"),
+            PBXCommentKind::Line => raw.trim_start_matches("//"),
+        }
+        .trim();
+        let is_section = kind == PBXCommentKind::Block
+            && (text.starts_with("Begin ") || text.starts_with("End "))
+            && text.ends_with(" section");
+        let is_annotation = kind == PBXCommentKind::Block
+            && self.content[..start]
+                .trim_end_matches([' ', '\t'])
+                .bytes()
+                .last()
+                .map(|byte| !b"{(;,=\r\n".contains(&byte))
+                .unwrap_or_default();
+        if let Some(collector) = self.comments.as_mut() {
+            if !is_section && !is_annotation {
+                collector.pending.push((text.to_string(), kind));
+            }
+        }
+    }
+
+    /// Enter the value of field or element `segment`, attaching pending comments to it
+    fn enter(&mut self, segment: impl ToString) {
+        if let Some(collector) = self.comments.as_mut() {
+            collector.path.push(segment.to_string());
+            collector.attach(false);
+        }
+    }
+
+    fn exit(&mut self) {
+        if let Some(collector) = self.comments.as_mut() {
+            collector.path.pop();
+        }
+    }
+
+    /// Attach pending comments to the end of the object or array being parsed
+    fn close(&mut self) {
+        if let Some(collector) = self.comments.as_mut() {
+            collector.attach(true);
+        }
+    }
+
+    /// Read a quoted string, a data literal or an unquoted token
+    pub(crate) fn token(&mut self) -> Option<&'a str> {
+        let start = self.position;
+        if self.peek()? == b'"' {
+            self.skip_string()?;
+        } else if self.peek()? == b'<' {
+            self.position += self.content[start..].find('>')? + 1;
+        } else {
+            while let Some(byte) = self.peek() {
+                match byte {
+                    byte if is_whitespace(byte) => break,
+                    b'=' | b';' | b',' | b'(' | b')' | b'{' | b'}' | b'"' => break,
+                    b'/' if self.bytes.get(self.position + 1) == Some(&b'*') => break,
+                    _ => self.position += 1,
+                }
+            }
+        }
+        (self.position > start).then(|| &self.content[start..self.position])
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'\\' => self.position += 2,
+                b'"' => {
+                    self.position += 1;
+                    return Some(());
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    /// Skip a value, including nested objects and arrays
+    pub(crate) fn skip_value(&mut self) -> Option<()> {
+        let mut depth = 0usize;
+        loop {
+            self.skip_trivia();
+            match self.peek()? {
+                b'{' | b'(' => {
+                    depth += 1;
+                    self.position += 1;
+                }
+                b'}' | b')' => {
+                    depth = depth.checked_sub(1)?;
+                    self.position += 1;
+                }
+                b'=' | b';' | b',' if depth > 0 => self.position += 1,
+                b'=' | b';' | b',' => return None,
+                _ => {
+                    self.token()?;
+                }
+            }
+            if depth == 0 {
+                return Some(());
+            }
+        }
+    }
+
+    /// Parse a whole pbxproj file
+    pub(crate) fn parse_file(&mut self) -> Result<PBXHashMap> {
+        // Checked up front, markers would otherwise fail at an arbitrary token
+        if let Some(line) = find_conflict_marker(self.content) {
+            return Err(PBXMergeConflictError { line }.into());
+        }
+        if self.content.starts_with('\u{FEFF}') {
+            self.position += '\u{FEFF}'.len_utf8();
+        }
+        while self.peek().map(is_whitespace) == Some(true) {
+            self.position += 1;
+        }
+        if self.content[self.position..].starts_with(HEADER) {
+            self.position += HEADER.len();
+        } else {
+            self.recover(self.error(format!("Expected {HEADER:?} header")))?;
+        }
+        self.parse_document()
+    }
+
+    /// Parse a standalone `{ key = value; ... }` object
+    pub(crate) fn parse_document(&mut self) -> Result<PBXHashMap> {
+        self.skip_trivia();
+        let object = self.parse_object()?;
+        self.skip_trivia();
+        if self.position < self.bytes.len() {
+            self.recover(self.error("Unexpected content after root object"))?;
+        }
+        Ok(object)
+    }
+
+    fn parse_object(&mut self) -> Result<PBXHashMap> {
+        self.expect_byte(b'{')?;
+        let mut fields = HashMap::new();
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                Some(b'}') => {
+                    self.close();
+                    self.position += 1;
+                    return Ok(PBXHashMap::new(fields));
+                }
+                None => {
+                    self.recover(self.error("Expected `}`"))?;
+                    return Ok(PBXHashMap::new(fields));
+                }
+                _ => {}
+            }
+
+            let start = self.position;
+            match self.parse_field() {
+                // Entries of the objects section, one level below the root object
+                Ok((key, PBXValue::Object(object)))
+                    if self.depth == 1 && fields.contains_key(&key) =>
+                {
+                    self.duplicates.push((key.to_string(), object));
+                }
+                Ok((key, value)) => {
+                    fields.insert(key, value);
+                }
+                Err(error) if self.depth == 1 && self.skipped.is_some() => {
+                    self.position = start;
+                    let reference = self.token().map(String::from);
+                    self.position = start;
+                    self.skip_field();
+                    let entry = PBXSkippedEntry {
+                        reference,
+                        span: start..self.position,
+                        error: PBXParseError::from_error(error),
+                    };
+                    self.skipped.get_or_insert_with(Vec::new).push(entry);
+                }
+                Err(error) => {
+                    self.recover(error)?;
+                    self.position = start;
+                    self.skip_field();
+                }
+            }
+        }
+    }
+
+    fn parse_field(&mut self) -> Result<(SmolStr, PBXValue)> {
+        let key = self
+            .token()
+            .map(|token| SmolStr::new(unquote_key(token)))
+            .ok_or_else(|| self.error("Expected key"))?;
+        self.enter(&key);
+        let value = self.parse_field_value();
+        self.exit();
+        Ok((key, value?))
+    }
+
+    fn parse_field_value(&mut self) -> Result<PBXValue> {
+        self.skip_trivia();
+        self.expect_byte(b'=')?;
+        self.skip_trivia();
+        // Legacy projects may leave a value empty, `key = ;`
+        if self.expect(b';').is_some() {
+            return Ok(PBXValue::String(String::new()));
+        }
+        let value = self.parse_value()?;
+        self.skip_trivia();
+        self.expect_byte(b';')?;
+        Ok(value)
+    }
+
+    fn parse_array(&mut self) -> Result<PBXVec> {
+        self.expect_byte(b'(')?;
+        let mut values = vec![];
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(b')') {
+                self.close();
+                self.position += 1;
+                return Ok(PBXVec::new(values));
+            }
+            self.enter(values.len());
+            let value = self.parse_value();
+            self.exit();
+            values.push(value?);
+            self.skip_trivia();
+            if self.expect(b',').is_none() && self.peek() != Some(b')') {
+                return Err(self.error("Expected `,` or `)`"));
+            }
+        }
+    }
+
+    fn parse_nested<T>(&mut self, parse: fn(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("Exceeded maximum nesting depth"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_value(&mut self) -> Result<PBXValue> {
+        match self.peek() {
+            Some(b'{') => self.parse_nested(Self::parse_object).map(PBXValue::Object),
+            Some(b'(') => self.parse_nested(Self::parse_array).map(PBXValue::Vec),
+            Some(b'"') => {
+                let token = self
+                    .token()
+                    .ok_or_else(|| self.error("Unterminated string"))?;
+                Ok(PBXValue::String(unescape(&token[1..token.len() - 1])))
+            }
+            Some(b'<') => {
+                let start = self.position;
+                let token = self
+                    .token()
+                    .ok_or_else(|| self.error("Unterminated data"))?;
+                data_value(token).map(PBXValue::Data).ok_or_else(|| {
+                    self.position = start;
+                    self.error(format!("{token:?} is not parseable as data"))
+                })
+            }
+            _ => {
+                let start = self.position;
+                let token = self.token().ok_or_else(|| self.error("Expected value"))?;
+                unquoted_value(token).ok_or_else(|| {
+                    self.position = start;
+                    self.error(format!("{token:?} is not parseable as number"))
+                })
+            }
+        }
+    }
+
+    fn expect_byte(&mut self, byte: u8) -> Result<()> {
+        self.expect(byte)
+            .ok_or_else(|| self.error(format!("Expected `{}`", byte as char)))
+    }
+
+    /// Record `error` and continue when lossy, fail otherwise
+    fn recover(&mut self, error: anyhow::Error) -> Result<()> {
+        match self.errors.as_mut() {
+            Some(errors) => {
+                errors.push(PBXParseError::from_error(error));
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Skip a malformed field up to and including the next `;`, or up to the closing `}` of
+    /// the object holding it.
+    fn skip_field(&mut self) {
+        let mut depth = 0usize;
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'"' => {
+                    if self.skip_string().is_none() {
+                        self.position = self.bytes.len();
+                    }
+                    continue;
+                }
+                b'/' if self.bytes.get(self.position + 1) == Some(&b'*') => {
+                    self.skip_trivia();
+                    continue;
+                }
+                b'{' | b'(' => depth += 1,
+                b'}' if depth == 0 => return,
+                b'}' | b')' => depth = depth.saturating_sub(1),
+                b';' if depth == 0 => {
+                    self.position += 1;
+                    return;
+                }
+                _ => {}
+            }
+            self.position += 1;
+        }
+    }
+
+    fn error<S: std::fmt::Display>(&self, message: S) -> anyhow::Error {
+        let (line, column) = self.location();
+        let text = self.content.lines().nth(line - 1).unwrap_or_default();
+        let caret = text
+            .chars()
+            .take(column - 1)
+            .map(|char| if char == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        anyhow::Error::new(PBXParseError {
+            message: message.to_string(),
+            location: Some((line, column)),
+            token: self.current_token(),
+            snippet: Some(format!("{}\n{caret}^", text.trim_end_matches('\r'))),
+        })
+    }
+
+    /// Token at current position, without reading it
+    fn current_token(&self) -> Option<String> {
+        let mut position = self.position.min(self.content.len());
+        while !self.content.is_char_boundary(position) {
+            position -= 1;
+        }
+        let rest = &self.content[position..];
+        let end = match rest.as_bytes().first()? {
+            b'"' => rest[1..].find('"').map(|end| end + 2),
+            b'{' | b'}' | b'(' | b')' | b'=' | b';' | b',' => Some(1),
+            _ => rest.find(|char: char| char.is_whitespace() || "{}()=;,\"".contains(char)),
+        }
+        .unwrap_or(rest.len());
+        let token = match rest[..end].char_indices().nth(MAX_TOKEN) {
+            Some((index, _)) => format!("{}…", &rest[..index]),
+            None => rest[..end].to_string(),
+        };
+        Some(token).filter(|token| !token.is_empty())
+    }
+
+    /// 1-based line and column of current position
+    fn location(&self) -> (usize, usize) {
+        let mut position = self.position.min(self.content.len());
+        while !self.content.is_char_boundary(position) {
+            position -= 1;
+        }
+        let before = &self.content[..position];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map(|i| i + 1).unwrap_or_default()..]
+            .chars()
+            .count()
+            + 1;
+        (line, column)
+    }
+}
+
+/// Whether `byte` separates tokens, including vertical tabs and form feeds found in legacy
+/// projects
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b'\x0B' | b'\x0C')
+}
+
+/// Key `token` as read back into a [`PBXHashMap`]: quoted keys, e.g.
+/// `"CODE_SIGN_IDENTITY[sdk=iphoneos*]"`, are unquoted and unescaped like string values.
+///
+/// [`PBXHashMap`]: crate::pbxproj::PBXHashMap
+pub(crate) fn unquote_key(token: &str) -> std::borrow::Cow<'_, str> {
+    match token.len() > 1 && token.starts_with('"') && token.ends_with('"') {
+        true => unescape(&token[1..token.len() - 1]).into(),
+        false => token.into(),
+    }
+}
+
+/// Decode escape sequences of quoted string `raw`: `\n`, `\t`, `\"`, `\\`, other C escapes,
+/// `\U` followed by 4 hex digits and octal `\012`. Unknown sequences are kept as is.
+pub(crate) fn unescape(raw: &str) -> String {
+    if !raw.contains('\\') {
+        return raw.to_string();
+    }
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            value.push(char);
+            continue;
+        }
+        let Some(escaped) = chars.next() else {
+            value.push(char);
+            break;
+        };
+        match escaped {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            'r' => value.push('\r'),
+            'a' => value.push('\u{7}'),
+            'b' => value.push('\u{8}'),
+            'v' => value.push('\u{B}'),
+            'f' => value.push('\u{C}'),
+            '"' | '\\' | '/' | '\'' => value.push(escaped),
+            'u' | 'U' => {
+                let digits = chars.clone().take(4).collect::<String>();
+                match u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    Some(decoded) if digits.len() == 4 => {
+                        value.push(decoded);
+                        chars.nth(3);
+                    }
+                    _ => value.extend([char, escaped]),
+                }
+            }
+            '0'..='7' => {
+                let mut code = escaped.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|next| next.to_digit(8)) {
+                        Some(digit) => {
+                            code = code * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                value.extend(char::from_u32(code));
+            }
+            escaped => value.extend([char, escaped]),
+        }
+    }
+    value
+}
+
+/// Bytes of data literal `token`, e.g. `<0fbd771c 2735ae>`, made of hex digit pairs
+fn data_value(token: &str) -> Option<Vec<u8>> {
+    let digits = token[1..token.len() - 1]
+        .bytes()
+        .filter(|byte| !is_whitespace(*byte))
+        .map(|byte| (byte as char).to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair[1])
+            .collect(),
+    )
+}
+
+/// Value of an unquoted token, following grammar precedence: bool, kind, number then string.
+pub(crate) fn unquoted_value(token: &str) -> Option<PBXValue> {
+    if token.eq_ignore_ascii_case("YES") {
+        return Some(PBXValue::Bool(true));
+    } else if token.eq_ignore_ascii_case("NO") {
+        return Some(PBXValue::Bool(false));
+    }
+
+    match PBXObjectKind::from(token) {
+        PBXObjectKind::Other(_) => {}
+        kind => return Some(PBXValue::Kind(kind)),
+    }
+
+    // Versions, e.g. `1.2.0`, are kept as strings
+    if token.bytes().all(|b| b.is_ascii_digit()) {
+        return token.parse().ok().map(PBXValue::Number);
+    }
+    if let Some(decimal) = PBXDecimal::parse(token) {
+        return Some(PBXValue::Float(decimal));
+    }
+
+    Some(PBXValue::String(token.to_string()))
+}
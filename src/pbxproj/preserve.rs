@@ -0,0 +1,585 @@
+use crate::pbxproj::pest::{unquote_key, Scanner};
+use crate::pbxproj::writer::{field_order, quote, section_of, PBXWriter, SINGLE_LINE, UNANNOTATED};
+use crate::pbxproj::*;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+
+impl PBXRootObject {
+    /// Serialize as project.pbxproj content keeping the layout of the content the project was
+    /// loaded from, so diffs only show actual changes.
+    ///
+    /// Unchanged objects, fields and array elements are written as read, along with their
+    /// order, quoting and comments. Changed values are written like
+    /// [`PBXRootObject::to_pbxproj_string`] does, added fields in sorted position and added
+    /// objects in sorted position of their section. Falls back to
+    /// [`PBXRootObject::to_pbxproj_string`] for projects not loaded from content, or loaded
+    /// from content with duplicate references.
+    pub fn to_pbxproj_string_preserving(&self) -> String {
+        self.preserved_pbxproj_string()
+            .unwrap_or_else(|| self.to_pbxproj_string())
+    }
+
+    fn preserved_pbxproj_string(&self) -> Option<String> {
+        let content = self.source.as_ref()?.content();
+        let mut old = Scanner::new(content).parse_file().ok()?;
+        let old_objects = old.try_remove_object("objects").ok()?;
+
+        let mut scanner = Scanner::new(content);
+        scanner.skip_line_comment();
+        scanner.skip_trivia();
+        let start = scanner.position;
+        let (fields, closing) = raw_fields(&content[start..])?;
+
+        let mut new = PBXHashMap::default();
+        new.insert_value("archiveVersion", self.archive_version as isize);
+        new.insert_value("classes", self.classes.clone());
+        new.insert_value("objectVersion", self.object_version as isize);
+        new.insert_value("rootObject", self.root_object_reference.as_str());
+
+        let annotations = self.annotations();
+        let patcher = Patcher {
+            annotations: &annotations,
+        };
+        let mut output = format!("{}{{", &content[..start]);
+        for field in fields {
+            output.push_str(field.separator);
+            if field.key == "objects" {
+                output.push_str(field.head);
+                output.push_str(&patcher.objects(field.value, &old_objects, &self.objects)?);
+                output.push(';');
+                continue;
+            }
+            match (old.get_value(&field.key), new.get_value(&field.key)) {
+                (Some(old), Some(new)) if old != new => {
+                    output.push_str(field.head);
+                    output.push_str(&patcher.value(field.value, old, new, 1, false, true));
+                    output.push(';');
+                }
+                _ => output.push_str(field.text),
+            }
+        }
+        output.push_str(closing);
+        Some(output)
+    }
+}
+
+/// Field of an object, or entry of the objects section, as written
+struct RawField<'a> {
+    /// Whitespace and comments preceding the field
+    separator: &'a str,
+    /// Key without quotes, see [`unquote_key`]
+    key: Cow<'a, str>,
+    /// Text from the key up to the value, e.g. `key = `
+    head: &'a str,
+    /// Value as written
+    value: &'a str,
+    /// Text from the key up to the closing `;`
+    text: &'a str,
+}
+
+/// Split object `text`, `{ ... }`, into fields and the text closing it
+fn raw_fields(text: &str) -> Option<(Vec<RawField<'_>>, &str)> {
+    let mut scanner = Scanner::new(text);
+    scanner.expect(b'{')?;
+    let mut fields = vec![];
+    loop {
+        let separator = scanner.position;
+        scanner.skip_trivia();
+        if scanner.peek()? == b'}' {
+            return Some((fields, &text[separator..]));
+        }
+        let start = scanner.position;
+        let key = unquote_key(scanner.token()?);
+        scanner.skip_trivia();
+        scanner.expect(b'=')?;
+        scanner.skip_trivia();
+        let value = scanner.position;
+        scanner.skip_value()?;
+        let value_end = scanner.position;
+        scanner.skip_trivia();
+        scanner.expect(b';')?;
+        fields.push(RawField {
+            separator: &text[separator..start],
+            key,
+            head: &text[start..value],
+            value: &text[value..value_end],
+            text: &text[start..scanner.position],
+        });
+    }
+}
+
+/// Element of an array as written
+struct RawElement<'a> {
+    /// Whitespace and comments preceding the element
+    separator: &'a str,
+    /// Text from the value up to the closing `,`
+    text: &'a str,
+}
+
+/// Split array `text`, `( ... )`, into elements and the text closing it
+fn raw_elements(text: &str) -> Option<(Vec<RawElement<'_>>, &str)> {
+    let mut scanner = Scanner::new(text);
+    scanner.expect(b'(')?;
+    let mut elements = vec![];
+    loop {
+        let separator = scanner.position;
+        scanner.skip_trivia();
+        if scanner.peek()? == b')' {
+            return Some((elements, &text[separator..]));
+        }
+        let start = scanner.position;
+        scanner.skip_value()?;
+        scanner.skip_trivia();
+        if scanner.peek()? == b',' {
+            scanner.position += 1;
+        }
+        elements.push(RawElement {
+            separator: &text[separator..start],
+            text: &text[start..scanner.position],
+        });
+    }
+}
+
+/// Separator Xcode writes before field or element `index` of an object or array at `depth`
+fn separator(inline: bool, index: usize, depth: usize) -> String {
+    match (inline, index) {
+        (true, 0) => String::new(),
+        (true, _) => " ".into(),
+        (false, _) => format!("\n{}", "\t".repeat(depth + 1)),
+    }
+}
+
+/// Writer of values changed since content was read, see
+/// [`PBXRootObject::to_pbxproj_string_preserving`]
+struct Patcher<'a> {
+    annotations: &'a HashMap<&'a str, String>,
+}
+
+impl Patcher<'_> {
+    fn render(&self, write: impl FnOnce(&mut PBXWriter)) -> String {
+        let mut writer = PBXWriter::new(self.annotations);
+        write(&mut writer);
+        writer.output
+    }
+
+    /// Write `new` in place of `old`, written as `text` at `depth`
+    fn value(
+        &self,
+        text: &str,
+        old: &PBXValue,
+        new: &PBXValue,
+        depth: usize,
+        inline: bool,
+        annotate: bool,
+    ) -> String {
+        let patched = match (old, new) {
+            (PBXValue::Object(old), PBXValue::Object(new)) => self.object(text, old, new, depth),
+            (PBXValue::Vec(old), PBXValue::Vec(new)) => self.array(text, old, new, depth, annotate),
+            _ => None,
+        };
+        patched.unwrap_or_else(|| self.render(|w| w.value(new, depth, inline, annotate)))
+    }
+
+    fn field(&self, key: &str, value: &PBXValue, depth: usize, inline: bool) -> String {
+        self.render(|w| {
+            w.push(&quote(key));
+            w.push(" = ");
+            w.value(value, depth + 1, inline, !UNANNOTATED.contains(&key));
+            w.push(";");
+        })
+    }
+
+    fn object(
+        &self,
+        text: &str,
+        old: &PBXHashMap,
+        new: &PBXHashMap,
+        depth: usize,
+    ) -> Option<String> {
+        let (fields, closing) = raw_fields(text)?;
+        if fields.len() != old.len() {
+            return None;
+        }
+        let inline = !text.contains('\n');
+        let mut added = new
+            .iter()
+            .filter(|(key, value)| !old.contains_key(*key) && !value.is_null())
+            .collect::<Vec<_>>();
+        added.sort_by(|(a, _), (b, _)| field_order(a).cmp(&field_order(b)));
+        let mut added = added.into_iter().peekable();
+
+        let mut output = String::from("{");
+        let mut index = 0;
+        for field in fields {
+            while let Some((key, value)) =
+                added.next_if(|(key, _)| field_order(key) < field_order(&field.key))
+            {
+                output.push_str(&separator(inline, index, depth));
+                output.push_str(&self.field(key, value, depth, inline));
+                index += 1;
+            }
+
+            let old = old.get_value(&field.key)?;
+            let Some(new) = new.get_value(&field.key).filter(|value| !value.is_null()) else {
+                continue;
+            };
+            match inline {
+                true => output.push_str(&separator(inline, index, depth)),
+                false => output.push_str(field.separator),
+            }
+            if old == new {
+                output.push_str(field.text);
+            } else {
+                let annotate = !UNANNOTATED.contains(&field.key.as_ref());
+                output.push_str(field.head);
+                output.push_str(&self.value(field.value, old, new, depth + 1, inline, annotate));
+                output.push(';');
+            }
+            index += 1;
+        }
+        for (key, value) in added {
+            output.push_str(&separator(inline, index, depth));
+            output.push_str(&self.field(key, value, depth, inline));
+            index += 1;
+        }
+        output.push_str(closing);
+        Some(output)
+    }
+
+    fn array(
+        &self,
+        text: &str,
+        old: &PBXVec,
+        new: &PBXVec,
+        depth: usize,
+        annotate: bool,
+    ) -> Option<String> {
+        let (elements, closing) = raw_elements(text)?;
+        if elements.len() != old.len() {
+            return None;
+        }
+        let inline = !text.contains('\n');
+        let mut used = vec![false; old.len()];
+
+        let mut output = String::from("(");
+        for (index, value) in new.iter().filter(|value| !value.is_null()).enumerate() {
+            let matched = (0..old.len()).find(|&j| !used[j] && &old[j] == value);
+            match matched {
+                Some(j) => {
+                    used[j] = true;
+                    match inline {
+                        true => output.push_str(&separator(inline, index, depth)),
+                        false => output.push_str(elements[j].separator),
+                    }
+                    output.push_str(elements[j].text);
+                    if !elements[j].text.ends_with(',') {
+                        output.push(',');
+                    }
+                }
+                None => {
+                    output.push_str(&separator(inline, index, depth));
+                    output.push_str(&self.render(|w| w.value(value, depth + 1, inline, annotate)));
+                    output.push(',');
+                }
+            }
+        }
+        output.push_str(closing);
+        Some(output)
+    }
+
+    /// Write the objects section, `text`, with `new` objects in place of `old` ones
+    fn objects(&self, text: &str, old: &PBXHashMap, new: &PBXObjectCollection) -> Option<String> {
+        let (entries, closing) = raw_fields(text)?;
+        if entries.len() != old.len() {
+            return None;
+        }
+        let existing = text
+            .match_indices("/* Begin ")
+            .filter_map(|(index, _)| section_marker(&text[index..]))
+            .map(|(isa, _)| isa)
+            .collect::<HashSet<_>>();
+        let mut pending = BTreeMap::<String, Vec<&String>>::new();
+        for (reference, object) in new.iter() {
+            if !old.contains_key(reference.as_str()) {
+                pending
+                    .entry(section_of(object))
+                    .or_default()
+                    .push(reference);
+            }
+        }
+        pending
+            .values_mut()
+            .for_each(|references| references.sort());
+
+        let mut sections = Sections {
+            patcher: self,
+            objects: new,
+            existing,
+            pending,
+            current: None,
+        };
+        let mut output = String::from("{");
+        for entry in entries {
+            let separator = sections.separator(entry.separator, Some(&entry.key));
+            let reference = entry.key.as_ref();
+            let old = old.get_object(reference)?;
+            let Some(new) = new.map.get(reference) else {
+                output.push_str(strip_line(&separator));
+                continue;
+            };
+            output.push_str(&separator);
+            if old == new {
+                output.push_str(entry.text);
+                continue;
+            }
+            let inline = SINGLE_LINE.contains(&section_of(new).as_str());
+            // Keep the original key text unless its annotation may need renaming
+            match entry.head.contains("/*") {
+                true => output.push_str(&self.render(|w| {
+                    w.push(&quote(reference));
+                    w.annotate(reference);
+                    w.push(" = ");
+                })),
+                false => output.push_str(entry.head),
+            }
+            let patched = self.object(entry.value, old, new, 2);
+            output.push_str(&patched.unwrap_or_else(|| self.render(|w| w.object(new, 2, inline))));
+            output.push(';');
+        }
+        output.push_str(&sections.separator(closing, None));
+
+        // Sections left empty by removed objects are dropped, as Xcode does
+        for isa in sections.existing {
+            output = output.replace(
+                &format!("\n/* Begin {isa} section */\n/* End {isa} section */\n"),
+                "",
+            );
+        }
+        Some(output)
+    }
+
+    /// Write entry `reference` of the objects section on its own line
+    fn entry(&self, reference: &str, object: &PBXHashMap) -> String {
+        let inline = SINGLE_LINE.contains(&section_of(object).as_str());
+        self.render(|w| {
+            w.indent(2);
+            w.push(&quote(reference));
+            w.annotate(reference);
+            w.push(" = ");
+            w.object(object, 2, inline);
+            w.push(";\n");
+        })
+    }
+}
+
+/// Sections of the objects section, tracking objects to add to them
+struct Sections<'a> {
+    patcher: &'a Patcher<'a>,
+    objects: &'a PBXObjectCollection,
+    /// Sections found in content
+    existing: HashSet<&'a str>,
+    /// References of added objects not written yet, by section
+    pending: BTreeMap<String, Vec<&'a String>>,
+    /// Section of the entries being written
+    current: Option<String>,
+}
+
+impl Sections<'_> {
+    /// Write `separator` preceding entry `next`, or closing the objects section if `None`,
+    /// adding pending objects before it in sorted position.
+    fn separator(&mut self, separator: &str, next: Option<&str>) -> String {
+        let mut output = String::new();
+        let mut rest = separator;
+        while let Some(index) = rest
+            .find("/* Begin ")
+            .into_iter()
+            .chain(rest.find("/* End "))
+            .min()
+        {
+            let Some((isa, length)) = section_marker(&rest[index..]) else {
+                output.push_str(&rest[..index + 3]);
+                rest = &rest[index + 3..];
+                continue;
+            };
+            output.push_str(&rest[..index]);
+            if rest[index..].starts_with("/* End ") {
+                output.push_str(&self.entries(isa, None));
+                self.current = None;
+            } else {
+                let added = self
+                    .pending
+                    .keys()
+                    .filter(|section| {
+                        section.as_str() < isa && !self.existing.contains(section.as_str())
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for section in added {
+                    let entries = self.entries(&section, None);
+                    output.push_str(&format!(
+                        "/* Begin {section} section */\n{entries}/* End {section} section */\n\n"
+                    ));
+                }
+                self.current = Some(isa.to_string());
+            }
+            output.push_str(&rest[index..index + length]);
+            rest = &rest[index + length..];
+        }
+
+        let line = rest.rfind('\n').map(|index| index + 1).unwrap_or_default();
+        output.push_str(&rest[..line]);
+        match next {
+            Some(next) => {
+                if let Some(section) = self.current.clone() {
+                    output.push_str(&self.entries(&section, Some(next)));
+                }
+            }
+            None => {
+                for section in self.pending.keys().cloned().collect::<Vec<_>>() {
+                    let entries = self.entries(&section, None);
+                    match self.existing.is_empty() || self.existing.contains(section.as_str()) {
+                        true => output.push_str(&entries),
+                        false => output.push_str(&format!(
+                            "\n/* Begin {section} section */\n{entries}/* End {section} section */\n"
+                        )),
+                    }
+                }
+            }
+        }
+        output.push_str(&rest[line..]);
+        output
+    }
+
+    /// Write pending entries of `section` sorting before `next`, all if `None`
+    fn entries(&mut self, section: &str, next: Option<&str>) -> String {
+        let Some(references) = self.pending.get_mut(section) else {
+            return String::new();
+        };
+        let count = references
+            .iter()
+            .take_while(|reference| next.is_none_or(|next| reference.as_str() < next))
+            .count();
+        let written = references.drain(..count).collect::<Vec<_>>();
+        if references.is_empty() {
+            self.pending.remove(section);
+        }
+        written
+            .into_iter()
+            .map(|reference| self.patcher.entry(reference, &self.objects[reference]))
+            .collect()
+    }
+}
+
+/// Get section name and length of marker `/* Begin X section */` or `/* End X section */`
+/// starting `text`
+fn section_marker(text: &str) -> Option<(&str, usize)> {
+    let end = text.find("*/")?;
+    let marker = text[..end].trim_start_matches("/*").trim();
+    let isa = marker
+        .strip_prefix("Begin ")
+        .or_else(|| marker.strip_prefix("End "))?
+        .strip_suffix(" section")?;
+    Some((isa, end + 2))
+}
+
+/// Drop the line break and indentation ending `separator`, for a removed entry
+fn strip_line(separator: &str) -> &str {
+    match separator.rfind('\n') {
+        Some(index) if separator[index..].trim().is_empty() => {
+            separator[..index].trim_end_matches('\r')
+        }
+        _ => separator.trim_end_matches([' ', '\t']),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    fn sample(name: &str) -> String {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/samples/{name}.pbxproj"));
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn preserve_unmodified() {
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        for entry in std::fs::read_dir(samples).unwrap().flatten() {
+            let content = std::fs::read_to_string(entry.path()).unwrap();
+            let root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+            let preserved = root_object.to_pbxproj_string_preserving();
+            assert!(content == preserved, "{:?}", entry.path());
+        }
+    }
+
+    #[test]
+    fn preserve_modified() {
+        // Saved by Xcode, so patching the content matches writing it anew
+        let mut root_object = PBXRootObject::try_from(sample("demo11").as_str()).unwrap();
+        root_object.set_project_name("CreatingAndCombiningViews");
+        let target = root_object.get_target_by_name("Landmarks").unwrap().id;
+        let group = root_object.root_group().id;
+        let objects = root_object.objects_mut();
+        objects
+            .set_setting_all(&target, "SWIFT_VERSION", "6.0")
+            .unwrap();
+        objects
+            .remove_file_reference("C2AC4E8924DE1856005E019B")
+            .unwrap();
+        objects
+            .add_file_reference(&group, "New File.swift")
+            .unwrap();
+        root_object
+            .add_swift_package(
+                &target,
+                "https://github.com/apple/swift-log",
+                "Logging",
+                XCVersionRequirement::UpToNextMajorVersion("1.5.0".into()),
+            )
+            .unwrap();
+        assert!(root_object.to_pbxproj_string() == root_object.to_pbxproj_string_preserving());
+
+        // Hand edited, with quoting and line breaks Xcode doesn't write, kept where unchanged
+        let content = sample("legacy1");
+        let mut root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+        let project = root_object.root_project().id;
+        root_object
+            .objects_mut()
+            .set_setting_all(&project, "SDKROOT", "macosx")
+            .unwrap();
+        let preserved = root_object.to_pbxproj_string_preserving();
+        let changed = content
+            .lines()
+            .zip(preserved.lines())
+            .filter(|(old, new)| old != new)
+            .collect::<Vec<_>>();
+        assert_eq!(content.lines().count(), preserved.lines().count());
+        assert!(!changed.is_empty());
+        assert!(
+            changed.iter().all(|(_, new)| new.contains("SDKROOT")),
+            "{changed:#?}"
+        );
+
+        // Without annotations, only the edited settings lines differ
+        let content = sample("demo1");
+        let mut root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+        let project = root_object.root_project().id;
+        root_object
+            .objects_mut()
+            .set_setting_all(&project, "SDKROOT", "macosx")
+            .unwrap();
+        let preserved = root_object.to_pbxproj_string_preserving();
+        let changed = content
+            .lines()
+            .zip(preserved.lines())
+            .filter(|(old, new)| old != new)
+            .collect::<Vec<_>>();
+        assert_eq!(content.lines().count(), preserved.lines().count());
+        assert_eq!(changed.len(), 2, "{changed:#?}");
+        assert!(
+            changed.iter().all(|(_, new)| new.contains("SDKROOT")),
+            "{changed:#?}"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::pbxproj::pest::Scanner;
+use crate::pbxproj::*;
+use std::ops::Range;
+
+/// Malformed entry of the objects section skipped by [`PBXRootObject::parse_with_recovery`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PBXSkippedEntry {
+    /// Reference of the entry, if its key could be read
+    pub reference: Option<String>,
+    /// Byte range of the entry in the parsed content, from its key to its `;`
+    pub span: Range<usize>,
+    /// Error found parsing the entry
+    pub error: PBXParseError,
+}
+
+impl PBXRootObject {
+    /// Parse `content`, skipping malformed entries of the objects section as a whole instead of
+    /// failing, and returning the project made of the remaining objects along with every
+    /// skipped entry, empty for well formed content.
+    ///
+    /// Unlike [`PBXRootObject::parse_lossy`], objects are either kept intact or dropped, and
+    /// errors outside the objects section still fail. Spans of skipped entries allow
+    /// inspecting or repairing them in the original content.
+    pub fn parse_with_recovery(content: &str) -> Result<(Self, Vec<PBXSkippedEntry>)> {
+        let mut scanner = Scanner::recovering(content);
+        let map = scanner.parse_file()?;
+        let skipped = scanner.take_skipped();
+        let mut root_object = Self::try_from(map)?;
+        root_object.set_duplicates(scanner.take_duplicates());
+        Ok((root_object, skipped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn parse_with_recovery() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let (root_object, skipped) = PBXRootObject::parse_with_recovery(content).unwrap();
+        assert!(skipped.is_empty());
+        assert_eq!(
+            test_demo_file!(demo1).fingerprint(),
+            root_object.fingerprint()
+        );
+
+        let damaged = content.replace("path = Log.swift;", "path = = Log.swift;");
+        assert!(PBXRootObject::try_from(damaged.as_str()).is_err());
+
+        let (root_object, skipped) = PBXRootObject::parse_with_recovery(&damaged).unwrap();
+        assert_eq!(1, skipped.len());
+        let entry = &skipped[0];
+        assert_eq!(Some("D890FDAA55C865F97B25842A"), entry.reference.as_deref());
+        assert!(entry.error.location.is_some());
+        assert!(damaged[entry.span.clone()].starts_with("D890FDAA55C865F97B25842A"));
+        assert!(damaged[entry.span.clone()].ends_with("};"));
+        assert!(root_object.get_file("D890FDAA55C865F97B25842A").is_none());
+        assert_eq!(
+            test_demo_file!(demo1).objects().len() - 1,
+            root_object.objects().len()
+        );
+
+        let broken = content.replace("rootObject = ", "rootObject = = ");
+        assert!(PBXRootObject::parse_with_recovery(&broken).is_err());
+    }
+}
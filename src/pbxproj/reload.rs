@@ -0,0 +1,299 @@
+use crate::pbxproj::pest::{PBXProjectParser, Scanner};
+use crate::pbxproj::*;
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Content the project was loaded from
+#[derive(Clone, Debug)]
+pub(crate) struct PBXSource {
+    content: Arc<str>,
+    /// Index of content, built on first reload
+    index: OnceLock<Option<PBXSourceIndex>>,
+    /// Hand written comments of content, collected on first use
+    comments: OnceLock<Vec<PBXComment>>,
+}
+
+impl PBXSource {
+    pub(crate) fn new(content: &str) -> Self {
+        Self {
+            content: content.into(),
+            index: OnceLock::new(),
+            comments: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn index(&self) -> Option<&PBXSourceIndex> {
+        self.index
+            .get_or_init(|| PBXSourceIndex::new(&self.content))
+            .as_ref()
+    }
+
+    /// Hand written comments of content, see [`PBXRootObject::comments`]
+    pub(crate) fn comments(&self) -> &[PBXComment] {
+        self.comments.get_or_init(|| {
+            let mut scanner = Scanner::with_comments(&self.content);
+            match scanner.parse_file() {
+                Ok(_) => scanner.take_comments(),
+                Err(_) => vec![],
+            }
+        })
+    }
+
+    /// Objects parsed from content
+    pub(crate) fn objects(&self) -> PBXObjectCollection {
+        PBXProjectParser::try_from_str(&*self.content)
+            .and_then(|mut map| PBXObjectCollection::from_map(map.try_remove_object("objects")?))
+            .expect("loaded content to be parseable")
+    }
+}
+
+/// Hashes of raw text blocks of the loaded file, used to find objects changed on disk.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PBXSourceIndex {
+    /// Hash of everything outside the objects section
+    header: u64,
+    /// Hash of every `reference = { ... };` entry of the objects section
+    objects: HashMap<String, u64>,
+}
+
+impl PBXSourceIndex {
+    /// Index `content`, `None` if its objects section can't be located.
+    pub(crate) fn new(content: &str) -> Option<Self> {
+        let section = ObjectsSection::new(content)?;
+        Some(Self {
+            header: hash(&section.header()),
+            objects: section
+                .entries
+                .iter()
+                .map(|(reference, block)| (reference.to_string(), hash(block)))
+                .collect(),
+        })
+    }
+}
+
+impl PBXRootObject {
+    /// Reload project from `content`, the new content of the file the project was loaded from.
+    ///
+    /// Only objects whose raw text changed are parsed and patched into the object graph, which
+    /// keeps reloading huge projects cheap. In-memory changes to other objects are kept. Falls
+    /// back to a full reload when anything outside the objects section changed. Returns applied
+    /// changes, which are also sent to registered observers.
+    pub fn reload_from_str(&mut self, content: &str) -> Result<Vec<PBXObjectChange>> {
+        let source = self.source.as_ref().and_then(|source| source.index());
+        let (section, source) = match (ObjectsSection::new(content), source) {
+            (Some(section), Some(source)) if hash(&section.header()) == source.header => {
+                (section, source)
+            }
+            _ => return self.full_reload(content),
+        };
+
+        let changed = section
+            .entries
+            .iter()
+            .filter(|(reference, block)| source.objects.get(*reference) != Some(&hash(block)))
+            .map(|(_, block)| *block)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut changed = PBXProjectParser::try_object_from_str(format!("{{\n{changed}\n}}"))?;
+        let references = section
+            .entries
+            .iter()
+            .map(|(reference, _)| *reference)
+            .collect::<HashSet<_>>();
+        let removed = source
+            .objects
+            .keys()
+            .filter(|reference| !references.contains(reference.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // A baseline not parsed yet stays in sync by deriving it from the new content later
+        let mut baseline = self.baseline.get_mut();
+        let mut changes = vec![];
+        self.objects.invalidate_path_index();
+        for reference in removed {
+            if let Some(baseline) = baseline.as_mut() {
+                baseline.map.remove(&reference);
+            }
+            if self.objects.map.remove(&reference).is_some() {
+                changes.push(PBXObjectChange::Removed { reference });
+            }
+        }
+
+        let mut references = changed.keys().map(|r| r.to_string()).collect::<Vec<_>>();
+        references.sort();
+        for reference in references {
+            let object = changed
+                .remove_value(&reference)
+                .unwrap()
+                .try_into_object()?;
+            if let Some(baseline) = baseline.as_mut() {
+                baseline.map.insert(reference.clone(), object.clone());
+            }
+            let change = match self.objects.map.insert(reference.clone(), object.clone()) {
+                None => PBXObjectChange::Added { reference },
+                Some(old) => PBXObjectChange::Modified {
+                    fields: field_changes(&old, &object),
+                    reference,
+                },
+            };
+            changes.push(change);
+        }
+
+        self.source = Some(PBXSource::new(content));
+        self.objects.observers.notify(&changes);
+
+        Ok(changes)
+    }
+
+    /// Reload project from the file at `path`, see [`PBXRootObject::reload_from_str`].
+    pub fn reload_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<PBXObjectChange>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("PBXProjectData from path {path:?}: {e}"))?;
+        self.reload_from_str(&content)
+    }
+
+    fn full_reload(&mut self, content: &str) -> Result<Vec<PBXObjectChange>> {
+        let mut reloaded = Self::try_from(content)?;
+        let changes = object_changes(&self.objects, &reloaded.objects);
+        reloaded
+            .objects
+            .set_reference_mode(self.objects.reference_mode());
+        reloaded.objects.set_decode_mode(self.objects.decode_mode());
+        reloaded.objects.observers = std::mem::take(&mut self.objects.observers);
+        *self = reloaded;
+        self.objects.observers.notify(&changes);
+        Ok(changes)
+    }
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Raw text entries of the objects section of a pbxproj file.
+struct ObjectsSection<'a> {
+    content: &'a str,
+    /// Byte range of the objects section body
+    body: (usize, usize),
+    /// Reference and raw `reference = { ... };` text of each entry
+    entries: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> ObjectsSection<'a> {
+    fn new(content: &'a str) -> Option<Self> {
+        let mut scanner = Scanner::new(content);
+        scanner.skip_line_comment();
+        scanner.expect(b'{')?;
+
+        // Find `objects = {` among top level fields
+        let body_start = loop {
+            scanner.skip_trivia();
+            let key = scanner.token()?;
+            scanner.skip_trivia();
+            scanner.expect(b'=')?;
+            scanner.skip_trivia();
+            if key == "objects" {
+                scanner.expect(b'{')?;
+                break scanner.position;
+            }
+            scanner.skip_value()?;
+            scanner.skip_trivia();
+            scanner.expect(b';')?;
+        };
+
+        let mut entries = vec![];
+        let body_end = loop {
+            scanner.skip_trivia();
+            if scanner.peek()? == b'}' {
+                break scanner.position;
+            }
+            let start = scanner.position;
+            let reference = scanner.token()?.trim_matches('"');
+            scanner.skip_trivia();
+            scanner.expect(b'=')?;
+            scanner.skip_trivia();
+            scanner.skip_value()?;
+            scanner.skip_trivia();
+            scanner.expect(b';')?;
+            entries.push((reference, &content[start..scanner.position]));
+        };
+
+        Some(Self {
+            content,
+            body: (body_start, body_end),
+            entries,
+        })
+    }
+
+    /// Content without the objects section body
+    fn header(&self) -> String {
+        let (start, end) = self.body;
+        format!("{}{}", &self.content[..start], &self.content[end..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn reload_changed_objects() {
+        let content = include_str!("../../tests/samples/demo1.pbxproj");
+        let mut root_object = PBXRootObject::try_from(content).unwrap();
+        let group = root_object.get_or_add_group("Kit").unwrap();
+
+        let reloaded = content
+            .replace("path = Log.swift;", "path = Logger.swift;")
+            .replace(
+                "\t\tF2E640B5C2B85914F6801498 = {isa = PBXFileReference; lastKnownFileType = sourcecode.swift; path = shake.swift; sourceTree = \"<group>\"; };\n",
+                "",
+            );
+        let changes = root_object.reload_from_str(&reloaded).unwrap();
+
+        assert_eq!(
+            vec![
+                PBXObjectChange::Removed {
+                    reference: "F2E640B5C2B85914F6801498".into()
+                },
+                PBXObjectChange::Modified {
+                    reference: "D890FDAA55C865F97B25842A".into(),
+                    fields: vec![PBXFieldChange {
+                        key: "path".into(),
+                        old: Some("Log.swift".into()),
+                        new: Some("Logger.swift".into()),
+                    }]
+                }
+            ],
+            changes
+        );
+        assert!(root_object.get_group(&group).is_some());
+        assert_eq!(
+            Some(&"Logger.swift".to_string()),
+            root_object
+                .get_file("D890FDAA55C865F97B25842A")
+                .unwrap()
+                .path
+        );
+
+        assert!(root_object.reload_from_str(&reloaded).unwrap().is_empty());
+
+        let changes = root_object
+            .reload_from_str(&reloaded.replace("objectVersion = 51;", "objectVersion = 55;"))
+            .unwrap();
+        assert_eq!(55, root_object.object_version());
+        assert!(root_object.get_group(&group).is_none());
+        assert!(!changes.is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! [`Serialize`] and [`Deserialize`] implementations, behind the `serde` feature.
+//!
+//! Values map to their natural serde counterparts, e.g. [`PBXValue::Bool`] to a bool and
+//! [`PBXValue::Kind`] to its isa string, with object keys sorted and unquoted. Decimals map to
+//! strings, keeping them as written. Typed objects
+//! borrow from their collection, so they are only serializable, as their fields written to
+//! project.pbxproj, see [`ToPBXHashMap`]. Deserialize a [`PBXHashMap`] and insert it into the
+//! collection instead.
+use crate::pbxproj::*;
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for PBXValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PBXValue::String(value) => serializer.serialize_str(value),
+            PBXValue::Object(map) => map.serialize(serializer),
+            PBXValue::Vec(values) => values.serialize(serializer),
+            PBXValue::Number(value) => serializer.serialize_i64(*value as i64),
+            // Kept as written, e.g. `5.10`, which a float would turn into `5.1`
+            PBXValue::Float(value) => serializer.serialize_str(value.as_str()),
+            PBXValue::Bool(value) => serializer.serialize_bool(*value),
+            PBXValue::Kind(kind) => serializer.collect_str(kind),
+            PBXValue::Data(bytes) => serializer.serialize_bytes(bytes),
+            PBXValue::Null(()) => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl Serialize for PBXHashMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut fields = self.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(key, _)| *key);
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
+            map.serialize_entry(key.trim_matches('"'), value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for PBXVec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl Serialize for PBXObjectCollection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut objects = self.iter().collect::<Vec<_>>();
+        objects.sort_by_key(|(reference, _)| *reference);
+        serializer.collect_map(objects)
+    }
+}
+
+impl Serialize for PBXRootObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("archiveVersion", &self.archive_version())?;
+        map.serialize_entry("classes", self.classes())?;
+        map.serialize_entry("objectVersion", &self.object_version())?;
+        map.serialize_entry("objects", self.objects())?;
+        map.serialize_entry("rootObject", self.root_object_reference())?;
+        map.end()
+    }
+}
+
+macro_rules! serialize_lowered {
+    ($($object:ident),*) => {
+        $(
+            impl Serialize for $object<'_> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.to_hashmap().serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_lowered!(
+    PBXAppleScriptBuildPhase,
+    PBXBuildFile,
+    PBXBuildPhase,
+    PBXBuildRule,
+    PBXContainerItemProxy,
+    PBXFSReference,
+    PBXFileSystemSynchronizedBuildFileExceptionSet,
+    PBXFileSystemSynchronizedRootGroup,
+    PBXProject,
+    PBXShellScriptBuildPhase,
+    PBXTarget,
+    PBXTargetDependency,
+    XCBuildConfiguration,
+    XCConfigurationList,
+    XCRemoteSwiftPackageReference,
+    XCSwiftPackageProductDependency
+);
+
+struct PBXValueVisitor;
+
+impl<'de> Visitor<'de> for PBXValueVisitor {
+    type Value = PBXValue;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a pbxproj value")
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<PBXValue, E> {
+        Ok(PBXValue::Bool(value))
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<PBXValue, E> {
+        Ok(PBXValue::Number(value as isize))
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<PBXValue, E> {
+        Ok(PBXValue::Number(value as isize))
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<PBXValue, E> {
+        Ok(match PBXDecimal::parse(&value.to_string()) {
+            Some(decimal) => PBXValue::Float(decimal),
+            None => PBXValue::String(value.to_string()),
+        })
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<PBXValue, E> {
+        // Strings naming an object kind are read as such, so isa of objects decodes, and so are
+        // decimals, written as strings. Decimals still read as strings, see `PBXValue::as_text`
+        if let Some(decimal) = PBXDecimal::parse(value) {
+            return Ok(PBXValue::Float(decimal));
+        }
+        Ok(match PBXObjectKind::from(value) {
+            PBXObjectKind::Other(_) => PBXValue::String(value.to_string()),
+            kind => PBXValue::Kind(kind),
+        })
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<PBXValue, E> {
+        Ok(PBXValue::Data(value.to_vec()))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<PBXValue, E> {
+        Ok(PBXValue::Null(()))
+    }
+
+    fn visit_none<E: Error>(self) -> Result<PBXValue, E> {
+        Ok(PBXValue::Null(()))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<PBXValue, D::Error> {
+        PBXValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PBXValue, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(PBXVec::new(values).into())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<PBXValue, A::Error> {
+        PBXHashMapVisitor.visit_map(map).map(PBXValue::Object)
+    }
+}
+
+struct PBXHashMapVisitor;
+
+impl<'de> Visitor<'de> for PBXHashMapVisitor {
+    type Value = PBXHashMap;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a pbxproj object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PBXHashMap, A::Error> {
+        let mut fields = HashMap::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some((key, value)) = map.next_entry::<String, PBXValue>()? {
+            fields.insert(key.into(), value);
+        }
+        Ok(PBXHashMap::new(fields))
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PBXValueVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXHashMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(PBXHashMapVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXVec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(PBXVec::new)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXObjectCollection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(PBXObjectCollection::new)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXRootObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = PBXHashMap::deserialize(deserializer)?;
+        PBXRootObject::try_from(map).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let root_object = test_demo_file!(demo1);
+        let json = serde_json::to_value(&root_object).unwrap();
+        assert_eq!(1, json["archiveVersion"]);
+        let target = root_object.targets().into_iter().next().unwrap();
+        let lowered = serde_json::to_value(&target).unwrap();
+        assert_eq!("PBXNativeTarget", lowered["isa"]);
+        assert_eq!(json["objects"][&target.id]["name"], lowered["name"]);
+
+        let read = serde_json::from_value::<PBXRootObject>(json).unwrap();
+        assert_eq!(root_object.objects(), read.objects());
+        assert_eq!(root_object.object_version(), read.object_version());
+    }
+}
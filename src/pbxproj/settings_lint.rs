@@ -0,0 +1,171 @@
+use crate::pbxproj::*;
+use crate::xcode::{
+    build_setting_name, xcode_build_setting_type, xcode_build_settings, XCBuildSettingType,
+};
+
+/// Build setting of a configuration that likely won't do what its author meant, see
+/// [`PBXRootObject::validate_build_settings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PBXBuildSettingIssue {
+    /// Unknown setting close to a known one, e.g. `IPHONEOS_DEPLOYMENT_TARGE`
+    LikelyTypo {
+        /// Build configuration reference
+        configuration: String,
+        /// Setting key
+        key: String,
+        /// Known setting the key is close to
+        suggestion: &'static str,
+    },
+    /// Setting value doesn't match the type of the setting, e.g. a string where `YES` or `NO`
+    /// is expected
+    TypeMismatch {
+        /// Build configuration reference
+        configuration: String,
+        /// Setting key
+        key: String,
+        /// Setting value type
+        expected: XCBuildSettingType,
+    },
+}
+
+impl std::fmt::Display for PBXBuildSettingIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LikelyTypo {
+                configuration,
+                key,
+                suggestion,
+            } => write!(
+                f,
+                "{configuration:?}: unknown setting {key:?}, did you mean {suggestion:?}?"
+            ),
+            Self::TypeMismatch {
+                configuration,
+                key,
+                expected,
+            } => write!(f, "{configuration:?}: {key:?} expects a {expected:?} value"),
+        }
+    }
+}
+
+impl PBXRootObject {
+    /// Check build settings of every configuration against the catalog of known settings,
+    /// flagging unknown keys close to a known one and values of the wrong type. Unknown keys
+    /// far from any known one are taken for user-defined settings. Issues are sorted.
+    pub fn validate_build_settings(&self) -> Vec<PBXBuildSettingIssue> {
+        let mut issues = vec![];
+        for configuration in self.build_configurations() {
+            for (key, value) in configuration.build_settings.0.iter() {
+                let issue = match xcode_build_setting_type(key) {
+                    Some(expected) if !value_matches(value, expected) => {
+                        PBXBuildSettingIssue::TypeMismatch {
+                            configuration: configuration.id.clone(),
+                            key: key.to_string(),
+                            expected,
+                        }
+                    }
+                    Some(_) => continue,
+                    None => match closest_setting(key) {
+                        Some(suggestion) => PBXBuildSettingIssue::LikelyTypo {
+                            configuration: configuration.id.clone(),
+                            key: key.to_string(),
+                            suggestion,
+                        },
+                        None => continue,
+                    },
+                };
+                issues.push(issue);
+            }
+        }
+        issues.sort_by_key(|issue| issue.to_string());
+        issues
+    }
+}
+
+/// Whether `value` is valid for settings of type `expected`. Values using build setting
+/// macros are assumed valid.
+fn value_matches(value: &PBXValue, expected: XCBuildSettingType) -> bool {
+    match (value, expected) {
+        (PBXValue::String(value), _) if value.contains("$(") || value.contains("${") => true,
+        (PBXValue::Bool(_), XCBuildSettingType::Boolean) => true,
+        (PBXValue::String(value), XCBuildSettingType::Boolean) => value == "YES" || value == "NO",
+        (_, XCBuildSettingType::Boolean) => false,
+        (PBXValue::Vec(_), XCBuildSettingType::String) => false,
+        _ => true,
+    }
+}
+
+/// Known setting within typo distance of `key`, ignoring conditions, if any
+fn closest_setting(key: &str) -> Option<&'static str> {
+    let key = build_setting_name(key);
+    // Short keys are too close to each other to tell typos apart
+    let max_distance = if key.len() < 12 { 1 } else { 2 };
+    xcode_build_settings()
+        .map(|setting| (edit_distance(key, setting), setting))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, setting)| setting)
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_build_settings() {
+        let mut root_object = test_demo_file!(demo1);
+        assert_eq!(
+            Vec::<PBXBuildSettingIssue>::new(),
+            root_object.validate_build_settings()
+        );
+
+        let configuration = root_object.build_configurations()[0].id.clone();
+        let mut object = root_object
+            .objects_mut()
+            .get_object_mut(&configuration)
+            .unwrap();
+        let settings = object.object_entry("buildSettings");
+        settings.insert_value("IPHONEOS_DEPLOYMENT_TARGE", "15.0");
+        settings.insert_value("ENABLE_BITCODE", "false");
+        settings.insert_value("SKIP_INSTALL", "$(SKIP)");
+        settings.insert_value("PRODUCT_NAME", vec!["A", "B"]);
+        settings.insert_value("MY_CUSTOM_FLAG", "1");
+        drop(object);
+
+        assert_eq!(
+            vec![
+                PBXBuildSettingIssue::TypeMismatch {
+                    configuration: configuration.clone(),
+                    key: "ENABLE_BITCODE".into(),
+                    expected: XCBuildSettingType::Boolean,
+                },
+                PBXBuildSettingIssue::TypeMismatch {
+                    configuration: configuration.clone(),
+                    key: "PRODUCT_NAME".into(),
+                    expected: XCBuildSettingType::String,
+                },
+                PBXBuildSettingIssue::LikelyTypo {
+                    configuration: configuration.clone(),
+                    key: "IPHONEOS_DEPLOYMENT_TARGE".into(),
+                    suggestion: "IPHONEOS_DEPLOYMENT_TARGET",
+                },
+            ],
+            root_object.validate_build_settings()
+        );
+    }
+}
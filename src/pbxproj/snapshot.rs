@@ -0,0 +1,54 @@
+use crate::pbxproj::*;
+
+/// Opaque snapshot of the project object graph, see [`PBXRootObject::snapshot`]
+#[derive(Debug, Clone)]
+pub struct PBXSnapshot {
+    objects: PBXObjectCollection,
+}
+
+impl PBXRootObject {
+    /// Take a snapshot of the object graph that can be restored later with
+    /// [`PBXRootObject::restore`], e.g. to implement undo stacks.
+    pub fn snapshot(&self) -> PBXSnapshot {
+        PBXSnapshot {
+            objects: self.objects.clone(),
+        }
+    }
+
+    /// Revert the object graph to `snapshot`. Registered observers are notified of reverted
+    /// changes, and [`PBXRootObject::changes`] is still relative to the loaded project.
+    pub fn restore(&mut self, snapshot: PBXSnapshot) {
+        let changes = if self.objects.observers.is_empty() {
+            vec![]
+        } else {
+            object_changes(&self.objects, &snapshot.objects)
+        };
+
+        let observers = std::mem::take(&mut self.objects.observers);
+        self.objects = snapshot.objects;
+        self.objects.observers = observers;
+        self.objects.observers.notify(&changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut root_object = test_demo_file!(demo1);
+        let snapshot = root_object.snapshot();
+
+        root_object.get_or_add_group("Kit").unwrap();
+        let modified = root_object.snapshot();
+        assert!(root_object.is_modified());
+
+        root_object.restore(snapshot);
+        assert!(!root_object.is_modified());
+        assert!(root_object.get_group_by_name_or_path("Kit").is_none());
+
+        root_object.restore(modified);
+        assert!(root_object.get_group_by_name_or_path("Kit").is_some());
+    }
+}
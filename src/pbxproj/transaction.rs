@@ -0,0 +1,62 @@
+use crate::pbxproj::*;
+use anyhow::Result;
+
+impl PBXRootObject {
+    /// Apply multi-step edits atomically.
+    ///
+    /// `edit` mutates a working copy of the project, which replaces the project only when `edit`
+    /// succeeds. On error every mutation is discarded and the project is left untouched.
+    /// Registered observers are notified of committed changes.
+    ///
+    /// ```ignore
+    /// root_object.transaction(|tx| {
+    ///     let target = tx.add_native_target("App", PBXProductType::Application, platform)?;
+    ///     tx.set_bridging_header(&target, "App/App-Bridging-Header.h")
+    /// })?;
+    /// ```
+    pub fn transaction<T, F>(&mut self, edit: F) -> Result<T>
+    where
+        F: FnOnce(&mut PBXRootObject) -> Result<T>,
+    {
+        let mut working_copy = self.clone();
+        let value = edit(&mut working_copy)?;
+        let changes = if self.objects.observers.is_empty() {
+            vec![]
+        } else {
+            object_changes(&self.objects, &working_copy.objects)
+        };
+
+        working_copy.objects.observers = std::mem::take(&mut self.objects.observers);
+        *self = working_copy;
+        self.objects.observers.notify(&changes);
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn transaction() {
+        let mut root_object = test_demo_file!(demo1);
+        let objects = root_object.objects().len();
+
+        let result = root_object.transaction(|tx| {
+            tx.add_native_target("Kit", PBXProductType::Framework, PBXTargetPlatform::IOS)?;
+            tx.add_native_target("Kit", PBXProductType::Framework, PBXTargetPlatform::IOS)
+        });
+        assert!(result.is_err());
+        assert_eq!(objects, root_object.objects().len());
+        assert_eq!(1, root_object.targets().len());
+
+        let target = root_object
+            .transaction(|tx| {
+                tx.add_native_target("Kit", PBXProductType::Framework, PBXTargetPlatform::IOS)
+            })
+            .unwrap();
+        assert_eq!(2, root_object.targets().len());
+        assert!(root_object.get_target(&target).is_some());
+    }
+}
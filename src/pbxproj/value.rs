@@ -6,8 +6,10 @@ use enum_as_inner::EnumAsInner;
 use enum_variant_macros::FromVariants;
 use std::collections::HashMap;
 
+pub use smol_str::SmolStr;
+
 /// Repersentation of all values that can be collected from pbxproj file.
-#[derive(Debug, PartialEq, Eq, FromVariants, EnumAsInner, is_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, FromVariants, EnumAsInner, is_enum_variant)]
 pub enum PBXValue {
     /// Normal String value. NOTE: This may literal string!
     String(String),
@@ -17,10 +19,14 @@ pub enum PBXValue {
     Vec(PBXVec),
     /// Number
     Number(isize),
+    /// Decimal number, e.g. `1.5`
+    Float(PBXDecimal),
     /// Boolean representation of YES, NO
     Bool(bool),
     /// ObjectKind
     Kind(PBXObjectKind),
+    /// Data literal, e.g. `<0fbd771c 2735ae>`
+    Data(Vec<u8>),
     /// Nothing
     Null(()),
 }
@@ -65,6 +71,14 @@ impl TryFrom<PBXValue> for isize {
     }
 }
 
+impl TryFrom<PBXValue> for PBXDecimal {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PBXValue) -> Result<Self, Self::Error> {
+        value.try_into_float()
+    }
+}
+
 impl TryFrom<PBXValue> for PBXObjectKind {
     type Error = anyhow::Error;
 
@@ -83,7 +97,7 @@ where
             .try_into_object()?
             .0
             .into_iter()
-            .map(|(k, v)| (k, v.into()))
+            .map(|(k, v)| (k.into(), v.into()))
             .collect())
     }
 }
@@ -95,10 +109,7 @@ where
     PBXValue: From<T>,
 {
     fn from(v: HashMap<String, T>) -> Self {
-        let inner = v
-            .into_iter()
-            .map(|(k, v)| (k, v.try_into().unwrap()))
-            .collect();
+        let inner = v.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
 
         PBXValue::Object(PBXHashMap(inner))
     }
@@ -152,6 +163,18 @@ where
 }
 
 impl PBXValue {
+    /// Get text of [`String`] values, and of [`Float`] values as written, e.g. `5.10` read
+    /// unquoted for `SWIFT_VERSION`.
+    ///
+    /// [`String`]: PBXValue::String
+    /// [`Float`]: PBXValue::Float
+    pub fn as_text(&self) -> Option<&String> {
+        match self {
+            Self::String(value) | Self::Float(PBXDecimal(value)) => Some(value),
+            _ => None,
+        }
+    }
+
     /// Returns `Ok(T)` if the value is [`String`].
     ///
     /// [`String`]: PBXValue::String
@@ -195,6 +218,17 @@ impl PBXValue {
         }
     }
 
+    /// Returns `Ok(T)` if the value is [`Float`].
+    ///
+    /// [`Float`]: PBXValue::Float
+    pub fn try_into_float(self) -> Result<PBXDecimal> {
+        if let Self::Float(v) = self {
+            Ok(v)
+        } else {
+            bail!("expected float got {self:#?}")
+        }
+    }
+
     /// Returns `Ok(T)` if the value is [`Bool`].
     ///
     /// [`Bool`]: PBXValue::Bool
@@ -218,13 +252,48 @@ impl PBXValue {
     }
 }
 
-/// [`HashMap`] wrapper for [`PBXValue`] with helpers
-#[derive(Default, Debug, Deref, PartialEq, Eq)]
-pub struct PBXHashMap(pub(crate) HashMap<String, PBXValue>);
+/// Decimal number as written in the project file, e.g. `1.5` for `MARKETING_VERSION`.
+///
+/// The text is kept as read so values are written back exactly, `5.10` staying `5.10`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PBXDecimal(String);
+
+impl PBXDecimal {
+    /// Parse `text` made of digits with a single decimal point, e.g. `1.5`. Versions like
+    /// `1.2.0` are not decimals.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (integer, fraction) = text.split_once('.')?;
+        let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        (is_digits(integer) && is_digits(fraction)).then(|| Self(text.into()))
+    }
+
+    /// Get the decimal as written
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Get the decimal as a float, possibly losing precision
+    pub fn as_f64(&self) -> f64 {
+        self.0.parse().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for PBXDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// [`HashMap`] wrapper for [`PBXValue`] with helpers.
+///
+/// Keys are [`SmolStr`]s, short identifiers stored inline without heap allocation. String
+/// values, see [`PBXValue::String`], remain [`String`]s.
+#[derive(Clone, Default, Debug, Deref, PartialEq, Eq)]
+pub struct PBXHashMap(pub(crate) HashMap<SmolStr, PBXValue>);
 
 impl PBXHashMap {
     /// ...
-    pub fn new(inner: HashMap<String, PBXValue>) -> Self {
+    pub fn new(inner: HashMap<SmolStr, PBXValue>) -> Self {
         Self(inner)
     }
     /// Get Value from map
@@ -232,6 +301,26 @@ impl PBXHashMap {
         self.0.get(key)
     }
 
+    /// Get key of the map matching `key`: the key itself if present, otherwise a key equal to it
+    /// ignoring ASCII case and surrounding quotes, e.g. `"ProvisioningStyle"` for
+    /// `provisioningStyle`. Keys are kept as read, so the first one in sorted order is returned
+    /// when several differ only by case.
+    pub fn find_key(&self, key: &str) -> Option<&SmolStr> {
+        if let Some((key, _)) = self.0.get_key_value(key) {
+            return Some(key);
+        }
+        let key = key.trim_matches('"');
+        self.0
+            .keys()
+            .filter(|candidate| candidate.trim_matches('"').eq_ignore_ascii_case(key))
+            .min()
+    }
+
+    /// Get value of key matching `key` ignoring case, see [`PBXHashMap::find_key`]
+    pub fn get_value_ignore_case(&self, key: &str) -> Option<&PBXValue> {
+        self.0.get(self.find_key(key)?)
+    }
+
     /// Try Get Value from map or error
     pub fn try_get_value(&self, key: &str) -> Result<&PBXValue> {
         self.0
@@ -250,22 +339,89 @@ impl PBXHashMap {
             .remove(key)
             .ok_or_else(|| anyhow::anyhow!("{key} is not found!"))
     }
+
+    /// Insert value into map, returning the previous value if any
+    pub fn insert_value<K, V>(&mut self, key: K, value: V) -> Option<PBXValue>
+    where
+        K: Into<SmolStr>,
+        V: Into<PBXValue>,
+    {
+        self.0.insert(key.into(), value.into())
+    }
+
+    /// Insert value into map if some, e.g. for optional fields
+    pub(crate) fn insert_some<K, V>(&mut self, key: K, value: Option<V>)
+    where
+        K: Into<SmolStr>,
+        V: Into<PBXValue>,
+    {
+        if let Some(value) = value {
+            self.insert_value(key, value);
+        }
+    }
+
+    /// Get mutable value from map
+    pub fn get_value_mut(&mut self, key: &str) -> Option<&mut PBXValue> {
+        self.0.get_mut(key)
+    }
+
+    /// Get mutable vec at `key`, inserting an empty one if missing or of another type
+    pub fn vec_entry<K: Into<SmolStr>>(&mut self, key: K) -> &mut PBXVec {
+        let value = self
+            .0
+            .entry(key.into())
+            .or_insert_with(|| PBXVec::default().into());
+        if !value.is_vec() {
+            *value = PBXVec::default().into();
+        }
+        value.as_vec_mut().unwrap()
+    }
+
+    /// Get mutable object at `key`, inserting an empty one if missing or of another type
+    pub fn object_entry<K: Into<SmolStr>>(&mut self, key: K) -> &mut PBXHashMap {
+        let value = self
+            .0
+            .entry(key.into())
+            .or_insert_with(|| PBXHashMap::default().into());
+        if !value.is_object() {
+            *value = PBXHashMap::default().into();
+        }
+        value.as_object_mut().unwrap()
+    }
 }
 
 gen_hash_map_helpers! {
-    [string, String],
-    [vec, PBXVec],
-    [bool, bool],
-    [number, isize],
-    [kind, PBXObjectKind],
-    [object, PBXHashMap]
+    [string, String, as_text],
+    [vec, PBXVec, as_vec],
+    [bool, bool, as_bool],
+    [number, isize, as_number],
+    [float, PBXDecimal, as_float],
+    [kind, PBXObjectKind, as_kind],
+    [object, PBXHashMap, as_object]
 }
 
 /// [`Vec`] wrapper for [`PBXValue`] with helpers
-#[derive(Default, Debug, Deref, PartialEq, Eq, derive_new::new)]
+#[derive(Clone, Default, Debug, Deref, PartialEq, Eq, derive_new::new)]
 pub struct PBXVec(pub(crate) Vec<PBXValue>);
 
 impl PBXVec {
+    /// Push value to the end of the vector
+    pub fn push<V: Into<PBXValue>>(&mut self, value: V) {
+        self.0.push(value.into())
+    }
+
+    /// Insert value at `index`, shifting later values to the end
+    pub fn insert<V: Into<PBXValue>>(&mut self, index: usize, value: V) {
+        self.0.insert(index, value.into())
+    }
+
+    /// Get index of the string value equal to `value`
+    pub fn position(&self, value: &str) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|v| v.as_string().map(|v| v.as_str()) == Some(value))
+    }
+
     pub(crate) fn try_into_vec_strings(self) -> Result<Vec<String>> {
         let mut collector = vec![];
         for value in self.0 {
@@ -274,7 +430,7 @@ impl PBXVec {
         Ok(collector)
     }
 
-    pub(crate) fn as_vec_strings<'a>(&'a self) -> Vec<&'a String> {
+    pub(crate) fn as_vec_strings(&self) -> Vec<&String> {
         let mut collector = vec![];
         for value in self.0.iter() {
             if let Some(str) = value.as_string() {
@@ -287,8 +443,38 @@ impl PBXVec {
     pub(crate) fn try_into_vec<T: TryFrom<T> + From<PBXValue>>(self) -> Result<Vec<T>> {
         let mut collector = vec![];
         for value in self.0 {
-            collector.push(value.try_into()?);
+            collector.push(value.into());
         }
         Ok(collector)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_key_ignoring_case() {
+        let mut map = PBXHashMap::default();
+        map.insert_value("ProvisioningStyle", "Automatic");
+        map.insert_value("provisioningStyle", "Manual");
+        map.insert_value("\"CODE_SIGN_IDENTITY[sdk=iphoneos*]\"", "iPhone Developer");
+
+        assert_eq!(
+            Some(&"Manual".to_string()),
+            map.get_string("provisioningStyle")
+        );
+        assert_eq!(None, map.get_string("PROVISIONINGSTYLE"));
+        assert_eq!(
+            Some(&"Automatic".to_string()),
+            map.get_string_ignore_case("PROVISIONINGSTYLE")
+        );
+        assert_eq!(
+            Some("\"CODE_SIGN_IDENTITY[sdk=iphoneos*]\""),
+            map.find_key("code_sign_identity[sdk=iphoneos*]")
+                .map(SmolStr::as_str)
+        );
+        assert_eq!(None, map.get_number_ignore_case("provisioningStyle"));
+        assert_eq!(None, map.find_key("missing"));
+    }
+}
@@ -0,0 +1,759 @@
+use crate::pbxproj::reload::PBXSource;
+use crate::pbxproj::*;
+use crate::XCSaveOptions;
+use std::collections::{BTreeMap, HashMap};
+use tap::Pipe;
+
+/// Object kinds Xcode writes on a single line
+pub(crate) const SINGLE_LINE: [&str; 2] = ["PBXBuildFile", "PBXFileReference"];
+
+/// Keys whose references Xcode writes without annotation
+pub(crate) const UNANNOTATED: [&str; 2] = ["remoteGlobalIDString", "TestTargetID"];
+
+impl PBXRootObject {
+    /// Serialize as project.pbxproj content, laid out like Xcode does: objects grouped in
+    /// sections by `isa`, sections and objects sorted, and fields sorted by key with `isa`
+    /// first.
+    ///
+    /// References are annotated with comments derived from the objects they reference, e.g.
+    /// `/* main.swift in Sources */`, so diffs against files saved by Xcode stay readable.
+    /// Strings are written quoted when needed, escaping quotes, backslashes and control
+    /// characters like Xcode does, e.g. `\"` and `\n`. Hand written comments, see
+    /// [`PBXRootObject::comments`], are written back before the value they are attached to.
+    pub fn to_pbxproj_string(&self) -> String {
+        let annotations = self.annotations();
+        let mut writer = PBXWriter::new(&annotations).with_comments(self.comments());
+        writer.push("// !$*UTF8*$!\n{\n");
+        writer.field(
+            "archiveVersion",
+            &PBXValue::Number(self.archive_version as isize),
+            1,
+        );
+        writer.indent(1);
+        writer.enter("classes");
+        writer.leading_comments(1, false);
+        writer.push("classes = ");
+        writer.object(&self.classes, 1, false);
+        writer.exit();
+        writer.push(";\n");
+        writer.field(
+            "objectVersion",
+            &PBXValue::Number(self.object_version as isize),
+            1,
+        );
+
+        let mut sections = BTreeMap::<String, Vec<(&String, &PBXHashMap)>>::new();
+        for (reference, object) in self.objects.iter() {
+            sections
+                .entry(section_of(object))
+                .or_default()
+                .push((reference, object));
+        }
+
+        writer.indent(1);
+        writer.enter("objects");
+        writer.leading_comments(1, false);
+        writer.push("objects = {\n");
+        for (isa, mut objects) in sections {
+            objects.sort_by_key(|(reference, _)| *reference);
+            writer.push(&format!("\n/* Begin {isa} section */\n"));
+            let inline = SINGLE_LINE.contains(&isa.as_str());
+            for (reference, object) in objects {
+                writer.indent(2);
+                writer.enter(reference);
+                writer.leading_comments(2, false);
+                writer.push(&quote(reference));
+                writer.annotate(reference);
+                writer.push(" = ");
+                writer.object(object, 2, inline);
+                writer.exit();
+                writer.push(";\n");
+            }
+            writer.push(&format!("/* End {isa} section */\n"));
+        }
+        writer.trailing_comments(1, false);
+        writer.exit();
+        writer.push("\t};\n");
+
+        writer.field(
+            "rootObject",
+            &PBXValue::String(self.root_object_reference.clone()),
+            1,
+        );
+        writer.trailing_comments(0, false);
+        writer.push("}\n");
+        writer.output
+    }
+
+    /// Write project.pbxproj content to `writer`, e.g. a buffer or a socket, see
+    /// [`PBXRootObject::to_pbxproj_string`]. Unlike [`PBXRootObject::write_to_file`], the
+    /// project isn't marked as saved.
+    pub fn write_to<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        writer
+            .write_all(self.to_pbxproj_string().as_bytes())
+            .context("Write project content")?;
+        writer.flush().context("Flush project content")
+    }
+
+    /// Write project.pbxproj content to `path`, see [`PBXRootObject::to_pbxproj_string`], and
+    /// mark the project as saved.
+    pub fn write_to_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.write_to_file_with(path, &XCSaveOptions::new())
+    }
+
+    /// Write project.pbxproj content to `path` with the given options, e.g. to keep a backup,
+    /// and mark the project as saved.
+    pub fn write_to_file_with<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        options: &XCSaveOptions,
+    ) -> Result<()> {
+        let content = self.to_pbxproj_string_with(options);
+        options.write(path, &content)?;
+        self.mark_written(&content);
+        Ok(())
+    }
+
+    /// Get project.pbxproj content written with the given options
+    pub(crate) fn to_pbxproj_string_with(&self, options: &XCSaveOptions) -> String {
+        match options.preserves_layout() {
+            true => self.to_pbxproj_string_preserving(),
+            false => self.to_pbxproj_string(),
+        }
+    }
+
+    /// Record `content` as written to disk, marking the project as saved
+    pub(crate) fn mark_written(&mut self, content: &str) {
+        self.source = Some(PBXSource::new(content));
+        self.mark_saved();
+    }
+
+    /// Get comments Xcode writes next to references, by reference
+    pub(crate) fn annotations(&self) -> HashMap<&str, String> {
+        let objects = &self.objects.map;
+        let name = |reference: &str| {
+            let object = objects.get(reference)?;
+            object
+                .get_string("name")
+                .or_else(|| object.get_string("path"))
+                .cloned()
+        };
+        let phase_name = |phase: &PBXHashMap| {
+            phase.get_string("name").cloned().or_else(|| {
+                match phase.get_kind("isa")?.as_pbx_build_phase()? {
+                    PBXBuildPhaseKind::RunScript => "ShellScript".to_string(),
+                    kind => kind.to_string(),
+                }
+                .pipe(Some)
+            })
+        };
+
+        let mut phases = HashMap::new();
+        let mut lists = HashMap::new();
+        let mut folders = HashMap::new();
+        for (reference, object) in objects.iter() {
+            match object.get_kind("isa") {
+                Some(PBXObjectKind::PBXBuildPhase(_)) => {
+                    let files = object.get_vec("files").map(|v| v.as_vec_strings());
+                    phases.extend(files.into_iter().flatten().map(|f| (f.as_str(), object)));
+                }
+                Some(PBXObjectKind::PBXTarget(_) | PBXObjectKind::PBXProject) => {
+                    if let Some(list) = object.get_string("buildConfigurationList") {
+                        lists.insert(list.as_str(), object);
+                    }
+                }
+                Some(PBXObjectKind::PBXFileSystemSynchronizedRootGroup) => {
+                    let exceptions = object.get_vec("exceptions").map(|v| v.as_vec_strings());
+                    folders.extend(
+                        exceptions
+                            .into_iter()
+                            .flatten()
+                            .map(|e| (e.as_str(), reference)),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let mut annotations = HashMap::new();
+        for (reference, object) in objects.iter() {
+            let Some(kind) = object.get_kind("isa") else {
+                continue;
+            };
+            let annotation = match kind {
+                PBXObjectKind::PBXFSReference(_)
+                | PBXObjectKind::PBXReferenceProxy
+                | PBXObjectKind::PBXFileSystemSynchronizedRootGroup => name(reference),
+                PBXObjectKind::PBXTarget(_) | PBXObjectKind::XCBuildConfiguration
+                    if object.get_string("name").is_some() =>
+                {
+                    object.get_string("name").cloned()
+                }
+                PBXObjectKind::PBXProject => Some("Project object".into()),
+                PBXObjectKind::PBXBuildPhase(_) => phase_name(object),
+                PBXObjectKind::PBXBuildFile => {
+                    let file = object
+                        .get_string("fileRef")
+                        .and_then(|file| name(file))
+                        .or_else(|| {
+                            let product = objects.get(object.get_string("productRef")?)?;
+                            product.get_string("productName").cloned()
+                        });
+                    match (
+                        file,
+                        phases.get(reference.as_str()).and_then(|p| phase_name(p)),
+                    ) {
+                        (Some(file), Some(phase)) => Some(format!("{file} in {phase}")),
+                        (file, _) => file,
+                    }
+                }
+                PBXObjectKind::XCConfigurationList => lists.get(reference.as_str()).map(|owner| {
+                    let isa = owner.get_kind("isa").cloned().unwrap_or(kind.clone());
+                    let name = match isa {
+                        PBXObjectKind::PBXProject => self.project_name(),
+                        _ => owner.get_string("name").map(String::as_str),
+                    };
+                    match name {
+                        Some(name) => format!("Build configuration list for {isa} \"{name}\""),
+                        None => format!("Build configuration list for {isa}"),
+                    }
+                }),
+                PBXObjectKind::XCRemoteSwiftPackageReference => {
+                    object.get_string("repositoryURL").map(|url| {
+                        let name = url.trim_end_matches('/').rsplit('/').next().unwrap_or(url);
+                        let name = name.strip_suffix(".git").unwrap_or(name);
+                        format!("{kind} \"{name}\"")
+                    })
+                }
+                PBXObjectKind::XCLocalSwiftPackageReference => object
+                    .get_string("relativePath")
+                    .map(|path| format!("{kind} \"{path}\"")),
+                PBXObjectKind::XCSwiftPackageProductDependency => {
+                    object.get_string("productName").cloned()
+                }
+                PBXObjectKind::PBXFileSystemSynchronizedBuildFileExceptionSet => {
+                    let folder = folders.get(reference.as_str()).and_then(|f| name(f));
+                    let target = object.get_string("target").and_then(|t| name(t));
+                    match (folder, target) {
+                        (Some(folder), Some(target)) => Some(format!(
+                            "Exceptions for \"{folder}\" folder in \"{target}\" target"
+                        )),
+                        _ => Some(kind.to_string()),
+                    }
+                }
+                PBXObjectKind::Other(_) => object.get_string("name").cloned(),
+                kind => Some(kind.to_string()),
+            };
+            if let Some(annotation) = annotation {
+                annotations.insert(reference.as_str(), annotation);
+            }
+        }
+        annotations
+    }
+}
+
+/// Name of the section Xcode writes `object` in, its isa
+pub(crate) fn section_of(object: &PBXHashMap) -> String {
+    object
+        .get_value("isa")
+        .map(|isa| match isa {
+            PBXValue::Kind(kind) => kind.to_string(),
+            PBXValue::String(isa) => isa.clone(),
+            _ => String::new(),
+        })
+        .unwrap_or_default()
+}
+
+/// Serializer of pbxproj values, see [`PBXRootObject::to_pbxproj_string`]
+pub(crate) struct PBXWriter<'a> {
+    pub(crate) output: String,
+    /// Comments written next to references, by reference
+    annotations: &'a HashMap<&'a str, String>,
+    /// Hand written comments, written before the value they are attached to
+    comments: &'a [PBXComment],
+    /// Keys and array indices leading to the value being written, tracked with comments only
+    path: Vec<String>,
+}
+
+impl<'a> PBXWriter<'a> {
+    pub(crate) fn new(annotations: &'a HashMap<&'a str, String>) -> Self {
+        Self {
+            output: String::new(),
+            annotations,
+            comments: &[],
+            path: vec![],
+        }
+    }
+
+    pub(crate) fn with_comments(mut self, comments: &'a [PBXComment]) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Enter the value of field or element `segment`
+    fn enter(&mut self, segment: impl ToString) {
+        if !self.comments.is_empty() {
+            self.path.push(segment.to_string());
+        }
+    }
+
+    fn exit(&mut self) {
+        self.path.pop();
+    }
+
+    fn comments_here(&self, trailing: bool) -> Vec<&'a PBXComment> {
+        if self.comments.is_empty() {
+            return vec![];
+        }
+        self.comments
+            .iter()
+            .filter(|comment| comment.trailing == trailing && comment.path == self.path)
+            .collect()
+    }
+
+    fn comment(&mut self, comment: &PBXComment, inline: bool) {
+        // Line comments would swallow the rest of inline objects and arrays
+        match (comment.kind, inline) {
+            (PBXCommentKind::Line, false) => self.push(&format!("// {}", comment.text)),
+            _ => self.push(&format!("/* {} */", comment.text.replace("*/", "(*)/"))),
+        }
+    }
+
+    /// Write comments attached to the value entered last, once indented at `depth`
+    fn leading_comments(&mut self, depth: usize, inline: bool) {
+        for comment in self.comments_here(false) {
+            self.comment(comment, inline);
+            match inline {
+                true => self.push(" "),
+                false => {
+                    self.push("\n");
+                    self.indent(depth);
+                }
+            }
+        }
+    }
+
+    /// Write comments attached to the end of the object or array at `depth` being written
+    fn trailing_comments(&mut self, depth: usize, inline: bool) {
+        for comment in self.comments_here(true) {
+            match inline {
+                true => {
+                    self.comment(comment, inline);
+                    self.push(" ");
+                }
+                // Objects section and root end on their own line
+                false if self.output.ends_with('\n') => {
+                    self.indent(depth + 1);
+                    self.comment(comment, inline);
+                    self.push("\n");
+                }
+                false => {
+                    self.push("\n");
+                    self.indent(depth + 1);
+                    self.comment(comment, inline);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn push(&mut self, content: &str) {
+        self.output.push_str(content);
+    }
+
+    pub(crate) fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            self.output.push('\t');
+        }
+    }
+
+    /// Write ` /* annotation */` if `reference` has one
+    pub(crate) fn annotate(&mut self, reference: &str) {
+        if let Some(annotation) = self.annotations.get(reference) {
+            // Keep annotations from closing the comment early
+            let annotation = annotation.replace("*/", "(*)/");
+            self.output.push_str(&format!(" /* {annotation} */"));
+        }
+    }
+
+    /// Write `key = value;` line at `depth`
+    fn field(&mut self, key: &str, value: &PBXValue, depth: usize) {
+        self.indent(depth);
+        self.enter(key);
+        self.leading_comments(depth, false);
+        self.push(key);
+        self.push(" = ");
+        self.value(value, depth, false, true);
+        self.exit();
+        self.push(";\n");
+    }
+
+    pub(crate) fn value(&mut self, value: &PBXValue, depth: usize, inline: bool, annotate: bool) {
+        match value {
+            PBXValue::String(value) => {
+                self.output.push_str(&quote_string(value));
+                if annotate {
+                    self.annotate(value);
+                }
+            }
+            PBXValue::Object(object) => self.object(object, depth, inline),
+            PBXValue::Vec(values) => self.array(values, depth, inline, annotate),
+            PBXValue::Number(value) => self.output.push_str(&value.to_string()),
+            PBXValue::Float(value) => self.push(value.as_str()),
+            PBXValue::Bool(value) => self.push(if *value { "YES" } else { "NO" }),
+            PBXValue::Kind(kind) => self.output.push_str(&quote(&kind.to_string())),
+            PBXValue::Data(bytes) => self.push(&data_literal(bytes)),
+            PBXValue::Null(_) => self.push("\"\""),
+        }
+    }
+
+    pub(crate) fn object(&mut self, object: &PBXHashMap, depth: usize, inline: bool) {
+        let mut fields = object
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .collect::<Vec<_>>();
+        fields.sort_by(|(a, _), (b, _)| field_order(a).cmp(&field_order(b)));
+
+        self.push("{");
+        for (key, value) in fields {
+            if !inline {
+                self.push("\n");
+                self.indent(depth + 1);
+            }
+            self.enter(key);
+            self.leading_comments(depth + 1, inline);
+            self.push(&quote(key));
+            self.push(" = ");
+            let annotate = !UNANNOTATED.contains(&key.as_str());
+            self.value(value, depth + 1, inline, annotate);
+            self.exit();
+            self.push(";");
+            if inline {
+                self.push(" ");
+            }
+        }
+        self.trailing_comments(depth, inline);
+        if !inline {
+            self.push("\n");
+            self.indent(depth);
+        }
+        self.push("}");
+    }
+
+    pub(crate) fn array(&mut self, values: &PBXVec, depth: usize, inline: bool, annotate: bool) {
+        self.push("(");
+        for (index, value) in values.iter().enumerate() {
+            if value.is_null() {
+                continue;
+            }
+            if !inline {
+                self.push("\n");
+                self.indent(depth + 1);
+            }
+            self.enter(index);
+            self.leading_comments(depth + 1, inline);
+            self.value(value, depth + 1, inline, annotate);
+            self.exit();
+            self.push(",");
+            if inline {
+                self.push(" ");
+            }
+        }
+        self.trailing_comments(depth, inline);
+        if !inline {
+            self.push("\n");
+            self.indent(depth);
+        }
+        self.push(")");
+    }
+}
+
+/// Write `bytes` as data literal, hex digits grouped by 4 bytes like Xcode does, e.g.
+/// `<0fbd771c 2735ae>`
+pub(crate) fn data_literal(bytes: &[u8]) -> String {
+    let groups = bytes
+        .chunks(4)
+        .map(|group| {
+            group
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    format!("<{}>", groups.join(" "))
+}
+
+/// Sort key of fields, `isa` first then by key without quotes
+pub(crate) fn field_order(key: &str) -> (bool, &str) {
+    (key != "isa", key.trim_matches('"'))
+}
+
+/// Quote string `value` also when it would otherwise be read back as a number, a decimal, a
+/// bool or an object kind
+fn quote_string(value: &str) -> std::borrow::Cow<'_, str> {
+    let is_other_type = value.bytes().all(|b| b.is_ascii_digit())
+        || PBXDecimal::parse(value).is_some()
+        || value.eq_ignore_ascii_case("YES")
+        || value.eq_ignore_ascii_case("NO")
+        || !matches!(PBXObjectKind::from(value), PBXObjectKind::Other(_));
+    match is_other_type && !value.is_empty() {
+        true => format!("\"{value}\"").into(),
+        false => quote(value),
+    }
+}
+
+/// Quote `value` unless made of characters Xcode leaves unquoted only
+pub(crate) fn quote(value: &str) -> std::borrow::Cow<'_, str> {
+    let is_plain = !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$' | b'.' | b'/'))
+        && !value.contains("//")
+        && !value.contains("___");
+    if is_plain {
+        return value.into();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for char in value.chars() {
+        match char {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            '\u{7}' => quoted.push_str("\\a"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{B}' => quoted.push_str("\\v"),
+            '\u{C}' => quoted.push_str("\\f"),
+            char if char.is_control() => quoted.push_str(&format!("\\U{:04x}", char as u32)),
+            char => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+    quoted.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn write_round_trip() {
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+        for entry in std::fs::read_dir(samples).unwrap().flatten() {
+            let root_object = PBXRootObject::try_from(entry.path()).unwrap();
+            let content = root_object.to_pbxproj_string();
+            let written = PBXRootObject::try_from(content.as_str()).unwrap();
+            assert_eq!(
+                root_object.objects(),
+                written.objects(),
+                "{:?}",
+                entry.path()
+            );
+            assert_eq!(root_object.object_version(), written.object_version());
+            assert_eq!(
+                root_object.root_object_reference(),
+                written.root_object_reference()
+            );
+            assert_eq!(content, written.to_pbxproj_string());
+        }
+    }
+
+    #[test]
+    fn write_annotations() {
+        // Samples saved by Xcode
+        for (sample, name) in [
+            ("demo10", "Scrumdinger"),
+            ("demo11", "CreatingAndCombiningViews"),
+            ("demo13", "Notes"),
+        ] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join(format!("tests/samples/{sample}.pbxproj"));
+            let content = std::fs::read_to_string(path).unwrap();
+            let mut root_object = PBXRootObject::try_from(content.as_str()).unwrap();
+            root_object.set_project_name(name);
+            assert!(content == root_object.to_pbxproj_string(), "{sample}");
+        }
+    }
+
+    #[test]
+    fn write_annotations_closing_comment() {
+        let mut root_object = test_demo_file!(demo1);
+        let file = root_object.files()[0].id.clone();
+        root_object
+            .objects_mut()
+            .get_object_mut(&file)
+            .unwrap()
+            .insert_value("name", "a */ b.swift");
+
+        let content = root_object.to_pbxproj_string();
+        assert!(content.contains(&format!("{file} /* a (*)/ b.swift */ = {{")));
+        assert!(content.contains("name = \"a */ b.swift\";"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), written.objects());
+    }
+
+    #[test]
+    fn write_escapes() {
+        let mut root_object = test_demo_file!(demo1);
+        let phase = root_object.build_phases()[0].id.clone();
+        let script = "echo \"$SRCROOT\"\n\tcd C:\\Temp\u{7}";
+        root_object
+            .objects_mut()
+            .get_object_mut(&phase)
+            .unwrap()
+            .insert_value("shellScript", script);
+
+        let content = root_object.to_pbxproj_string();
+        assert!(content.contains("shellScript = \"echo \\\"$SRCROOT\\\"\\n\\tcd C:\\\\Temp\\a\";"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(
+            Some(&script.to_string()),
+            written.objects()[&phase].get_string("shellScript")
+        );
+
+        let content = "// !$*UTF8*$!\n{ a = \"\\U00e9\\101\\q\"; }";
+        let parsed = crate::pbxproj::pest::PBXProjectParser::try_from_str(content).unwrap();
+        assert_eq!(Some(&"éA\\q".to_string()), parsed.get_string("a"));
+    }
+
+    #[test]
+    fn write_conditional_keys() {
+        let key = "CODE_SIGN_IDENTITY[sdk=iphoneos*]";
+        let mut root_object = test_demo_file!(demo3);
+        let configuration = root_object
+            .build_configurations()
+            .into_iter()
+            .find(|configuration| configuration.build_settings.contains_key(key))
+            .unwrap()
+            .id;
+        let mut object = root_object
+            .objects_mut()
+            .get_object_mut(&configuration)
+            .unwrap();
+        let settings = object.object_entry("buildSettings");
+        assert_eq!(
+            Some(&"iPhone Developer".to_string()),
+            settings.get_string(key)
+        );
+        settings.insert_value(key, "Apple Development");
+        settings.insert_value("OTHER_LDFLAGS[arch=\"x86_64\"]", "-ObjC");
+
+        drop(object);
+        let content = root_object.to_pbxproj_string();
+        assert!(content
+            .contains("\t\t\t\t\"CODE_SIGN_IDENTITY[sdk=iphoneos*]\" = \"Apple Development\";\n"));
+        assert!(content.contains("\t\t\t\t\"OTHER_LDFLAGS[arch=\\\"x86_64\\\"]\" = \"-ObjC\";\n"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), written.objects());
+    }
+
+    #[test]
+    fn write_data() {
+        let mut root_object = test_demo_file!(demo1);
+        let project = root_object.root_object_reference().to_string();
+        let bytes = vec![0x0f, 0xbd, 0x77, 0x1c, 0x27, 0x35, 0xae];
+        root_object
+            .objects_mut()
+            .get_object_mut(&project)
+            .unwrap()
+            .insert_value("userData", PBXValue::Data(bytes.clone()));
+
+        let content = root_object.to_pbxproj_string();
+        assert!(content.contains("\t\t\tuserData = <0fbd771c 2735ae>;\n"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), written.objects());
+    }
+
+    #[test]
+    fn write_decimals() {
+        let mut root_object = test_demo_file!(demo1);
+        let configuration = root_object
+            .build_configurations()
+            .into_iter()
+            .find(|configuration| configuration.build_settings.contains_key("SWIFT_VERSION"))
+            .unwrap()
+            .id;
+        let mut object = root_object
+            .objects_mut()
+            .get_object_mut(&configuration)
+            .unwrap();
+        let settings = object.object_entry("buildSettings");
+        assert_eq!(
+            Some(&"5.0".to_string()),
+            settings.get_string("SWIFT_VERSION")
+        );
+        let version = settings.get_float("SWIFT_VERSION").unwrap();
+        assert_eq!("5.0", version.as_str());
+        assert_eq!(5.0, version.as_f64());
+        settings.insert_value("SWIFT_VERSION", PBXDecimal::parse("5.10").unwrap());
+        settings.insert_value("MARKETING_VERSION", "1.2.0");
+
+        drop(object);
+        let content = root_object.to_pbxproj_string();
+        assert!(content.contains("\t\t\t\tSWIFT_VERSION = 5.10;\n"));
+        assert!(content.contains("\t\t\t\tMARKETING_VERSION = 1.2.0;\n"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), written.objects());
+    }
+
+    #[test]
+    fn write_decimal_strings() {
+        let mut root_object = test_demo_file!(demo1);
+        let list = root_object.targets()[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .id
+            .clone();
+        root_object
+            .objects_mut()
+            .set_setting_all(&list, "SWIFT_VERSION", "5.10")
+            .unwrap();
+
+        let content = root_object.to_pbxproj_string();
+        assert!(content.contains("\t\t\t\tSWIFT_VERSION = \"5.10\";\n"));
+        let written = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), written.objects());
+        let targets = written.targets();
+        let list = targets[0].build_configuration_list.as_ref().unwrap();
+        for configuration in list.build_configurations.iter() {
+            assert_eq!(
+                Some(&"5.10".to_string()),
+                configuration.build_settings.get_string("SWIFT_VERSION")
+            );
+        }
+    }
+
+    #[test]
+    fn write_to_file() {
+        let mut root_object = test_demo_file!(demo1);
+        let group = root_object.root_group().id;
+        root_object
+            .objects_mut()
+            .add_file_reference(&group, "New File.swift")
+            .unwrap();
+        assert!(root_object.is_modified());
+
+        let content = root_object.to_pbxproj_string();
+        let mut written = vec![];
+        root_object.write_to(&mut written).unwrap();
+        assert_eq!(content.as_bytes(), written);
+        assert!(content.starts_with("// !$*UTF8*$!\n{\n\tarchiveVersion = 1;\n\tclasses = {\n\t};"));
+        assert!(content.contains("\n/* Begin PBXBuildFile section */\n"));
+        assert!(content.contains("path = \"New File.swift\"; sourceTree = \"<group>\"; };\n"));
+        assert!(content.contains("/* New File.swift */ = {isa = PBXFileReference; "));
+        assert!(content.contains("\trootObject = C656082C1B36B3E01F1C245F /* Project object */;\n"));
+        assert!(content.contains("\t\t\tisa = PBXGroup;\n\t\t\tchildren = (\n"));
+        assert!(content.ends_with(";\n}\n"));
+
+        let path =
+            std::env::temp_dir().join(format!("xcodeproj-writer-{}.pbxproj", std::process::id()));
+        root_object.write_to_file(&path).unwrap();
+        assert!(!root_object.is_modified());
+        let written = PBXRootObject::try_from(path.as_path()).unwrap();
+        assert!(written.find_file_by_path("New File.swift").is_some());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+use crate::pbxproj::json::string_value;
+use crate::pbxproj::*;
+
+/// Whether `content` is an XML plist rather than OpenStep content
+pub(crate) fn is_xml_plist(content: &str) -> bool {
+    let content = content.trim_start_matches('\u{FEFF}').trim_start();
+    content.starts_with("<?xml")
+        || content.starts_with("<!DOCTYPE plist")
+        || content.starts_with("<plist")
+}
+
+/// Parse XML plist `content` into the same map OpenStep content parses into
+pub(crate) fn parse_xml_plist(content: &str) -> Result<PBXHashMap> {
+    let value = plist::Value::from_reader_xml(content.trim_start().as_bytes())
+        .context("Parse XML plist")?;
+    match from_plist(value)? {
+        PBXValue::Object(map) => Ok(map),
+        _ => anyhow::bail!("Expected a dictionary as XML plist root"),
+    }
+}
+
+fn from_plist(value: plist::Value) -> Result<PBXValue> {
+    Ok(match value {
+        plist::Value::String(value) => string_value(value),
+        plist::Value::Dictionary(map) => PBXHashMap::new(
+            map.into_iter()
+                .map(|(key, value)| Ok((key.into(), from_plist(value)?)))
+                .collect::<Result<_>>()?,
+        )
+        .into(),
+        plist::Value::Array(values) => {
+            PBXVec::new(values.into_iter().map(from_plist).collect::<Result<_>>()?).into()
+        }
+        plist::Value::Integer(value) => value
+            .as_signed()
+            .map(|value| PBXValue::Number(value as isize))
+            .ok_or_else(|| anyhow::anyhow!("Integer {value} out of range"))?,
+        plist::Value::Boolean(value) => PBXValue::Bool(value),
+        plist::Value::Data(bytes) => PBXValue::Data(bytes),
+        plist::Value::Real(value) => match PBXDecimal::parse(&value.to_string()) {
+            Some(decimal) => PBXValue::Float(decimal),
+            None => PBXValue::String(value.to_string()),
+        },
+        plist::Value::Date(date) => PBXValue::String(date.to_xml_format()),
+        value => anyhow::bail!("Unsupported XML plist value {value:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pbxproj::*;
+
+    #[test]
+    fn parse_xml_plist() {
+        let root_object = test_demo_file!(demo1);
+        let mut content = vec![];
+        plist::to_writer_xml(
+            &mut content,
+            &serde_json::from_str::<plist::Value>(&root_object.to_json()).unwrap(),
+        )
+        .unwrap();
+        let content = String::from_utf8(content).unwrap();
+        assert!(content.starts_with("<?xml"));
+
+        let read = PBXRootObject::try_from(content.as_str()).unwrap();
+        assert_eq!(root_object.objects(), read.objects());
+        assert_eq!(root_object.object_version(), read.object_version());
+        assert_eq!(
+            root_object.root_object_reference(),
+            read.root_object_reference()
+        );
+        assert!(read.to_pbxproj_string().starts_with("// !$*UTF8*$!"));
+    }
+}
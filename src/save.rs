@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// How files are saved, e.g. project.pbxproj or *.xcscheme.
+///
+/// Content is written to a temporary file next to the destination, which then replaces it with
+/// an atomic rename, so a crashed write never leaves a truncated file behind. Permissions of the
+/// replaced file are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCSaveOptions {
+    backup: bool,
+    single_backup: bool,
+    preserve_layout: bool,
+}
+
+impl XCSaveOptions {
+    /// Create default options, without backups
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep a copy of the replaced file next to it, named after the file with the save time
+    /// appended, e.g. `project.pbxproj.1700000000.backup`
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Keep a single backup named after the file with `.bak` appended, e.g.
+    /// `project.pbxproj.bak`, replaced on every save, instead of one per save. Implies
+    /// [`XCSaveOptions::backup`].
+    pub fn single_backup(mut self, single_backup: bool) -> Self {
+        self.single_backup = single_backup;
+        self
+    }
+
+    /// Keep the layout of the project.pbxproj content the project was loaded from, see
+    /// [`PBXRootObject::to_pbxproj_string_preserving`]. Ignored for other files.
+    ///
+    /// [`PBXRootObject::to_pbxproj_string_preserving`]: crate::pbxproj::PBXRootObject::to_pbxproj_string_preserving
+    pub fn preserve_layout(mut self, preserve_layout: bool) -> Self {
+        self.preserve_layout = preserve_layout;
+        self
+    }
+
+    pub(crate) fn preserves_layout(&self) -> bool {
+        self.preserve_layout
+    }
+
+    /// Write `contents` to `path` atomically, returning the path of the backup if one was made
+    pub fn write<P, C>(&self, path: P, contents: C) -> Result<Option<PathBuf>>
+    where
+        P: AsRef<Path>,
+        C: AsRef<[u8]>,
+    {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid save path {path:?}"))?;
+        let temp = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+        let permissions = fs::metadata(path).ok().map(|meta| meta.permissions());
+
+        let written = (|| -> Result<()> {
+            let mut file = fs::File::create(&temp)?;
+            file.write_all(contents.as_ref())?;
+            if let Some(permissions) = permissions.clone() {
+                file.set_permissions(permissions)?;
+            }
+            file.sync_all()?;
+            Ok(())
+        })();
+        if let Err(error) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(error.context(format!("Write {temp:?}")));
+        }
+
+        let backup = match (self.backup || self.single_backup) && permissions.is_some() {
+            true => {
+                let backup = match self.single_backup {
+                    true => {
+                        // Copies keep permissions, so a read-only backup can't be overwritten
+                        let backup = path.with_file_name(format!("{file_name}.bak"));
+                        let _ = fs::remove_file(&backup);
+                        backup
+                    }
+                    false => {
+                        let timestamp = SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs();
+                        path.with_file_name(format!("{file_name}.{timestamp}.backup"))
+                    }
+                };
+                if let Err(error) = fs::copy(path, &backup) {
+                    let _ = fs::remove_file(&temp);
+                    return Err(error).with_context(|| format!("Backup {path:?}"));
+                }
+                Some(backup)
+            }
+            false => None,
+        };
+
+        if let Err(error) = fs::rename(&temp, path) {
+            let _ = fs::remove_file(&temp);
+            return Err(error).with_context(|| format!("Replace {path:?}"));
+        }
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_atomic() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-save-{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("project.pbxproj");
+
+        assert_eq!(
+            None,
+            XCSaveOptions::new().backup(true).write(&path, "a").unwrap()
+        );
+        let mut permissions = fs::metadata(&path).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&path, permissions).unwrap();
+
+        let backup = XCSaveOptions::new()
+            .backup(true)
+            .write(&path, "b")
+            .unwrap()
+            .unwrap();
+        assert_eq!("a", fs::read_to_string(backup).unwrap());
+        assert_eq!("b", fs::read_to_string(&path).unwrap());
+        assert!(fs::metadata(&path).unwrap().permissions().readonly());
+        // Only the file and its backup, no leftover temporary file
+        assert_eq!(2, fs::read_dir(&root).unwrap().count());
+
+        let options = XCSaveOptions::new().single_backup(true);
+        let single = root.join("project.pbxproj.bak");
+        assert_eq!(Some(single.clone()), options.write(&path, "c").unwrap());
+        assert_eq!(Some(single.clone()), options.write(&path, "d").unwrap());
+        assert_eq!("c", fs::read_to_string(&single).unwrap());
+        assert_eq!(3, fs::read_dir(&root).unwrap().count());
+
+        assert!(XCSaveOptions::new()
+            .write(root.join("missing/project.pbxproj"), "c")
+            .is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
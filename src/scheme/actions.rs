@@ -0,0 +1,244 @@
+use super::{XCBuildableReference, XCScheme, XCSchemeElement};
+
+/// Scheme action elements in the order Xcode writes them
+const ACTION_ORDER: [&str; 6] = [
+    "BuildAction",
+    "TestAction",
+    "LaunchAction",
+    "ProfileAction",
+    "AnalyzeAction",
+    "ArchiveAction",
+];
+
+/// `ArchiveAction` of a scheme. `None` fields are left unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCArchiveAction {
+    /// Configuration archived, `buildConfiguration`
+    pub build_configuration: Option<String>,
+    /// Show the archive in the organizer once done, `revealArchiveInOrganizer`
+    pub reveal_archive_in_organizer: Option<bool>,
+    /// Archive name instead of the scheme name, `customArchiveName`
+    pub custom_archive_name: Option<String>,
+}
+
+/// `ProfileAction` of a scheme. `None` fields are left unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCProfileAction {
+    /// Configuration profiled, `buildConfiguration`
+    pub build_configuration: Option<String>,
+    /// Reuse launch action arguments and environment, `shouldUseLaunchSchemeArgsEnv`
+    pub should_use_launch_scheme_args_env: Option<bool>,
+    /// Instruments template, `savedToolIdentifier`
+    pub saved_tool_identifier: Option<String>,
+    /// Use a custom working directory, `useCustomWorkingDirectory`
+    pub use_custom_working_directory: Option<bool>,
+    /// Enable document versioning, `debugDocumentVersioning`
+    pub debug_document_versioning: Option<bool>,
+}
+
+/// `AnalyzeAction` of a scheme. `None` fields are left unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCAnalyzeAction {
+    /// Configuration analyzed, `buildConfiguration`
+    pub build_configuration: Option<String>,
+}
+
+fn string(element: &XCSchemeElement, key: &str) -> Option<String> {
+    element.attribute(key).map(String::from)
+}
+
+impl From<&XCSchemeElement> for XCArchiveAction {
+    fn from(element: &XCSchemeElement) -> Self {
+        Self {
+            build_configuration: string(element, "buildConfiguration"),
+            reveal_archive_in_organizer: element.bool_attribute("revealArchiveInOrganizer"),
+            custom_archive_name: string(element, "customArchiveName"),
+        }
+    }
+}
+
+impl From<&XCSchemeElement> for XCProfileAction {
+    fn from(element: &XCSchemeElement) -> Self {
+        Self {
+            build_configuration: string(element, "buildConfiguration"),
+            should_use_launch_scheme_args_env: element
+                .bool_attribute("shouldUseLaunchSchemeArgsEnv"),
+            saved_tool_identifier: string(element, "savedToolIdentifier"),
+            use_custom_working_directory: element.bool_attribute("useCustomWorkingDirectory"),
+            debug_document_versioning: element.bool_attribute("debugDocumentVersioning"),
+        }
+    }
+}
+
+impl From<&XCSchemeElement> for XCAnalyzeAction {
+    fn from(element: &XCSchemeElement) -> Self {
+        Self {
+            build_configuration: string(element, "buildConfiguration"),
+        }
+    }
+}
+
+impl XCScheme {
+    /// Get mutable action element with the given name, inserting it at its canonical position
+    /// if missing.
+    pub(crate) fn action_entry(&mut self, name: &str) -> &mut XCSchemeElement {
+        let children = &mut self.document.children;
+        let index = match children.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                let rank = |n: &str| ACTION_ORDER.iter().position(|a| *a == n);
+                let order = rank(name);
+                let index = children
+                    .iter()
+                    .position(|c| rank(&c.name) > order)
+                    .unwrap_or(children.len());
+                children.insert(index, XCSchemeElement::new(name));
+                index
+            }
+        };
+        &mut children[index]
+    }
+
+    /// Build the target of `reference` for every action, creating the build action if missing.
+    /// A target already built is kept as is.
+    pub fn add_build_target(&mut self, reference: &XCBuildableReference) {
+        let action = self.action_entry("BuildAction");
+        for key in ["parallelizeBuildables", "buildImplicitDependencies"] {
+            if action.attribute(key).is_none() {
+                action.set_bool_attribute(key, Some(true));
+            }
+        }
+        let entries = action.child_entry("BuildActionEntries");
+        let is_new = entries.children.iter().all(|entry| {
+            entry
+                .child("BuildableReference")
+                .and_then(|b| b.attribute("BlueprintIdentifier"))
+                != reference.blueprint_identifier.as_deref()
+        });
+        if is_new {
+            let mut entry = XCSchemeElement::new("BuildActionEntry");
+            for key in [
+                "buildForTesting",
+                "buildForRunning",
+                "buildForProfiling",
+                "buildForArchiving",
+                "buildForAnalyzing",
+            ] {
+                entry.set_bool_attribute(key, Some(true));
+            }
+            entry.children.push(reference.to_element());
+            entries.children.push(entry);
+        }
+    }
+
+    /// Get archive action, `None` if the scheme has none
+    pub fn archive_action(&self) -> Option<XCArchiveAction> {
+        self.document.child("ArchiveAction").map(Into::into)
+    }
+
+    /// Set archive action attributes, creating the action if missing
+    pub fn set_archive_action(&mut self, action: &XCArchiveAction) {
+        let element = self.action_entry("ArchiveAction");
+        let configuration = action.build_configuration.as_deref();
+        element.set_attribute("buildConfiguration", configuration);
+        element.set_bool_attribute(
+            "revealArchiveInOrganizer",
+            action.reveal_archive_in_organizer,
+        );
+        element.set_attribute("customArchiveName", action.custom_archive_name.as_deref());
+    }
+
+    /// Get profile action, `None` if the scheme has none
+    pub fn profile_action(&self) -> Option<XCProfileAction> {
+        self.document.child("ProfileAction").map(Into::into)
+    }
+
+    /// Set profile action attributes, creating the action if missing
+    pub fn set_profile_action(&mut self, action: &XCProfileAction) {
+        let element = self.action_entry("ProfileAction");
+        let configuration = action.build_configuration.as_deref();
+        element.set_attribute("buildConfiguration", configuration);
+        element.set_bool_attribute(
+            "shouldUseLaunchSchemeArgsEnv",
+            action.should_use_launch_scheme_args_env,
+        );
+        element.set_attribute(
+            "savedToolIdentifier",
+            action.saved_tool_identifier.as_deref(),
+        );
+        element.set_bool_attribute(
+            "useCustomWorkingDirectory",
+            action.use_custom_working_directory,
+        );
+        element.set_bool_attribute("debugDocumentVersioning", action.debug_document_versioning);
+    }
+
+    /// Get analyze action, `None` if the scheme has none
+    pub fn analyze_action(&self) -> Option<XCAnalyzeAction> {
+        self.document.child("AnalyzeAction").map(Into::into)
+    }
+
+    /// Set analyze action attributes, creating the action if missing
+    pub fn set_analyze_action(&mut self, action: &XCAnalyzeAction) {
+        let element = self.action_entry("AnalyzeAction");
+        let configuration = action.build_configuration.as_deref();
+        element.set_attribute("buildConfiguration", configuration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn scheme_actions() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let mut scheme = XCScheme::new(path).unwrap();
+        assert_eq!(None, scheme.archive_action());
+
+        let archive = XCArchiveAction {
+            build_configuration: Some("Release".into()),
+            reveal_archive_in_organizer: Some(true),
+            custom_archive_name: Some("Nightly".into()),
+        };
+        let profile = XCProfileAction {
+            build_configuration: Some("Release".into()),
+            should_use_launch_scheme_args_env: Some(true),
+            ..Default::default()
+        };
+        scheme.set_archive_action(&archive);
+        scheme.set_profile_action(&profile);
+        scheme.set_analyze_action(&XCAnalyzeAction {
+            build_configuration: Some("Debug".into()),
+        });
+
+        let scheme = XCScheme::from_xml("demo1", &scheme.to_xml()).unwrap();
+        assert_eq!(Some(archive), scheme.archive_action());
+        assert_eq!(Some(profile), scheme.profile_action());
+        assert_eq!(
+            Some("Debug"),
+            scheme
+                .analyze_action()
+                .unwrap()
+                .build_configuration
+                .as_deref()
+        );
+        let actions = scheme
+            .document
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "BuildAction",
+                "TestAction",
+                "ProfileAction",
+                "AnalyzeAction",
+                "ArchiveAction"
+            ],
+            actions
+        );
+    }
+}
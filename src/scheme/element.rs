@@ -0,0 +1,170 @@
+use anyhow::Result;
+use xml::reader::{EventReader, XmlEvent};
+
+/// Element of a `.xcscheme` document, kept so schemes are written back with unknown content
+/// preserved. Attribute order is kept as read.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCSchemeElement {
+    /// Element name, e.g. `TestAction`
+    pub name: String,
+    /// Element attributes
+    pub attributes: Vec<(String, String)>,
+    /// Child elements
+    pub children: Vec<XCSchemeElement>,
+}
+
+impl XCSchemeElement {
+    /// Create element without attributes and children
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse `.xcscheme` content, returning the root `Scheme` element
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut stack = vec![Self::default()];
+        for event in EventReader::from_str(content) {
+            match event? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => stack.push(Self {
+                    name: name.local_name,
+                    attributes: attributes
+                        .into_iter()
+                        .map(|a| (a.name.local_name, a.value))
+                        .collect(),
+                    children: vec![],
+                }),
+                XmlEvent::EndElement { .. } => {
+                    let element = stack.pop().unwrap();
+                    stack
+                        .last_mut()
+                        .ok_or_else(|| anyhow::anyhow!("Unbalanced scheme element"))?
+                        .children
+                        .push(element);
+                }
+                _ => {}
+            }
+        }
+        stack
+            .pop()
+            .and_then(|document| document.children.into_iter().next())
+            .ok_or_else(|| anyhow::anyhow!("Empty scheme document"))
+    }
+
+    /// Get attribute value
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set attribute value in place, appending new attributes and removing it if `value` is
+    /// `None`.
+    pub fn set_attribute(&mut self, key: &str, value: Option<&str>) {
+        let index = self.attributes.iter().position(|(k, _)| k == key);
+        match (index, value) {
+            (Some(index), Some(value)) => self.attributes[index].1 = value.to_string(),
+            (None, Some(value)) => self.attributes.push((key.into(), value.into())),
+            (Some(index), None) => {
+                self.attributes.remove(index);
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Get `YES`/`NO` attribute value
+    pub fn bool_attribute(&self, key: &str) -> Option<bool> {
+        self.attribute(key).map(|v| v == "YES")
+    }
+
+    /// Set `YES`/`NO` attribute value, removing it if `value` is `None`.
+    pub fn set_bool_attribute(&mut self, key: &str, value: Option<bool>) {
+        self.set_attribute(key, value.map(|v| if v { "YES" } else { "NO" }))
+    }
+
+    /// Get first child with the given name
+    pub fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Get mutable first child with the given name
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Self> {
+        self.children.iter_mut().find(|c| c.name == name)
+    }
+
+    /// Get mutable first child with the given name, appending an empty one if missing
+    pub fn child_entry(&mut self, name: &str) -> &mut Self {
+        let index = match self.children.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                self.children.push(Self::new(name));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+
+    /// Remove children with the given name
+    pub fn remove_children(&mut self, name: &str) {
+        self.children.retain(|c| c.name != name)
+    }
+
+    /// Render as `.xcscheme` content, laid out like Xcode does
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.write(&mut xml, 0);
+        xml
+    }
+
+    fn write(&self, xml: &mut String, depth: usize) {
+        let indent = "   ".repeat(depth);
+        xml.push_str(&format!("{indent}<{}", self.name));
+        for (key, value) in self.attributes.iter() {
+            xml.push_str(&format!("\n{indent}   {key} = \"{}\"", escape(value)));
+        }
+        xml.push_str(">\n");
+        for child in self.children.iter() {
+            child.write(xml, depth + 1);
+        }
+        xml.push_str(&format!("{indent}</{}>\n", self.name));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn round_trip() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let content = std::fs::read_to_string(path).unwrap();
+        let mut scheme = XCSchemeElement::parse(&content).unwrap();
+        assert_eq!("Scheme", scheme.name);
+        assert_eq!(Some("1.3"), scheme.attribute("version"));
+
+        let test_action = scheme.child_mut("TestAction").unwrap();
+        test_action.set_attribute("buildConfiguration", Some("Release"));
+        test_action.set_bool_attribute("codeCoverageEnabled", None);
+        test_action.set_attribute("note", Some("\"quoted\" & <escaped>\nline"));
+
+        let xml = scheme.to_xml();
+        assert!(xml.contains("<TestAction\n      buildConfiguration = \"Release\"\n"));
+        let reparsed = XCSchemeElement::parse(&xml).unwrap();
+        assert_eq!(scheme, reparsed);
+        assert_eq!(xml, reparsed.to_xml());
+    }
+}
@@ -0,0 +1,441 @@
+use super::{XCScheme, XCSchemeElement};
+
+/// Runnable elements a launch action may hold, only one at a time
+const RUNNABLES: [&str; 3] = ["BuildableProductRunnable", "PathRunnable", "RemoteRunnable"];
+
+/// `BuildableReference` of a scheme, pointing at a target of a project
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCBuildableReference {
+    /// `BuildableIdentifier`, usually `primary`
+    pub buildable_identifier: Option<String>,
+    /// Target reference, `BlueprintIdentifier`
+    pub blueprint_identifier: Option<String>,
+    /// Product name, `BuildableName`
+    pub buildable_name: Option<String>,
+    /// Target name, `BlueprintName`
+    pub blueprint_name: Option<String>,
+    /// Project of the target, e.g. `container:App.xcodeproj`, `ReferencedContainer`
+    pub referenced_container: Option<String>,
+}
+
+impl XCBuildableReference {
+    const KEYS: [&'static str; 5] = [
+        "BuildableIdentifier",
+        "BlueprintIdentifier",
+        "BuildableName",
+        "BlueprintName",
+        "ReferencedContainer",
+    ];
+
+    fn values(&self) -> [Option<&str>; 5] {
+        [
+            self.buildable_identifier.as_deref(),
+            self.blueprint_identifier.as_deref(),
+            self.buildable_name.as_deref(),
+            self.blueprint_name.as_deref(),
+            self.referenced_container.as_deref(),
+        ]
+    }
+
+    pub(crate) fn to_element(&self) -> XCSchemeElement {
+        let mut element = XCSchemeElement::new("BuildableReference");
+        for (key, value) in Self::KEYS.into_iter().zip(self.values()) {
+            element.set_attribute(key, value);
+        }
+        element
+    }
+}
+
+impl From<&XCSchemeElement> for XCBuildableReference {
+    fn from(element: &XCSchemeElement) -> Self {
+        let string = |key| element.attribute(key).map(String::from);
+        Self {
+            buildable_identifier: string("BuildableIdentifier"),
+            blueprint_identifier: string("BlueprintIdentifier"),
+            buildable_name: string("BuildableName"),
+            blueprint_name: string("BlueprintName"),
+            referenced_container: string("ReferencedContainer"),
+        }
+    }
+}
+
+/// What a launch action runs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XCLaunchRunnable {
+    /// Product of a target, `BuildableProductRunnable`
+    Product(XCBuildableReference),
+    /// Executable at a path, e.g. a helper binary hosting the product, `PathRunnable`
+    Path(String),
+}
+
+/// GPU frame capture of a launch action, `enableGPUFrameCaptureMode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCGPUFrameCaptureMode {
+    /// Enabled for whichever API the app uses, Xcode's default
+    Automatic,
+    /// Metal only
+    Metal,
+    /// OpenGL ES only
+    OpenGLES,
+    /// Disabled
+    Disabled,
+}
+
+impl XCGPUFrameCaptureMode {
+    const VALUES: [(Self, &'static str); 4] = [
+        (Self::Automatic, "0"),
+        (Self::Metal, "1"),
+        (Self::OpenGLES, "2"),
+        (Self::Disabled, "3"),
+    ];
+}
+
+/// Metal API validation of a launch action, `enableGPUValidationMode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCMetalAPIValidation {
+    /// Enabled, Xcode's default
+    Enabled,
+    /// Disabled
+    Disabled,
+    /// Enabled with extended checks
+    Extended,
+}
+
+impl XCMetalAPIValidation {
+    const VALUES: [(Self, &'static str); 3] = [
+        (Self::Enabled, "0"),
+        (Self::Disabled, "1"),
+        (Self::Extended, "2"),
+    ];
+}
+
+/// Location simulated on launch, `LocationScenarioReference`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum XCSimulatedLocation {
+    /// Location built into Xcode, e.g. `London, England`
+    Builtin(String),
+    /// GPX file, relative to the scheme file, e.g. `../../Fixtures/Route.gpx`
+    Gpx(String),
+}
+
+impl XCSimulatedLocation {
+    fn to_element(&self) -> XCSchemeElement {
+        let (identifier, reference_type) = match self {
+            Self::Builtin(name) => (name, "1"),
+            Self::Gpx(path) => (path, "0"),
+        };
+        let mut element = XCSchemeElement::new("LocationScenarioReference");
+        element.set_attribute("identifier", Some(identifier));
+        element.set_attribute("referenceType", Some(reference_type));
+        element
+    }
+
+    fn from_element(element: &XCSchemeElement) -> Option<Self> {
+        let identifier = element.attribute("identifier")?.to_string();
+        match element.attribute("referenceType") {
+            Some("1") => Some(Self::Builtin(identifier)),
+            _ => Some(Self::Gpx(identifier)),
+        }
+    }
+}
+
+/// Get enum variant stored as `value` in `values`
+fn variant<T: Copy>(values: &[(T, &'static str)], value: Option<&str>) -> Option<T> {
+    let value = value?;
+    values.iter().find(|(_, v)| *v == value).map(|(t, _)| *t)
+}
+
+/// Get value `variant` is stored as in `values`
+fn value<T: PartialEq>(values: &[(T, &'static str)], variant: Option<T>) -> Option<&'static str> {
+    let variant = variant?;
+    values.iter().find(|(t, _)| *t == variant).map(|(_, v)| *v)
+}
+
+/// Children of a launch action following `LocationScenarioReference`, in Xcode's order
+const AFTER_LOCATION: [&str; 2] = ["StoreKitConfigurationFileReference", "AdditionalOptions"];
+
+/// `LaunchAction` of a scheme. `None` fields are left unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCLaunchAction {
+    /// Configuration launched, `buildConfiguration`
+    pub build_configuration: Option<String>,
+    /// What is launched, `None` for "Ask on Launch" or no executable
+    pub runnable: Option<XCLaunchRunnable>,
+    /// Working directory, `customWorkingDirectory`
+    pub custom_working_directory: Option<String>,
+    /// Use [`XCLaunchAction::custom_working_directory`], `useCustomWorkingDirectory`
+    pub use_custom_working_directory: Option<bool>,
+    /// GPU frame capture, `enableGPUFrameCaptureMode`
+    pub gpu_frame_capture_mode: Option<XCGPUFrameCaptureMode>,
+    /// Metal API validation, `enableGPUValidationMode`
+    pub metal_api_validation: Option<XCMetalAPIValidation>,
+    /// Allow simulating a location, `allowLocationSimulation`
+    pub allow_location_simulation: Option<bool>,
+    /// Location simulated on launch, `LocationScenarioReference`
+    pub default_location: Option<XCSimulatedLocation>,
+    /// StoreKit configuration file, relative to the scheme file, e.g.
+    /// `../../App/Products.storekit`, `StoreKitConfigurationFileReference`
+    pub store_kit_configuration: Option<String>,
+}
+
+impl From<&XCSchemeElement> for XCLaunchAction {
+    fn from(element: &XCSchemeElement) -> Self {
+        let runnable = if let Some(runnable) = element.child("BuildableProductRunnable") {
+            runnable
+                .child("BuildableReference")
+                .map(|reference| XCLaunchRunnable::Product(reference.into()))
+        } else {
+            element
+                .child("PathRunnable")
+                .and_then(|runnable| runnable.attribute("FilePath"))
+                .map(|path| XCLaunchRunnable::Path(path.into()))
+        };
+        Self {
+            build_configuration: element.attribute("buildConfiguration").map(String::from),
+            runnable,
+            custom_working_directory: element
+                .attribute("customWorkingDirectory")
+                .map(String::from),
+            use_custom_working_directory: element.bool_attribute("useCustomWorkingDirectory"),
+            gpu_frame_capture_mode: variant(
+                &XCGPUFrameCaptureMode::VALUES,
+                element.attribute("enableGPUFrameCaptureMode"),
+            ),
+            metal_api_validation: variant(
+                &XCMetalAPIValidation::VALUES,
+                element.attribute("enableGPUValidationMode"),
+            ),
+            allow_location_simulation: element.bool_attribute("allowLocationSimulation"),
+            default_location: element
+                .child("LocationScenarioReference")
+                .and_then(XCSimulatedLocation::from_element),
+            store_kit_configuration: element
+                .child("StoreKitConfigurationFileReference")
+                .and_then(|reference| reference.attribute("identifier"))
+                .map(String::from),
+        }
+    }
+}
+
+impl XCScheme {
+    /// Get launch action, `None` if the scheme has none
+    pub fn launch_action(&self) -> Option<XCLaunchAction> {
+        self.document.child("LaunchAction").map(Into::into)
+    }
+
+    /// Set what the launch action runs, creating the action if missing. `None` removes the
+    /// runnable.
+    ///
+    /// Switching away from a target product keeps its buildable reference as `MacroExpansion`,
+    /// like Xcode does, so `$(BUILT_PRODUCTS_DIR)` and friends still expand in arguments and
+    /// environment variables.
+    pub fn set_launch_runnable(&mut self, runnable: Option<&XCLaunchRunnable>) {
+        let action = self.action_entry("LaunchAction");
+        let index = action
+            .children
+            .iter()
+            .position(|c| RUNNABLES.contains(&c.name.as_str()))
+            .unwrap_or_default();
+        let product = action
+            .child("BuildableProductRunnable")
+            .and_then(|runnable| runnable.child("BuildableReference"))
+            .cloned();
+        for name in RUNNABLES {
+            action.remove_children(name);
+        }
+
+        let name = match runnable {
+            Some(XCLaunchRunnable::Product(_)) => "BuildableProductRunnable",
+            Some(XCLaunchRunnable::Path(_)) => "PathRunnable",
+            None => return,
+        };
+        let mut element = XCSchemeElement::new(name);
+        element.set_attribute("runnableDebuggingMode", Some("0"));
+        match runnable {
+            Some(XCLaunchRunnable::Product(reference)) => {
+                action.remove_children("MacroExpansion");
+                element.children.push(reference.to_element());
+            }
+            Some(XCLaunchRunnable::Path(path)) => element.set_attribute("FilePath", Some(path)),
+            None => {}
+        }
+        action.children.insert(index, element);
+
+        if let Some(product) = product {
+            if action.child("MacroExpansion").is_none() {
+                let mut expansion = XCSchemeElement::new("MacroExpansion");
+                expansion.children.push(product);
+                action.children.insert(index + 1, expansion);
+            }
+        }
+    }
+
+    /// Set launch action working directory, creating the action if missing. `None` goes back
+    /// to the product directory.
+    pub fn set_launch_working_directory(&mut self, directory: Option<&str>) {
+        let action = self.action_entry("LaunchAction");
+        action.set_attribute("customWorkingDirectory", directory);
+        action.set_bool_attribute("useCustomWorkingDirectory", Some(directory.is_some()));
+    }
+
+    /// Set launch action GPU frame capture, creating the action if missing. `None` goes back
+    /// to Xcode's default.
+    pub fn set_launch_gpu_frame_capture_mode(&mut self, mode: Option<XCGPUFrameCaptureMode>) {
+        let action = self.action_entry("LaunchAction");
+        let mode = value(&XCGPUFrameCaptureMode::VALUES, mode);
+        action.set_attribute("enableGPUFrameCaptureMode", mode);
+    }
+
+    /// Set launch action Metal API validation, creating the action if missing. `None` goes
+    /// back to Xcode's default.
+    pub fn set_launch_metal_api_validation(&mut self, validation: Option<XCMetalAPIValidation>) {
+        let action = self.action_entry("LaunchAction");
+        let validation = value(&XCMetalAPIValidation::VALUES, validation);
+        action.set_attribute("enableGPUValidationMode", validation);
+    }
+
+    /// Set whether the launch action allows simulating a location and which location is
+    /// simulated on launch, creating the action if missing. `location` is dropped when
+    /// simulation isn't allowed, as Xcode ignores it then.
+    pub fn set_launch_location_simulation(
+        &mut self,
+        allow: bool,
+        location: Option<&XCSimulatedLocation>,
+    ) {
+        let action = self.action_entry("LaunchAction");
+        action.set_bool_attribute("allowLocationSimulation", Some(allow));
+        let index = action
+            .children
+            .iter()
+            .position(|c| {
+                c.name == "LocationScenarioReference" || AFTER_LOCATION.contains(&c.name.as_str())
+            })
+            .unwrap_or(action.children.len());
+        action.remove_children("LocationScenarioReference");
+        if let Some(location) = location.filter(|_| allow) {
+            action.children.insert(index, location.to_element());
+        }
+    }
+
+    /// Set launch action StoreKit configuration file, relative to the scheme file, creating
+    /// the action if missing. `None` removes it, testing in-app purchases against the App
+    /// Store sandbox.
+    pub fn set_launch_store_kit_configuration(&mut self, path: Option<&str>) {
+        let action = self.action_entry("LaunchAction");
+        let index = action
+            .children
+            .iter()
+            .position(|c| AFTER_LOCATION.contains(&c.name.as_str()))
+            .unwrap_or(action.children.len());
+        action.remove_children("StoreKitConfigurationFileReference");
+        if let Some(path) = path {
+            let mut element = XCSchemeElement::new("StoreKitConfigurationFileReference");
+            element.set_attribute("identifier", Some(path));
+            action.children.insert(index, element);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn launch_runnable() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let mut scheme = XCScheme::new(path).unwrap();
+        assert_eq!(None, scheme.launch_action());
+
+        let product = XCLaunchRunnable::Product(XCBuildableReference {
+            buildable_identifier: Some("primary".into()),
+            buildable_name: Some("demo1".into()),
+            blueprint_name: Some("demo1".into()),
+            ..Default::default()
+        });
+        scheme.set_launch_runnable(Some(&product));
+        assert_eq!(Some(product), scheme.launch_action().unwrap().runnable);
+
+        let helper = XCLaunchRunnable::Path("/usr/local/bin/helper".into());
+        scheme.set_launch_runnable(Some(&helper));
+        scheme.set_launch_working_directory(Some("$(SRCROOT)/Fixtures"));
+
+        let scheme = XCScheme::from_xml("demo1", &scheme.to_xml()).unwrap();
+        let action = scheme.launch_action().unwrap();
+        assert_eq!(Some(helper), action.runnable);
+        assert_eq!(
+            Some("$(SRCROOT)/Fixtures"),
+            action.custom_working_directory.as_deref()
+        );
+        assert_eq!(Some(true), action.use_custom_working_directory);
+
+        let launch = scheme.document.child("LaunchAction").unwrap();
+        let children = launch
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["PathRunnable", "MacroExpansion"], children);
+        assert_eq!(
+            Some("0"),
+            launch.children[0].attribute("runnableDebuggingMode")
+        );
+    }
+
+    #[test]
+    fn launch_options() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let mut scheme = XCScheme::new(path).unwrap();
+        let london = XCSimulatedLocation::Builtin("London, England".into());
+        scheme.set_launch_store_kit_configuration(Some("../../App/Products.storekit"));
+        scheme.set_launch_location_simulation(true, Some(&london));
+        scheme.set_launch_gpu_frame_capture_mode(Some(XCGPUFrameCaptureMode::Disabled));
+        scheme.set_launch_metal_api_validation(Some(XCMetalAPIValidation::Extended));
+
+        let mut scheme = XCScheme::from_xml("demo1", &scheme.to_xml()).unwrap();
+        let action = scheme.launch_action().unwrap();
+        assert_eq!(
+            Some(XCGPUFrameCaptureMode::Disabled),
+            action.gpu_frame_capture_mode
+        );
+        assert_eq!(
+            Some(XCMetalAPIValidation::Extended),
+            action.metal_api_validation
+        );
+        assert_eq!(Some(true), action.allow_location_simulation);
+        assert_eq!(Some(london.clone()), action.default_location);
+        assert_eq!(
+            Some("../../App/Products.storekit"),
+            action.store_kit_configuration.as_deref()
+        );
+        let launch = scheme.document.child("LaunchAction").unwrap();
+        assert_eq!(Some("3"), launch.attribute("enableGPUFrameCaptureMode"));
+        let children = launch
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "LocationScenarioReference",
+                "StoreKitConfigurationFileReference"
+            ],
+            children
+        );
+
+        let route = XCSimulatedLocation::Gpx("../../Fixtures/Route.gpx".into());
+        scheme.set_launch_location_simulation(true, Some(&route));
+        assert_eq!(
+            Some(route),
+            scheme.launch_action().unwrap().default_location
+        );
+        scheme.set_launch_location_simulation(false, Some(&london));
+        scheme.set_launch_store_kit_configuration(None);
+        scheme.set_launch_gpu_frame_capture_mode(None);
+        let action = scheme.launch_action().unwrap();
+        assert_eq!(Some(false), action.allow_location_simulation);
+        assert_eq!(None, action.default_location);
+        assert_eq!(None, action.store_kit_configuration);
+        assert_eq!(None, action.gpu_frame_capture_mode);
+    }
+}
@@ -0,0 +1,256 @@
+mod actions;
+mod element;
+mod launch_action;
+mod share;
+mod test_action;
+
+use crate::macros::log;
+use std::path::{Path, PathBuf};
+
+use crate::XCSaveOptions;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+pub use actions::*;
+pub use element::XCSchemeElement;
+pub use launch_action::*;
+pub use test_action::*;
+
+/// XCode Scheme representation
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct XCScheme {
+    /// Scheme name (reflects scheme file name)
+    #[serde(default)]
+    pub name: String,
+    /// ...
+    pub last_upgrade_version: Option<String>,
+    /// ...
+    pub version: Option<String>,
+    /// ...
+    pub was_created_for_app_extension: Option<bool>,
+    /// Whether the scheme is shared or private to a user
+    #[serde(skip)]
+    pub ownership: XCSchemeOwnership,
+    /// File the scheme was read from, `None` for schemes not saved yet
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    /// Full scheme document, edited by action setters and written by [`XCScheme::to_xml`]
+    #[serde(skip)]
+    pub document: XCSchemeElement,
+}
+
+/// Owner of a scheme, reflecting the directory the scheme file is stored in
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum XCSchemeOwnership {
+    /// Scheme under `xcshareddata/xcschemes`, usually checked into version control.
+    #[default]
+    Shared,
+    /// Scheme under `xcuserdata/<username>.xcuserdatad/xcschemes`, with the owning username.
+    User(String),
+}
+
+impl XCScheme {
+    /// Create an empty shared scheme with the given name, not saved yet
+    pub(crate) fn named(name: &str) -> Self {
+        let mut document = XCSchemeElement::new("Scheme");
+        document.set_attribute("LastUpgradeVersion", Some("1500"));
+        document.set_attribute("version", Some("1.7"));
+        Self {
+            name: name.into(),
+            last_upgrade_version: Some("1500".into()),
+            version: Some("1.7".into()),
+            was_created_for_app_extension: None,
+            ownership: XCSchemeOwnership::Shared,
+            path: None,
+            document,
+        }
+    }
+
+    /// Read and parse *.xcscheme content
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .split(".")
+            .next()
+            .unwrap_or_default();
+        let mut scheme = Self::from_xml(name, &content)?;
+        scheme.path = Some(path.to_path_buf());
+        Ok(scheme)
+    }
+
+    /// Parse *.xcscheme content of the scheme with the given name
+    pub fn from_xml(name: &str, content: &str) -> Result<Self> {
+        let mut scheme = serde_xml_rs::from_str::<Self>(content)?;
+        scheme.name = name.into();
+        scheme.document = XCSchemeElement::parse(content)?;
+        Ok(scheme)
+    }
+
+    /// Render as *.xcscheme content
+    pub fn to_xml(&self) -> String {
+        self.document.to_xml()
+    }
+
+    /// Write *.xcscheme content to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.save_with(path, &XCSaveOptions::new())
+    }
+
+    /// Write *.xcscheme content to `path` with the given options, e.g. to keep a backup
+    pub fn save_with<P: AsRef<Path>>(&self, path: P, options: &XCSaveOptions) -> Result<()> {
+        options.write(path, self.to_xml())?;
+        Ok(())
+    }
+
+    /// Read schemes of a `.xcodeproj` or `.xcworkspace` bundle, both shared ones and those of
+    /// every user, sorted with shared schemes first. Unparseable scheme files are skipped.
+    pub fn from_bundle<P: AsRef<Path>>(bundle: P) -> Result<Vec<Self>> {
+        let bundle = bundle.as_ref();
+        let mut folders = vec![(
+            bundle.join("xcshareddata").join("xcschemes"),
+            XCSchemeOwnership::Shared,
+        )];
+
+        let userdata = bundle.join("xcuserdata");
+        if userdata.is_dir() {
+            for entry in std::fs::read_dir(userdata)? {
+                let path = entry?.path();
+                let username = path
+                    .file_name()
+                    .and_then(|name| name.to_str()?.strip_suffix(".xcuserdatad"))
+                    .map(|name| name.to_string());
+                if let Some(username) = username {
+                    folders.push((path.join("xcschemes"), XCSchemeOwnership::User(username)));
+                }
+            }
+        }
+
+        let mut schemes = vec![];
+        for (folder, ownership) in folders {
+            for path in scheme_files(&folder)? {
+                match Self::new(&path) {
+                    Ok(scheme) => schemes.push(Self {
+                        ownership: ownership.clone(),
+                        ..scheme
+                    }),
+                    Err(error) => log!(warn, "Skipping scheme {path:?}: {error}"),
+                }
+            }
+        }
+        schemes.sort_by(|a, b| (&a.ownership, &a.name).cmp(&(&b.ownership, &b.name)));
+        Ok(schemes)
+    }
+
+    /// Path the scheme is read from, or Xcode's location for it under `bundle` if not saved
+    /// yet
+    pub(crate) fn path_in(&self, bundle: &Path) -> PathBuf {
+        self.path.clone().unwrap_or_else(|| {
+            let folder = match &self.ownership {
+                XCSchemeOwnership::Shared => bundle.join("xcshareddata"),
+                XCSchemeOwnership::User(username) => bundle
+                    .join("xcuserdata")
+                    .join(format!("{username}.xcuserdatad")),
+            };
+            folder
+                .join("xcschemes")
+                .join(format!("{}.xcscheme", self.name))
+        })
+    }
+
+    /// Whether the scheme is shared
+    pub fn is_shared(&self) -> bool {
+        self.ownership == XCSchemeOwnership::Shared
+    }
+
+    /// Username owning the scheme, `None` if the scheme is shared
+    pub fn username(&self) -> Option<&str> {
+        match &self.ownership {
+            XCSchemeOwnership::Shared => None,
+            XCSchemeOwnership::User(username) => Some(username),
+        }
+    }
+}
+
+/// `*.xcscheme` files in `folder`, empty if `folder` doesn't exist
+fn scheme_files(folder: &Path) -> Result<Vec<PathBuf>> {
+    if !folder.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut files = vec![];
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.extension().map(|ext| ext == "xcscheme") == Some(true) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+macro_rules! test_demo_file {
+    ($name:expr) => {{
+        let (root, name) = (env!("CARGO_MANIFEST_DIR"), stringify!($name));
+        let path = format!("{root}/tests/schemes/{name}.xcscheme");
+        let scheme = super::XCScheme::new(path);
+        if scheme.is_err() {
+            eprintln!("Error: {:#?}", scheme.as_ref().unwrap_err())
+        }
+        assert!(scheme.is_ok());
+        scheme.unwrap()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! test_samples {
+        ($($name:ident),*) => {
+            $(#[test]
+                fn $name() {
+                    let scheme = test_demo_file!($name);
+                    println!("{scheme:?}")
+                })*
+        };
+    }
+
+    test_samples![demo1];
+
+    #[test]
+    fn from_bundle() {
+        use super::*;
+        let bundle = std::env::temp_dir()
+            .join(format!("xcodeproj-schemes-{}", std::process::id()))
+            .join("Demo.xcodeproj");
+        let sample = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        for (folder, name) in [
+            ("xcshareddata/xcschemes", "Demo"),
+            ("xcuserdata/alice.xcuserdatad/xcschemes", "Debug"),
+            ("xcuserdata/alice.xcuserdatad/xcschemes", "Alpha"),
+            ("xcuserdata/bob.xcuserdatad/xcschemes", "Beta"),
+        ] {
+            let folder = bundle.join(folder);
+            std::fs::create_dir_all(&folder).unwrap();
+            std::fs::copy(&sample, folder.join(format!("{name}.xcscheme"))).unwrap();
+        }
+
+        let schemes = XCScheme::from_bundle(&bundle)
+            .unwrap()
+            .into_iter()
+            .map(|scheme| (scheme.username().map(String::from), scheme.name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (None, "Demo".to_string()),
+                (Some("alice".to_string()), "Alpha".to_string()),
+                (Some("alice".to_string()), "Debug".to_string()),
+                (Some("bob".to_string()), "Beta".to_string()),
+            ],
+            schemes
+        );
+
+        std::fs::remove_dir_all(bundle.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use super::XCSchemeOwnership;
+use crate::XCodeProject;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Scheme state file next to scheme files
+const MANAGEMENT_FILE: &str = "xcschememanagement.plist";
+/// Suffix of `SchemeUserState` keys referring to shared schemes
+const SHARED_SUFFIX: &str = "_^#shared#^_";
+
+impl XCodeProject {
+    /// Promote the scheme with the given name owned by `from_user` to a shared scheme.
+    ///
+    /// The scheme file is moved from `xcuserdata/<from_user>.xcuserdatad/xcschemes` to
+    /// `xcshareddata/xcschemes` of the same bundle, project or sibling workspace, and its
+    /// `SchemeUserState` entry, e.g. order hint, is re-keyed as a shared scheme in
+    /// `xcschememanagement.plist` of both folders when present. Returns the new scheme path.
+    pub fn share_scheme(&mut self, name: &str, from_user: &str) -> Result<PathBuf> {
+        let file = format!("{name}.xcscheme");
+        let (bundle, source) = ["xcodeproj", "xcworkspace"]
+            .into_iter()
+            .map(|extension| self.root.join(format!("{}.{extension}", self.name)))
+            .find_map(|bundle| {
+                let source = bundle
+                    .join("xcuserdata")
+                    .join(format!("{from_user}.xcuserdatad"))
+                    .join("xcschemes")
+                    .join(&file);
+                source.is_file().then_some((bundle, source))
+            })
+            .ok_or_else(|| anyhow::anyhow!("No scheme {name:?} owned by {from_user:?}"))?;
+
+        let shared = bundle.join("xcshareddata").join("xcschemes");
+        let destination = shared.join(&file);
+        if destination.exists() {
+            anyhow::bail!("Shared scheme {name:?} already exists");
+        }
+        std::fs::create_dir_all(&shared)?;
+        std::fs::rename(&source, &destination)?;
+
+        let shared_key = format!("{file}{SHARED_SUFFIX}");
+        let mut state = None;
+        update_scheme_states(&source.with_file_name(MANAGEMENT_FILE), |states| {
+            state = states.remove(&file);
+            if let Some(state) = state.clone() {
+                states.insert(shared_key.clone(), state);
+            }
+        })?;
+        update_scheme_states(&shared.join(MANAGEMENT_FILE), |states| {
+            states.remove(&file);
+            if let Some(state) = state {
+                states.insert(shared_key, state);
+            }
+        })?;
+
+        let owner = XCSchemeOwnership::User(from_user.into());
+        let schemes = self.schemes_mut();
+        if let Some(scheme) = schemes
+            .iter_mut()
+            .find(|scheme| scheme.name == name && scheme.ownership == owner)
+        {
+            scheme.ownership = XCSchemeOwnership::Shared;
+            scheme.path = Some(destination.clone());
+        }
+        schemes.sort_by(|a, b| (&a.ownership, &a.name).cmp(&(&b.ownership, &b.name)));
+
+        Ok(destination)
+    }
+}
+
+/// Apply `update` to `SchemeUserState` of the `xcschememanagement.plist` at `path`, if any.
+fn update_scheme_states<F>(path: &Path, update: F) -> Result<()>
+where
+    F: FnOnce(&mut plist::Dictionary),
+{
+    if !path.is_file() {
+        return Ok(());
+    }
+    let mut management = plist::Value::from_file(path)?
+        .into_dictionary()
+        .ok_or_else(|| anyhow::anyhow!("{path:?} is not a dictionary"))?;
+    if !management.contains_key("SchemeUserState") {
+        let states = plist::Dictionary::new().into();
+        management.insert("SchemeUserState".into(), states);
+    }
+    let states = management
+        .get_mut("SchemeUserState")
+        .and_then(|states| states.as_dictionary_mut())
+        .ok_or_else(|| anyhow::anyhow!("{path:?} SchemeUserState is not a dictionary"))?;
+    update(states);
+    plist::Value::Dictionary(management).to_file_xml(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    #[test]
+    fn share_scheme() {
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            std::env::temp_dir().join(format!("xcodeproj-share-scheme-{}", std::process::id())),
+        )
+        .unwrap();
+        let bundle = project.root.join("Demo.xcodeproj");
+        let user = bundle.join("xcuserdata/alice.xcuserdatad/xcschemes");
+        std::fs::create_dir_all(&user).unwrap();
+        let sample = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        std::fs::copy(sample, user.join("Nightly.xcscheme")).unwrap();
+
+        let mut order = plist::Dictionary::new();
+        order.insert("orderHint".into(), 3.into());
+        let mut states = plist::Dictionary::new();
+        states.insert("Nightly.xcscheme".into(), order.clone().into());
+        let mut management = plist::Dictionary::new();
+        management.insert("SchemeUserState".into(), states.into());
+        plist::Value::Dictionary(management)
+            .to_file_xml(user.join(MANAGEMENT_FILE))
+            .unwrap();
+
+        assert!(project.share_scheme("Nightly", "bob").is_err());
+        let path = project.share_scheme("Nightly", "alice").unwrap();
+        assert_eq!(bundle.join("xcshareddata/xcschemes/Nightly.xcscheme"), path);
+        assert!(path.is_file());
+        assert!(!user.join("Nightly.xcscheme").exists());
+
+        let management = plist::Value::from_file(user.join(MANAGEMENT_FILE)).unwrap();
+        let states = management
+            .as_dictionary()
+            .and_then(|m| m.get("SchemeUserState")?.as_dictionary())
+            .unwrap();
+        assert_eq!(None, states.get("Nightly.xcscheme"));
+        assert_eq!(
+            Some(&plist::Value::from(order)),
+            states.get("Nightly.xcscheme_^#shared#^_")
+        );
+
+        std::fs::remove_dir_all(&project.root).unwrap();
+    }
+}
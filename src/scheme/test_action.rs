@@ -0,0 +1,328 @@
+use super::{XCBuildableReference, XCScheme, XCSchemeElement};
+use anyhow::Result;
+
+/// Order tests of a testable run in, `testExecutionOrdering`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCTestExecutionOrdering {
+    /// Alphabetical, Xcode's default
+    Alphabetical,
+    /// Random
+    Random,
+}
+
+/// `TestableReference` of a scheme test action
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCTestableReference {
+    /// Name of the test target, `BlueprintName` of the buildable reference
+    pub blueprint_name: Option<String>,
+    /// Reference of the test target, `BlueprintIdentifier` of the buildable reference
+    pub blueprint_identifier: Option<String>,
+    /// Whether the whole testable is skipped
+    pub skipped: bool,
+    /// Whether tests run in parallel
+    pub parallelizable: bool,
+    /// Test order, `None` for Xcode's default
+    pub test_execution_ordering: Option<XCTestExecutionOrdering>,
+    /// Identifiers of skipped tests, e.g. `LoginTests/testFlaky()`
+    pub skipped_tests: Vec<String>,
+    /// Identifiers of the only tests run, empty to run all tests
+    pub selected_tests: Vec<String>,
+}
+
+/// `TestPlanReference` of a scheme test action
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCTestPlanReference {
+    /// Test plan location, e.g. `container:App.xctestplan`
+    pub reference: String,
+    /// Whether the plan is the one run by default
+    pub default: bool,
+}
+
+impl From<&XCSchemeElement> for XCTestableReference {
+    fn from(element: &XCSchemeElement) -> Self {
+        let buildable = element.child("BuildableReference");
+        let blueprint = |key| Some(buildable?.attribute(key)?.to_string());
+        let tests = |name| {
+            element
+                .child(name)
+                .map(|tests| {
+                    tests
+                        .children
+                        .iter()
+                        .filter_map(|test| test.attribute("Identifier").map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            blueprint_name: blueprint("BlueprintName"),
+            blueprint_identifier: blueprint("BlueprintIdentifier"),
+            skipped: element.bool_attribute("skipped").unwrap_or_default(),
+            parallelizable: element.bool_attribute("parallelizable").unwrap_or_default(),
+            test_execution_ordering: match element.attribute("testExecutionOrdering") {
+                Some("random") => Some(XCTestExecutionOrdering::Random),
+                Some(_) => Some(XCTestExecutionOrdering::Alphabetical),
+                None => None,
+            },
+            skipped_tests: tests("SkippedTests"),
+            selected_tests: tests("SelectedTests"),
+        }
+    }
+}
+
+impl XCScheme {
+    /// Get testable references of the test action
+    pub fn testables(&self) -> Vec<XCTestableReference> {
+        self.document
+            .child("TestAction")
+            .and_then(|action| action.child("Testables"))
+            .map(|testables| testables.children.iter().map(Into::into).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get test plans of the test action
+    pub fn test_plans(&self) -> Vec<XCTestPlanReference> {
+        self.document
+            .child("TestAction")
+            .and_then(|action| action.child("TestPlans"))
+            .map(|plans| {
+                plans
+                    .children
+                    .iter()
+                    .filter_map(|plan| {
+                        Some(XCTestPlanReference {
+                            reference: plan.attribute("reference")?.to_string(),
+                            default: plan.bool_attribute("default").unwrap_or_default(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Add test plan at `reference`, e.g. `container:App.xctestplan`, to the test action,
+    /// creating the action if missing. A plan already added is kept in place.
+    ///
+    /// The plan is made the default one if `default` is set or if it is the first plan.
+    pub fn add_test_plan(&mut self, reference: &str, default: bool) {
+        let action = self.action_entry("TestAction");
+        if action.child("TestPlans").is_none() {
+            action.children.insert(0, XCSchemeElement::new("TestPlans"));
+        }
+        let plans = action.child_entry("TestPlans");
+        let is_new = plans
+            .children
+            .iter()
+            .all(|plan| plan.attribute("reference") != Some(reference));
+        if is_new {
+            let mut plan = XCSchemeElement::new("TestPlanReference");
+            plan.set_attribute("reference", Some(reference));
+            plans.children.push(plan);
+        }
+        if default || plans.children.len() == 1 {
+            set_default_plan(plans, reference);
+        }
+    }
+
+    /// Run tests of the target of `reference` with the test action, creating the action if
+    /// missing. A target already tested is kept as is.
+    pub fn add_testable(&mut self, reference: &XCBuildableReference) {
+        let testables = self.action_entry("TestAction").child_entry("Testables");
+        let is_new = testables.children.iter().all(|testable| {
+            testable
+                .child("BuildableReference")
+                .and_then(|b| b.attribute("BlueprintIdentifier"))
+                != reference.blueprint_identifier.as_deref()
+        });
+        if is_new {
+            let mut testable = XCSchemeElement::new("TestableReference");
+            testable.set_bool_attribute("skipped", Some(false));
+            testable.children.push(reference.to_element());
+            testables.children.push(testable);
+        }
+    }
+
+    /// Make the test plan at `reference` the one run by default
+    pub fn set_default_test_plan(&mut self, reference: &str) -> Result<()> {
+        let plans = self
+            .document
+            .child_mut("TestAction")
+            .and_then(|action| action.child_mut("TestPlans"))
+            .filter(|plans| {
+                plans
+                    .children
+                    .iter()
+                    .any(|plan| plan.attribute("reference") == Some(reference))
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!("No test plan {reference:?} in scheme {:?}", self.name)
+            })?;
+        set_default_plan(plans, reference);
+        Ok(())
+    }
+
+    /// Get mutable testable reference of the test target with the given name
+    fn testable_mut(&mut self, blueprint_name: &str) -> Result<&mut XCSchemeElement> {
+        self.document
+            .child_mut("TestAction")
+            .and_then(|action| action.child_mut("Testables"))
+            .and_then(|testables| {
+                testables.children.iter_mut().find(|testable| {
+                    testable
+                        .child("BuildableReference")
+                        .and_then(|b| b.attribute("BlueprintName"))
+                        == Some(blueprint_name)
+                })
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!("No testable {blueprint_name:?} in scheme {:?}", self.name)
+            })
+    }
+
+    /// Set whether the testable of the given test target is skipped
+    pub fn set_testable_skipped(&mut self, blueprint_name: &str, skipped: bool) -> Result<()> {
+        self.testable_mut(blueprint_name)?
+            .set_bool_attribute("skipped", Some(skipped));
+        Ok(())
+    }
+
+    /// Set whether tests of the given test target run in parallel
+    pub fn set_testable_parallelizable(&mut self, blueprint_name: &str, value: bool) -> Result<()> {
+        self.testable_mut(blueprint_name)?
+            .set_bool_attribute("parallelizable", Some(value));
+        Ok(())
+    }
+
+    /// Set test order of the given test target, `None` for Xcode's default
+    pub fn set_testable_execution_ordering(
+        &mut self,
+        blueprint_name: &str,
+        ordering: Option<XCTestExecutionOrdering>,
+    ) -> Result<()> {
+        let ordering = ordering.map(|ordering| match ordering {
+            XCTestExecutionOrdering::Alphabetical => "alphabetical",
+            XCTestExecutionOrdering::Random => "random",
+        });
+        self.testable_mut(blueprint_name)?
+            .set_attribute("testExecutionOrdering", ordering);
+        Ok(())
+    }
+
+    /// Skip test with the given identifier, e.g. `LoginTests/testFlaky()`, in the given test
+    /// target. Skipping an already skipped test is a no-op.
+    pub fn add_skipped_test(&mut self, blueprint_name: &str, identifier: &str) -> Result<()> {
+        add_test(
+            self.testable_mut(blueprint_name)?,
+            "SkippedTests",
+            identifier,
+        );
+        Ok(())
+    }
+
+    /// Stop skipping test with the given identifier in the given test target
+    pub fn remove_skipped_test(&mut self, blueprint_name: &str, identifier: &str) -> Result<()> {
+        remove_test(
+            self.testable_mut(blueprint_name)?,
+            "SkippedTests",
+            identifier,
+        );
+        Ok(())
+    }
+
+    /// Add test with the given identifier to the only tests run in the given test target
+    pub fn add_selected_test(&mut self, blueprint_name: &str, identifier: &str) -> Result<()> {
+        let testable = self.testable_mut(blueprint_name)?;
+        add_test(testable, "SelectedTests", identifier);
+        testable.set_bool_attribute("useTestSelectionWhitelist", Some(true));
+        Ok(())
+    }
+
+    /// Remove test with the given identifier from the only tests run in the given test target.
+    /// Removing the last selected test runs all tests again.
+    pub fn remove_selected_test(&mut self, blueprint_name: &str, identifier: &str) -> Result<()> {
+        let testable = self.testable_mut(blueprint_name)?;
+        remove_test(testable, "SelectedTests", identifier);
+        if testable.child("SelectedTests").is_none() {
+            testable.set_attribute("useTestSelectionWhitelist", None);
+        }
+        Ok(())
+    }
+}
+
+/// Mark the plan at `reference` as default, unmarking the others as Xcode keeps a single one
+fn set_default_plan(plans: &mut XCSchemeElement, reference: &str) {
+    for plan in plans.children.iter_mut() {
+        let default = plan.attribute("reference") == Some(reference);
+        plan.set_bool_attribute("default", default.then_some(true));
+    }
+}
+
+fn add_test(testable: &mut XCSchemeElement, list: &str, identifier: &str) {
+    let tests = testable.child_entry(list);
+    if tests
+        .children
+        .iter()
+        .all(|test| test.attribute("Identifier") != Some(identifier))
+    {
+        let mut test = XCSchemeElement::new("Test");
+        test.set_attribute("Identifier", Some(identifier));
+        tests.children.push(test);
+    }
+}
+
+/// Remove test from `list`, and `list` itself once empty as Xcode does
+fn remove_test(testable: &mut XCSchemeElement, list: &str, identifier: &str) {
+    if let Some(tests) = testable.child_mut(list) {
+        tests
+            .children
+            .retain(|test| test.attribute("Identifier") != Some(identifier));
+        if tests.children.is_empty() {
+            testable.remove_children(list);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn edit_testables() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let mut scheme = XCScheme::new(path).unwrap();
+        let target = "NoBlueprintIDTests";
+        assert_eq!(1, scheme.testables().len());
+        assert!(scheme.add_skipped_test("Missing", "A/test()").is_err());
+
+        scheme.set_testable_parallelizable(target, true).unwrap();
+        scheme
+            .set_testable_execution_ordering(target, Some(XCTestExecutionOrdering::Random))
+            .unwrap();
+        scheme.add_skipped_test(target, "A/testFlaky()").unwrap();
+        scheme.add_skipped_test(target, "A/testFlaky()").unwrap();
+        scheme.add_skipped_test(target, "B").unwrap();
+        scheme.remove_skipped_test(target, "B").unwrap();
+        scheme.add_selected_test(target, "A/testOnly()").unwrap();
+
+        let scheme_xml = scheme.to_xml();
+        let mut scheme = XCScheme::from_xml("demo1", &scheme_xml).unwrap();
+        let testable = &scheme.testables()[0];
+        assert_eq!(
+            Some("NoBlueprintIDTests"),
+            testable.blueprint_name.as_deref()
+        );
+        assert!(testable.parallelizable && !testable.skipped);
+        assert_eq!(
+            Some(XCTestExecutionOrdering::Random),
+            testable.test_execution_ordering
+        );
+        assert_eq!(vec!["A/testFlaky()".to_string()], testable.skipped_tests);
+        assert_eq!(vec!["A/testOnly()".to_string()], testable.selected_tests);
+        assert!(scheme_xml.contains("useTestSelectionWhitelist = \"YES\""));
+
+        scheme.remove_selected_test(target, "A/testOnly()").unwrap();
+        assert!(!scheme.to_xml().contains("useTestSelectionWhitelist"));
+        assert!(!scheme.to_xml().contains("SelectedTests"));
+    }
+}
@@ -0,0 +1,440 @@
+use crate::pbxproj::*;
+use crate::template::{empty_project, move_products_group_last};
+use crate::XCodeProject;
+#[cfg(feature = "scheme")]
+use crate::{XCBuildableReference, XCLaunchRunnable, XCScheme};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Declarative description of a project, turned into one with [`XCodeProject::from_spec`].
+///
+/// ```no_run
+/// use xcodeproj::pbxproj::*;
+/// use xcodeproj::{ProjectSpec, SchemeSpec, TargetSpec, XCodeProject};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let spec = ProjectSpec {
+///     name: "App".into(),
+///     targets: vec![
+///         TargetSpec {
+///             name: "App".into(),
+///             product_type: PBXProductType::Application,
+///             platform: PBXTargetPlatform::IOS,
+///             deployment_target: Some("16.0".into()),
+///             sources: vec!["App/**/*.swift".into()],
+///             resources: vec!["App/Assets.xcassets".into()],
+///             ..Default::default()
+///         },
+///         TargetSpec {
+///             name: "AppTests".into(),
+///             product_type: PBXProductType::UnitTestBundle,
+///             platform: PBXTargetPlatform::IOS,
+///             sources: vec!["AppTests/**/*.swift".into()],
+///             dependencies: vec!["App".into()],
+///             ..Default::default()
+///         },
+///     ],
+///     schemes: vec![SchemeSpec {
+///         name: "App".into(),
+///         build_targets: vec!["App".into()],
+///         run_target: Some("App".into()),
+///         test_targets: vec!["AppTests".into()],
+///     }],
+///     ..Default::default()
+/// };
+/// let mut project = XCodeProject::from_spec(&spec, ".")?;
+/// project.save()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectSpec {
+    /// Project name, also the name of the `.xcodeproj` bundle
+    pub name: String,
+    /// Build settings set in every project configuration
+    pub settings: BTreeMap<String, String>,
+    /// Remote swift packages targets can link products of
+    pub packages: Vec<PackageSpec>,
+    /// Native targets, created in dependency order
+    pub targets: Vec<TargetSpec>,
+    /// Shared schemes
+    pub schemes: Vec<SchemeSpec>,
+}
+
+/// Remote swift package of a [`ProjectSpec`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageSpec {
+    /// Name targets refer to the package with, see [`TargetSpec::packages`]
+    pub name: String,
+    /// Repository URL
+    pub url: String,
+    /// Version requirement
+    pub requirement: XCVersionRequirement,
+}
+
+/// Native target of a [`ProjectSpec`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TargetSpec {
+    /// Target name
+    pub name: String,
+    /// Product type
+    pub product_type: PBXProductType,
+    /// Platform, iOS when unknown
+    pub platform: PBXTargetPlatform,
+    /// Minimum OS version of the platform, e.g. `16.0`
+    pub deployment_target: Option<String>,
+    /// Globs of compiled files, relative to the project root, e.g. `App/**/*.swift`
+    pub sources: Vec<String>,
+    /// Globs of files copied to the product resources, relative to the project root. Paths
+    /// matching an existing directory, e.g. `App/Assets.xcassets`, are added as is.
+    pub resources: Vec<String>,
+    /// Build settings set in every target configuration
+    pub settings: BTreeMap<String, String>,
+    /// Names of targets of the project this target depends on
+    pub dependencies: Vec<String>,
+    /// Linked frameworks, see [`PBXTargetBuilder::framework`]
+    pub frameworks: Vec<String>,
+    /// Linked package products, as pairs of [`PackageSpec::name`] and product name
+    pub packages: Vec<(String, String)>,
+}
+
+/// Shared scheme of a [`ProjectSpec`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemeSpec {
+    /// Scheme name
+    pub name: String,
+    /// Names of targets built by every action
+    pub build_targets: Vec<String>,
+    /// Name of the target run by the launch action
+    pub run_target: Option<String>,
+    /// Names of test targets run by the test action, also built
+    pub test_targets: Vec<String>,
+}
+
+impl ProjectSpec {
+    /// Get targets sorted so that every target comes after its dependencies, keeping the spec
+    /// order otherwise
+    fn sorted_targets(&self) -> Result<Vec<&TargetSpec>> {
+        fn visit<'a>(
+            spec: &'a ProjectSpec,
+            target: &'a TargetSpec,
+            visiting: &mut Vec<&'a str>,
+            sorted: &mut Vec<&'a TargetSpec>,
+        ) -> Result<()> {
+            if sorted.iter().any(|t| t.name == target.name) {
+                return Ok(());
+            }
+            if visiting.contains(&target.name.as_str()) {
+                anyhow::bail!("Target {:?} depends on itself", target.name);
+            }
+            visiting.push(&target.name);
+            for name in target.dependencies.iter() {
+                let dependency = spec
+                    .targets
+                    .iter()
+                    .find(|t| &t.name == name)
+                    .ok_or_else(|| anyhow::anyhow!("Target {name:?} not found"))?;
+                visit(spec, dependency, visiting, sorted)?;
+            }
+            visiting.pop();
+            sorted.push(target);
+            Ok(())
+        }
+
+        let mut sorted = vec![];
+        for target in self.targets.iter() {
+            visit(self, target, &mut vec![], &mut sorted)?;
+        }
+        Ok(sorted)
+    }
+}
+
+/// Expand `patterns` to paths under `root`, relative to it. Patterns matching an existing path
+/// are taken as is when `directories` is set, others match files only, sorted.
+fn expand(root: &Path, patterns: &[String], directories: bool) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for pattern in patterns {
+        let path = root.join(pattern);
+        if path.is_file() || (directories && path.is_dir()) {
+            paths.push(PathBuf::from(pattern));
+            continue;
+        }
+
+        let glob = wax::Glob::new(pattern)
+            .map_err(|error| anyhow::anyhow!("Invalid glob {pattern:?}: {error}"))?;
+        let mut matches = vec![];
+        for entry in glob.walk(root) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                matches.push(entry.path().strip_prefix(root)?.to_path_buf());
+            }
+        }
+        matches.sort();
+        paths.extend(matches);
+    }
+    Ok(paths)
+}
+
+impl XCodeProject {
+    /// Create a project from `spec`, with `root` as the directory holding the `.xcodeproj`
+    /// bundle and the one globs of the spec are relative to.
+    ///
+    /// Groups mirror the directories of matched files. Targets get default configurations for
+    /// their platform, which settings of the spec are applied on top of. References are
+    /// deterministic, see [`PBXReferenceMode::Deterministic`], so generating a project from the
+    /// same spec and files yields the same project.pbxproj. Nothing is written until
+    /// [`XCodeProject::save`].
+    pub fn from_spec<P: AsRef<Path>>(spec: &ProjectSpec, root: P) -> Result<Self> {
+        let root = root.as_ref();
+        let platform = spec
+            .targets
+            .first()
+            .map(|target| target.platform.clone())
+            .filter(|platform| !platform.is_unknown())
+            .unwrap_or(PBXTargetPlatform::IOS);
+        let mut pbxproj = empty_project(&platform, PBXReferenceMode::Deterministic)?;
+
+        let project = pbxproj.root_object_reference().to_string();
+        for (key, value) in spec.settings.iter() {
+            pbxproj
+                .objects_mut()
+                .set_setting_all(&project, key, value.as_str())?;
+        }
+
+        for target in spec.sorted_targets()? {
+            let platform = match target.platform {
+                PBXTargetPlatform::Unknown => PBXTargetPlatform::IOS,
+                ref platform => platform.clone(),
+            };
+            let mut builder = PBXTarget::builder(&target.name)
+                .product_type(target.product_type.clone())
+                .platform(platform);
+            if let Some(version) = &target.deployment_target {
+                builder = builder.deployment_target(version);
+            }
+            for (key, value) in target.settings.iter() {
+                builder = builder.setting(key.as_str(), value.as_str());
+            }
+            for path in expand(root, &target.sources, false)? {
+                builder = builder.source(path);
+            }
+            for path in expand(root, &target.resources, true)? {
+                builder = builder.resource(path);
+            }
+            for path in target.frameworks.iter() {
+                builder = builder.framework(path);
+            }
+            for (name, product) in target.packages.iter() {
+                let package = spec
+                    .packages
+                    .iter()
+                    .find(|package| &package.name == name)
+                    .ok_or_else(|| anyhow::anyhow!("Package {name:?} not found"))?;
+                builder = builder.package(&package.url, product, package.requirement.clone());
+            }
+            for name in target.dependencies.iter() {
+                builder = builder.dependency(name);
+            }
+            builder.build(&mut pbxproj)?;
+        }
+        move_products_group_last(&mut pbxproj);
+
+        let bundle = root.join(format!("{}.xcodeproj", spec.name));
+        #[cfg(feature = "scheme")]
+        let bundle = {
+            let schemes = spec
+                .schemes
+                .iter()
+                .map(|scheme| scheme_from_spec(&pbxproj, &spec.name, scheme))
+                .collect::<Result<Vec<_>>>()?;
+            crate::bundle::XCProjectBundle::with_schemes(bundle, schemes)
+        };
+        #[cfg(not(feature = "scheme"))]
+        let bundle = crate::bundle::XCProjectBundle::new(bundle);
+
+        pbxproj.set_project_name(&spec.name);
+        Ok(Self {
+            name: spec.name.clone(),
+            root: root.to_path_buf(),
+            pbxproj,
+            bundle,
+        })
+    }
+}
+
+/// Create scheme of `spec` for targets of `pbxproj`, a project named `project`
+#[cfg(feature = "scheme")]
+pub(crate) fn scheme_from_spec(
+    pbxproj: &PBXRootObject,
+    project: &str,
+    spec: &SchemeSpec,
+) -> Result<XCScheme> {
+    let reference = |name: &str| -> Result<XCBuildableReference> {
+        let target = pbxproj
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("Target {name:?} not found"))?;
+        Ok(XCBuildableReference {
+            buildable_identifier: Some("primary".into()),
+            blueprint_identifier: Some(target.id.clone()),
+            buildable_name: target.product.as_ref().and_then(|p| p.path).cloned(),
+            blueprint_name: Some(name.into()),
+            referenced_container: Some(format!("container:{project}.xcodeproj")),
+        })
+    };
+
+    let mut scheme = XCScheme::named(&spec.name);
+    for name in spec.build_targets.iter().chain(spec.test_targets.iter()) {
+        scheme.add_build_target(&reference(name)?);
+    }
+    for name in spec.test_targets.iter() {
+        scheme.add_testable(&reference(name)?);
+    }
+    if let Some(name) = &spec.run_target {
+        scheme.set_launch_runnable(Some(&XCLaunchRunnable::Product(reference(name)?)));
+    }
+    for (action, configuration) in [
+        ("TestAction", "Debug"),
+        ("LaunchAction", "Debug"),
+        ("ProfileAction", "Release"),
+        ("AnalyzeAction", "Debug"),
+        ("ArchiveAction", "Release"),
+    ] {
+        scheme
+            .action_entry(action)
+            .set_attribute("buildConfiguration", Some(configuration));
+    }
+    Ok(scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn from_spec() {
+        let root = std::env::temp_dir().join(format!("xcodeproj-spec-{}", std::process::id()));
+        for path in [
+            "App/AppMain.swift",
+            "App/Views/ContentView.swift",
+            "App/Assets.xcassets/Contents.json",
+            "AppTests/AppTests.swift",
+        ] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), "").unwrap();
+        }
+
+        let spec = ProjectSpec {
+            name: "App".into(),
+            settings: BTreeMap::from([("SWIFT_VERSION".into(), "5.0".into())]),
+            packages: vec![PackageSpec {
+                name: "Logging".into(),
+                url: "https://github.com/apple/swift-log".into(),
+                requirement: XCVersionRequirement::UpToNextMajorVersion("1.5.0".into()),
+            }],
+            targets: vec![
+                TargetSpec {
+                    name: "AppTests".into(),
+                    product_type: PBXProductType::UnitTestBundle,
+                    sources: vec!["AppTests/**/*.swift".into()],
+                    dependencies: vec!["App".into()],
+                    ..Default::default()
+                },
+                TargetSpec {
+                    name: "App".into(),
+                    product_type: PBXProductType::Application,
+                    platform: PBXTargetPlatform::IOS,
+                    deployment_target: Some("16.0".into()),
+                    sources: vec!["App/**/*.swift".into()],
+                    resources: vec!["App/Assets.xcassets".into()],
+                    settings: BTreeMap::from([(
+                        "PRODUCT_BUNDLE_IDENTIFIER".into(),
+                        "com.example.App".into(),
+                    )]),
+                    packages: vec![("Logging".into(), "Logging".into())],
+                    ..Default::default()
+                },
+            ],
+            schemes: vec![SchemeSpec {
+                name: "App".into(),
+                build_targets: vec!["App".into()],
+                run_target: Some("App".into()),
+                test_targets: vec!["AppTests".into()],
+            }],
+        };
+        let project = XCodeProject::from_spec(&spec, &root).unwrap();
+        assert_eq!(&root.join("App.xcodeproj"), project.path());
+
+        let targets = project.root_project().targets;
+        let names = targets.iter().map(|t| t.name.unwrap()).collect::<Vec<_>>();
+        assert_eq!(vec!["App", "AppTests"], names);
+        let app = &targets[0];
+        let files = |kind: PBXBuildPhaseKind| {
+            app.build_phases
+                .iter()
+                .find(|phase| phase.kind == kind)
+                .unwrap()
+                .files
+                .iter()
+                .filter_map(|file| file.file.as_ref()?.path.cloned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec!["AppMain.swift", "ContentView.swift"],
+            files(PBXBuildPhaseKind::Sources)
+        );
+        assert_eq!(vec!["Assets.xcassets"], files(PBXBuildPhaseKind::Resources));
+        assert_eq!(1, app.package_product_dependencies.len());
+        assert_eq!(1, targets[1].target_dependencies.len());
+
+        let settings = &app
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+        assert_eq!(
+            Some(&"com.example.App".to_string()),
+            settings.get_string("PRODUCT_BUNDLE_IDENTIFIER")
+        );
+        let project_settings = &project
+            .root_project()
+            .build_configuration_list
+            .build_configurations[0]
+            .build_settings;
+        assert_eq!(
+            Some(&"5.0".to_string()),
+            project_settings.get_string("SWIFT_VERSION")
+        );
+        let groups = project.root_group().children;
+        assert_eq!(Some(&"Products".to_string()), groups.last().unwrap().name);
+
+        #[cfg(feature = "scheme")]
+        {
+            let scheme = &project.schemes()[0];
+            assert_eq!("App", scheme.name);
+            assert_eq!(1, scheme.testables().len());
+            assert!(matches!(
+                scheme.launch_action().unwrap().runnable,
+                Some(XCLaunchRunnable::Product(_))
+            ));
+            let xml = scheme.to_xml();
+            assert_eq!(2, xml.matches("<BuildActionEntry\n").count());
+            assert!(xml.contains("BuildableName = \"App.app\""));
+        }
+
+        // Generating again yields the same references
+        for _ in 0..3 {
+            let again = XCodeProject::from_spec(&spec, &root).unwrap();
+            assert_eq!(
+                project.pbxproj().to_pbxproj_string(),
+                again.pbxproj().to_pbxproj_string()
+            );
+            #[cfg(feature = "scheme")]
+            assert_eq!(project.schemes()[0].to_xml(), again.schemes()[0].to_xml());
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,559 @@
+use crate::pbxproj::*;
+#[cfg(feature = "scheme")]
+use crate::spec::scheme_from_spec;
+use crate::XCodeProject;
+use anyhow::Result;
+use std::path::Path;
+
+/// Project templates used to create new projects from scratch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Template {
+    /// iOS Application with a single app target
+    IosApp {
+        /// Project and target name
+        name: String,
+        /// Application bundle identifier
+        bundle_id: String,
+        /// Whether to use SwiftUI life cycle instead of UIKit storyboards
+        swiftui: bool,
+    },
+}
+
+impl Template {
+    /// Get template project name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::IosApp { name, .. } => name,
+        }
+    }
+
+    /// Source files created for the app target, paths are relative to the target group.
+    fn sources(&self) -> Vec<String> {
+        self.files()
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| path.ends_with(".swift"))
+            .collect()
+    }
+
+    /// Files written next to the project on first save, paths are relative to the target
+    /// group.
+    fn files(&self) -> Vec<(String, String)> {
+        match self {
+            Self::IosApp { name, swiftui, .. } => {
+                let mut files = if *swiftui {
+                    vec![
+                        (format!("{name}App.swift"), swiftui_app(name)),
+                        ("ContentView.swift".into(), SWIFTUI_CONTENT_VIEW.into()),
+                    ]
+                } else {
+                    vec![
+                        ("AppDelegate.swift".into(), UIKIT_APP_DELEGATE.into()),
+                        ("SceneDelegate.swift".into(), UIKIT_SCENE_DELEGATE.into()),
+                        ("ViewController.swift".into(), UIKIT_VIEW_CONTROLLER.into()),
+                        (
+                            "Base.lproj/Main.storyboard".into(),
+                            storyboard("BYZ-38-t0r", false),
+                        ),
+                        (
+                            "Base.lproj/LaunchScreen.storyboard".into(),
+                            storyboard("01J-lp-oVM", true),
+                        ),
+                    ]
+                };
+                files.extend([
+                    (
+                        "Assets.xcassets/Contents.json".into(),
+                        ASSETS_CONTENTS.into(),
+                    ),
+                    (
+                        "Assets.xcassets/AppIcon.appiconset/Contents.json".into(),
+                        APP_ICON_CONTENTS.into(),
+                    ),
+                    (
+                        "Assets.xcassets/AccentColor.colorset/Contents.json".into(),
+                        ACCENT_COLOR_CONTENTS.into(),
+                    ),
+                ]);
+                files
+            }
+        }
+    }
+}
+
+fn swiftui_app(name: &str) -> String {
+    let name = name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+    format!(
+        r#"import SwiftUI
+
+@main
+struct {name}App: App {{
+    var body: some Scene {{
+        WindowGroup {{
+            ContentView()
+        }}
+    }}
+}}
+"#
+    )
+}
+
+const SWIFTUI_CONTENT_VIEW: &str = r#"import SwiftUI
+
+struct ContentView: View {
+    var body: some View {
+        Text("Hello, world!")
+            .padding()
+    }
+}
+"#;
+
+const UIKIT_APP_DELEGATE: &str = r#"import UIKit
+
+@main
+class AppDelegate: UIResponder, UIApplicationDelegate {
+    func application(
+        _ application: UIApplication,
+        didFinishLaunchingWithOptions launchOptions: [UIApplication.LaunchOptionsKey: Any]?
+    ) -> Bool {
+        true
+    }
+
+    func application(
+        _ application: UIApplication,
+        configurationForConnecting connectingSceneSession: UISceneSession,
+        options: UIScene.ConnectionOptions
+    ) -> UISceneConfiguration {
+        UISceneConfiguration(name: "Default Configuration", sessionRole: connectingSceneSession.role)
+    }
+}
+"#;
+
+const UIKIT_SCENE_DELEGATE: &str = r#"import UIKit
+
+class SceneDelegate: UIResponder, UIWindowSceneDelegate {
+    var window: UIWindow?
+}
+"#;
+
+const UIKIT_VIEW_CONTROLLER: &str = r#"import UIKit
+
+class ViewController: UIViewController {
+    override func viewDidLoad() {
+        super.viewDidLoad()
+    }
+}
+"#;
+
+/// Storyboard with a single view controller scene, `ViewController` unless `launch_screen`
+fn storyboard(id: &str, launch_screen: bool) -> String {
+    let (launch_screen, class) = if launch_screen {
+        (r#" launchScreen="YES""#, "")
+    } else {
+        (
+            "",
+            r#" customClass="ViewController" customModuleProvider="target""#,
+        )
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<document type="com.apple.InterfaceBuilder3.CocoaTouch.Storyboard.XIB" version="3.0" toolsVersion="22155" targetRuntime="iOS.CocoaTouch" propertyAccessControl="none" useAutolayout="YES"{launch_screen} useTraitCollections="YES" useSafeAreas="YES" colorMatched="YES" initialViewController="{id}">
+    <scenes>
+        <scene sceneID="{id}-scene">
+            <objects>
+                <viewController id="{id}"{class} sceneMemberID="viewController">
+                    <view key="view" contentMode="scaleToFill" id="{id}-view">
+                        <rect key="frame" x="0.0" y="0.0" width="393" height="852"/>
+                        <autoresizingMask key="autoresizingMask" widthSizable="YES" heightSizable="YES"/>
+                        <color key="backgroundColor" systemColor="systemBackgroundColor"/>
+                    </view>
+                </viewController>
+                <placeholder placeholderIdentifier="IBFirstResponder" id="{id}-responder" sceneMemberID="firstResponder"/>
+            </objects>
+        </scene>
+    </scenes>
+</document>
+"#
+    )
+}
+
+const ASSETS_CONTENTS: &str = r#"{
+  "info" : {
+    "author" : "xcode",
+    "version" : 1
+  }
+}
+"#;
+
+const APP_ICON_CONTENTS: &str = r#"{
+  "images" : [
+    {
+      "idiom" : "universal",
+      "platform" : "ios",
+      "size" : "1024x1024"
+    }
+  ],
+  "info" : {
+    "author" : "xcode",
+    "version" : 1
+  }
+}
+"#;
+
+const ACCENT_COLOR_CONTENTS: &str = r#"{
+  "colors" : [
+    {
+      "idiom" : "universal"
+    }
+  ],
+  "info" : {
+    "author" : "xcode",
+    "version" : 1
+  }
+}
+"#;
+
+/// Create a project without targets: main and products groups, and default configurations for
+/// `platform`. Objects inserted later get references generated with `reference_mode`.
+pub(crate) fn empty_project(
+    platform: &PBXTargetPlatform,
+    reference_mode: PBXReferenceMode,
+) -> Result<PBXRootObject> {
+    let mut objects = PBXObjectCollection::default();
+    objects.set_reference_mode(reference_mode);
+
+    let mut main_group = PBXHashMap::default();
+    main_group.insert_value("isa", PBXObjectKind::from("PBXGroup"));
+    main_group.insert_value("children", Vec::<String>::new());
+    main_group.insert_value("sourceTree", PBXSourceTree::Group.to_string());
+    let main_group_reference = String::from(objects.insert_object(main_group, None));
+
+    let mut products_group = PBXHashMap::default();
+    products_group.insert_value("isa", PBXObjectKind::from("PBXGroup"));
+    products_group.insert_value("children", Vec::<String>::new());
+    products_group.insert_value("name", "Products");
+    products_group.insert_value("sourceTree", PBXSourceTree::Group.to_string());
+    let products_group_reference = objects.add_child(&main_group_reference, products_group)?;
+
+    let build_configuration_list =
+        objects.add_default_configurations(XCBuildSettingsPreset::Project, platform);
+
+    let mut attributes = PBXHashMap::default();
+    attributes.insert_value("BuildIndependentTargetsInParallel", 1);
+    attributes.insert_value("LastSwiftUpdateCheck", 1500);
+    attributes.insert_value("LastUpgradeCheck", 1500);
+
+    let mut project = PBXHashMap::default();
+    project.insert_value("isa", PBXObjectKind::PBXProject);
+    project.insert_value("attributes", attributes);
+    project.insert_value("buildConfigurationList", build_configuration_list);
+    project.insert_value("compatibilityVersion", "Xcode 14.0");
+    project.insert_value("developmentRegion", "en");
+    project.insert_value("hasScannedForEncodings", 0);
+    project.insert_value("knownRegions", vec!["en", "Base"]);
+    project.insert_value("mainGroup", main_group_reference);
+    project.insert_value("productRefGroup", products_group_reference);
+    project.insert_value("projectDirPath", "");
+    project.insert_value("projectRoot", "");
+    project.insert_value("targets", Vec::<String>::new());
+    let project_reference = String::from(objects.insert_object(project, None));
+
+    Ok(PBXRootObject::new(
+        1,
+        56,
+        Default::default(),
+        objects,
+        project_reference,
+    ))
+}
+
+/// Move the products group after other main group children, where Xcode keeps it
+pub(crate) fn move_products_group_last(pbxproj: &mut PBXRootObject) {
+    let main_group = pbxproj.root_group().id;
+    let Some(products_group) = pbxproj
+        .objects()
+        .map
+        .get(pbxproj.root_object_reference())
+        .and_then(|project| project.get_string("productRefGroup"))
+        .cloned()
+    else {
+        return;
+    };
+    if let Some(mut main_group) = pbxproj.objects_mut().get_group_mut(&main_group) {
+        let children = main_group.children_mut();
+        if let Some(index) = children.position(&products_group) {
+            let products_group = children.0.remove(index);
+            children.0.push(products_group);
+        }
+    }
+}
+
+impl XCodeProject {
+    /// Create a new project with targets, groups, schemes and build settings produced by the
+    /// given template, with `root` as the directory holding the `.xcodeproj` bundle.
+    ///
+    /// Nothing is written until [`XCodeProject::save`], which also creates the source files,
+    /// storyboards and asset catalog of the template when missing.
+    pub fn from_template<P: AsRef<Path>>(template: Template, root: P) -> Result<Self> {
+        let root = root.as_ref();
+        let name = template.name().to_string();
+        let platform = PBXTargetPlatform::IOS;
+        let mut pbxproj = empty_project(&platform, PBXReferenceMode::Random)?;
+        let main_group_reference = pbxproj.root_group().id;
+        let app_group_reference = pbxproj
+            .objects_mut()
+            .add_group(&main_group_reference, &name)?;
+        move_products_group_last(&mut pbxproj);
+
+        let target =
+            pbxproj.add_native_target(&name, PBXProductType::Application, platform.clone())?;
+
+        let (sources_phase, resources_phase, configurations) = {
+            let target = pbxproj.try_get::<PBXTarget, _>(&target)?;
+            let phase = |kind: PBXBuildPhaseKind| {
+                target
+                    .build_phases
+                    .iter()
+                    .find(|p| p.kind == kind)
+                    .map(|p| p.id.clone())
+                    .ok_or_else(|| anyhow::anyhow!("Build phase not found"))
+            };
+            let configurations = target
+                .build_configuration_list
+                .iter()
+                .flat_map(|l| l.build_configurations.iter().map(|c| c.id.clone()))
+                .collect::<Vec<_>>();
+            (
+                phase(PBXBuildPhaseKind::Sources)?,
+                phase(PBXBuildPhaseKind::Resources)?,
+                configurations,
+            )
+        };
+
+        let objects = pbxproj.objects_mut();
+        for source in template.sources() {
+            let file = objects.add_file_reference(&app_group_reference, source)?;
+            objects.add_build_file(&sources_phase, &file)?;
+        }
+
+        let assets = objects.add_file_reference(&app_group_reference, "Assets.xcassets")?;
+        objects.add_build_file(&resources_phase, &assets)?;
+
+        let Template::IosApp {
+            bundle_id, swiftui, ..
+        } = &template;
+
+        if !swiftui {
+            for storyboard in ["Main", "LaunchScreen"] {
+                let mut variant_group = PBXHashMap::default();
+                variant_group.insert_value("isa", PBXObjectKind::from("PBXVariantGroup"));
+                variant_group.insert_value("children", Vec::<String>::new());
+                variant_group.insert_value("name", format!("{storyboard}.storyboard"));
+                variant_group.insert_value("sourceTree", PBXSourceTree::Group.to_string());
+                let variant_group = objects.add_child(&app_group_reference, variant_group)?;
+
+                let file = objects.add_file_reference(
+                    &variant_group,
+                    format!("Base.lproj/{storyboard}.storyboard"),
+                )?;
+                if let Some(mut file) = objects.get_object_mut(&file) {
+                    file.insert_value("name", "Base");
+                }
+                objects.add_build_file(&resources_phase, &variant_group)?;
+            }
+        }
+
+        for configuration in configurations {
+            let mut object = objects.get_object_mut(&configuration);
+            let settings = object
+                .as_mut()
+                .and_then(|c| c.get_object_mut("buildSettings"))
+                .ok_or_else(|| anyhow::anyhow!("Build settings not found for {configuration}"))?;
+
+            settings.insert_value("PRODUCT_BUNDLE_IDENTIFIER", bundle_id.as_str());
+            if !swiftui {
+                settings.remove_value("ENABLE_PREVIEWS");
+                settings.remove_value("INFOPLIST_KEY_UILaunchScreen_Generation");
+                settings.insert_value("INFOPLIST_KEY_UILaunchStoryboardName", "LaunchScreen");
+                settings.insert_value("INFOPLIST_KEY_UIMainStoryboardFile", "Main");
+            }
+        }
+
+        let bundle = root.join(format!("{name}.xcodeproj"));
+        #[cfg(feature = "scheme")]
+        let bundle = {
+            let spec = crate::SchemeSpec {
+                name: name.clone(),
+                build_targets: vec![name.clone()],
+                run_target: Some(name.clone()),
+                test_targets: vec![],
+            };
+            let scheme = scheme_from_spec(&pbxproj, &name, &spec)?;
+            crate::bundle::XCProjectBundle::with_schemes(bundle, vec![scheme])
+        };
+        #[cfg(not(feature = "scheme"))]
+        let bundle = crate::bundle::XCProjectBundle::new(bundle);
+
+        let files = template
+            .files()
+            .into_iter()
+            .map(|(path, content)| (root.join(&name).join(path), content))
+            .collect();
+
+        pbxproj.set_project_name(&name);
+        Ok(Self {
+            name,
+            root: root.to_path_buf(),
+            pbxproj,
+            bundle: bundle.with_files(files),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn ios_swiftui_app() {
+        let project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            "/tmp",
+        )
+        .unwrap();
+
+        let targets = project.targets();
+        let target = &targets[0];
+        let settings = target
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+
+        assert_eq!(1, targets.len());
+        assert_eq!(Some(&"Demo".to_string()), target.name);
+        assert_eq!(2, target.build_phases[0].files.len());
+        assert_eq!(1, target.build_phases[2].files.len());
+        assert_eq!(
+            Some(&"com.example.Demo".to_string()),
+            settings.get_string("PRODUCT_BUNDLE_IDENTIFIER")
+        );
+        assert_eq!(Some(&true), settings.get_bool("GENERATE_INFOPLIST_FILE"));
+        assert_eq!(2, project.root_group().children.len());
+        assert_eq!(&PathBuf::from("/tmp/Demo.xcodeproj"), project.path());
+    }
+
+    #[cfg(feature = "scheme")]
+    #[test]
+    fn ios_app_scheme() {
+        use crate::{XCBuildableReference, XCLaunchRunnable};
+
+        let project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            "/tmp",
+        )
+        .unwrap();
+        let target = &project.targets()[0];
+        let reference = XCBuildableReference {
+            buildable_identifier: Some("primary".into()),
+            blueprint_identifier: Some(target.id.clone()),
+            buildable_name: Some("Demo.app".into()),
+            blueprint_name: Some("Demo".into()),
+            referenced_container: Some("container:Demo.xcodeproj".into()),
+        };
+
+        let scheme = &project.schemes()[0];
+        let launch = scheme.launch_action().unwrap();
+        assert_eq!("Demo", scheme.name);
+        assert_eq!(Some(XCLaunchRunnable::Product(reference)), launch.runnable);
+        assert_eq!(Some("Debug".into()), launch.build_configuration);
+
+        let xml = scheme.to_xml();
+        assert_eq!(1, xml.matches("<BuildActionEntry\n").count());
+        assert_eq!(
+            2,
+            xml.matches(&format!("BlueprintIdentifier = \"{}\"", target.id))
+                .count()
+        );
+        assert!(xml.contains("buildConfiguration = \"Debug\""));
+    }
+
+    #[test]
+    fn ios_app_files() {
+        let root = std::env::temp_dir().join(format!(
+            "xcodeproj-template-{}",
+            PBXObjectCollection::default().generate_reference()
+        ));
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: false,
+            },
+            &root,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("Demo")).unwrap();
+        std::fs::write(root.join("Demo/AppDelegate.swift"), "// kept").unwrap();
+        project.save().unwrap();
+
+        let objects = project.pbxproj().objects();
+        let files = objects
+            .map
+            .iter()
+            .filter(|(_, object)| {
+                object.get_kind("isa") == Some(&PBXObjectKind::from("PBXFileReference"))
+            })
+            .filter_map(|(reference, _)| objects.get_full_path(reference, &root))
+            .collect::<Vec<_>>();
+        assert_eq!(6, files.len());
+        for path in files {
+            assert!(path.exists(), "{path:?} missing");
+        }
+        assert!(root.join("Demo/Assets.xcassets/Contents.json").exists());
+        assert_eq!(
+            "// kept",
+            std::fs::read_to_string(root.join("Demo/AppDelegate.swift")).unwrap()
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn ios_uikit_app() {
+        let project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: false,
+            },
+            "/tmp",
+        )
+        .unwrap();
+
+        let targets = project.targets();
+        let settings = targets[0]
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations[0]
+            .build_settings;
+
+        assert_eq!(3, targets[0].build_phases[0].files.len());
+        assert_eq!(3, targets[0].build_phases[2].files.len());
+        assert_eq!(
+            Some(&"Main".to_string()),
+            settings.get_string("INFOPLIST_KEY_UIMainStoryboardFile")
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{XCTestExecutionOrdering, XCodeProject};
+use anyhow::Result;
+use md5::{Digest, Md5};
+use serde_json::json;
+use std::path::PathBuf;
+
+/// Options of a test plan generated by [`XCodeProject::generate_test_plan`]. `None` fields are
+/// left to Xcode's defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XCTestPlanOptions {
+    /// Gather code coverage, `codeCoverage`
+    pub code_coverage: Option<bool>,
+    /// Test order, `testExecutionOrdering`
+    pub test_execution_ordering: Option<XCTestExecutionOrdering>,
+    /// Run tests of every target in parallel, `parallelizable`
+    pub parallelizable: Option<bool>,
+    /// Make the plan the default one of the schemes it is added to
+    pub default: bool,
+}
+
+impl XCodeProject {
+    /// Generate `<name>.xctestplan` next to the project running the test targets with the given
+    /// names, and add it to the project and to the test action of every scheme testing one of
+    /// them. Returns the test plan path.
+    ///
+    /// Schemes are written back to disk, the project file is not.
+    pub fn generate_test_plan<S: AsRef<str>>(
+        &mut self,
+        name: &str,
+        test_targets: &[S],
+        options: &XCTestPlanOptions,
+    ) -> Result<PathBuf> {
+        let container = format!("container:{}.xcodeproj", self.name);
+        let mut targets = vec![];
+        let mut blueprints = vec![];
+        for target_name in test_targets {
+            let target_name = target_name.as_ref();
+            let target = self
+                .pbxproj
+                .objects()
+                .get_target_by_name(target_name)
+                .ok_or_else(|| anyhow::anyhow!("Target {target_name:?} not found"))?;
+            let mut entry = json!({
+                "target": {
+                    "containerPath": container,
+                    "identifier": target.id,
+                    "name": target_name,
+                }
+            });
+            if let Some(parallelizable) = options.parallelizable {
+                entry["parallelizable"] = json!(parallelizable);
+            }
+            targets.push(entry);
+            blueprints.push(target.id.clone());
+            blueprints.push(target_name.to_string());
+        }
+
+        let mut default_options = serde_json::Map::new();
+        if let Some(code_coverage) = options.code_coverage {
+            default_options.insert("codeCoverage".into(), json!(code_coverage));
+        }
+        if let Some(ordering) = options.test_execution_ordering {
+            let ordering = match ordering {
+                XCTestExecutionOrdering::Alphabetical => "alphabetical",
+                XCTestExecutionOrdering::Random => "random",
+            };
+            default_options.insert("testExecutionOrdering".into(), json!(ordering));
+        }
+        let plan = json!({
+            "configurations": [{
+                "id": configuration_id(&self.name, name),
+                "name": "Configuration 1",
+                "options": {},
+            }],
+            "defaultOptions": default_options,
+            "testTargets": targets,
+            "version": 1,
+        });
+
+        let file_name = format!("{name}.xctestplan");
+        let path = self.root.join(&file_name);
+        std::fs::write(&path, serde_json::to_string_pretty(&plan)? + "\n")?;
+        self.pbxproj.get_or_add_file_reference(&file_name)?;
+
+        let reference = format!("container:{file_name}");
+        let bundle = self.root.join(format!("{}.xcodeproj", self.name));
+        for scheme in self.schemes_mut().iter_mut() {
+            let tests_target = scheme.testables().iter().any(|testable| {
+                [&testable.blueprint_identifier, &testable.blueprint_name]
+                    .into_iter()
+                    .flatten()
+                    .any(|blueprint| blueprints.contains(blueprint))
+            });
+            if tests_target {
+                scheme.add_test_plan(&reference, options.default);
+                let scheme_path = scheme.path_in(&bundle);
+                if let Some(parent) = scheme_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                scheme.save(&scheme_path)?;
+                scheme.path = Some(scheme_path);
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Stable UUID of the plan configuration, so regenerating a plan yields the same file
+fn configuration_id(project: &str, plan: &str) -> String {
+    let digest = Md5::digest(format!("{project}/{plan}"));
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Template, XCScheme};
+
+    #[test]
+    fn generate_test_plan() {
+        let mut project = XCodeProject::from_template(
+            Template::IosApp {
+                name: "Demo".into(),
+                bundle_id: "com.example.Demo".into(),
+                swiftui: true,
+            },
+            std::env::temp_dir().join(format!("xcodeproj-test-plan-{}", std::process::id())),
+        )
+        .unwrap();
+        std::fs::create_dir_all(&project.root).unwrap();
+
+        let sample =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemes/demo1.xcscheme");
+        let content = std::fs::read_to_string(sample)
+            .unwrap()
+            .replace("NoBlueprintIDTests", "Demo");
+        let mut scheme = XCScheme::from_xml("Demo", &content).unwrap();
+        scheme.add_test_plan("container:Existing.xctestplan", true);
+        *project.schemes_mut() = vec![scheme];
+
+        assert!(project
+            .generate_test_plan("Missing", &["Missing"], &Default::default())
+            .is_err());
+        let options = XCTestPlanOptions {
+            code_coverage: Some(true),
+            test_execution_ordering: Some(XCTestExecutionOrdering::Random),
+            parallelizable: Some(true),
+            default: true,
+        };
+        let path = project
+            .generate_test_plan("Demo", &["Demo"], &options)
+            .unwrap();
+
+        let plan: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json!(true), plan["defaultOptions"]["codeCoverage"]);
+        assert_eq!(
+            json!("random"),
+            plan["defaultOptions"]["testExecutionOrdering"]
+        );
+        assert_eq!(json!("Demo"), plan["testTargets"][0]["target"]["name"]);
+        assert_eq!(json!(true), plan["testTargets"][0]["parallelizable"]);
+        assert_eq!(json!(1), plan["version"]);
+
+        let scheme = XCScheme::new(
+            project
+                .root
+                .join("Demo.xcodeproj/xcshareddata/xcschemes/Demo.xcscheme"),
+        )
+        .unwrap();
+        let plans = scheme
+            .test_plans()
+            .into_iter()
+            .map(|plan| (plan.reference, plan.default))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("container:Existing.xctestplan".to_string(), false),
+                ("container:Demo.xctestplan".to_string(), true)
+            ],
+            plans
+        );
+        assert!(project
+            .files()
+            .iter()
+            .any(|file| file.path.map(|p| p.as_str()) == Some("Demo.xctestplan")));
+
+        std::fs::remove_dir_all(&project.root).unwrap();
+    }
+}
@@ -0,0 +1,598 @@
+use super::normalize;
+use crate::XCSaveOptions;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Content of a `.xcworkspace` bundle, `contents.xcworkspacedata`
+///
+/// Workspaces are built by adding file references and groups to [`XCWorkspaceData::default`],
+/// then written with [`XCWorkspaceData::save`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCWorkspaceData {
+    /// Format version, `1.0` for every Xcode release so far
+    pub version: Option<String>,
+    /// Top level elements of the workspace navigator, in document order
+    pub children: Vec<XCWorkspaceElement>,
+}
+
+/// Element of the workspace navigator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XCWorkspaceElement {
+    /// Reference to a file, usually a `.xcodeproj` bundle, `<FileRef>`
+    FileRef {
+        /// File location
+        location: XCWorkspaceLocation,
+    },
+    /// Group of elements, `<Group>`
+    Group {
+        /// Group location, which `group:` locations of its children are relative to
+        location: Option<XCWorkspaceLocation>,
+        /// Name displayed in the navigator
+        name: Option<String>,
+        /// Elements of the group, in document order
+        children: Vec<XCWorkspaceElement>,
+    },
+}
+
+/// Location of a workspace element, `<kind>:<path>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XCWorkspaceLocation {
+    /// Relative to the location of the enclosing group, `group:`
+    Group(String),
+    /// Relative to the directory holding the `.xcworkspace` bundle, `container:`
+    Container(String),
+    /// Absolute path, `absolute:`
+    Absolute(String),
+    /// Relative to the developer directory of the selected Xcode, `developer:`
+    Developer(String),
+    /// Project holding the workspace, `self:`, found in `project.xcworkspace` of `.xcodeproj`
+    /// bundles
+    SelfReference(String),
+}
+
+impl Default for XCWorkspaceData {
+    fn default() -> Self {
+        Self {
+            version: Some("1.0".into()),
+            children: vec![],
+        }
+    }
+}
+
+impl XCWorkspaceData {
+    /// Read `contents.xcworkspacedata` of the `.xcworkspace` bundle at `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = path.as_ref().join("contents.xcworkspacedata");
+        let content = std::fs::read_to_string(&data).with_context(|| format!("Read {data:?}"))?;
+        Self::parse(&content).with_context(|| format!("Parse {data:?}"))
+    }
+
+    /// Parse `contents.xcworkspacedata` content
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut version = None;
+        // Groups being read, with the elements read so far, the workspace being the first
+        let mut groups = vec![(None, None, vec![])];
+        for tag in content.split('<').skip(1) {
+            let tag = tag.split('>').next().unwrap_or_default();
+            let is_empty = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let location = attribute(tag, "location")
+                .map(|location| location.parse::<XCWorkspaceLocation>())
+                .transpose()?;
+            let element = match tag.split_whitespace().next().unwrap_or_default() {
+                "Workspace" => {
+                    version = attribute(tag, "version");
+                    continue;
+                }
+                "FileRef" => XCWorkspaceElement::FileRef {
+                    location: location
+                        .ok_or_else(|| anyhow::anyhow!("FileRef without location"))?,
+                },
+                "Group" if is_empty => XCWorkspaceElement::Group {
+                    location,
+                    name: attribute(tag, "name"),
+                    children: vec![],
+                },
+                "Group" => {
+                    groups.push((location, attribute(tag, "name"), vec![]));
+                    continue;
+                }
+                "/Group" if groups.len() > 1 => {
+                    let (location, name, children) = groups.pop().unwrap();
+                    XCWorkspaceElement::Group {
+                        location,
+                        name,
+                        children,
+                    }
+                }
+                "/Group" => anyhow::bail!("Unexpected </Group>"),
+                _ => continue,
+            };
+            groups.last_mut().unwrap().2.push(element);
+        }
+        if groups.len() != 1 {
+            anyhow::bail!("Unclosed Group");
+        }
+
+        Ok(Self {
+            version,
+            children: groups.pop().unwrap().2,
+        })
+    }
+
+    /// Get paths of files referenced by the `.xcworkspace` bundle at `path` with this content,
+    /// in document order. Files relative to the developer directory are skipped.
+    pub fn file_paths<P: AsRef<Path>>(&self, path: P) -> Vec<PathBuf> {
+        let container = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        let mut paths = vec![];
+        collect_paths(&self.children, container, container, &mut paths);
+        paths
+    }
+
+    /// Get paths of `.xcodeproj` bundles referenced by the `.xcworkspace` bundle at `path` with
+    /// this content, in document order.
+    pub fn project_paths<P: AsRef<Path>>(&self, path: P) -> Vec<PathBuf> {
+        self.file_paths(path)
+            .into_iter()
+            .filter(|path| path.extension().map(|ext| ext == "xcodeproj") == Some(true))
+            .collect()
+    }
+
+    /// Add reference to `location` to the group at `groups`, names of nested groups from the
+    /// top level, adding missing groups with a `container:` location. Returns false if the group
+    /// already references `location`.
+    pub fn add_file_ref(&mut self, groups: &[&str], location: XCWorkspaceLocation) -> bool {
+        let elements = group_entry(&mut self.children, groups);
+        let exists = elements.iter().any(|element| {
+            matches!(element, XCWorkspaceElement::FileRef { location: existing } if existing == &location)
+        });
+        if !exists {
+            elements.push(XCWorkspaceElement::FileRef { location });
+        }
+        !exists
+    }
+
+    /// Add reference to file at `path`, absolute or relative to the directory holding the
+    /// `.xcworkspace` bundle at `workspace`, e.g. a `.xcodeproj` bundle, to the group at
+    /// `groups`, see [`XCWorkspaceData::add_file_ref`]. The file is located relative to the
+    /// group if inside its directory, by absolute path otherwise.
+    pub fn add_path<P, Q>(&mut self, workspace: P, groups: &[&str], path: Q) -> bool
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let container = workspace.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        let path = normalize(&container.join(path));
+        group_entry(&mut self.children, groups);
+        let group = group_directory(&self.children, groups, container);
+        let location = match path.strip_prefix(&group) {
+            Ok(relative) => XCWorkspaceLocation::Group(relative.to_string_lossy().into()),
+            Err(_) => XCWorkspaceLocation::Absolute(path.to_string_lossy().into()),
+        };
+        self.add_file_ref(groups, location)
+    }
+
+    /// Remove every reference to file at `path`, absolute or relative to the directory holding
+    /// the `.xcworkspace` bundle at `workspace`, whatever its group. Returns whether any was
+    /// removed.
+    pub fn remove_path<P, Q>(&mut self, workspace: P, path: Q) -> bool
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let container = workspace.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        let path = normalize(&container.join(path));
+        remove_paths(&mut self.children, container, container, &path)
+    }
+
+    /// Remove the group at `groups`, names of nested groups from the top level, with its
+    /// elements. Returns whether the group existed.
+    pub fn remove_group(&mut self, groups: &[&str]) -> bool {
+        let (name, parents) = match groups.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        let mut elements = &mut self.children;
+        for parent in parents {
+            match elements.iter_mut().find(|e| e.is_group_named(parent)) {
+                Some(XCWorkspaceElement::Group { children, .. }) => elements = children,
+                _ => return false,
+            }
+        }
+        let count = elements.len();
+        elements.retain(|element| !element.is_group_named(name));
+        elements.len() != count
+    }
+
+    /// Render as `contents.xcworkspacedata` content, laid out like Xcode does
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Workspace");
+        if let Some(version) = self.version.as_ref() {
+            xml.push_str(&format!("\n   version = \"{}\"", escape(version)));
+        }
+        xml.push_str(">\n");
+        write_elements(&self.children, &mut xml, 1);
+        xml.push_str("</Workspace>\n");
+        xml
+    }
+
+    /// Write `contents.xcworkspacedata` of the `.xcworkspace` bundle at `path`, creating the
+    /// bundle if missing
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.save_with(path, &XCSaveOptions::new())
+    }
+
+    /// Write `contents.xcworkspacedata` of the `.xcworkspace` bundle at `path` with the given
+    /// options, e.g. to keep a backup
+    pub fn save_with<P: AsRef<Path>>(&self, path: P, options: &XCSaveOptions) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path).with_context(|| format!("Create {path:?}"))?;
+        options.write(path.join("contents.xcworkspacedata"), self.to_xml())?;
+        Ok(())
+    }
+}
+
+impl XCWorkspaceElement {
+    /// Get element location
+    pub fn location(&self) -> Option<&XCWorkspaceLocation> {
+        match self {
+            Self::FileRef { location } => Some(location),
+            Self::Group { location, .. } => location.as_ref(),
+        }
+    }
+
+    fn is_group_named(&self, group: &str) -> bool {
+        matches!(self, Self::Group { name: Some(name), .. } if name == group)
+    }
+
+    /// Get group children, empty for file references
+    pub fn children(&self) -> &[XCWorkspaceElement] {
+        match self {
+            Self::FileRef { .. } => &[],
+            Self::Group { children, .. } => children,
+        }
+    }
+}
+
+impl XCWorkspaceLocation {
+    /// Get location path, without its kind
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Group(path)
+            | Self::Container(path)
+            | Self::Absolute(path)
+            | Self::Developer(path)
+            | Self::SelfReference(path) => path,
+        }
+    }
+
+    /// Resolve location against the directory of the enclosing `group` and the `container`
+    /// directory holding the `.xcworkspace` bundle. `None` for locations relative to the
+    /// developer directory.
+    pub fn resolve(&self, group: &Path, container: &Path) -> Option<PathBuf> {
+        let path = match self {
+            Self::Group(path) => group.join(path),
+            Self::Container(path) => container.join(path),
+            Self::Absolute(path) => PathBuf::from(path),
+            Self::Developer(_) => return None,
+            Self::SelfReference(_) => container.to_path_buf(),
+        };
+        Some(normalize(&path))
+    }
+}
+
+impl FromStr for XCWorkspaceLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, path) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Location {s:?} has no kind"))?;
+        let path = path.to_string();
+        Ok(match kind {
+            "group" => Self::Group(path),
+            "container" => Self::Container(path),
+            "absolute" => Self::Absolute(path),
+            "developer" => Self::Developer(path),
+            "self" => Self::SelfReference(path),
+            _ => anyhow::bail!("Unknown location kind {kind:?}"),
+        })
+    }
+}
+
+impl std::fmt::Display for XCWorkspaceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Self::Group(_) => "group",
+            Self::Container(_) => "container",
+            Self::Absolute(_) => "absolute",
+            Self::Developer(_) => "developer",
+            Self::SelfReference(_) => "self",
+        };
+        write!(f, "{kind}:{}", self.path())
+    }
+}
+
+fn collect_paths(
+    elements: &[XCWorkspaceElement],
+    group: &Path,
+    container: &Path,
+    paths: &mut Vec<PathBuf>,
+) {
+    for element in elements {
+        let location = element
+            .location()
+            .and_then(|location| location.resolve(group, container));
+        match element {
+            XCWorkspaceElement::FileRef { .. } => paths.extend(location),
+            XCWorkspaceElement::Group { children, .. } => {
+                let group = location.unwrap_or_else(|| group.to_path_buf());
+                collect_paths(children, &group, container, paths);
+            }
+        }
+    }
+}
+
+/// Get children of the group at `groups`, adding missing groups
+fn group_entry<'a>(
+    elements: &'a mut Vec<XCWorkspaceElement>,
+    groups: &[&str],
+) -> &'a mut Vec<XCWorkspaceElement> {
+    let (name, groups) = match groups.split_first() {
+        Some(split) => split,
+        None => return elements,
+    };
+    let index = match elements.iter().position(|e| e.is_group_named(name)) {
+        Some(index) => index,
+        None => {
+            elements.push(XCWorkspaceElement::Group {
+                location: Some(XCWorkspaceLocation::Container(String::new())),
+                name: Some(name.to_string()),
+                children: vec![],
+            });
+            elements.len() - 1
+        }
+    };
+    match &mut elements[index] {
+        XCWorkspaceElement::Group { children, .. } => group_entry(children, groups),
+        XCWorkspaceElement::FileRef { .. } => unreachable!(),
+    }
+}
+
+/// Get directory `group:` locations of the group at `groups` are relative to
+fn group_directory(elements: &[XCWorkspaceElement], groups: &[&str], container: &Path) -> PathBuf {
+    let mut directory = container.to_path_buf();
+    let mut elements = elements;
+    for name in groups {
+        let group = match elements.iter().find(|e| e.is_group_named(name)) {
+            Some(group) => group,
+            None => break,
+        };
+        if let Some(location) = group.location() {
+            directory = location.resolve(&directory, container).unwrap_or(directory);
+        }
+        elements = group.children();
+    }
+    directory
+}
+
+fn remove_paths(
+    elements: &mut Vec<XCWorkspaceElement>,
+    group: &Path,
+    container: &Path,
+    path: &Path,
+) -> bool {
+    let count = elements.len();
+    elements.retain(|element| match element {
+        XCWorkspaceElement::FileRef { location } => {
+            location.resolve(group, container).as_deref() != Some(path)
+        }
+        XCWorkspaceElement::Group { .. } => true,
+    });
+    let mut removed = elements.len() != count;
+    for element in elements.iter_mut() {
+        if let XCWorkspaceElement::Group {
+            location, children, ..
+        } = element
+        {
+            let group = location
+                .as_ref()
+                .and_then(|location| location.resolve(group, container))
+                .unwrap_or_else(|| group.to_path_buf());
+            removed |= remove_paths(children, &group, container, path);
+        }
+    }
+    removed
+}
+
+fn write_elements(elements: &[XCWorkspaceElement], xml: &mut String, depth: usize) {
+    let indent = "   ".repeat(depth);
+    for element in elements {
+        let (tag, name) = match element {
+            XCWorkspaceElement::FileRef { .. } => ("FileRef", None),
+            XCWorkspaceElement::Group { name, .. } => ("Group", name.as_ref()),
+        };
+        xml.push_str(&format!("{indent}<{tag}"));
+        if let Some(location) = element.location() {
+            let location = escape(&location.to_string());
+            xml.push_str(&format!("\n{indent}   location = \"{location}\""));
+        }
+        if let Some(name) = name {
+            xml.push_str(&format!("\n{indent}   name = \"{}\"", escape(name)));
+        }
+        xml.push_str(">\n");
+        write_elements(element.children(), xml, depth + 1);
+        xml.push_str(&format!("{indent}</{tag}>\n"));
+    }
+}
+
+/// Value of `key` attribute of an xml tag, e.g. `location = "group:App.xcodeproj"`
+fn attribute(tag: &str, key: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(start) = rest.find(key) {
+        rest = &rest[start + key.len()..];
+        if let Some(value) = rest.trim_start().strip_prefix('=') {
+            let value = value.trim_start().strip_prefix('"')?;
+            return value.split('"').next().map(unescape);
+        }
+    }
+    None
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "&#10;")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tree() {
+        let data = XCWorkspaceData::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Workspace
+   version = "1.0">
+   <FileRef
+      location = "group:App/App.xcodeproj">
+   </FileRef>
+   <Group
+      location = "container:Libs"
+      name = "Libs &amp; Tools">
+      <Group
+         location = "group:Kit"
+         name = "Kit">
+         <FileRef
+            location = "group:Kit.xcodeproj">
+         </FileRef>
+      </Group>
+      <Group name = "Empty"/>
+      <FileRef
+         location = "absolute:/tmp/Shared/Shared.xcodeproj">
+      </FileRef>
+   </Group>
+   <FileRef
+      location = "developer:Platforms/Tools.xcodeproj">
+   </FileRef>
+   <FileRef
+      location = "group:README.md">
+   </FileRef>
+</Workspace>
+"#,
+        )
+        .unwrap();
+        assert_eq!(Some("1.0"), data.version.as_deref());
+        assert_eq!(4, data.children.len());
+        match &data.children[1] {
+            XCWorkspaceElement::Group {
+                location,
+                name,
+                children,
+            } => {
+                assert_eq!(
+                    Some(&XCWorkspaceLocation::Container("Libs".into())),
+                    location.as_ref()
+                );
+                assert_eq!(Some("Libs & Tools"), name.as_deref());
+                assert_eq!(3, children.len());
+                assert!(children[1].children().is_empty());
+                assert_eq!(None, children[1].location());
+            }
+            element => panic!("{element:?} isn't a group"),
+        }
+        assert_eq!(
+            "developer:Platforms/Tools.xcodeproj",
+            data.children[2].location().unwrap().to_string()
+        );
+
+        assert_eq!(
+            vec![
+                PathBuf::from("/work/App/App.xcodeproj"),
+                PathBuf::from("/work/Libs/Kit/Kit.xcodeproj"),
+                PathBuf::from("/tmp/Shared/Shared.xcodeproj"),
+            ],
+            data.project_paths("/work/Demo.xcworkspace")
+        );
+        assert_eq!(4, data.file_paths("/work/Demo.xcworkspace").len());
+
+        assert!(XCWorkspaceData::parse("<Workspace><Group></Workspace>").is_err());
+        assert!(XCWorkspaceData::parse("<Workspace></Group></Workspace>").is_err());
+        assert!(XCWorkspaceData::parse("<Workspace><FileRef location = \"nope\"/>").is_err());
+    }
+
+    #[test]
+    fn build_workspace() {
+        let workspace = Path::new("/work/Demo.xcworkspace");
+        let mut data = XCWorkspaceData::default();
+        assert!(data.add_path(workspace, &[], "App/App.xcodeproj"));
+        assert!(!data.add_path(workspace, &[], "/work/App/App.xcodeproj"));
+        assert!(data.add_file_ref(
+            &["Libs"],
+            XCWorkspaceLocation::Container("Libs/Kit/Kit.xcodeproj".into())
+        ));
+        assert!(data.add_path(workspace, &["Libs", "Vendor"], "/work/Vendor/Net.xcodeproj"));
+        assert!(data.add_path(workspace, &[], "/tmp/Shared/Shared.xcodeproj"));
+
+        let xml = data.to_xml();
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Workspace
+   version = "1.0">
+   <FileRef
+      location = "group:App/App.xcodeproj">
+   </FileRef>
+   <Group
+      location = "container:"
+      name = "Libs">
+      <FileRef
+         location = "container:Libs/Kit/Kit.xcodeproj">
+      </FileRef>
+      <Group
+         location = "container:"
+         name = "Vendor">
+         <FileRef
+            location = "group:Vendor/Net.xcodeproj">
+         </FileRef>
+      </Group>
+   </Group>
+   <FileRef
+      location = "absolute:/tmp/Shared/Shared.xcodeproj">
+   </FileRef>
+</Workspace>
+"#,
+            xml
+        );
+        assert_eq!(data, XCWorkspaceData::parse(&xml).unwrap());
+        assert_eq!(4, data.project_paths(workspace).len());
+
+        assert!(data.remove_path(workspace, "Vendor/Net.xcodeproj"));
+        assert!(!data.remove_path(workspace, "Vendor/Net.xcodeproj"));
+        assert!(data.children[1].children()[1].children().is_empty());
+        assert!(!data.remove_group(&["Vendor"]));
+        assert!(data.remove_group(&["Libs", "Vendor"]));
+        assert!(data.remove_group(&["Libs"]));
+        assert_eq!(
+            vec![
+                PathBuf::from("/work/App/App.xcodeproj"),
+                PathBuf::from("/tmp/Shared/Shared.xcodeproj"),
+            ],
+            data.project_paths(workspace)
+        );
+    }
+}
@@ -0,0 +1,428 @@
+mod data;
+
+use crate::package_resolved::package_url_key;
+use crate::pbxproj::{PBXTarget, XCVersionRequirement};
+use crate::XCodeProject;
+use anyhow::{Context, Result};
+pub use data::*;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+/// `.xcworkspace` bundle with the projects it references loaded
+#[derive(Clone, Debug)]
+pub struct XCWorkspace {
+    name: String,
+    path: PathBuf,
+    projects: Vec<XCodeProject>,
+}
+
+/// Remote Swift package referenced by projects of a workspace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCWorkspacePackage {
+    /// Repository url, as first referenced
+    pub repository_url: String,
+    /// Name of each project referencing the package, with its version requirement
+    pub requirements: Vec<(String, Option<XCVersionRequirement>)>,
+}
+
+impl XCWorkspacePackage {
+    /// Whether projects require different versions of the package
+    pub fn has_conflict(&self) -> bool {
+        self.requirements
+            .iter()
+            .any(|(_, requirement)| requirement != &self.requirements[0].1)
+    }
+}
+
+/// Target depending on a target of another project of the same workspace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCWorkspaceDependency {
+    /// Name of the project of the dependent target
+    pub project: String,
+    /// Reference of the dependent target
+    pub target: String,
+    /// Name of the project of the dependency target
+    pub dependency_project: String,
+    /// Reference of the dependency target
+    pub dependency_target: String,
+}
+
+impl XCWorkspace {
+    /// Read the `.xcworkspace` bundle at `path` and load the projects it references
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let projects = Self::project_paths(&path)?
+            .into_iter()
+            .map(|project| XCodeProject::new(&project).with_context(|| format!("Load {project:?}")))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            name,
+            path,
+            projects,
+        })
+    }
+
+    /// Get paths of `.xcodeproj` bundles referenced by the `.xcworkspace` bundle at `path`,
+    /// in `contents.xcworkspacedata` order.
+    pub fn project_paths<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>> {
+        let path = path.as_ref();
+        Ok(XCWorkspaceData::new(path)?.project_paths(path))
+    }
+
+    /// Add file at `path`, absolute or relative to the directory holding the workspace, to the
+    /// top level of the workspace navigator, e.g. a `.playground` bundle exploring the APIs of
+    /// its projects. Files already referenced at the top level are skipped.
+    ///
+    /// Only `.xcodeproj` references are loaded as projects, so [`XCWorkspace::projects`] is
+    /// left untouched.
+    pub fn add_file_reference<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut data = XCWorkspaceData::new(&self.path)?;
+        if data.add_path(&self.path, &[], path) {
+            data.save(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Create the `.xcworkspace` bundle at `path` referencing the `.xcodeproj` bundles at
+    /// `projects`, absolute or relative to the directory holding the workspace, then load it.
+    /// Existing workspace content is replaced.
+    pub fn create<P, I, Q>(path: P, projects: I) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = Q>,
+        Q: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut data = XCWorkspaceData::default();
+        for project in projects {
+            data.add_path(path, &[], project);
+        }
+        data.save(path)?;
+        Self::new(path)
+    }
+
+    /// Add `.xcodeproj` bundle at `path`, absolute or relative to the directory holding the
+    /// workspace, to the group at `groups` of the workspace navigator, see
+    /// [`XCWorkspaceData::add_path`], and load it. Returns false if the group already
+    /// references the project.
+    pub fn add_project<P: AsRef<Path>>(&mut self, groups: &[&str], path: P) -> Result<bool> {
+        let container = self.path.parent().unwrap_or_else(|| Path::new(""));
+        let path = normalize(&container.join(path));
+        let mut data = XCWorkspaceData::new(&self.path)?;
+        if !data.add_path(&self.path, groups, &path) {
+            return Ok(false);
+        }
+        let project = XCodeProject::new(&path).with_context(|| format!("Load {path:?}"))?;
+        data.save(&self.path)?;
+        let mut projects = std::mem::take(&mut self.projects);
+        projects.push(project);
+        self.projects = data
+            .project_paths(&self.path)
+            .into_iter()
+            .filter_map(|bundle| {
+                let index = projects.iter().position(|p| project_bundle(p) == bundle)?;
+                Some(projects.remove(index))
+            })
+            .collect();
+        Ok(true)
+    }
+
+    /// Remove every reference to the project with the given name from the workspace. Returns
+    /// whether the workspace had such project.
+    pub fn remove_project(&mut self, name: &str) -> Result<bool> {
+        let index = match self.projects.iter().position(|p| p.name() == name) {
+            Some(index) => index,
+            None => return Ok(false),
+        };
+        let mut data = XCWorkspaceData::new(&self.path)?;
+        let bundle = project_bundle(&self.projects[index]);
+        data.remove_path(&self.path, &bundle);
+        data.save(&self.path)?;
+        self.projects
+            .retain(|project| project_bundle(project) != bundle);
+        Ok(true)
+    }
+
+    /// Get a reference to the workspace's name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get a reference to the workspace's `.xcworkspace` bundle path.
+    #[must_use]
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Get projects of the workspace, in `contents.xcworkspacedata` order.
+    #[must_use]
+    pub fn projects(&self) -> &[XCodeProject] {
+        self.projects.as_ref()
+    }
+
+    /// Get project with the given name
+    pub fn get_project(&self, name: &str) -> Option<&XCodeProject> {
+        self.projects.iter().find(|project| project.name() == name)
+    }
+
+    /// Find target with the given name in any project of the workspace, searching projects in
+    /// workspace order.
+    pub fn find_target(&self, name: &str) -> Option<(&XCodeProject, PBXTarget<'_>)> {
+        self.projects.iter().find_map(|project| {
+            let target = project
+                .targets()
+                .into_iter()
+                .find(|target| target.name.map(|n| n.as_str()) == Some(name))?;
+            Some((project, target))
+        })
+    }
+
+    /// Get remote Swift packages referenced by projects of the workspace, sorted by url.
+    /// References whose url differs only by case, a trailing slash or `.git` suffix are
+    /// grouped, see [`XCWorkspacePackage::has_conflict`].
+    pub fn swift_packages(&self) -> Vec<XCWorkspacePackage> {
+        let mut packages = BTreeMap::<String, XCWorkspacePackage>::new();
+        for project in self.projects.iter() {
+            for package in project.swift_package_references() {
+                let url = match package.repository_url {
+                    Some(url) => url,
+                    None => continue,
+                };
+                packages
+                    .entry(package_url_key(url))
+                    .or_insert_with(|| XCWorkspacePackage {
+                        repository_url: url.clone(),
+                        requirements: vec![],
+                    })
+                    .requirements
+                    .push((project.name().to_string(), package.version_requirement));
+            }
+        }
+        packages.into_values().collect()
+    }
+
+    /// Get dependencies of targets on targets of other projects of the workspace, declared
+    /// through `PBXContainerItemProxy` objects pointing at another `.xcodeproj` bundle.
+    pub fn project_dependencies(&self) -> Vec<XCWorkspaceDependency> {
+        let bundles = self.projects.iter().map(project_bundle).collect::<Vec<_>>();
+
+        let mut dependencies = vec![];
+        for project in self.projects.iter() {
+            for target in project.targets() {
+                for dependency in target.target_dependencies.iter() {
+                    let proxy = match &dependency.target_proxy {
+                        Some(proxy) if dependency.target.is_none() => proxy,
+                        _ => continue,
+                    };
+                    let resolved = project
+                        .objects()
+                        .get_full_path(proxy.container_portal_reference, project.root())
+                        .and_then(|portal| {
+                            let portal = normalize(&portal);
+                            let index = bundles.iter().position(|bundle| bundle == &portal)?;
+                            let other = &self.projects[index];
+                            let reference = proxy.remote_global_id_reference?;
+                            other.get_target(reference)?;
+                            Some((other.name().to_string(), reference.clone()))
+                        });
+                    if let Some((dependency_project, dependency_target)) = resolved {
+                        dependencies.push(XCWorkspaceDependency {
+                            project: project.name().to_string(),
+                            target: target.id.clone(),
+                            dependency_project,
+                            dependency_target,
+                        });
+                    }
+                }
+            }
+        }
+        dependencies
+    }
+}
+
+/// Path of the `.xcodeproj` bundle of `project`
+fn project_bundle(project: &XCodeProject) -> PathBuf {
+    normalize(&project.root().join(format!("{}.xcodeproj", project.name())))
+}
+
+/// Resolve `.` and `..` components of `path` without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_queries() {
+        let root = std::env::temp_dir().join(format!("xcodeproj-workspace-{}", std::process::id()));
+        let sample = std::fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples/demo1.pbxproj"),
+        )
+        .unwrap();
+        let target = "0B66A7280B2FFE35D5F8E9A6";
+
+        // App depends on the Wordle target of Kit, which pins another swift-log version
+        let app = sample
+            .replacen(
+                "objects = {\n",
+                &format!(
+                    "objects = {{\n\
+                     \t\tAAAAAAAAAAAAAAAAAAAAAAA1 = {{isa = PBXFileReference; \
+                     lastKnownFileType = \"wrapper.pb-project\"; path = ../Libs/Kit/Kit.xcodeproj; \
+                     sourceTree = \"<group>\"; }};\n\
+                     \t\tAAAAAAAAAAAAAAAAAAAAAAA2 = {{isa = PBXContainerItemProxy; \
+                     containerPortal = AAAAAAAAAAAAAAAAAAAAAAA1; proxyType = 1; \
+                     remoteGlobalIDString = {target}; remoteInfo = Wordle; }};\n\
+                     \t\tAAAAAAAAAAAAAAAAAAAAAAA3 = {{isa = PBXTargetDependency; name = Wordle; \
+                     targetProxy = AAAAAAAAAAAAAAAAAAAAAAA2; }};\n"
+                ),
+                1,
+            )
+            .replacen(
+                "dependencies = (\n",
+                "dependencies = (\n\t\t\t\tAAAAAAAAAAAAAAAAAAAAAAA3,\n",
+                1,
+            )
+            .replacen(
+                "BD239D52735A5DC1B53272DD = {\n\t\t\tisa = PBXGroup;\n\t\t\tchildren = (\n",
+                "BD239D52735A5DC1B53272DD = {\n\t\t\tisa = PBXGroup;\n\t\t\tchildren = (\n\
+                 \t\t\t\tAAAAAAAAAAAAAAAAAAAAAAA1,\n",
+                1,
+            );
+        let kit = sample.replace("version = 1.4.2;", "version = 1.5.0;");
+        for (path, content) in [("App/App.xcodeproj", app), ("Libs/Kit/Kit.xcodeproj", kit)] {
+            let bundle = root.join(path);
+            std::fs::create_dir_all(&bundle).unwrap();
+            std::fs::write(bundle.join("project.pbxproj"), content).unwrap();
+        }
+        let workspace = root.join("Demo.xcworkspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(
+            workspace.join("contents.xcworkspacedata"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Workspace
+   version = "1.0">
+   <FileRef
+      location = "group:App/App.xcodeproj">
+   </FileRef>
+   <Group
+      location = "container:Libs"
+      name = "Libs">
+      <FileRef
+         location = "group:Kit/Kit.xcodeproj">
+      </FileRef>
+   </Group>
+   <FileRef
+      location = "group:README.md">
+   </FileRef>
+</Workspace>
+"#,
+        )
+        .unwrap();
+
+        let workspace = XCWorkspace::new(&workspace).unwrap();
+        assert_eq!("Demo", workspace.name());
+        assert_eq!(
+            vec!["App", "Kit"],
+            workspace
+                .projects()
+                .iter()
+                .map(|p| p.name())
+                .collect::<Vec<_>>()
+        );
+
+        workspace.add_file_reference("Explore.playground").unwrap();
+        workspace
+            .add_file_reference(root.join("Explore.playground"))
+            .unwrap();
+        let data =
+            std::fs::read_to_string(workspace.path().join("contents.xcworkspacedata")).unwrap();
+        assert_eq!(1, data.matches("group:Explore.playground").count());
+        assert!(data.ends_with(
+            "   <FileRef\n      location = \"group:Explore.playground\">\n   </FileRef>\n</Workspace>\n"
+        ));
+        assert_eq!(
+            2,
+            XCWorkspace::project_paths(workspace.path()).unwrap().len()
+        );
+
+        let mut workspace = workspace;
+        assert!(workspace.remove_project("Kit").unwrap());
+        assert!(!workspace.remove_project("Kit").unwrap());
+        assert_eq!(1, workspace.projects().len());
+        assert!(workspace
+            .add_project(&["Libs"], root.join("Libs/Kit/Kit.xcodeproj"))
+            .unwrap());
+        assert!(!workspace
+            .add_project(&["Libs"], "Libs/Kit/Kit.xcodeproj")
+            .unwrap());
+        assert!(workspace.add_project(&[], "Missing.xcodeproj").is_err());
+        assert_eq!(
+            vec!["App", "Kit"],
+            workspace
+                .projects()
+                .iter()
+                .map(|p| p.name())
+                .collect::<Vec<_>>()
+        );
+        let created = XCWorkspace::create(
+            root.join("Created.xcworkspace"),
+            ["App/App.xcodeproj", "Libs/Kit/Kit.xcodeproj"],
+        )
+        .unwrap();
+        assert_eq!(2, created.projects().len());
+
+        let (project, found) = workspace.find_target("Wordle").unwrap();
+        assert_eq!(("App", target), (project.name(), found.id.as_str()));
+        assert!(workspace.find_target("Missing").is_none());
+
+        let packages = workspace.swift_packages();
+        assert_eq!(1, packages.len());
+        assert!(packages[0].has_conflict());
+        assert_eq!(
+            vec![
+                (
+                    "App".to_string(),
+                    Some(XCVersionRequirement::Exact("1.4.2".into()))
+                ),
+                (
+                    "Kit".to_string(),
+                    Some(XCVersionRequirement::Exact("1.5.0".into()))
+                )
+            ],
+            packages[0].requirements
+        );
+
+        assert_eq!(
+            vec![XCWorkspaceDependency {
+                project: "App".into(),
+                target: target.into(),
+                dependency_project: "Kit".into(),
+                dependency_target: target.into(),
+            }],
+            workspace.project_dependencies()
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
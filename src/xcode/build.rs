@@ -0,0 +1,202 @@
+use crate::pbxproj::PBXTargetPlatform;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Project or workspace an `xcodebuild` invocation operates on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XCBuildContainer {
+    /// `.xcodeproj` bundle, passed with `-project`
+    Project(PathBuf),
+    /// `.xcworkspace` bundle, passed with `-workspace`
+    Workspace(PathBuf),
+}
+
+/// Builder of `xcodebuild` arguments.
+///
+/// ```no_run
+/// use xcodeproj::xcode::XCBuildCommand;
+///
+/// let args = XCBuildCommand::project("Demo.xcodeproj")
+///     .scheme("Demo")
+///     .configuration("Debug")
+///     .destination("platform=iOS Simulator,name=iPhone 15")
+///     .derived_data_path(".build")
+///     .args()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XCBuildCommand {
+    container: XCBuildContainer,
+    scheme: Option<String>,
+    target: Option<String>,
+    configuration: Option<String>,
+    destination: Option<String>,
+    derived_data_path: Option<PathBuf>,
+    actions: Vec<String>,
+    extra: Vec<String>,
+}
+
+impl XCBuildCommand {
+    /// Build command operating on `container`
+    pub fn new(container: XCBuildContainer) -> Self {
+        Self {
+            container,
+            scheme: None,
+            target: None,
+            configuration: None,
+            destination: None,
+            derived_data_path: None,
+            actions: vec![],
+            extra: vec![],
+        }
+    }
+
+    /// Build command operating on the `.xcodeproj` bundle at `path`
+    pub fn project<P: AsRef<Path>>(path: P) -> Self {
+        Self::new(XCBuildContainer::Project(path.as_ref().to_path_buf()))
+    }
+
+    /// Build command operating on the `.xcworkspace` bundle at `path`
+    pub fn workspace<P: AsRef<Path>>(path: P) -> Self {
+        Self::new(XCBuildContainer::Workspace(path.as_ref().to_path_buf()))
+    }
+
+    /// Build the given scheme, required for workspaces
+    pub fn scheme<S: Into<String>>(mut self, scheme: S) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Build the given target, only valid for projects
+    pub fn target<S: Into<String>>(mut self, target: S) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Build configuration, e.g. `Debug`
+    pub fn configuration<S: Into<String>>(mut self, configuration: S) -> Self {
+        self.configuration = Some(configuration.into());
+        self
+    }
+
+    /// Destination specifier, e.g. `platform=iOS Simulator,name=iPhone 15`
+    pub fn destination<S: Into<String>>(mut self, destination: S) -> Self {
+        self.destination = Some(destination.into());
+        self
+    }
+
+    /// Generic destination of `platform`, e.g. `generic/platform=iOS`
+    pub fn generic_destination(self, platform: &PBXTargetPlatform) -> Self {
+        self.destination(format!("generic/platform={platform}"))
+    }
+
+    /// Derived data path, where intermediate files and products are written
+    pub fn derived_data_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.derived_data_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add build action, e.g. `build`, `test` or `clean`. xcodebuild defaults to `build`.
+    pub fn action<S: Into<String>>(mut self, action: S) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+
+    /// Add raw argument, e.g. a `KEY=VALUE` build setting override
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
+        self.extra.push(arg.into());
+        self
+    }
+
+    /// Get argument vector, without the `xcodebuild` program itself.
+    ///
+    /// Fails for workspaces without scheme, and when both scheme and target are set.
+    pub fn args(&self) -> Result<Vec<String>> {
+        let mut args = vec![];
+        match &self.container {
+            XCBuildContainer::Project(path) => {
+                args.extend(["-project".into(), path.to_string_lossy().to_string()])
+            }
+            XCBuildContainer::Workspace(path) => {
+                if self.scheme.is_none() {
+                    anyhow::bail!("Building workspace {path:?} requires a scheme");
+                }
+                args.extend(["-workspace".into(), path.to_string_lossy().to_string()])
+            }
+        }
+
+        match (&self.scheme, &self.target) {
+            (Some(_), Some(_)) => anyhow::bail!("Either scheme or target can be built, not both"),
+            (Some(scheme), None) => args.extend(["-scheme".into(), scheme.clone()]),
+            (None, Some(target)) => args.extend(["-target".into(), target.clone()]),
+            (None, None) => {}
+        }
+        if let Some(configuration) = &self.configuration {
+            args.extend(["-configuration".into(), configuration.clone()]);
+        }
+        if let Some(destination) = &self.destination {
+            args.extend(["-destination".into(), destination.clone()]);
+        }
+        if let Some(path) = &self.derived_data_path {
+            args.extend([
+                "-derivedDataPath".into(),
+                path.to_string_lossy().to_string(),
+            ]);
+        }
+        args.extend(self.actions.iter().cloned());
+        args.extend(self.extra.iter().cloned());
+
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_args() {
+        let args = XCBuildCommand::workspace("Demo.xcworkspace")
+            .scheme("Demo")
+            .configuration("Release")
+            .generic_destination(&PBXTargetPlatform::IOS)
+            .derived_data_path(".build")
+            .action("build")
+            .arg("CODE_SIGNING_ALLOWED=NO")
+            .args()
+            .unwrap();
+        assert_eq!(
+            vec![
+                "-workspace",
+                "Demo.xcworkspace",
+                "-scheme",
+                "Demo",
+                "-configuration",
+                "Release",
+                "-destination",
+                "generic/platform=iOS",
+                "-derivedDataPath",
+                ".build",
+                "build",
+                "CODE_SIGNING_ALLOWED=NO"
+            ],
+            args
+        );
+
+        assert!(XCBuildCommand::workspace("Demo.xcworkspace")
+            .args()
+            .is_err());
+        assert!(XCBuildCommand::project("Demo.xcodeproj")
+            .scheme("Demo")
+            .target("Demo")
+            .args()
+            .is_err());
+        assert_eq!(
+            vec!["-project", "Demo.xcodeproj", "-target", "Kit"],
+            XCBuildCommand::project("Demo.xcodeproj")
+                .target("Kit")
+                .args()
+                .unwrap()
+        );
+    }
+}
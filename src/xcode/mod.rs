@@ -1,4 +1,7 @@
 //! Xcode related types and helpers
+mod build;
+pub use build::{XCBuildCommand, XCBuildContainer};
+
 include!(concat!(env!("OUT_DIR"), "/file_types.rs"));
 
 /// Translate file extsnion to xcode file type
@@ -7,3 +10,38 @@ pub fn xcode_file_type<S: AsRef<str>>(extension: S) -> Option<String> {
         .get(extension.as_ref())
         .map(|s| s.to_string())
 }
+
+include!(concat!(env!("OUT_DIR"), "/build_settings.rs"));
+
+/// Value type of a known build setting
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XCBuildSettingType {
+    /// `YES` or `NO`
+    Boolean,
+    /// Single value, e.g. a name, path, version or one of a set of options
+    String,
+    /// Space separated or array value, e.g. search paths or flags
+    StringList,
+}
+
+/// Get value type of a known build setting, ignoring conditions, e.g. `[sdk=iphoneos*]`
+pub fn xcode_build_setting_type<S: AsRef<str>>(key: S) -> Option<XCBuildSettingType> {
+    XCODE_BUILD_SETTINGS
+        .get(build_setting_name(key.as_ref()))
+        .map(|setting_type| match *setting_type {
+            "bool" => XCBuildSettingType::Boolean,
+            "list" => XCBuildSettingType::StringList,
+            _ => XCBuildSettingType::String,
+        })
+}
+
+/// Get names of known build settings, in no particular order
+pub fn xcode_build_settings() -> impl Iterator<Item = &'static str> {
+    XCODE_BUILD_SETTINGS.keys().copied()
+}
+
+/// Name of build setting `key`, without quotes and conditions
+pub(crate) fn build_setting_name(key: &str) -> &str {
+    let key = key.trim_matches('"');
+    key.split_once('[').map(|(key, _)| key).unwrap_or(key)
+}